@@ -0,0 +1,122 @@
+use std::time::{Duration, Instant};
+
+use bytes::BytesMut;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use futures::SinkExt;
+use simple_redis::backend::Backend;
+use simple_redis::network::{request_handle, stream_handle, RespFrameCodec, Session};
+use simple_redis::resp::frame::Frame;
+use simple_redis::resp::RespEncode;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Runtime;
+use tokio_stream::StreamExt;
+use tokio_util::codec::{Decoder, Framed};
+
+const PIPELINE: usize = 100;
+
+// The connection loop as it was before pipelining support, flushing after
+// every reply, kept to compare against.
+async fn unbatched_stream_handle(stream: TcpStream, backend: Backend) -> anyhow::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut framed = Framed::new(stream, RespFrameCodec::default());
+    let mut session = Session::new(&backend);
+
+    while let Some(frame) = framed.next().await {
+        for response in request_handle(frame?, backend.clone(), &mut session).await? {
+            framed.send(response).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn start_server(batched: bool) -> TcpStream {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let backend = Backend::new();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let backend = backend.clone();
+
+            tokio::spawn(async move {
+                if batched {
+                    stream_handle(stream, backend).await
+                } else {
+                    unbatched_stream_handle(stream, backend).await
+                }
+            });
+        }
+    });
+
+    let stream = TcpStream::connect(addr).await.unwrap();
+    stream.set_nodelay(true).unwrap();
+    stream
+}
+
+fn pipelined_requests() -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for i in 0..PIPELINE / 2 {
+        let key = format!("key:{}", i);
+        let set: Frame = vec![b"SET".into(), key.as_bytes().into(), b"value".into()].into();
+        let get: Frame = vec![b"GET".into(), key.as_bytes().into()].into();
+        data.extend(set.encode());
+        data.extend(get.encode());
+    }
+
+    data
+}
+
+async fn round_trip(stream: &mut TcpStream, requests: &[u8]) {
+    stream.write_all(requests).await.unwrap();
+
+    let mut codec = RespFrameCodec::default();
+    let mut buf = BytesMut::new();
+    let mut replies = 0;
+
+    while replies < PIPELINE {
+        stream.read_buf(&mut buf).await.unwrap();
+
+        while codec.decode(&mut buf).unwrap().is_some() {
+            replies += 1;
+        }
+    }
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let requests = pipelined_requests();
+
+    let mut group = c.benchmark_group("pipelined_get_set");
+    group.throughput(Throughput::Elements(PIPELINE as u64));
+
+    for (name, batched) in [("flush_per_reply", false), ("batched", true)] {
+        let mut stream = runtime.block_on(start_server(batched));
+
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter_custom(|iters| {
+                runtime.block_on(async {
+                    let start = Instant::now();
+
+                    for _ in 0..iters {
+                        round_trip(&mut stream, &requests).await;
+                    }
+
+                    start.elapsed()
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(5));
+    targets = bench_pipeline
+}
+criterion_main!(benches);
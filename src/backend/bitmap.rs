@@ -0,0 +1,573 @@
+use bytes::{Bytes, BytesMut};
+
+use super::Backend;
+use crate::command::CommandError;
+
+// Bits are numbered from the most significant bit of the first byte, like
+// Redis does. Strings grow with zero bytes when writing past their end.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitUnit {
+    Byte,
+    Bit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOp {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitfieldOverflow {
+    Wrap,
+    Sat,
+    Fail,
+}
+
+// Signed integers are up to 64 bits wide, unsigned ones up to 63.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitfieldType {
+    pub signed: bool,
+    pub bits: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitfieldOp {
+    Get {
+        ty: BitfieldType,
+        offset: u64,
+    },
+    Set {
+        ty: BitfieldType,
+        offset: u64,
+        value: i64,
+        overflow: BitfieldOverflow,
+    },
+    IncrBy {
+        ty: BitfieldType,
+        offset: u64,
+        increment: i64,
+        overflow: BitfieldOverflow,
+    },
+}
+
+impl BitfieldType {
+    pub fn parse(s: &str) -> Result<Self, CommandError> {
+        let invalid = || {
+            CommandError::InvalidArgument(
+                "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."
+                    .to_string(),
+            )
+        };
+
+        let (signed, bits) = match s.split_at_checked(1) {
+            Some(("i" | "I", bits)) => (true, bits),
+            Some(("u" | "U", bits)) => (false, bits),
+            _ => return Err(invalid()),
+        };
+
+        match bits.parse::<u32>() {
+            Ok(bits @ 1..=64) if signed => Ok(Self { signed, bits }),
+            Ok(bits @ 1..=63) => Ok(Self { signed, bits }),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn min(&self) -> i128 {
+        match self.signed {
+            true => -(1 << (self.bits - 1)),
+            false => 0,
+        }
+    }
+
+    fn max(&self) -> i128 {
+        match self.signed {
+            true => (1 << (self.bits - 1)) - 1,
+            false => (1 << self.bits) - 1,
+        }
+    }
+
+    // Brings the value back into range following the overflow policy, None
+    // when the operation must fail.
+    fn fit(&self, value: i128, overflow: BitfieldOverflow) -> Option<i64> {
+        let (min, max) = (self.min(), self.max());
+
+        if (min..=max).contains(&value) {
+            return Some(value as i64);
+        }
+
+        match overflow {
+            BitfieldOverflow::Wrap => Some(((value - min).rem_euclid(1 << self.bits) + min) as i64),
+            BitfieldOverflow::Sat => Some(value.clamp(min, max) as i64),
+            BitfieldOverflow::Fail => None,
+        }
+    }
+
+    fn read(&self, bytes: &[u8], offset: u64) -> i64 {
+        let mut value = 0u64;
+        for i in 0..self.bits as u64 {
+            value = (value << 1) | get_bit(bytes, offset + i) as u64;
+        }
+
+        // sign extension
+        if self.signed && self.bits < 64 && value & (1 << (self.bits - 1)) != 0 {
+            value |= u64::MAX << self.bits;
+        }
+
+        value as i64
+    }
+
+    fn write(&self, bytes: &mut BytesMut, offset: u64, value: i64) {
+        for i in 0..self.bits as u64 {
+            let bit = (value as u64 >> (self.bits as u64 - 1 - i)) & 1 == 1;
+            set_bit(bytes, offset + i, bit);
+        }
+    }
+}
+
+impl BitfieldOp {
+    fn end(&self) -> u64 {
+        let (ty, offset) = match *self {
+            BitfieldOp::Get { ty, offset }
+            | BitfieldOp::Set { ty, offset, .. }
+            | BitfieldOp::IncrBy { ty, offset, .. } => (ty, offset),
+        };
+
+        offset + ty.bits as u64
+    }
+
+    fn is_write(&self) -> bool {
+        !matches!(self, BitfieldOp::Get { .. })
+    }
+}
+
+fn get_bit(bytes: &[u8], offset: u64) -> bool {
+    let byte = bytes.get((offset / 8) as usize).copied().unwrap_or(0);
+    byte & (0x80 >> (offset % 8)) != 0
+}
+
+fn set_bit(bytes: &mut BytesMut, offset: u64, bit: bool) {
+    let index = (offset / 8) as usize;
+    if bytes.len() <= index {
+        bytes.resize(index + 1, 0);
+    }
+
+    match bit {
+        true => bytes[index] |= 0x80 >> (offset % 8),
+        false => bytes[index] &= !(0x80 >> (offset % 8)),
+    }
+}
+
+// Resolves a range with inclusive, possibly negative, offsets against a
+// length. None when the range is empty.
+fn normalize_range(start: i64, end: i64, len: i64) -> Option<(u64, u64)> {
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let end = if end < 0 {
+        (len + end).max(0)
+    } else {
+        end.min(len - 1)
+    };
+
+    match start > end || len == 0 {
+        true => None,
+        false => Some((start as u64, end as u64)),
+    }
+}
+
+// Bit range a BITCOUNT or BITPOS range covers.
+fn bit_range(bytes: &[u8], start: i64, end: i64, unit: BitUnit) -> Option<(u64, u64)> {
+    match unit {
+        BitUnit::Byte => normalize_range(start, end, bytes.len() as i64)
+            .map(|(start, end)| (start * 8, end * 8 + 7)),
+        BitUnit::Bit => normalize_range(start, end, bytes.len() as i64 * 8),
+    }
+}
+
+impl Backend {
+    // Returns the previous value of the bit.
+    pub fn setbit(&self, key: &[u8], offset: u64, bit: bool) -> Result<bool, CommandError> {
+        self.write_string(key, |current| {
+            let mut bytes = BytesMut::from(current.map_or(&[][..], |current| current));
+            let previous = get_bit(&bytes, offset);
+            set_bit(&mut bytes, offset, bit);
+
+            Ok((bytes.freeze(), previous))
+        })
+    }
+
+    pub fn getbit(&self, key: &[u8], offset: u64) -> Result<bool, CommandError> {
+        Ok(self.get(key)?.is_some_and(|bytes| get_bit(&bytes, offset)))
+    }
+
+    pub fn bitcount(
+        &self,
+        key: &[u8],
+        range: Option<(i64, i64, BitUnit)>,
+    ) -> Result<usize, CommandError> {
+        let Some(bytes) = self.get(key)? else {
+            return Ok(0);
+        };
+
+        let (start, end) = match range {
+            None => return Ok(bytes.iter().map(|byte| byte.count_ones() as usize).sum()),
+            Some((start, end, unit)) => match bit_range(&bytes, start, end, unit) {
+                Some(range) => range,
+                None => return Ok(0),
+            },
+        };
+
+        let mut count = 0;
+        let mut offset = start;
+        while offset <= end {
+            // whole bytes at once
+            if offset % 8 == 0 && offset + 7 <= end {
+                count += bytes[(offset / 8) as usize].count_ones() as usize;
+                offset += 8;
+            } else {
+                count += get_bit(&bytes, offset) as usize;
+                offset += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    // Position of the first bit set to `bit`, or -1. Looking for a clear bit
+    // without an explicit end finds the one right after the string when all
+    // of its bits are set.
+    pub fn bitpos(
+        &self,
+        key: &[u8],
+        bit: bool,
+        start: Option<i64>,
+        end: Option<i64>,
+        unit: BitUnit,
+    ) -> Result<i64, CommandError> {
+        let Some(bytes) = self.get(key)? else {
+            return Ok(if bit { -1 } else { 0 });
+        };
+
+        let Some((first, last)) = bit_range(&bytes, start.unwrap_or(0), end.unwrap_or(-1), unit)
+        else {
+            return Ok(-1);
+        };
+
+        let skip = if bit { 0x00 } else { 0xff };
+        let mut offset = first;
+        while offset <= last {
+            if offset % 8 == 0 && offset + 7 <= last && bytes[(offset / 8) as usize] == skip {
+                offset += 8;
+                continue;
+            }
+
+            if get_bit(&bytes, offset) == bit {
+                return Ok(offset as i64);
+            }
+            offset += 1;
+        }
+
+        match (bit, end) {
+            (false, None) => Ok(last as i64 + 1),
+            _ => Ok(-1),
+        }
+    }
+
+    // Stores the result in the destination, replacing whatever it held.
+    // Missing keys are strings of zero bytes. Returns the length stored.
+    pub fn bitop(
+        &self,
+        op: BitOp,
+        destination: &[u8],
+        keys: &[Bytes],
+    ) -> Result<usize, CommandError> {
+        let sources = keys
+            .iter()
+            .map(|key| Ok(self.get(key)?.unwrap_or_default()))
+            .collect::<Result<Vec<_>, CommandError>>()?;
+
+        let len = sources.iter().map(|source| source.len()).max().unwrap_or(0);
+        let byte = |source: &Bytes, i: usize| source.get(i).copied().unwrap_or(0);
+
+        let result = (0..len)
+            .map(|i| {
+                let mut bytes = sources.iter().map(|source| byte(source, i));
+                let first = bytes.next().unwrap_or(0);
+
+                match op {
+                    BitOp::And => bytes.fold(first, |acc, b| acc & b),
+                    BitOp::Or => bytes.fold(first, |acc, b| acc | b),
+                    BitOp::Xor => bytes.fold(first, |acc, b| acc ^ b),
+                    BitOp::Not => !first,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match len {
+            0 => {
+                self.remove(destination);
+            }
+            _ => self.set(destination, result.into()),
+        }
+
+        Ok(len)
+    }
+
+    // Runs the operations in order, None for the ones that failed on
+    // overflow. Keys are only written when some operation is not a GET.
+    pub fn bitfield(
+        &self,
+        key: &[u8],
+        ops: &[BitfieldOp],
+    ) -> Result<Vec<Option<i64>>, CommandError> {
+        if !ops.iter().any(BitfieldOp::is_write) {
+            let bytes = self.get(key)?.unwrap_or_default();
+
+            return Ok(ops
+                .iter()
+                .map(|op| match *op {
+                    BitfieldOp::Get { ty, offset } => Some(ty.read(&bytes, offset)),
+                    _ => None,
+                })
+                .collect());
+        }
+
+        self.write_string(key, |current| {
+            let mut bytes = BytesMut::from(current.map_or(&[][..], |current| current));
+
+            // the string grows to fit every field, even the failing ones
+            let end = ops.iter().map(BitfieldOp::end).max().unwrap_or(0);
+            if (bytes.len() as u64) * 8 < end {
+                bytes.resize(end.div_ceil(8) as usize, 0);
+            }
+
+            let results = ops
+                .iter()
+                .map(|op| match *op {
+                    BitfieldOp::Get { ty, offset } => Some(ty.read(&bytes, offset)),
+                    BitfieldOp::Set {
+                        ty,
+                        offset,
+                        value,
+                        overflow,
+                    } => {
+                        let previous = ty.read(&bytes, offset);
+                        let value = ty.fit(value as i128, overflow)?;
+                        ty.write(&mut bytes, offset, value);
+                        Some(previous)
+                    }
+                    BitfieldOp::IncrBy {
+                        ty,
+                        offset,
+                        increment,
+                        overflow,
+                    } => {
+                        let current = ty.read(&bytes, offset);
+                        let value = ty.fit(current as i128 + increment as i128, overflow)?;
+                        ty.write(&mut bytes, offset, value);
+                        Some(value)
+                    }
+                })
+                .collect();
+
+            Ok((bytes.freeze(), results))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ty(s: &str) -> BitfieldType {
+        BitfieldType::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_backend_setbit_getbit_bitcount() {
+        let backend = Backend::new();
+
+        assert!(!backend.setbit(b"key", 7, true).unwrap());
+        assert!(backend.setbit(b"key", 7, true).unwrap());
+        assert_eq!(backend.get(b"key").unwrap(), Some(Bytes::from_static(&[1])));
+
+        backend.setbit(b"key", 100, true).unwrap();
+        assert_eq!(backend.strlen(b"key").unwrap(), 13);
+        assert!(backend.getbit(b"key", 100).unwrap());
+        assert!(!backend.getbit(b"key", 1000).unwrap());
+
+        backend.set(b"key", "foobar".into());
+        assert_eq!(backend.bitcount(b"key", None).unwrap(), 26);
+        assert_eq!(
+            backend
+                .bitcount(b"key", Some((1, 1, BitUnit::Byte)))
+                .unwrap(),
+            6
+        );
+        assert_eq!(
+            backend
+                .bitcount(b"key", Some((5, 30, BitUnit::Bit)))
+                .unwrap(),
+            17
+        );
+        assert_eq!(
+            backend
+                .bitcount(b"key", Some((-1, -2, BitUnit::Byte)))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_backend_bitpos() {
+        let backend = Backend::new();
+        backend.set(b"key", Bytes::from_static(&[0xff, 0xf0, 0x00]));
+
+        assert_eq!(
+            backend
+                .bitpos(b"key", false, None, None, BitUnit::Byte)
+                .unwrap(),
+            12
+        );
+        assert_eq!(
+            backend
+                .bitpos(b"key", true, Some(2), Some(-1), BitUnit::Byte)
+                .unwrap(),
+            -1
+        );
+        assert_eq!(
+            backend
+                .bitpos(b"key", true, Some(7), Some(15), BitUnit::Bit)
+                .unwrap(),
+            7
+        );
+
+        backend.set(b"ones", Bytes::from_static(&[0xff]));
+        assert_eq!(
+            backend
+                .bitpos(b"ones", false, None, None, BitUnit::Byte)
+                .unwrap(),
+            8
+        );
+        assert_eq!(
+            backend
+                .bitpos(b"ones", false, Some(0), Some(-1), BitUnit::Byte)
+                .unwrap(),
+            -1
+        );
+        assert_eq!(
+            backend
+                .bitpos(b"missing", false, None, None, BitUnit::Byte)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_backend_bitop() {
+        let backend = Backend::new();
+        backend.set(b"a", "foobar".into());
+        backend.set(b"b", "abcdef".into());
+        let keys = [Bytes::from("a"), Bytes::from("b")];
+
+        assert_eq!(backend.bitop(BitOp::And, b"dest", &keys).unwrap(), 6);
+        assert_eq!(backend.get(b"dest").unwrap(), Some("`bc`ab".into()));
+
+        backend.bitop(BitOp::Or, b"dest", &keys).unwrap();
+        assert_eq!(backend.get(b"dest").unwrap(), Some("goofev".into()));
+
+        backend
+            .bitop(BitOp::Not, b"dest", &[Bytes::from_static(b"a")])
+            .unwrap();
+        assert_eq!(
+            backend.getrange(b"dest", 0, 0).unwrap(),
+            Bytes::from_static(&[!b'f'])
+        );
+
+        assert_eq!(
+            backend
+                .bitop(BitOp::Xor, b"dest", &[Bytes::from("missing")])
+                .unwrap(),
+            0
+        );
+        assert!(!backend.exists(b"dest"));
+    }
+
+    #[test]
+    fn test_backend_bitfield() {
+        let backend = Backend::new();
+
+        let results = backend
+            .bitfield(
+                b"key",
+                &[
+                    BitfieldOp::IncrBy {
+                        ty: ty("i5"),
+                        offset: 100,
+                        increment: 1,
+                        overflow: BitfieldOverflow::Wrap,
+                    },
+                    BitfieldOp::Get {
+                        ty: ty("u4"),
+                        offset: 0,
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(results, vec![Some(1), Some(0)]);
+
+        let incr = |overflow| BitfieldOp::IncrBy {
+            ty: ty("u2"),
+            offset: 102,
+            increment: 1,
+            overflow,
+        };
+        let results = backend
+            .bitfield(
+                b"key",
+                &[
+                    incr(BitfieldOverflow::Wrap),
+                    incr(BitfieldOverflow::Wrap),
+                    incr(BitfieldOverflow::Sat),
+                    incr(BitfieldOverflow::Fail),
+                ],
+            )
+            .unwrap();
+        assert_eq!(results, vec![Some(1), Some(2), Some(3), None]);
+
+        let results = backend
+            .bitfield(
+                b"signed",
+                &[
+                    BitfieldOp::Set {
+                        ty: ty("i8"),
+                        offset: 0,
+                        value: 200,
+                        overflow: BitfieldOverflow::Wrap,
+                    },
+                    BitfieldOp::Get {
+                        ty: ty("i8"),
+                        offset: 0,
+                    },
+                    BitfieldOp::IncrBy {
+                        ty: ty("i8"),
+                        offset: 0,
+                        increment: -100,
+                        overflow: BitfieldOverflow::Sat,
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(results, vec![Some(0), Some(-56), Some(-128)]);
+
+        assert!(BitfieldType::parse("u64").is_err());
+        assert!(BitfieldType::parse("i0").is_err());
+        assert_eq!(ty("i64").read(&[0xff; 8], 0), -1);
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use bytes::Bytes;
+use tokio::sync::oneshot;
+
+use super::Backend;
+use crate::resp::frame::Frame;
+
+// What a blocked client gets once one of its keys can be served: the reply to
+// send, and the non-blocking command to propagate in place of its own. Reads
+// like XREAD propagate nothing.
+#[derive(Debug)]
+pub struct Served {
+    pub reply: Frame,
+    pub propagate: Option<Frame>,
+}
+
+// Tries to serve a client from one key, None while the key has nothing for it.
+pub type BlockOp = Arc<dyn Fn(&Backend, &[u8]) -> Option<Served> + Send + Sync>;
+
+// How a blocking command waits when none of its keys can serve it yet.
+pub struct Block {
+    pub keys: Vec<Bytes>,
+    pub op: BlockOp,
+    // None blocks forever
+    pub timeout: Option<Duration>,
+    pub timeout_reply: Frame,
+}
+
+pub enum BlockResult {
+    Served(Served),
+    Blocked(u64, oneshot::Receiver<Frame>),
+}
+
+struct Waiter {
+    keys: Vec<Bytes>,
+    op: BlockOp,
+    reply: oneshot::Sender<Frame>,
+}
+
+#[derive(Default)]
+pub struct Blocking {
+    // clients blocked on each key, in the order they blocked
+    keys: HashMap<Bytes, VecDeque<u64>>,
+    waiters: HashMap<u64, Waiter>,
+    next_id: u64,
+}
+
+impl fmt::Debug for Blocking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blocking")
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Blocking {
+    fn remove(&mut self, id: u64) -> Option<Waiter> {
+        let waiter = self.waiters.remove(&id)?;
+
+        for key in &waiter.keys {
+            if let Some(ids) = self.keys.get_mut(key) {
+                ids.retain(|other| *other != id);
+
+                if ids.is_empty() {
+                    self.keys.remove(key);
+                }
+            }
+        }
+
+        Some(waiter)
+    }
+}
+
+impl Backend {
+    fn lock_blocking(&self) -> MutexGuard<'_, Blocking> {
+        self.blocking.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn blocked_clients(&self) -> usize {
+        self.blocked_clients.load(Ordering::SeqCst)
+    }
+
+    // Serves the client right away when one of the keys has data, otherwise
+    // parks it on all of them. The count of blocked clients is raised before
+    // trying, so a concurrent push either is seen here or signals the key.
+    pub fn block(&self, keys: Vec<Bytes>, op: BlockOp) -> BlockResult {
+        let mut blocking = self.lock_blocking();
+        self.blocked_clients.fetch_add(1, Ordering::SeqCst);
+
+        if let Some(served) = keys.iter().find_map(|key| op(self, key)) {
+            self.blocked_clients.fetch_sub(1, Ordering::SeqCst);
+            return BlockResult::Served(served);
+        }
+
+        let id = blocking.next_id;
+        blocking.next_id += 1;
+
+        for key in &keys {
+            blocking.keys.entry(key.clone()).or_default().push_back(id);
+        }
+
+        let (reply, receiver) = oneshot::channel();
+        blocking.waiters.insert(id, Waiter { keys, op, reply });
+
+        BlockResult::Blocked(id, receiver)
+    }
+
+    // Removes a client that timed out or disconnected, if it is still blocked.
+    pub fn unblock(&self, id: u64) {
+        if self.lock_blocking().remove(id).is_some() {
+            self.blocked_clients.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    // Marks a key that received data, so the clients blocked on it are served
+    // once the command that changed it is done.
+    pub(crate) fn signal_ready(&self, key: &[u8]) {
+        if self.blocked_clients() == 0 {
+            return;
+        }
+
+        let mut ready = self
+            .ready_keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if !ready.iter().any(|k| k == key) {
+            ready.push(Bytes::copy_from_slice(key));
+        }
+    }
+
+    // Serves the clients blocked on the keys signaled as ready, in the order
+    // they blocked, while the keys have data for them. Serving a client may make
+    // other keys ready, like BLMOVE pushing to its destination. Returns the
+    // commands to propagate for the clients served.
+    pub fn serve_blocked(&self) -> Vec<Frame> {
+        let mut propagate = Vec::new();
+
+        loop {
+            let keys = std::mem::take(
+                &mut *self
+                    .ready_keys
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner),
+            );
+
+            if keys.is_empty() {
+                break;
+            }
+
+            let mut blocking = self.lock_blocking();
+
+            for key in keys {
+                let ids = blocking.keys.get(&key).cloned().unwrap_or_default();
+
+                for id in ids {
+                    let Some(waiter) = blocking.waiters.get(&id) else {
+                        continue;
+                    };
+
+                    // the client is gone, it must not consume anything
+                    if waiter.reply.is_closed() {
+                        blocking.remove(id);
+                        self.blocked_clients.fetch_sub(1, Ordering::SeqCst);
+                        continue;
+                    }
+
+                    // stream readers wait for entries after different IDs, so
+                    // a client that can't be served does not stop the others
+                    let Some(served) = (waiter.op)(self, &key) else {
+                        continue;
+                    };
+
+                    if let Some(waiter) = blocking.remove(id) {
+                        self.blocked_clients.fetch_sub(1, Ordering::SeqCst);
+                        let _ = waiter.reply.send(served.reply);
+                    }
+
+                    propagate.extend(served.propagate);
+                }
+            }
+        }
+
+        propagate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    fn lpop() -> BlockOp {
+        Arc::new(|backend: &Backend, key: &[u8]| {
+            let element = backend.pop(key, ListEnd::Left, 1).ok()??.pop()?;
+
+            Some(Served {
+                reply: element.into(),
+                propagate: Some(vec![b"LPOP".into(), key.into()].into()),
+            })
+        })
+    }
+
+    fn blocked(result: BlockResult) -> (u64, oneshot::Receiver<Frame>) {
+        match result {
+            BlockResult::Blocked(id, receiver) => (id, receiver),
+            BlockResult::Served(_) => panic!("Expected Blocked"),
+        }
+    }
+
+    #[test]
+    fn test_block_served_right_away() {
+        let backend = Backend::new();
+        backend
+            .push(b"b", ListEnd::Right, vec!["x".into()])
+            .unwrap();
+
+        match backend.block(vec!["a".into(), "b".into()], lpop()) {
+            BlockResult::Served(served) => assert_eq!(served.reply, b"x".into()),
+            BlockResult::Blocked(..) => panic!("Expected Served"),
+        }
+        assert_eq!(backend.blocked_clients(), 0);
+    }
+
+    #[test]
+    fn test_serve_blocked_in_order() {
+        let backend = Backend::new();
+        let (_, mut first) = blocked(backend.block(vec!["list".into()], lpop()));
+        let (_, mut second) = blocked(backend.block(vec!["other".into(), "list".into()], lpop()));
+        let (third, _receiver) = blocked(backend.block(vec!["list".into()], lpop()));
+        assert_eq!(backend.blocked_clients(), 3);
+
+        backend
+            .push(b"list", ListEnd::Right, vec!["a".into(), "b".into()])
+            .unwrap();
+        let propagate = backend.serve_blocked();
+
+        assert_eq!(propagate.len(), 2);
+        assert_eq!(first.try_recv().unwrap(), b"a".into());
+        assert_eq!(second.try_recv().unwrap(), b"b".into());
+        assert_eq!(backend.blocked_clients(), 1);
+
+        backend.unblock(third);
+        assert_eq!(backend.blocked_clients(), 0);
+        assert!(backend.lock_blocking().keys.is_empty());
+    }
+
+    #[test]
+    fn test_serve_blocked_skips_gone_clients() {
+        let backend = Backend::new();
+        let (_, gone) = blocked(backend.block(vec!["list".into()], lpop()));
+        let (_, mut waiting) = blocked(backend.block(vec!["list".into()], lpop()));
+        drop(gone);
+
+        backend
+            .push(b"list", ListEnd::Left, vec!["a".into()])
+            .unwrap();
+        backend.serve_blocked();
+
+        assert_eq!(waiting.try_recv().unwrap(), b"a".into());
+        assert_eq!(backend.blocked_clients(), 0);
+    }
+}
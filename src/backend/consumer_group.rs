@@ -0,0 +1,993 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+
+use bytes::Bytes;
+
+use super::stream::{Stream, StreamFields, StreamId};
+use super::{now_millis, Backend};
+use crate::command::CommandError;
+
+// An entry delivered to a consumer and not acknowledged yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingEntry {
+    pub consumer: Bytes,
+    // unix milliseconds of the last delivery
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consumer {
+    // unix milliseconds of the last interaction, and of the last one that
+    // read or claimed entries
+    pub seen_time: u64,
+    pub active_time: Option<u64>,
+    pub pending: BTreeSet<StreamId>,
+}
+
+impl Consumer {
+    pub fn new(now: u64) -> Self {
+        Self {
+            seen_time: now,
+            active_time: None,
+            pending: BTreeSet::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsumerGroup {
+    // ID of the last entry delivered to the group
+    pub last_id: StreamId,
+    // entries delivered up to `last_id`, None when unknown
+    pub entries_read: Option<u64>,
+    // pending entries of all the consumers
+    pub pending: BTreeMap<StreamId, PendingEntry>,
+    pub consumers: BTreeMap<Bytes, Consumer>,
+}
+
+// The ID XREADGROUP reads a stream from: `>` for the entries never delivered
+// to the group, or an ID to read the consumer's pending entries after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupReadId {
+    New,
+    Pending(StreamId),
+}
+
+// Entries read by a group, None for pending entries that were deleted.
+pub type GroupEntry = (StreamId, Option<StreamFields>);
+
+// The delivery time XCLAIM sets, from its IDLE or TIME option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClaimTime {
+    Idle(i64),
+    Unix(i64),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClaimOptions {
+    pub time: Option<ClaimTime>,
+    pub retry_count: Option<u64>,
+    // creates the pending entries that do not exist
+    pub force: bool,
+    // leaves the delivery counters unchanged
+    pub justid: bool,
+    pub last_id: Option<StreamId>,
+}
+
+// Reply of XPENDING without a range.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PendingSummary {
+    pub count: usize,
+    pub first: Option<StreamId>,
+    pub last: Option<StreamId>,
+    // consumers with pending entries, with how many they have
+    pub consumers: Vec<(Bytes, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingInfo {
+    pub id: StreamId,
+    pub consumer: Bytes,
+    pub idle: u64,
+    pub delivery_count: u64,
+}
+
+// Reply of XAUTOCLAIM: the ID to continue from, 0-0 once the whole pending
+// entries list was scanned, the claimed entries and the pending entries
+// dropped because they were deleted from the stream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutoClaimed {
+    pub cursor: StreamId,
+    pub claimed: Vec<GroupEntry>,
+    pub deleted: Vec<StreamId>,
+}
+
+// Replies of XINFO GROUPS and XINFO CONSUMERS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupInfo {
+    pub name: Bytes,
+    pub consumers: usize,
+    pub pending: usize,
+    pub last_id: StreamId,
+    pub entries_read: Option<u64>,
+    pub lag: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumerInfo {
+    pub name: Bytes,
+    pub pending: usize,
+    // milliseconds since the last interaction, and since the last one that
+    // read or claimed entries, None when it never did
+    pub idle: u64,
+    pub inactive: Option<u64>,
+}
+
+impl ConsumerGroup {
+    pub fn new(last_id: StreamId, entries_read: Option<u64>) -> Self {
+        Self {
+            last_id,
+            entries_read,
+            ..Default::default()
+        }
+    }
+
+    // Returns the consumer, creating it when missing.
+    pub fn consumer(&mut self, name: &[u8], now: u64) -> &mut Consumer {
+        if !self.consumers.contains_key(name) {
+            self.consumers
+                .insert(Bytes::copy_from_slice(name), Consumer::new(now));
+        }
+
+        self.consumers
+            .get_mut(name)
+            .expect("consumer was just created")
+    }
+
+    // Makes the entry pending for `consumer`, which must exist, taking it from
+    // the consumer it was pending for.
+    pub fn assign(&mut self, id: StreamId, consumer: &Bytes, delivery_time: u64, count: u64) {
+        let entry = PendingEntry {
+            consumer: consumer.clone(),
+            delivery_time,
+            delivery_count: count,
+        };
+
+        if let Some(previous) = self.pending.insert(id, entry) {
+            if let Some(previous) = self.consumers.get_mut(&previous.consumer) {
+                previous.pending.remove(&id);
+            }
+        }
+
+        if let Some(consumer) = self.consumers.get_mut(consumer) {
+            consumer.pending.insert(id);
+        }
+    }
+
+    pub fn acknowledge(&mut self, id: &StreamId) -> Option<PendingEntry> {
+        let entry = self.pending.remove(id)?;
+
+        if let Some(consumer) = self.consumers.get_mut(&entry.consumer) {
+            consumer.pending.remove(id);
+        }
+
+        Some(entry)
+    }
+}
+
+impl ClaimOptions {
+    // The delivery time claimed entries get, the current time when none was
+    // given, or when it is negative or in the future like in Redis.
+    pub fn delivery_time(&self, now: u64) -> u64 {
+        let time = match self.time {
+            Some(ClaimTime::Idle(idle)) => (now as i64).saturating_sub(idle),
+            Some(ClaimTime::Unix(time)) => time,
+            None => return now,
+        };
+
+        match time {
+            time if time < 0 || time as u64 > now => now,
+            time => time as u64,
+        }
+    }
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn no_such_key_or_group(key: &[u8], group: &[u8]) -> CommandError {
+    CommandError::NoGroup(format!(
+        "No such key '{}' or consumer group '{}'",
+        lossy(key),
+        lossy(group)
+    ))
+}
+
+pub fn no_such_group(key: &[u8], group: &[u8]) -> CommandError {
+    CommandError::NoGroup(format!(
+        "No such consumer group '{}' for key name '{}'",
+        lossy(group),
+        lossy(key)
+    ))
+}
+
+impl Stream {
+    fn group(&mut self, name: &[u8]) -> Option<&mut ConsumerGroup> {
+        self.groups.get_mut(name)
+    }
+
+    // Delivers the entry to the group, counting it as read.
+    fn deliver(&mut self, group: &[u8], consumer: &Bytes, id: StreamId, noack: bool, now: u64) {
+        let Some(entries_read) = self.groups.get(group).map(|group| group.entries_read) else {
+            return;
+        };
+
+        let entries_read = match entries_read {
+            Some(read) if !self.has_tombstones_after(id) => Some(read + 1),
+            _ if self.entries_added > 0 => self.entries_read_at(id),
+            read => read,
+        };
+
+        let Some(group) = self.group(group) else {
+            return;
+        };
+
+        group.entries_read = entries_read;
+        group.last_id = group.last_id.max(id);
+        if !noack {
+            group.assign(id, consumer, now, 1);
+        }
+    }
+
+    fn read_group(
+        &mut self,
+        group: &[u8],
+        consumer: &[u8],
+        id: GroupReadId,
+        count: Option<usize>,
+        noack: bool,
+        now: u64,
+    ) -> Vec<GroupEntry> {
+        let Some(group_ref) = self.group(group) else {
+            return Vec::new();
+        };
+
+        let consumer_ref = group_ref.consumer(consumer, now);
+        consumer_ref.seen_time = now;
+        let count = count.unwrap_or(usize::MAX);
+
+        let entries = match id {
+            GroupReadId::Pending(after) => {
+                let ids = consumer_ref
+                    .pending
+                    .range((Bound::Excluded(after), Bound::Unbounded))
+                    .take(count)
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                let entries = ids
+                    .into_iter()
+                    .map(|id| (id, self.get(&id).cloned()))
+                    .collect::<Vec<_>>();
+
+                // entries read again count as delivered again, like in Redis
+                if let Some(group) = self.group(group) {
+                    for (id, _) in entries.iter().filter(|(_, fields)| fields.is_some()) {
+                        if let Some(entry) = group.pending.get_mut(id) {
+                            entry.delivery_time = now;
+                            entry.delivery_count += 1;
+                        }
+                    }
+                }
+
+                entries
+            }
+            GroupReadId::New => {
+                let last_id = group_ref.last_id;
+                let consumer = Bytes::copy_from_slice(consumer);
+                let entries = self.after(last_id, Some(count));
+
+                for (id, _) in &entries {
+                    self.deliver(group, &consumer, *id, noack, now);
+                }
+
+                entries
+                    .into_iter()
+                    .map(|(id, fields)| (id, Some(fields)))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        if !entries.is_empty() {
+            if let Some(consumer) = self
+                .group(group)
+                .and_then(|g| g.consumers.get_mut(consumer))
+            {
+                consumer.active_time = Some(now);
+            }
+        }
+
+        entries
+    }
+
+    fn claim(
+        &mut self,
+        group: &[u8],
+        consumer: &[u8],
+        min_idle: u64,
+        ids: &[StreamId],
+        options: &ClaimOptions,
+        now: u64,
+    ) -> Vec<GroupEntry> {
+        let delivery_time = options.delivery_time(now);
+        let consumer = Bytes::copy_from_slice(consumer);
+        let mut claimed = Vec::new();
+
+        let Some(group_ref) = self.groups.get_mut(group) else {
+            return claimed;
+        };
+
+        if let Some(last_id) = options.last_id {
+            group_ref.last_id = group_ref.last_id.max(last_id);
+        }
+
+        for id in ids {
+            let Some(fields) = self.get(id).cloned() else {
+                // pending entries deleted from the stream are dropped
+                if let Some(group) = self.group(group) {
+                    group.acknowledge(id);
+                }
+                continue;
+            };
+
+            let Some(group) = self.group(group) else {
+                break;
+            };
+
+            let count = match group.pending.get(id) {
+                Some(entry) if now.saturating_sub(entry.delivery_time) < min_idle => continue,
+                Some(entry) => entry.delivery_count,
+                None if options.force => 0,
+                None => continue,
+            };
+
+            let count = match options.retry_count {
+                Some(retry_count) => retry_count,
+                None if options.justid => count,
+                None => count + 1,
+            };
+
+            let claimer = group.consumer(&consumer, now);
+            claimer.seen_time = now;
+            claimer.active_time = Some(now);
+            group.assign(*id, &consumer, delivery_time, count);
+
+            claimed.push((*id, (!options.justid).then_some(fields)));
+        }
+
+        claimed
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn auto_claim(
+        &mut self,
+        group: &[u8],
+        consumer: &[u8],
+        min_idle: u64,
+        start: StreamId,
+        count: usize,
+        justid: bool,
+        now: u64,
+    ) -> AutoClaimed {
+        let mut claimed = AutoClaimed::default();
+        let Some(group_ref) = self.groups.get(group) else {
+            return claimed;
+        };
+
+        // at most ten pending entries are looked at per entry to claim
+        let mut attempts = count.saturating_mul(10);
+        let mut scan = group_ref
+            .pending
+            .range(start..)
+            .map(|(id, entry)| (*id, entry.delivery_time))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let consumer = Bytes::copy_from_slice(consumer);
+
+        loop {
+            if attempts == 0 || claimed.claimed.len() == count {
+                claimed.cursor = scan.next().map_or(StreamId::MIN, |(id, _)| id);
+                break;
+            }
+
+            let Some((id, delivery_time)) = scan.next() else {
+                claimed.cursor = StreamId::MIN;
+                break;
+            };
+            attempts -= 1;
+
+            let fields = self.get(&id).cloned();
+            let Some(group) = self.group(group) else {
+                break;
+            };
+
+            let Some(fields) = fields else {
+                group.acknowledge(&id);
+                claimed.deleted.push(id);
+                continue;
+            };
+
+            if now.saturating_sub(delivery_time) < min_idle {
+                continue;
+            }
+
+            let count = group.pending[&id].delivery_count + u64::from(!justid);
+            let claimer = group.consumer(&consumer, now);
+            claimer.seen_time = now;
+            claimer.active_time = Some(now);
+            group.assign(id, &consumer, now, count);
+
+            claimed.claimed.push((id, (!justid).then_some(fields)));
+        }
+
+        // the consumer is created even when nothing was claimed
+        if let Some(group) = self.group(group) {
+            group.consumer(&consumer, now).seen_time = now;
+        }
+
+        claimed
+    }
+}
+
+impl Backend {
+    // Creates a consumer group reading after `id`, or after the last entry
+    // when None, for `$`.
+    pub fn xgroup_create(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        id: Option<StreamId>,
+        mkstream: bool,
+        entries_read: Option<u64>,
+    ) -> Result<(), CommandError> {
+        let create = |stream: &mut Stream| {
+            if stream.groups.contains_key(group) {
+                return (Err(CommandError::BusyGroup), false);
+            }
+
+            let id = id.unwrap_or(stream.last_id);
+            stream.groups.insert(
+                Bytes::copy_from_slice(group),
+                ConsumerGroup::new(id, entries_read),
+            );
+            (Ok(()), true)
+        };
+
+        match mkstream {
+            true => self.write(key, create)?,
+            false => self
+                .update(key, create)?
+                .unwrap_or_else(|| Err(key_required())),
+        }
+    }
+
+    pub fn xgroup_setid(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        id: Option<StreamId>,
+        entries_read: Option<u64>,
+    ) -> Result<(), CommandError> {
+        self.update(key, |stream: &mut Stream| {
+            let last_id = stream.last_id;
+            let Some(group_ref) = stream.group(group) else {
+                return (Err(no_such_group(key, group)), false);
+            };
+
+            group_ref.last_id = id.unwrap_or(last_id);
+            group_ref.entries_read = entries_read;
+            (Ok(()), true)
+        })?
+        .unwrap_or_else(|| Err(key_required()))
+    }
+
+    // Returns whether the group existed.
+    pub fn xgroup_destroy(&self, key: &[u8], group: &[u8]) -> Result<bool, CommandError> {
+        let destroyed = self.update(key, |stream: &mut Stream| {
+            let destroyed = stream.groups.remove(group).is_some();
+            (destroyed, destroyed)
+        })?;
+        let destroyed = destroyed.ok_or_else(key_required)?;
+
+        // clients blocked reading the group give up on it
+        if destroyed {
+            self.signal_ready(key);
+        }
+
+        Ok(destroyed)
+    }
+
+    // Returns whether the consumer was created.
+    pub fn xgroup_createconsumer(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        consumer: &[u8],
+    ) -> Result<bool, CommandError> {
+        self.update(key, |stream: &mut Stream| {
+            let Some(group_ref) = stream.group(group) else {
+                return (Err(no_such_group(key, group)), false);
+            };
+
+            let created = !group_ref.consumers.contains_key(consumer);
+            group_ref.consumer(consumer, now_millis());
+            (Ok(created), created)
+        })?
+        .unwrap_or_else(|| Err(key_required()))
+    }
+
+    // Deletes the consumer with its pending entries, returning how many it had.
+    pub fn xgroup_delconsumer(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        consumer: &[u8],
+    ) -> Result<usize, CommandError> {
+        self.update(key, |stream: &mut Stream| {
+            let Some(group_ref) = stream.group(group) else {
+                return (Err(no_such_group(key, group)), false);
+            };
+
+            let Some(removed) = group_ref.consumers.remove(consumer) else {
+                return (Ok(0), false);
+            };
+
+            for id in &removed.pending {
+                group_ref.pending.remove(id);
+            }
+            (Ok(removed.pending.len()), true)
+        })?
+        .unwrap_or_else(|| Err(key_required()))
+    }
+
+    // Reads the streams as `consumer` of `group`, which must exist in all of
+    // them. Streams with no new entries are left out, while the pending
+    // entries of the consumer are replied for every stream, even when none.
+    pub fn xreadgroup(
+        &self,
+        group: &[u8],
+        consumer: &[u8],
+        streams: &[(Bytes, GroupReadId)],
+        count: Option<usize>,
+        noack: bool,
+    ) -> Result<Vec<(Bytes, Vec<GroupEntry>)>, CommandError> {
+        for (key, _) in streams {
+            if !self.has_group(key, group)? {
+                return Err(CommandError::NoGroup(format!(
+                    "No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+                    lossy(key),
+                    lossy(group)
+                )));
+            }
+        }
+
+        let now = now_millis();
+        let mut read = Vec::new();
+
+        for (key, id) in streams {
+            let entries = self
+                .update(key, |stream: &mut Stream| {
+                    // the consumer is created and seen even without entries
+                    let entries = stream.read_group(group, consumer, *id, count, noack, now);
+                    (entries, true)
+                })?
+                .unwrap_or_default();
+
+            if *id != GroupReadId::New || !entries.is_empty() {
+                read.push((key.clone(), entries));
+            }
+        }
+
+        Ok(read)
+    }
+
+    // Whether the stream at key has the group, errors for other types.
+    pub fn has_group(&self, key: &[u8], group: &[u8]) -> Result<bool, CommandError> {
+        let found = self.read(key, |stream: &Stream| stream.groups.contains_key(group))?;
+        Ok(found.unwrap_or(false))
+    }
+
+    // Whether entries were added after the last one delivered to the group.
+    pub fn has_undelivered(&self, key: &[u8], group: &[u8]) -> Result<bool, CommandError> {
+        let undelivered = self.read(key, |stream: &Stream| {
+            let group = stream.groups.get(group)?;
+            Some(!stream.after(group.last_id, Some(1)).is_empty())
+        })?;
+
+        Ok(undelivered.flatten().unwrap_or(false))
+    }
+
+    pub fn xack(&self, key: &[u8], group: &[u8], ids: &[StreamId]) -> Result<usize, CommandError> {
+        let acknowledged = self.update(key, |stream: &mut Stream| {
+            let Some(group) = stream.group(group) else {
+                return (0, false);
+            };
+
+            let acknowledged = ids
+                .iter()
+                .filter(|id| group.acknowledge(id).is_some())
+                .count();
+            (acknowledged, acknowledged > 0)
+        })?;
+
+        Ok(acknowledged.unwrap_or(0))
+    }
+
+    pub fn xpending_summary(
+        &self,
+        key: &[u8],
+        group: &[u8],
+    ) -> Result<PendingSummary, CommandError> {
+        let summary = self.read(key, |stream: &Stream| {
+            let group = stream.groups.get(group)?;
+
+            Some(PendingSummary {
+                count: group.pending.len(),
+                first: group.pending.keys().next().copied(),
+                last: group.pending.keys().next_back().copied(),
+                consumers: group
+                    .consumers
+                    .iter()
+                    .filter(|(_, consumer)| !consumer.pending.is_empty())
+                    .map(|(name, consumer)| (name.clone(), consumer.pending.len()))
+                    .collect(),
+            })
+        })?;
+
+        summary
+            .flatten()
+            .ok_or_else(|| no_such_key_or_group(key, group))
+    }
+
+    // Pending entries between both inclusive bounds, optionally only the ones
+    // of a consumer or idle for at least `min_idle` milliseconds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn xpending(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        start: StreamId,
+        end: StreamId,
+        count: usize,
+        consumer: Option<&[u8]>,
+        min_idle: Option<u64>,
+    ) -> Result<Vec<PendingInfo>, CommandError> {
+        let now = now_millis();
+
+        let pending = self.read(key, |stream: &Stream| {
+            let group = stream.groups.get(group)?;
+            if start > end {
+                return Some(Vec::new());
+            }
+
+            let ids: Box<dyn Iterator<Item = &StreamId>> = match consumer {
+                Some(consumer) => match group.consumers.get(consumer) {
+                    Some(consumer) => Box::new(consumer.pending.range(start..=end)),
+                    None => Box::new(std::iter::empty()),
+                },
+                None => Box::new(group.pending.range(start..=end).map(|(id, _)| id)),
+            };
+
+            let pending = ids
+                .filter_map(|id| {
+                    let entry = group.pending.get(id)?;
+                    Some(PendingInfo {
+                        id: *id,
+                        consumer: entry.consumer.clone(),
+                        idle: now.saturating_sub(entry.delivery_time),
+                        delivery_count: entry.delivery_count,
+                    })
+                })
+                .filter(|info| min_idle.is_none_or(|min_idle| info.idle >= min_idle))
+                .take(count)
+                .collect();
+
+            Some(pending)
+        })?;
+
+        pending
+            .flatten()
+            .ok_or_else(|| no_such_key_or_group(key, group))
+    }
+
+    // Transfers the pending entries idle for at least `min_idle` milliseconds
+    // to `consumer`.
+    pub fn xclaim(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        consumer: &[u8],
+        min_idle: u64,
+        ids: &[StreamId],
+        options: &ClaimOptions,
+    ) -> Result<Vec<GroupEntry>, CommandError> {
+        if !self.has_group(key, group)? {
+            return Err(no_such_key_or_group(key, group));
+        }
+
+        let claimed = self.update(key, |stream: &mut Stream| {
+            let claimed = stream.claim(group, consumer, min_idle, ids, options, now_millis());
+            (claimed, true)
+        })?;
+
+        Ok(claimed.unwrap_or_default())
+    }
+
+    // Claims up to `count` entries idle for at least `min_idle` milliseconds,
+    // scanning the pending entries from `start`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn xautoclaim(
+        &self,
+        key: &[u8],
+        group: &[u8],
+        consumer: &[u8],
+        min_idle: u64,
+        start: StreamId,
+        count: usize,
+        justid: bool,
+    ) -> Result<AutoClaimed, CommandError> {
+        if !self.has_group(key, group)? {
+            return Err(no_such_key_or_group(key, group));
+        }
+
+        let claimed = self.update(key, |stream: &mut Stream| {
+            let claimed = stream.auto_claim(
+                group,
+                consumer,
+                min_idle,
+                start,
+                count,
+                justid,
+                now_millis(),
+            );
+            (claimed, true)
+        })?;
+
+        Ok(claimed.unwrap_or_default())
+    }
+}
+
+impl Backend {
+    pub fn xinfo_groups(&self, key: &[u8]) -> Result<Option<Vec<GroupInfo>>, CommandError> {
+        self.read(key, |stream: &Stream| {
+            stream
+                .groups
+                .iter()
+                .map(|(name, group)| GroupInfo {
+                    name: name.clone(),
+                    consumers: group.consumers.len(),
+                    pending: group.pending.len(),
+                    last_id: group.last_id,
+                    entries_read: group.entries_read,
+                    lag: stream.lag(group),
+                })
+                .collect()
+        })
+    }
+
+    pub fn xinfo_consumers(
+        &self,
+        key: &[u8],
+        group: &[u8],
+    ) -> Result<Option<Vec<ConsumerInfo>>, CommandError> {
+        let now = now_millis();
+
+        let consumers = self.read(key, |stream: &Stream| {
+            let group = stream
+                .groups
+                .get(group)
+                .ok_or_else(|| no_such_group(key, group))?;
+
+            let consumers = group
+                .consumers
+                .iter()
+                .map(|(name, consumer)| ConsumerInfo {
+                    name: name.clone(),
+                    pending: consumer.pending.len(),
+                    idle: now.saturating_sub(consumer.seen_time),
+                    inactive: consumer.active_time.map(|time| now.saturating_sub(time)),
+                })
+                .collect();
+            Ok(consumers)
+        })?;
+
+        consumers.transpose()
+    }
+}
+
+fn key_required() -> CommandError {
+    CommandError::InvalidArgument(
+        "The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically."
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    fn id(ms: u64, seq: u64) -> StreamId {
+        StreamId::new(ms, seq)
+    }
+
+    fn backend_with_group(entries: u64) -> Backend {
+        let backend = Backend::new();
+        for seq in 1..=entries {
+            let fields = vec![("f".into(), "v".into())];
+            backend
+                .xadd(b"s", XAddId::Explicit(id(1, seq)), fields, false, None)
+                .unwrap();
+        }
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+        backend
+    }
+
+    fn ids(entries: &[GroupEntry]) -> Vec<StreamId> {
+        entries.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn test_backend_xgroup_create() {
+        let backend = backend_with_group(1);
+
+        assert!(matches!(
+            backend.xgroup_create(b"s", b"g", None, false, None),
+            Err(CommandError::BusyGroup)
+        ));
+        assert!(backend
+            .xgroup_create(b"missing", b"g", None, false, None)
+            .is_err());
+        backend
+            .xgroup_create(b"empty", b"g", None, true, None)
+            .unwrap();
+        assert_eq!(backend.xlen(b"empty").unwrap(), 0);
+
+        // streams are kept once their groups are gone
+        assert!(backend.xgroup_destroy(b"empty", b"g").unwrap());
+        assert!(!backend.xgroup_destroy(b"empty", b"g").unwrap());
+        assert!(backend.exists(b"empty"));
+    }
+
+    #[test]
+    fn test_backend_xreadgroup_xack() {
+        let backend = backend_with_group(3);
+        let new = [("s".into(), GroupReadId::New)];
+
+        let read = backend
+            .xreadgroup(b"g", b"alice", &new, Some(2), false)
+            .unwrap();
+        assert_eq!(ids(&read[0].1), vec![id(1, 1), id(1, 2)]);
+        let read = backend.xreadgroup(b"g", b"bob", &new, None, false).unwrap();
+        assert_eq!(ids(&read[0].1), vec![id(1, 3)]);
+        assert!(backend
+            .xreadgroup(b"g", b"bob", &new, None, false)
+            .unwrap()
+            .is_empty());
+
+        let summary = backend.xpending_summary(b"s", b"g").unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(
+            summary.consumers,
+            vec![("alice".into(), 2), ("bob".into(), 1)]
+        );
+
+        // deleted pending entries are read back without their fields
+        backend.xdel(b"s", &[id(1, 1)]).unwrap();
+        let history = [("s".into(), GroupReadId::Pending(StreamId::MIN))];
+        let read = backend
+            .xreadgroup(b"g", b"alice", &history, None, false)
+            .unwrap();
+        assert_eq!(read[0].1[0], (id(1, 1), None));
+        assert_eq!(read[0].1[1].0, id(1, 2));
+
+        assert_eq!(
+            backend
+                .xack(b"s", b"g", &[id(1, 1), id(1, 2), id(9, 9)])
+                .unwrap(),
+            2
+        );
+        let read = backend
+            .xreadgroup(b"g", b"alice", &history, None, false)
+            .unwrap();
+        assert!(read[0].1.is_empty());
+
+        assert!(backend
+            .xreadgroup(b"missing", b"alice", &new, None, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_backend_xclaim() {
+        let backend = backend_with_group(2);
+        let new = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &new, None, false)
+            .unwrap();
+
+        let options = ClaimOptions::default();
+        let ids_ = [id(1, 1), id(1, 2)];
+        assert!(backend
+            .xclaim(b"s", b"g", b"bob", 60_000, &ids_, &options)
+            .unwrap()
+            .is_empty());
+
+        let claimed = backend
+            .xclaim(b"s", b"g", b"bob", 0, &ids_, &options)
+            .unwrap();
+        assert_eq!(ids(&claimed), ids_);
+
+        let pending = backend
+            .xpending(
+                b"s",
+                b"g",
+                StreamId::MIN,
+                StreamId::MAX,
+                10,
+                Some(b"bob"),
+                None,
+            )
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].delivery_count, 2);
+
+        let options = ClaimOptions {
+            time: Some(ClaimTime::Idle(1000)),
+            ..Default::default()
+        };
+        backend
+            .xclaim(b"s", b"g", b"alice", 0, &[id(1, 1)], &options)
+            .unwrap();
+        let pending = backend
+            .xpending(
+                b"s",
+                b"g",
+                StreamId::MIN,
+                StreamId::MAX,
+                10,
+                None,
+                Some(1000),
+            )
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].consumer, "alice");
+    }
+
+    #[test]
+    fn test_backend_xautoclaim() {
+        let backend = backend_with_group(3);
+        let new = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &new, None, false)
+            .unwrap();
+        backend.xdel(b"s", &[id(1, 2)]).unwrap();
+
+        let claimed = backend
+            .xautoclaim(b"s", b"g", b"bob", 0, StreamId::MIN, 1, true)
+            .unwrap();
+        assert_eq!(claimed.claimed, vec![(id(1, 1), None)]);
+        assert_eq!(claimed.cursor, id(1, 2));
+
+        let claimed = backend
+            .xautoclaim(b"s", b"g", b"bob", 0, claimed.cursor, 5, false)
+            .unwrap();
+        assert_eq!(ids(&claimed.claimed), vec![id(1, 3)]);
+        assert_eq!(claimed.deleted, vec![id(1, 2)]);
+        assert_eq!(claimed.cursor, StreamId::MIN);
+        assert_eq!(backend.xpending_summary(b"s", b"g").unwrap().count, 2);
+    }
+}
@@ -0,0 +1,238 @@
+use bytes::Bytes;
+use rand::seq::{IteratorRandom, SliceRandom};
+
+use super::string::{parse_float, parse_int};
+use super::{Backend, Hash};
+use crate::command::CommandError;
+
+impl Backend {
+    // Returns the number of fields created, updated fields are not counted.
+    pub fn hset(&self, key: &[u8], fields: Vec<(Bytes, Bytes)>) -> Result<usize, CommandError> {
+        self.write(key, |hash: &mut Hash| {
+            let mut changed = false;
+            let created = fields
+                .into_iter()
+                .filter(
+                    |(field, value)| match hash.insert(field.clone(), value.clone()) {
+                        Some(previous) => {
+                            changed |= previous != value;
+                            false
+                        }
+                        None => {
+                            changed = true;
+                            true
+                        }
+                    },
+                )
+                .count();
+            (created, changed)
+        })
+    }
+
+    // Returns true when the field was set, existing fields are left alone.
+    pub fn hsetnx(&self, key: &[u8], field: Bytes, value: Bytes) -> Result<bool, CommandError> {
+        self.write(key, |hash: &mut Hash| match hash.contains_key(&field) {
+            true => (false, false),
+            false => {
+                hash.insert(field, value);
+                (true, true)
+            }
+        })
+    }
+
+    pub fn hget(&self, key: &[u8], field: &[u8]) -> Result<Option<Bytes>, CommandError> {
+        let value = self.read(key, |hash: &Hash| hash.get(field).cloned())?;
+        Ok(value.flatten())
+    }
+
+    pub fn hgetall(&self, key: &[u8]) -> Result<Option<Hash>, CommandError> {
+        self.read(key, |hash: &Hash| hash.clone())
+    }
+
+    pub fn hdel(&self, key: &[u8], fields: &[Bytes]) -> Result<usize, CommandError> {
+        let removed = self.update(key, |hash: &mut Hash| {
+            let removed = fields
+                .iter()
+                .filter(|field| hash.remove(*field).is_some())
+                .count();
+            (removed, removed > 0)
+        })?;
+
+        Ok(removed.unwrap_or(0))
+    }
+
+    pub fn hexists(&self, key: &[u8], field: &[u8]) -> Result<bool, CommandError> {
+        let result = self.read(key, |hash: &Hash| hash.contains_key(field))?;
+        Ok(result.unwrap_or(false))
+    }
+
+    pub fn hlen(&self, key: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |hash: &Hash| hash.len())?;
+        Ok(len.unwrap_or(0))
+    }
+
+    pub fn hkeys(&self, key: &[u8]) -> Result<Vec<Bytes>, CommandError> {
+        let fields = self.read(key, |hash: &Hash| hash.keys().cloned().collect())?;
+        Ok(fields.unwrap_or_default())
+    }
+
+    pub fn hvals(&self, key: &[u8]) -> Result<Vec<Bytes>, CommandError> {
+        let values = self.read(key, |hash: &Hash| hash.values().cloned().collect())?;
+        Ok(values.unwrap_or_default())
+    }
+
+    pub fn hstrlen(&self, key: &[u8], field: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |hash: &Hash| {
+            hash.get(field).map_or(0, |value| value.len())
+        })?;
+        Ok(len.unwrap_or(0))
+    }
+
+    // Missing fields count as 0. Returns the value after the increment.
+    pub fn hincrby(&self, key: &[u8], field: Bytes, increment: i64) -> Result<i64, CommandError> {
+        self.write(key, |hash: &mut Hash| {
+            let current = match hash.get(&field).map(|value| parse_int(value)) {
+                Some(Some(current)) => current,
+                Some(None) => {
+                    let error = "hash value is not an integer".to_string();
+                    return (Err(CommandError::InvalidArgument(error)), false);
+                }
+                None => 0,
+            };
+
+            let Some(value) = current.checked_add(increment) else {
+                let error = "increment or decrement would overflow".to_string();
+                return (Err(CommandError::InvalidArgument(error)), false);
+            };
+
+            hash.insert(field, value.to_string().into());
+            (Ok(value), true)
+        })?
+    }
+
+    // Like `hincrby` for floats, the value is stored in its formatted form.
+    pub fn hincrbyfloat(
+        &self,
+        key: &[u8],
+        field: Bytes,
+        increment: f64,
+    ) -> Result<f64, CommandError> {
+        self.write(key, |hash: &mut Hash| {
+            let current = match hash.get(&field).map(|value| parse_float(value)) {
+                Some(Some(current)) => current,
+                Some(None) => {
+                    let error = "hash value is not a float".to_string();
+                    return (Err(CommandError::InvalidArgument(error)), false);
+                }
+                None => 0.0,
+            };
+
+            let value = current + increment;
+            if !value.is_finite() {
+                let error = "increment would produce NaN or Infinity".to_string();
+                return (Err(CommandError::InvalidArgument(error)), false);
+            }
+
+            hash.insert(field, value.to_string().into());
+            (Ok(value), true)
+        })?
+    }
+
+    // Returns up to `count` distinct random fields with their values, or
+    // exactly `-count` fields that may repeat when count is negative.
+    pub fn hrandfield(&self, key: &[u8], count: i64) -> Result<Vec<(Bytes, Bytes)>, CommandError> {
+        let fields = self.read(key, |hash: &Hash| {
+            let mut rng = rand::thread_rng();
+            let pairs = hash
+                .iter()
+                .map(|(field, value)| (field.clone(), value.clone()));
+
+            match count {
+                count if count >= 0 => pairs.choose_multiple(&mut rng, count as usize),
+                count => {
+                    let pairs = pairs.collect::<Vec<_>>();
+                    (0..count.unsigned_abs())
+                        .filter_map(|_| pairs.choose(&mut rng).cloned())
+                        .collect()
+                }
+            }
+        })?;
+
+        Ok(fields.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(items: &[(&'static str, &'static str)]) -> Vec<(Bytes, Bytes)> {
+        items
+            .iter()
+            .map(|(field, value)| (Bytes::from_static(field.as_bytes()), Bytes::from(*value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_backend_hset_hdel() {
+        let backend = Backend::new();
+
+        assert_eq!(
+            backend
+                .hset(b"hash", fields(&[("a", "1"), ("b", "2")]))
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            backend
+                .hset(b"hash", fields(&[("b", "3"), ("c", "4")]))
+                .unwrap(),
+            1
+        );
+        assert!(!backend.hsetnx(b"hash", "a".into(), "5".into()).unwrap());
+        assert_eq!(backend.hget(b"hash", b"a").unwrap(), Some("1".into()));
+        assert_eq!(backend.hstrlen(b"hash", b"b").unwrap(), 1);
+        assert!(backend.hexists(b"hash", b"c").unwrap());
+
+        assert_eq!(backend.hdel(b"hash", &["a".into(), "x".into()]).unwrap(), 1);
+        assert_eq!(backend.hlen(b"hash").unwrap(), 2);
+        assert_eq!(backend.hdel(b"hash", &["b".into(), "c".into()]).unwrap(), 2);
+        assert!(!backend.exists(b"hash"));
+        assert_eq!(backend.hdel(b"hash", &["b".into()]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_backend_hincrby() {
+        let backend = Backend::new();
+
+        assert_eq!(backend.hincrby(b"hash", "n".into(), 5).unwrap(), 5);
+        assert_eq!(backend.hincrby(b"hash", "n".into(), -7).unwrap(), -2);
+        assert_eq!(backend.hget(b"hash", b"n").unwrap(), Some("-2".into()));
+        assert!(backend.hincrby(b"hash", "n".into(), i64::MIN).is_err());
+
+        assert_eq!(
+            backend.hincrbyfloat(b"hash", "n".into(), 0.5).unwrap(),
+            -1.5
+        );
+        assert_eq!(backend.hget(b"hash", b"n").unwrap(), Some("-1.5".into()));
+        assert!(backend.hincrby(b"hash", "n".into(), 1).is_err());
+        assert!(backend
+            .hincrbyfloat(b"hash", "n".into(), f64::INFINITY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_backend_hrandfield() {
+        let backend = Backend::new();
+        backend
+            .hset(b"hash", fields(&[("a", "1"), ("b", "2")]))
+            .unwrap();
+
+        let mut distinct = backend.hrandfield(b"hash", 5).unwrap();
+        distinct.sort();
+        assert_eq!(distinct, fields(&[("a", "1"), ("b", "2")]));
+
+        assert_eq!(backend.hrandfield(b"hash", -5).unwrap().len(), 5);
+        assert!(backend.hrandfield(b"missing", -5).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,494 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+use super::Backend;
+use crate::command::CommandError;
+
+// HyperLogLogs are strings in the format Redis uses, so dumps and GET
+// replies can be fed to a real Redis. A 16 byte header ("HYLL", encoding,
+// 3 unused bytes, cached cardinality) is followed by 16384 6 bit registers,
+// either packed (dense) or run length encoded (sparse).
+const MAGIC: &[u8] = b"HYLL";
+const HEADER_LEN: usize = 16;
+const DENSE: u8 = 0;
+const SPARSE: u8 = 1;
+
+const P: u32 = 14;
+const Q: u32 = 64 - P;
+const REGISTERS: usize = 1 << P;
+const REGISTER_BITS: usize = 6;
+const DENSE_LEN: usize = HEADER_LEN + (REGISTERS * REGISTER_BITS).div_ceil(8);
+const ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+// Sparse values above it, or strings larger than Redis' default
+// hll-sparse-max-bytes, switch to the dense encoding.
+const SPARSE_MAX_VALUE: u8 = 32;
+const SPARSE_MAX_LEN: usize = 3000;
+
+// Sparse opcodes: ZERO 00xxxxxx, XZERO 01xxxxxx xxxxxxxx and VAL 1vvvvvxx.
+const ZERO_MAX_RUN: usize = 64;
+const XZERO_MAX_RUN: usize = 16384;
+const VAL_MAX_RUN: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Hll {
+    dense: bool,
+    registers: Vec<u8>,
+    // cached cardinality, None when it has to be computed again
+    cached: Option<u64>,
+}
+
+impl Hll {
+    fn new() -> Self {
+        Self {
+            dense: false,
+            registers: vec![0; REGISTERS],
+            cached: Some(0),
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CommandError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(CommandError::NotHll);
+        }
+
+        let mut card = [0; 8];
+        card.copy_from_slice(&bytes[8..16]);
+        let cached = match card[7] & 0x80 {
+            0 => Some(u64::from_le_bytes(card)),
+            _ => None,
+        };
+
+        let data = &bytes[HEADER_LEN..];
+        let registers = match bytes[4] {
+            DENSE if bytes.len() == DENSE_LEN => {
+                (0..REGISTERS).map(|index| dense_get(data, index)).collect()
+            }
+            SPARSE => sparse_decode(data).ok_or(CommandError::CorruptedHll)?,
+            _ => return Err(CommandError::NotHll),
+        };
+
+        Ok(Self {
+            dense: bytes[4] == DENSE,
+            registers,
+            cached,
+        })
+    }
+
+    fn encode(&mut self) -> Bytes {
+        let sparse = match self.dense {
+            true => None,
+            false => sparse_encode(&self.registers)
+                .filter(|sparse| HEADER_LEN + sparse.len() <= SPARSE_MAX_LEN),
+        };
+
+        // once dense, never sparse again
+        self.dense = sparse.is_none();
+
+        let mut bytes = BytesMut::with_capacity(DENSE_LEN);
+        bytes.put_slice(MAGIC);
+        bytes.put_u8(if self.dense { DENSE } else { SPARSE });
+        bytes.put_slice(&[0; 3]);
+        match self.cached {
+            Some(card) => bytes.put_u64_le(card),
+            None => bytes.put_slice(&[0, 0, 0, 0, 0, 0, 0, 0x80]),
+        }
+
+        match sparse {
+            Some(sparse) => bytes.put_slice(&sparse),
+            None => {
+                let mut data = vec![0; DENSE_LEN - HEADER_LEN];
+                for (index, value) in self.registers.iter().enumerate() {
+                    dense_set(&mut data, index, *value);
+                }
+                bytes.put_slice(&data);
+            }
+        }
+
+        bytes.freeze()
+    }
+
+    // Returns true when a register changed.
+    fn add(&mut self, element: &[u8]) -> bool {
+        let (index, count) = pattern(element);
+
+        if self.registers[index] >= count {
+            return false;
+        }
+
+        self.registers[index] = count;
+        self.cached = None;
+        true
+    }
+
+    fn merge(&mut self, other: &Hll) {
+        for (register, value) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*value);
+        }
+
+        self.dense |= other.dense;
+        self.cached = None;
+    }
+
+    // The improved estimator by Otmar Ertl Redis uses, working from the
+    // histogram of the register values.
+    fn count(&self) -> u64 {
+        let mut histogram = [0u32; 64];
+        for value in &self.registers {
+            histogram[*value as usize] += 1;
+        }
+
+        let m = REGISTERS as f64;
+        let mut z = m * tau((m - histogram[Q as usize + 1] as f64) / m);
+        for j in (1..=Q as usize).rev() {
+            z += histogram[j] as f64;
+            z *= 0.5;
+        }
+        z += m * sigma(histogram[0] as f64 / m);
+
+        (ALPHA_INF * m * m / z).round() as u64
+    }
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if previous == z {
+            return z / 3.0;
+        }
+    }
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if previous == z {
+            return z;
+        }
+    }
+}
+
+// MurmurHash64A with the seed Redis uses, so elements land in the same
+// registers.
+fn murmur_hash64a(key: &[u8]) -> u64 {
+    const SEED: u64 = 0xadc8_3b19;
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+
+    let mut h = SEED ^ (key.len() as u64).wrapping_mul(M);
+
+    let mut chunks = key.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+// Register index of the element, and the length of the run of zeros in the
+// rest of its hash plus one.
+fn pattern(element: &[u8]) -> (usize, u8) {
+    let hash = murmur_hash64a(element);
+    let index = (hash & (REGISTERS as u64 - 1)) as usize;
+    let rest = (hash >> P) | (1 << Q);
+
+    (index, rest.trailing_zeros() as u8 + 1)
+}
+
+// Registers are packed from the least significant bits of each byte, a
+// register may span two bytes.
+fn dense_get(data: &[u8], index: usize) -> u8 {
+    let bit = index * REGISTER_BITS;
+    let (byte, shift) = (bit / 8, bit % 8);
+    let low = data[byte] as u16 >> shift;
+    let high = (data.get(byte + 1).copied().unwrap_or(0) as u16) << (8 - shift);
+
+    ((low | high) & 0x3f) as u8
+}
+
+fn dense_set(data: &mut [u8], index: usize, value: u8) {
+    let bit = index * REGISTER_BITS;
+    let (byte, shift) = (bit / 8, bit % 8);
+
+    data[byte] &= !(0x3f << shift);
+    data[byte] |= value << shift;
+
+    if shift > 8 - REGISTER_BITS {
+        data[byte + 1] &= !(0x3f >> (8 - shift));
+        data[byte + 1] |= value >> (8 - shift);
+    }
+}
+
+// None when the runs do not cover exactly all the registers.
+fn sparse_decode(data: &[u8]) -> Option<Vec<u8>> {
+    let mut registers = Vec::with_capacity(REGISTERS);
+    let mut bytes = data.iter();
+
+    while let Some(&op) = bytes.next() {
+        let (value, run) = match op >> 6 {
+            0b00 => (0, (op & 0x3f) as usize + 1),
+            0b01 => {
+                let low = *bytes.next()?;
+                (0, (((op & 0x3f) as usize) << 8 | low as usize) + 1)
+            }
+            _ => (((op >> 2) & 0x1f) + 1, (op & 0x03) as usize + 1),
+        };
+
+        if registers.len() + run > REGISTERS {
+            return None;
+        }
+        registers.extend(std::iter::repeat_n(value, run));
+    }
+
+    (registers.len() == REGISTERS).then_some(registers)
+}
+
+// None when a register is too large for the sparse encoding.
+fn sparse_encode(registers: &[u8]) -> Option<Vec<u8>> {
+    let mut data = vec![];
+    let mut index = 0;
+
+    while index < registers.len() {
+        let value = registers[index];
+        if value > SPARSE_MAX_VALUE {
+            return None;
+        }
+
+        let run = registers[index..]
+            .iter()
+            .take_while(|other| **other == value)
+            .count();
+
+        match value {
+            0 => {
+                let mut left = run;
+                while left > 0 {
+                    let len = left.min(XZERO_MAX_RUN);
+                    match len > ZERO_MAX_RUN {
+                        true => {
+                            data.push(0x40 | ((len - 1) >> 8) as u8);
+                            data.push(((len - 1) & 0xff) as u8);
+                        }
+                        false => data.push((len - 1) as u8),
+                    }
+                    left -= len;
+                }
+            }
+            value => {
+                let mut left = run;
+                while left > 0 {
+                    let len = left.min(VAL_MAX_RUN);
+                    data.push(0x80 | ((value - 1) << 2) | (len - 1) as u8);
+                    left -= len;
+                }
+            }
+        }
+
+        index += run;
+    }
+
+    Some(data)
+}
+
+impl Backend {
+    // Returns true when a register changed or the key was created.
+    pub fn pfadd(&self, key: &[u8], elements: &[Bytes]) -> Result<bool, CommandError> {
+        self.write_string(key, |current| {
+            let (mut hll, created) = match current {
+                Some(current) => (Hll::decode(current)?, false),
+                None => (Hll::new(), true),
+            };
+
+            let mut changed = created;
+            for element in elements {
+                changed |= hll.add(element);
+            }
+
+            Ok((hll.encode(), changed))
+        })
+    }
+
+    // Several keys are merged on the fly. The cardinality of a single key is
+    // cached in its header, callers hold the keyspace lock exclusively.
+    pub fn pfcount(&self, keys: &[Bytes]) -> Result<u64, CommandError> {
+        if let [key] = keys {
+            let Some(current) = self.get(key)? else {
+                return Ok(0);
+            };
+
+            let mut hll = Hll::decode(&current)?;
+            if let Some(card) = hll.cached {
+                return Ok(card);
+            }
+
+            let card = hll.count();
+            hll.cached = Some(card);
+            self.write_string(key, |_| Ok((hll.encode(), ())))?;
+
+            return Ok(card);
+        }
+
+        let mut merged = Hll::new();
+        for key in keys {
+            if let Some(current) = self.get(key)? {
+                merged.merge(&Hll::decode(&current)?);
+            }
+        }
+
+        Ok(merged.count())
+    }
+
+    // The destination is merged too when it exists. The result is dense when
+    // any of the inputs is.
+    pub fn pfmerge(&self, destination: &[u8], keys: &[Bytes]) -> Result<(), CommandError> {
+        let mut merged = Hll::new();
+        for key in keys {
+            if let Some(current) = self.get(key)? {
+                merged.merge(&Hll::decode(&current)?);
+            }
+        }
+
+        self.write_string(destination, |current| {
+            if let Some(current) = current {
+                merged.merge(&Hll::decode(current)?);
+            }
+
+            Ok((merged.encode(), ()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(range: std::ops::Range<usize>) -> Vec<Bytes> {
+        range
+            .map(|i| Bytes::from(format!("element:{}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_hll_encoding_round_trip() {
+        let mut hll = Hll::new();
+        let empty = hll.encode();
+
+        // a single XZERO opcode covers all the registers
+        assert_eq!(&empty[..], b"HYLL\x01\0\0\0\0\0\0\0\0\0\0\0\x7f\xff");
+        assert_eq!(Hll::decode(&empty).unwrap(), hll);
+
+        for element in elements(0..100) {
+            hll.add(&element);
+        }
+        let sparse = hll.encode();
+        assert_eq!(sparse[4], SPARSE);
+        assert_eq!(Hll::decode(&sparse).unwrap(), hll);
+
+        hll.dense = true;
+        let dense = hll.encode();
+        assert_eq!(dense.len(), DENSE_LEN);
+        assert_eq!(Hll::decode(&dense).unwrap(), hll);
+
+        assert!(matches!(Hll::decode(b"hello"), Err(CommandError::NotHll)));
+        assert!(matches!(
+            Hll::decode(&sparse[..sparse.len() - 1]),
+            Err(CommandError::CorruptedHll)
+        ));
+    }
+
+    #[test]
+    fn test_hll_dense_registers() {
+        let mut data = vec![0; DENSE_LEN - HEADER_LEN];
+
+        for index in [0, 1, 2, 3, REGISTERS - 1] {
+            dense_set(&mut data, index, 0x2a);
+        }
+        dense_set(&mut data, 1, 0x3f);
+
+        assert_eq!(dense_get(&data, 0), 0x2a);
+        assert_eq!(dense_get(&data, 1), 0x3f);
+        assert_eq!(dense_get(&data, 2), 0x2a);
+        assert_eq!(dense_get(&data, 3), 0x2a);
+        assert_eq!(dense_get(&data, 4), 0);
+        assert_eq!(dense_get(&data, REGISTERS - 1), 0x2a);
+    }
+
+    #[test]
+    fn test_backend_pfadd_pfcount() {
+        let backend = Backend::new();
+
+        assert!(backend.pfadd(b"hll", &[]).unwrap());
+        assert!(!backend.pfadd(b"hll", &[]).unwrap());
+        assert!(backend.pfadd(b"hll", &elements(0..7)).unwrap());
+        assert!(!backend.pfadd(b"hll", &elements(0..7)).unwrap());
+        assert_eq!(backend.pfcount(&["hll".into()]).unwrap(), 7);
+
+        // the count is cached in the header
+        let value = backend.get(b"hll").unwrap().unwrap();
+        assert_eq!(value[8..16], 7u64.to_le_bytes());
+
+        backend.set(b"string", "value".into());
+        assert!(matches!(
+            backend.pfadd(b"string", &elements(0..1)),
+            Err(CommandError::NotHll)
+        ));
+        assert_eq!(backend.pfcount(&["missing".into()]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_backend_pfcount_error_bound() {
+        let backend = Backend::new();
+
+        for (i, chunk) in elements(0..100_000).chunks(1000).enumerate() {
+            let key = format!("hll:{}", i % 2);
+            backend.pfadd(key.as_bytes(), chunk).unwrap();
+        }
+
+        let value = backend.get(b"hll:0").unwrap().unwrap();
+        assert_eq!(value[4], DENSE);
+
+        // the standard error with 16384 registers is 0.81%
+        let count = backend.pfcount(&["hll:0".into(), "hll:1".into()]).unwrap();
+        let error = (count as f64 - 100_000.0).abs() / 100_000.0;
+        assert!(error < 0.02, "count {} is off by {}", count, error);
+
+        backend
+            .pfmerge(b"merged", &["hll:0".into(), "hll:1".into()])
+            .unwrap();
+        assert_eq!(backend.pfcount(&["merged".into()]).unwrap(), count);
+    }
+}
@@ -0,0 +1,390 @@
+use bytes::Bytes;
+
+use super::{Backend, List};
+use crate::command::CommandError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEnd {
+    Left,
+    Right,
+}
+
+// Resolves an inclusive range of list indexes, where negative indexes count
+// from the tail, to the positions it covers. Out of range indexes are clamped
+// like Redis does, None when the range is empty.
+pub(crate) fn list_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (start + len).max(0)
+    } else {
+        start
+    };
+    let stop = if stop < 0 {
+        stop + len
+    } else {
+        stop.min(len - 1)
+    };
+
+    if start > stop || start >= len {
+        return None;
+    }
+
+    Some((start as usize, stop as usize))
+}
+
+fn list_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { index + len as i64 } else { index };
+
+    match index >= 0 && index < len as i64 {
+        true => Some(index as usize),
+        false => None,
+    }
+}
+
+impl Backend {
+    // Returns the length of the list after the push.
+    pub fn push(
+        &self,
+        key: &[u8],
+        end: ListEnd,
+        elements: Vec<Bytes>,
+    ) -> Result<usize, CommandError> {
+        let len = self.write(key, |list: &mut List| {
+            for element in elements {
+                match end {
+                    ListEnd::Left => list.push_front(element),
+                    ListEnd::Right => list.push_back(element),
+                }
+            }
+
+            (list.len(), true)
+        })?;
+
+        self.signal_ready(key);
+
+        Ok(len)
+    }
+
+    // Pops an element from the source list and pushes it to the destination
+    // one, None when the source does not exist.
+    pub fn lmove(
+        &self,
+        source: &[u8],
+        destination: &[u8],
+        from: ListEnd,
+        to: ListEnd,
+    ) -> Result<Option<Bytes>, CommandError> {
+        // nothing is popped when the destination cannot take the element
+        self.read(destination, |_: &List| ())?;
+
+        let Some(element) = self.pop(source, from, 1)?.and_then(|mut e| e.pop()) else {
+            return Ok(None);
+        };
+
+        self.push(destination, to, vec![element.clone()])?;
+
+        Ok(Some(element))
+    }
+
+    // Pops up to `count` elements, None when the key does not exist.
+    pub fn pop(
+        &self,
+        key: &[u8],
+        end: ListEnd,
+        count: usize,
+    ) -> Result<Option<Vec<Bytes>>, CommandError> {
+        self.update(key, |list: &mut List| {
+            let count = count.min(list.len());
+
+            let popped: Vec<_> = match end {
+                ListEnd::Left => list.drain(..count).collect(),
+                ListEnd::Right => list.drain(list.len() - count..).rev().collect(),
+            };
+            (popped, count > 0)
+        })
+    }
+
+    pub fn llen(&self, key: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |list: &List| list.len())?;
+        Ok(len.unwrap_or(0))
+    }
+
+    pub fn lrange(&self, key: &[u8], start: i64, stop: i64) -> Result<Vec<Bytes>, CommandError> {
+        let range = self.read(key, |list: &List| {
+            match list_range(start, stop, list.len()) {
+                Some((start, stop)) => list.range(start..=stop).cloned().collect(),
+                None => vec![],
+            }
+        })?;
+
+        Ok(range.unwrap_or_default())
+    }
+
+    pub fn lindex(&self, key: &[u8], index: i64) -> Result<Option<Bytes>, CommandError> {
+        let element = self.read(key, |list: &List| {
+            list_index(index, list.len()).map(|i| list[i].clone())
+        })?;
+
+        Ok(element.flatten())
+    }
+
+    pub fn lset(&self, key: &[u8], index: i64, element: Bytes) -> Result<(), CommandError> {
+        let result = self.update(key, |list: &mut List| match list_index(index, list.len()) {
+            Some(i) => {
+                let changed = list[i] != element;
+                list[i] = element;
+                (Ok(()), changed)
+            }
+            None => {
+                let error = "index out of range".to_string();
+                (Err(CommandError::InvalidArgument(error)), false)
+            }
+        })?;
+
+        result.unwrap_or_else(|| Err(CommandError::InvalidArgument("no such key".to_string())))
+    }
+
+    // Removes `count` occurrences of the element starting from the head, or
+    // from the tail when count is negative, or all of them when it is zero.
+    // Returns the number of elements removed.
+    pub fn lrem(&self, key: &[u8], count: i64, element: &[u8]) -> Result<usize, CommandError> {
+        let removed = self.update(key, |list: &mut List| {
+            let limit = match count {
+                0 => usize::MAX,
+                count => count.unsigned_abs() as usize,
+            };
+
+            let mut removed = 0;
+
+            if count >= 0 {
+                list.retain(|e| {
+                    let remove = removed < limit && e == element;
+                    removed += remove as usize;
+                    !remove
+                });
+            } else {
+                let mut kept = List::with_capacity(list.len());
+
+                while let Some(e) = list.pop_back() {
+                    if removed < limit && e == element {
+                        removed += 1;
+                    } else {
+                        kept.push_front(e);
+                    }
+                }
+
+                *list = kept;
+            }
+
+            (removed, removed > 0)
+        })?;
+
+        Ok(removed.unwrap_or(0))
+    }
+
+    pub fn ltrim(&self, key: &[u8], start: i64, stop: i64) -> Result<(), CommandError> {
+        self.update(key, |list: &mut List| {
+            let len = list.len();
+            match list_range(start, stop, len) {
+                Some((start, stop)) => {
+                    list.truncate(stop + 1);
+                    list.drain(..start);
+                }
+                None => list.clear(),
+            }
+            ((), list.len() != len)
+        })?;
+
+        Ok(())
+    }
+
+    // Inserts the element next to the first occurrence of the pivot. Returns
+    // the length of the list, -1 when the pivot is not found and 0 when the
+    // key does not exist.
+    pub fn linsert(
+        &self,
+        key: &[u8],
+        end: ListEnd,
+        pivot: &[u8],
+        element: Bytes,
+    ) -> Result<i64, CommandError> {
+        let len = self.update(key, |list: &mut List| {
+            match list.iter().position(|e| e == pivot) {
+                Some(i) => {
+                    let at = match end {
+                        ListEnd::Left => i,
+                        ListEnd::Right => i + 1,
+                    };
+                    list.insert(at, element);
+                    (list.len() as i64, true)
+                }
+                None => (-1, false),
+            }
+        })?;
+
+        Ok(len.unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(items: &[&'static str]) -> Vec<Bytes> {
+        items
+            .iter()
+            .map(|item| Bytes::from_static(item.as_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_list_range() {
+        assert_eq!(list_range(0, -1, 5), Some((0, 4)));
+        assert_eq!(list_range(-3, -2, 5), Some((2, 3)));
+        assert_eq!(list_range(-100, 100, 5), Some((0, 4)));
+        assert_eq!(list_range(3, 1, 5), None);
+        assert_eq!(list_range(5, 10, 5), None);
+        assert_eq!(list_range(0, -1, 0), None);
+        assert_eq!(list_index(-1, 5), Some(4));
+        assert_eq!(list_index(-6, 5), None);
+        assert_eq!(list_index(5, 5), None);
+    }
+
+    #[test]
+    fn test_backend_push_pop() {
+        let backend = Backend::new();
+
+        assert_eq!(
+            backend
+                .push(b"list", ListEnd::Left, elements(&["b", "a"]))
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            backend
+                .push(b"list", ListEnd::Right, elements(&["c", "d"]))
+                .unwrap(),
+            4
+        );
+        assert_eq!(
+            backend.lrange(b"list", 0, -1).unwrap(),
+            elements(&["a", "b", "c", "d"])
+        );
+
+        assert_eq!(
+            backend.pop(b"list", ListEnd::Right, 3).unwrap(),
+            Some(elements(&["d", "c", "b"]))
+        );
+        assert_eq!(
+            backend.pop(b"list", ListEnd::Left, 5).unwrap(),
+            Some(elements(&["a"]))
+        );
+        assert!(!backend.exists(b"list"));
+        assert_eq!(backend.pop(b"list", ListEnd::Left, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_backend_lmove() {
+        let backend = Backend::new();
+        backend
+            .push(b"src", ListEnd::Right, elements(&["a", "b"]))
+            .unwrap();
+
+        assert_eq!(
+            backend
+                .lmove(b"src", b"dst", ListEnd::Right, ListEnd::Left)
+                .unwrap(),
+            Some("b".into())
+        );
+        assert_eq!(
+            backend
+                .lmove(b"src", b"src", ListEnd::Left, ListEnd::Right)
+                .unwrap(),
+            Some("a".into())
+        );
+        assert_eq!(backend.lrange(b"dst", 0, -1).unwrap(), elements(&["b"]));
+
+        backend.set(b"string", "value".into());
+        assert!(backend
+            .lmove(b"src", b"string", ListEnd::Left, ListEnd::Left)
+            .is_err());
+        assert_eq!(backend.llen(b"src").unwrap(), 1);
+        assert_eq!(
+            backend
+                .lmove(b"missing", b"dst", ListEnd::Left, ListEnd::Left)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_backend_lrem_ltrim() {
+        let backend = Backend::new();
+        backend
+            .push(
+                b"list",
+                ListEnd::Right,
+                elements(&["a", "b", "a", "c", "a"]),
+            )
+            .unwrap();
+
+        assert_eq!(backend.lrem(b"list", -2, b"a").unwrap(), 2);
+        assert_eq!(
+            backend.lrange(b"list", 0, -1).unwrap(),
+            elements(&["a", "b", "c"])
+        );
+        assert_eq!(backend.lrem(b"list", 0, b"x").unwrap(), 0);
+
+        backend.ltrim(b"list", 1, -1).unwrap();
+        assert_eq!(
+            backend.lrange(b"list", 0, -1).unwrap(),
+            elements(&["b", "c"])
+        );
+
+        backend.ltrim(b"list", 5, 10).unwrap();
+        assert!(!backend.exists(b"list"));
+    }
+
+    #[test]
+    fn test_backend_lset_linsert() {
+        let backend = Backend::new();
+
+        assert_eq!(
+            backend
+                .lset(b"list", 0, "x".into())
+                .unwrap_err()
+                .to_string(),
+            "ERR no such key"
+        );
+        assert_eq!(
+            backend
+                .linsert(b"list", ListEnd::Left, b"a", "x".into())
+                .unwrap(),
+            0
+        );
+
+        backend
+            .push(b"list", ListEnd::Right, elements(&["a", "b"]))
+            .unwrap();
+        backend.lset(b"list", -1, "c".into()).unwrap();
+        assert!(backend.lset(b"list", 2, "x".into()).is_err());
+
+        assert_eq!(
+            backend
+                .linsert(b"list", ListEnd::Right, b"a", "b".into())
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            backend
+                .linsert(b"list", ListEnd::Left, b"z", "x".into())
+                .unwrap(),
+            -1
+        );
+        assert_eq!(backend.lindex(b"list", 1).unwrap(), Some("b".into()));
+        assert_eq!(
+            backend.lrange(b"list", 0, -1).unwrap(),
+            elements(&["a", "b", "c"])
+        );
+    }
+}
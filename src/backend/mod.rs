@@ -1,7 +1,52 @@
-use dashmap::{DashMap, DashSet};
+mod bitmap;
+mod blocking;
+mod consumer_group;
+mod hash;
+mod hyperloglog;
+mod list;
+mod pubsub;
+mod set;
+mod stream;
+mod string;
+mod value;
+mod watch;
+mod zset;
+
+use bytes::Bytes;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, TryLockError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{ops::Deref, sync::Arc};
 
-use crate::resp::frame::Frame;
+use crate::command::CommandError;
+use crate::config::ServerConfig;
+use crate::persistence::aof::Aof;
+use crate::persistence::rdb::Entry as RdbEntry;
+use crate::scripting::{RunningScript, Scripting};
+use blocking::Blocking;
+use pubsub::Hub;
+use watch::WatchedKey;
+
+pub use bitmap::{BitOp, BitUnit, BitfieldOp, BitfieldOverflow, BitfieldType};
+pub use blocking::{Block, BlockOp, BlockResult, Served};
+pub use consumer_group::{
+    no_such_group, AutoClaimed, ClaimOptions, ClaimTime, Consumer, ConsumerGroup, ConsumerInfo,
+    GroupEntry, GroupInfo, GroupReadId, PendingEntry, PendingInfo, PendingSummary,
+};
+pub use list::ListEnd;
+pub use pubsub::{ChannelKind, Subscriber};
+pub use set::SetOp;
+pub use stream::{
+    invalid_stream_id, Stream, StreamEntry, StreamFields, StreamId, StreamInfo, StreamTrim,
+    TrimStrategy, XAddId, STREAM_NODE_MAX_ENTRIES,
+};
+pub use string::parse_int;
+pub use value::{Collection, Hash, List, Set, Value};
+pub use watch::WatchedKeys;
+pub use zset::{parse_score, LexBound, ScoreBound, ScoreComparison, ZRange, ZRangeBy, ZSet};
 
 #[derive(Debug, Clone)]
 pub struct Backend {
@@ -10,11 +55,91 @@ pub struct Backend {
 
 #[derive(Debug)]
 pub struct BackendInner {
-    set: DashMap<String, DashSet<String>>,
-    map: DashMap<String, Frame>,
-    hmap: DashMap<String, DashMap<String, Frame>>,
+    // keys are copied when first inserted, large values are stored as the
+    // slices of the request buffer they were decoded from
+    keyspace: DashMap<Bytes, Value>,
+    // absolute expiration time of volatile keys, in unix milliseconds
+    expires: DashMap<Bytes, u64>,
+    // number of changes since the last successful save
+    pub(crate) dirty: AtomicU64,
+    // unix time of the last successful save, in seconds
+    pub(crate) last_save: AtomicU64,
+    // unix time of the last failed background save, in seconds, 0 once one
+    // succeeds
+    pub(crate) last_bgsave_failure: AtomicU64,
+    pub(crate) saving: AtomicBool,
+    // open append only file, None while AOF is disabled
+    pub(crate) aof: Mutex<Option<Aof>>,
+    pub(crate) rewriting: AtomicBool,
+    config: RwLock<ServerConfig>,
+    pub stats: Stats,
+    // IDs of the connected clients, never reused
+    next_client_id: AtomicU64,
+    // clients blocked on list and stream keys, see `blocking.rs`
+    blocking: Mutex<Blocking>,
+    blocked_clients: AtomicUsize,
+    // keys that received data while clients are blocked
+    ready_keys: Mutex<Vec<Bytes>>,
+    // keys expired since the AOF last logged them
+    expired: Mutex<Vec<Bytes>>,
+    // Pub/Sub subscriptions, see `pubsub.rs`
+    pubsub: Mutex<Hub>,
+    // versions of the keys clients WATCH, see `watch.rs`
+    watched: DashMap<Bytes, WatchedKey>,
+    watching: AtomicUsize,
+    // the Lua interpreter, with the scripts loaded
+    scripting: Mutex<Scripting>,
+    running_script: Arc<RunningScript>,
+    // commands run holding it shared, the ones touching several keys at once
+    // hold it exclusively to apply atomically
+    keyspace_lock: RwLock<()>,
+}
+
+// Counters reported by the server, reset by CONFIG RESETSTAT.
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub connected_clients: AtomicU64,
+    pub total_connections_received: AtomicU64,
+    pub total_commands_processed: AtomicU64,
+    pub rejected_connections: AtomicU64,
+    pub expired_keys: AtomicU64,
+}
+
+impl Stats {
+    // Connected clients is a gauge rather than a counter, it is kept.
+    pub fn reset(&self) {
+        self.total_connections_received.store(0, Ordering::Relaxed);
+        self.total_commands_processed.store(0, Ordering::Relaxed);
+        self.rejected_connections.store(0, Ordering::Relaxed);
+        self.expired_keys.store(0, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetCondition {
+    Always,
+    IfNotExists,
+    IfExists,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetExpiry {
+    Clear,
+    Keep,
+    At(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpireCondition {
+    IfNoExpiry,
+    IfHasExpiry,
+    IfGreater,
+    IfLess,
 }
 
+const ACTIVE_EXPIRE_SAMPLES: usize = 20;
+const ACTIVE_EXPIRE_BUDGET: Duration = Duration::from_millis(25);
+
 impl Default for Backend {
     fn default() -> Self {
         let inner = Arc::new(BackendInner::default());
@@ -24,10 +149,32 @@ impl Default for Backend {
 
 impl Default for BackendInner {
     fn default() -> Self {
+        let running_script = Arc::new(RunningScript::default());
+
         Self {
-            set: DashMap::new(),
-            map: DashMap::new(),
-            hmap: DashMap::new(),
+            keyspace: DashMap::new(),
+            expires: DashMap::new(),
+            dirty: AtomicU64::new(0),
+            last_save: AtomicU64::new(now_millis() / 1000),
+            last_bgsave_failure: AtomicU64::new(0),
+            saving: AtomicBool::new(false),
+            aof: Mutex::new(None),
+            rewriting: AtomicBool::new(false),
+            config: RwLock::new(ServerConfig::default()),
+            stats: Stats::default(),
+            next_client_id: AtomicU64::new(1),
+            blocking: Mutex::new(Blocking::default()),
+            blocked_clients: AtomicUsize::new(0),
+            ready_keys: Mutex::new(Vec::new()),
+            expired: Mutex::new(Vec::new()),
+            pubsub: Mutex::new(Hub::default()),
+            watched: DashMap::new(),
+            watching: AtomicUsize::new(0),
+            scripting: Mutex::new(
+                Scripting::new(running_script.clone()).expect("Lua interpreter starts"),
+            ),
+            running_script,
+            keyspace_lock: RwLock::new(()),
         }
     }
 }
@@ -45,51 +192,478 @@ impl Backend {
         Self::default()
     }
 
-    pub fn get(&self, key: &str) -> Option<Frame> {
-        self.map.get(key).map(|v| v.value().clone())
+    // Runs `f` on the collection stored at key, None when the key does not
+    // exist.
+    pub fn read<T, R>(&self, key: &[u8], f: impl FnOnce(&T) -> R) -> Result<Option<R>, CommandError>
+    where
+        T: Collection,
+    {
+        self.expire_if_needed(key);
+
+        match self.keyspace.get(key) {
+            Some(value) => match T::from_value(value.value()) {
+                Some(collection) => Ok(Some(f(collection))),
+                None => Err(CommandError::WrongType),
+            },
+            None => Ok(None),
+        }
+    }
+
+    // Runs `f` on the collection stored at key, creating an empty one when
+    // the key does not exist. `f` returns its result along with whether it
+    // changed the collection, the key is only marked modified then.
+    // Collections left empty are removed, like Redis never keeps empty
+    // aggregate values around.
+    pub fn write<T, R>(
+        &self,
+        key: &[u8],
+        f: impl FnOnce(&mut T) -> (R, bool),
+    ) -> Result<R, CommandError>
+    where
+        T: Collection,
+    {
+        self.expire_if_needed(key);
+
+        let mut entry = self
+            .keyspace
+            .entry(Bytes::copy_from_slice(key))
+            .or_insert_with(|| T::default().into());
+
+        let collection = T::from_value_mut(entry.value_mut()).ok_or(CommandError::WrongType)?;
+        let (result, changed) = f(collection);
+        let empty = collection.is_empty();
+        drop(entry);
+
+        if changed {
+            self.modified(key);
+        }
+
+        if empty {
+            let removed = self.keyspace.remove_if(key, |_, value| {
+                T::from_value(value).is_some_and(|v| v.is_empty())
+            });
+
+            if removed.is_some() {
+                self.expires.remove(key);
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Like `write`, but leaves the key alone when it does not exist, returning
+    // None.
+    pub fn update<T, R>(
+        &self,
+        key: &[u8],
+        f: impl FnOnce(&mut T) -> (R, bool),
+    ) -> Result<Option<R>, CommandError>
+    where
+        T: Collection,
+    {
+        self.expire_if_needed(key);
+
+        let Some(mut value) = self.keyspace.get_mut(key) else {
+            return Ok(None);
+        };
+
+        let collection = T::from_value_mut(value.value_mut()).ok_or(CommandError::WrongType)?;
+        let (result, changed) = f(collection);
+        let empty = collection.is_empty();
+        drop(value);
+
+        if changed {
+            self.modified(key);
+        }
+
+        if empty {
+            let removed = self.keyspace.remove_if(key, |_, value| {
+                T::from_value(value).is_some_and(|v| v.is_empty())
+            });
+
+            if removed.is_some() {
+                self.expires.remove(key);
+            }
+        }
+
+        Ok(Some(result))
     }
 
-    pub fn set(&self, key: impl ToString, value: Frame) {
-        self.map.insert(key.to_string(), value);
+    // Runs `f` holding the keyspace lock, exclusively when no other command
+    // may run meanwhile.
+    pub fn with_keyspace_lock<R>(&self, exclusive: bool, f: impl FnOnce() -> R) -> R {
+        match exclusive {
+            true => {
+                let _guard = self
+                    .keyspace_lock
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner);
+                f()
+            }
+            false => {
+                let _guard = self
+                    .keyspace_lock
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner);
+                f()
+            }
+        }
     }
 
-    pub fn hset(&self, key: impl ToString, field: impl ToString, value: Frame) {
-        let hmap = self.hmap.entry(key.to_string()).or_default();
-        hmap.insert(field.to_string(), value);
+    // Like `with_keyspace_lock`, but None right away when the lock is taken.
+    pub fn try_with_keyspace_lock<R>(&self, exclusive: bool, f: impl FnOnce() -> R) -> Option<R> {
+        match exclusive {
+            true => {
+                let _guard = match self.keyspace_lock.try_write() {
+                    Ok(guard) => guard,
+                    Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                    Err(TryLockError::WouldBlock) => return None,
+                };
+                Some(f())
+            }
+            false => {
+                let _guard = match self.keyspace_lock.try_read() {
+                    Ok(guard) => guard,
+                    Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                    Err(TryLockError::WouldBlock) => return None,
+                };
+                Some(f())
+            }
+        }
     }
 
-    pub fn hget(&self, key: &str, field: &str) -> Option<Frame> {
-        self.hmap
-            .get(key)
-            .and_then(|hmap| hmap.get(field).map(|v| v.value().clone()))
+    pub fn get(&self, key: &[u8]) -> Result<Option<Bytes>, CommandError> {
+        self.expire_if_needed(key);
+
+        match self.keyspace.get(key).as_deref() {
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(CommandError::WrongType),
+            None => Ok(None),
+        }
+    }
+
+    pub fn set(&self, key: &[u8], value: Bytes) {
+        self.expires.remove(key);
+        self.keyspace
+            .insert(Bytes::copy_from_slice(key), Value::String(value));
+        self.modified(key);
+    }
+
+    // Returns whether the value was written and the value previously stored
+    // under the key. SET overwrites values of any type, but the previous value
+    // is only reported for strings.
+    pub fn set_with(
+        &self,
+        key: &[u8],
+        value: Bytes,
+        condition: SetCondition,
+        expiry: SetExpiry,
+    ) -> Result<(bool, Option<Bytes>), CommandError> {
+        self.expire_if_needed(key);
+
+        let (written, previous) = match self.keyspace.entry(Bytes::copy_from_slice(key)) {
+            Entry::Occupied(mut entry) => {
+                let previous = match entry.get() {
+                    Value::String(previous) => Some(previous.clone()),
+                    _ => None,
+                };
+
+                match condition {
+                    SetCondition::IfNotExists => (false, previous),
+                    _ => {
+                        self.set_expiry(key, expiry);
+                        entry.insert(Value::String(value));
+                        (true, previous)
+                    }
+                }
+            }
+            Entry::Vacant(entry) => match condition {
+                SetCondition::IfExists => (false, None),
+                _ => {
+                    self.set_expiry(key, expiry);
+                    entry.insert(Value::String(value));
+                    (true, None)
+                }
+            },
+        };
+
+        if written {
+            self.modified(key);
+        }
+
+        Ok((written, previous))
+    }
+
+    // Called with the value locked, for lazy expiration not to see the new
+    // value with the old deadline.
+    fn set_expiry(&self, key: &[u8], expiry: SetExpiry) {
+        match expiry {
+            SetExpiry::Clear => {
+                self.expires.remove(key);
+            }
+            SetExpiry::Keep => {}
+            SetExpiry::At(at) => {
+                self.expires.insert(Bytes::copy_from_slice(key), at);
+            }
+        }
+    }
+
+    pub fn exists(&self, key: &[u8]) -> bool {
+        self.expire_if_needed(key);
+        self.keyspace.contains_key(key)
     }
 
-    pub fn hgetall(&self, key: &str) -> Option<DashMap<String, Frame>> {
-        self.hmap.get(key).map(|v| v.clone())
+    pub fn key_type(&self, key: &[u8]) -> Option<&'static str> {
+        self.expire_if_needed(key);
+        self.keyspace.get(key).map(|value| value.type_name())
     }
 
-    pub fn sadd(&self, key: &str, field: &str) -> bool {
-        let set = self.set.entry(key.to_string()).or_default();
+    // Sets the absolute expiration time of a key, in unix milliseconds, when
+    // all the conditions hold. A time in the past deletes the key right away,
+    // like Redis does.
+    pub fn expire_at(&self, key: &[u8], at: u64, conditions: &[ExpireCondition]) -> bool {
+        if !self.exists(key) {
+            return false;
+        }
+
+        let current = self.expires.get(key).map(|v| *v);
+        let allowed = conditions
+            .iter()
+            .all(|condition| match (condition, current) {
+                (ExpireCondition::IfNoExpiry, current) => current.is_none(),
+                (ExpireCondition::IfHasExpiry, current) => current.is_some(),
+                (ExpireCondition::IfGreater, Some(current)) => at > current,
+                (ExpireCondition::IfGreater, None) => false,
+                (ExpireCondition::IfLess, Some(current)) => at < current,
+                (ExpireCondition::IfLess, None) => true,
+            });
+
+        if !allowed {
+            return false;
+        }
 
-        if set.contains(field) {
-            false
+        if at <= now_millis() {
+            self.remove(key);
         } else {
-            set.insert(field.to_string());
-            true
+            self.expires.insert(Bytes::copy_from_slice(key), at);
+            self.modified(key);
         }
+
+        true
     }
 
-    pub fn smembers(&self, key: &str) -> Option<Vec<String>> {
-        self.set
+    // Remaining time to live in milliseconds, None when the key does not
+    // exist and Some(None) when it has no associated expire.
+    pub fn pttl(&self, key: &[u8]) -> Option<Option<u64>> {
+        if !self.exists(key) {
+            return None;
+        }
+
+        let ttl = self
+            .expires
             .get(key)
-            .map(|v| v.iter().map(|v| v.clone()).collect())
+            .map(|at| at.saturating_sub(now_millis()));
+
+        Some(ttl)
+    }
+
+    pub fn persist(&self, key: &[u8]) -> bool {
+        self.expire_if_needed(key);
+        let removed = self.expires.remove(key).is_some();
+
+        if removed {
+            self.modified(key);
+        }
+
+        removed
+    }
+
+    pub fn remove(&self, key: &[u8]) -> Option<Value> {
+        self.expires.remove(key);
+        let value = self.keyspace.remove(key).map(|(_, value)| value);
+
+        if value.is_some() {
+            self.modified(key);
+        }
+
+        value
+    }
+
+    // Inserts a value as is, used when loading persisted data.
+    pub fn restore(&self, key: Bytes, value: Value, expire_at: Option<u64>) {
+        if let Some(at) = expire_at {
+            self.expires.insert(key.clone(), at);
+        } else {
+            self.expires.remove(&key);
+        }
+
+        self.keyspace.insert(key, value);
+    }
+
+    // Copies every live key with its expiration time. Each shard is only
+    // locked while being copied, so clients are not stopped during a save.
+    pub fn snapshot(&self) -> Vec<RdbEntry> {
+        let now = now_millis();
+
+        self.keyspace
+            .iter()
+            .filter_map(|entry| {
+                let expire_at = self.expires.get(entry.key()).map(|at| *at);
+
+                if expire_at.is_some_and(|at| at <= now) {
+                    return None;
+                }
+
+                Some(RdbEntry {
+                    key: entry.key().clone(),
+                    value: entry.value().clone(),
+                    expire_at,
+                })
+            })
+            .collect()
+    }
+
+    pub fn config(&self) -> ServerConfig {
+        self.config
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    // Reads part of the config without cloning all of it.
+    pub fn with_config<T>(&self, f: impl FnOnce(&ServerConfig) -> T) -> T {
+        match self.config.read() {
+            Ok(config) => f(&config),
+            Err(poisoned) => f(&poisoned.into_inner()),
+        }
+    }
+
+    pub fn set_config(&self, config: ServerConfig) {
+        if let Ok(mut current) = self.config.write() {
+            *current = config;
+        }
+    }
+
+    // Scripts run holding it, the keyspace locked exclusively anyway.
+    pub fn scripting(&self) -> MutexGuard<'_, Scripting> {
+        self.scripting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Checked without the interpreter, held by the script running.
+    pub fn running_script(&self) -> &RunningScript {
+        &self.running_script
+    }
+
+    pub fn next_client_id(&self) -> u64 {
+        self.next_client_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    // Called after the key changed rather than before, whoever sees its
+    // watched version bumped also sees the change.
+    fn modified(&self, key: &[u8]) {
+        self.dirty.fetch_add(1, Ordering::Relaxed);
+        self.touch_watched(key);
     }
 
-    pub fn sismember(&self, key: &str, field: &str) -> bool {
-        self.set.get(key).map_or(false, |v| v.contains(field))
+    // Lazy expiration: every access to a key first drops it when its time to
+    // live has elapsed.
+    // The deadline is removed, checked again, with the value locked, so a
+    // write that replaced the value meanwhile keeps it.
+    fn expire_if_needed(&self, key: &[u8]) -> bool {
+        let now = now_millis();
+        if self.expires.get(key).is_none_or(|at| *at > now) {
+            return false;
+        }
+
+        let Entry::Occupied(entry) = self.keyspace.entry(Bytes::copy_from_slice(key)) else {
+            self.expires.remove_if(key, |_, at| *at <= now);
+            return false;
+        };
+        if self.expires.remove_if(key, |_, at| *at <= now).is_none() {
+            return false;
+        }
+        entry.remove();
+
+        self.modified(key);
+        self.stats.expired_keys.fetch_add(1, Ordering::Relaxed);
+        self.expired
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Bytes::copy_from_slice(key));
+
+        true
+    }
+
+    // The keys expired since the last call, lazily or actively, for the AOF
+    // to log their deletion.
+    pub(crate) fn take_expired(&self) -> Vec<Bytes> {
+        std::mem::take(&mut *self.expired.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    // Active expiration, modeled after Redis: sample a few volatile keys,
+    // delete the expired ones and keep going while more than a quarter of
+    // the sample was expired and the time budget allows it. The cycle is
+    // skipped while a transaction or script holds the keyspace lock, keys
+    // don't expire in the middle of them.
+    pub fn active_expire_cycle(&self) -> usize {
+        let _guard = match self.keyspace_lock.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return 0,
+        };
+
+        let started = Instant::now();
+        let mut rng = rand::thread_rng();
+        let mut total = 0;
+
+        loop {
+            let len = self.expires.len();
+            if len == 0 {
+                break;
+            }
+
+            let start = if len > ACTIVE_EXPIRE_SAMPLES {
+                rng.gen_range(0..len)
+            } else {
+                0
+            };
+
+            let now = now_millis();
+            let sampled = self
+                .expires
+                .iter()
+                .skip(start)
+                .take(ACTIVE_EXPIRE_SAMPLES)
+                .map(|entry| (entry.key().clone(), *entry.value()))
+                .collect::<Vec<_>>();
+
+            let expired = sampled
+                .iter()
+                .filter(|(_, at)| *at <= now)
+                .map(|(key, _)| self.expire_if_needed(key))
+                .filter(|expired| *expired)
+                .count();
+
+            total += expired;
+
+            if expired * 4 <= sampled.len() || started.elapsed() > ACTIVE_EXPIRE_BUDGET {
+                break;
+            }
+        }
+
+        total
     }
 }
 
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,25 +671,163 @@ mod tests {
     #[test]
     fn test_backend_get_set() {
         let backend = Backend::new();
-        backend.set("key", "value".into());
-        let result = backend.get("key").unwrap();
-        assert_eq!(result, "value".into());
+        backend.set(b"key", "value".into());
+        let result = backend.get(b"key").unwrap().unwrap();
+        assert_eq!(result, "value");
     }
 
     #[test]
     fn test_backend_hset_hget() {
         let backend = Backend::new();
-        backend.hset("key", "field", "value".into());
-        let result = backend.hget("key", "field").unwrap();
-        assert_eq!(result, "value".into());
+        backend
+            .hset(b"key", vec![("field".into(), "value".into())])
+            .unwrap();
+        let result = backend.hget(b"key", b"field").unwrap().unwrap();
+        assert_eq!(result, "value");
     }
 
     #[test]
     fn test_backend_hgetall() {
         let backend = Backend::new();
-        backend.hset("key", "field1", "value1".into());
-        backend.hset("key", "field2", "value2".into());
-        let result = backend.hgetall("key").unwrap();
+        backend
+            .hset(b"key", vec![("field1".into(), "value1".into())])
+            .unwrap();
+        backend
+            .hset(b"key", vec![("field2".into(), "value2".into())])
+            .unwrap();
+        let result = backend.hgetall(b"key").unwrap().unwrap();
         assert_eq!(result.len(), 2);
     }
+
+    #[test]
+    fn test_backend_wrong_type() {
+        let backend = Backend::new();
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+
+        assert!(matches!(backend.get(b"hash"), Err(CommandError::WrongType)));
+        assert!(matches!(
+            backend.sadd(b"hash", vec!["member".into()]),
+            Err(CommandError::WrongType)
+        ));
+        assert!(matches!(
+            backend.hget(b"set", b"field"),
+            Err(CommandError::WrongType)
+        ));
+
+        backend.set(b"hash", "value".into());
+        assert_eq!(backend.key_type(b"hash"), Some("string"));
+        assert_eq!(backend.key_type(b"set"), Some("set"));
+        assert_eq!(backend.key_type(b"missing"), None);
+    }
+
+    #[test]
+    fn test_backend_write_removes_empty_collection() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+
+        backend
+            .write(b"set", |set: &mut Set| (set.remove(&b"member"[..]), true))
+            .unwrap();
+        assert!(!backend.exists(b"set"));
+
+        backend
+            .write(b"set", |set: &mut Set| (set.len(), false))
+            .unwrap();
+        assert!(!backend.exists(b"set"));
+    }
+
+    #[test]
+    fn test_backend_set_with_condition() {
+        let backend = Backend::new();
+
+        let result = backend.set_with(
+            b"key",
+            "value".into(),
+            SetCondition::IfExists,
+            SetExpiry::Clear,
+        );
+        assert_eq!(result.unwrap(), (false, None));
+
+        let result = backend.set_with(
+            b"key",
+            "value".into(),
+            SetCondition::IfNotExists,
+            SetExpiry::Clear,
+        );
+        assert_eq!(result.unwrap(), (true, None));
+
+        let result = backend.set_with(
+            b"key",
+            "other".into(),
+            SetCondition::IfNotExists,
+            SetExpiry::Clear,
+        );
+        assert_eq!(result.unwrap(), (false, Some("value".into())));
+    }
+
+    #[test]
+    fn test_backend_lazy_expire() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+
+        assert!(backend.expire_at(b"key", now_millis() + 10_000, &[]));
+        assert!(backend.pttl(b"key").unwrap().unwrap() > 9_000);
+
+        backend.expires.insert("key".into(), now_millis() - 1);
+        backend.expires.insert("hash".into(), now_millis() - 1);
+        assert_eq!(backend.get(b"key").unwrap(), None);
+        assert_eq!(backend.hget(b"hash", b"field").unwrap(), None);
+        assert_eq!(backend.pttl(b"key"), None);
+    }
+
+    #[test]
+    fn test_backend_expire_condition() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+        let at = now_millis() + 10_000;
+
+        assert!(!backend.expire_at(b"key", at, &[ExpireCondition::IfHasExpiry]));
+        assert!(!backend.expire_at(b"key", at, &[ExpireCondition::IfGreater]));
+        assert!(!backend.expire_at(
+            b"key",
+            at,
+            &[ExpireCondition::IfHasExpiry, ExpireCondition::IfLess]
+        ));
+        assert!(backend.expire_at(b"key", at, &[ExpireCondition::IfNoExpiry]));
+        assert!(!backend.expire_at(b"key", at - 1, &[ExpireCondition::IfGreater]));
+        assert!(backend.expire_at(b"key", at - 1, &[ExpireCondition::IfLess]));
+
+        assert!(backend.persist(b"key"));
+        assert_eq!(backend.pttl(b"key"), Some(None));
+    }
+
+    #[test]
+    fn test_backend_active_expire_cycle() {
+        let backend = Backend::new();
+
+        for i in 0..100 {
+            let key = format!("key{}", i);
+            backend.set(key.as_bytes(), "value".into());
+            backend.expires.insert(key.into(), now_millis() - 1);
+        }
+        backend.set(b"live", "value".into());
+
+        // nothing expires while a transaction or script runs
+        let skipped = backend.with_keyspace_lock(true, || backend.active_expire_cycle());
+        assert_eq!(skipped, 0);
+        assert_eq!(backend.expires.len(), 100);
+
+        while backend.active_expire_cycle() > 0 {}
+
+        assert_eq!(backend.keyspace.len(), 1);
+        assert!(backend.expires.is_empty());
+        assert_eq!(backend.take_expired().len(), 100);
+        assert!(backend.take_expired().is_empty());
+    }
 }
@@ -0,0 +1,363 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{MutexGuard, PoisonError};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+
+use super::Backend;
+use crate::glob;
+use crate::resp::frame::Frame;
+use crate::resp::push::Push;
+
+// Channels and the glob patterns matched against them are kept apart, a
+// client can subscribe to `news.*` both as a channel and as a pattern. Shard
+// channels are a namespace of their own, which a cluster would spread across
+// slots like keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelKind {
+    Channel,
+    Pattern,
+    Shard,
+}
+
+const CHANNEL_KINDS: [ChannelKind; 3] = [
+    ChannelKind::Channel,
+    ChannelKind::Pattern,
+    ChannelKind::Shard,
+];
+
+// The clients subscribed to each channel and pattern, and the queues the
+// messages published to them are pushed to.
+#[derive(Debug, Default)]
+pub struct Hub {
+    clients: HashMap<u64, mpsc::UnboundedSender<Frame>>,
+    channels: HashMap<Bytes, HashSet<u64>>,
+    patterns: HashMap<Bytes, HashSet<u64>>,
+    shard_channels: HashMap<Bytes, HashSet<u64>>,
+    next_id: u64,
+}
+
+impl Hub {
+    fn subscribers(&self, kind: ChannelKind) -> &HashMap<Bytes, HashSet<u64>> {
+        match kind {
+            ChannelKind::Channel => &self.channels,
+            ChannelKind::Pattern => &self.patterns,
+            ChannelKind::Shard => &self.shard_channels,
+        }
+    }
+
+    fn subscribers_mut(&mut self, kind: ChannelKind) -> &mut HashMap<Bytes, HashSet<u64>> {
+        match kind {
+            ChannelKind::Channel => &mut self.channels,
+            ChannelKind::Pattern => &mut self.patterns,
+            ChannelKind::Shard => &mut self.shard_channels,
+        }
+    }
+
+    fn send(&self, id: u64, message: Frame) -> bool {
+        self.clients
+            .get(&id)
+            .is_some_and(|client| client.send(message).is_ok())
+    }
+}
+
+// The subscriptions of a client connection and the messages published to
+// them. Dropping it, like when the client disconnects, unsubscribes it from
+// everything.
+#[derive(Debug)]
+pub struct Subscriber {
+    id: u64,
+    receiver: mpsc::UnboundedReceiver<Frame>,
+    channels: BTreeSet<Bytes>,
+    patterns: BTreeSet<Bytes>,
+    shard_channels: BTreeSet<Bytes>,
+    backend: Backend,
+}
+
+impl Subscriber {
+    pub fn new(backend: &Backend) -> Self {
+        let mut hub = backend.lock_pubsub();
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let id = hub.next_id;
+        hub.next_id += 1;
+        hub.clients.insert(id, sender);
+
+        Self {
+            id,
+            receiver,
+            channels: BTreeSet::new(),
+            patterns: BTreeSet::new(),
+            shard_channels: BTreeSet::new(),
+            backend: backend.clone(),
+        }
+    }
+
+    fn subscriptions_mut(&mut self, kind: ChannelKind) -> &mut BTreeSet<Bytes> {
+        match kind {
+            ChannelKind::Channel => &mut self.channels,
+            ChannelKind::Pattern => &mut self.patterns,
+            ChannelKind::Shard => &mut self.shard_channels,
+        }
+    }
+
+    pub fn subscriptions(&self, kind: ChannelKind) -> Vec<Bytes> {
+        let subscriptions = match kind {
+            ChannelKind::Channel => &self.channels,
+            ChannelKind::Pattern => &self.patterns,
+            ChannelKind::Shard => &self.shard_channels,
+        };
+
+        subscriptions.iter().cloned().collect()
+    }
+
+    // The count replied to (un)subscriptions, channels and patterns together
+    // while shard channels are counted apart.
+    pub fn count(&self, kind: ChannelKind) -> usize {
+        match kind {
+            ChannelKind::Channel | ChannelKind::Pattern => {
+                self.channels.len() + self.patterns.len()
+            }
+            ChannelKind::Shard => self.shard_channels.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty() && self.patterns.is_empty() && self.shard_channels.is_empty()
+    }
+
+    pub fn subscribe(&mut self, kind: ChannelKind, channel: Bytes) {
+        if !self.subscriptions_mut(kind).insert(channel.clone()) {
+            return;
+        }
+
+        let id = self.id;
+        let mut hub = self.backend.lock_pubsub();
+        hub.subscribers_mut(kind)
+            .entry(channel)
+            .or_default()
+            .insert(id);
+    }
+
+    pub fn unsubscribe(&mut self, kind: ChannelKind, channel: &[u8]) {
+        if !self.subscriptions_mut(kind).remove(channel) {
+            return;
+        }
+
+        let mut hub = self.backend.lock_pubsub();
+        let subscribers = hub.subscribers_mut(kind);
+
+        if let Some(ids) = subscribers.get_mut(channel) {
+            ids.remove(&self.id);
+
+            if ids.is_empty() {
+                subscribers.remove(channel);
+            }
+        }
+    }
+
+    pub async fn message(&mut self) -> Option<Frame> {
+        self.receiver.recv().await
+    }
+
+    pub fn try_message(&mut self) -> Option<Frame> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        for kind in CHANNEL_KINDS {
+            for channel in self.subscriptions(kind) {
+                self.unsubscribe(kind, &channel);
+            }
+        }
+
+        self.backend.lock_pubsub().clients.remove(&self.id);
+    }
+}
+
+impl Backend {
+    fn lock_pubsub(&self) -> MutexGuard<'_, Hub> {
+        self.pubsub.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Pushes the message to the subscribers of the channel and of the
+    // patterns matching it, returning how many were reached. A client
+    // subscribed both ways gets it twice, like in Redis.
+    pub fn publish(&self, channel: &Bytes, message: &Bytes) -> usize {
+        let hub = self.lock_pubsub();
+        let mut receivers = 0;
+
+        if let Some(ids) = hub.channels.get(channel) {
+            let frame: Frame = Push::new(vec![
+                b"message".into(),
+                channel.clone().into(),
+                message.clone().into(),
+            ])
+            .into();
+
+            for id in ids {
+                receivers += hub.send(*id, frame.clone()) as usize;
+            }
+        }
+
+        for (pattern, ids) in &hub.patterns {
+            if !glob::matches(pattern, channel, false) {
+                continue;
+            }
+
+            let frame: Frame = Push::new(vec![
+                b"pmessage".into(),
+                pattern.clone().into(),
+                channel.clone().into(),
+                message.clone().into(),
+            ])
+            .into();
+
+            for id in ids {
+                receivers += hub.send(*id, frame.clone()) as usize;
+            }
+        }
+
+        receivers
+    }
+
+    // Pushes the message to the subscribers of the shard channel only, no
+    // pattern is matched against shard channels.
+    pub fn spublish(&self, channel: &Bytes, message: &Bytes) -> usize {
+        let hub = self.lock_pubsub();
+
+        let Some(ids) = hub.shard_channels.get(channel) else {
+            return 0;
+        };
+
+        let frame: Frame = Push::new(vec![
+            b"smessage".into(),
+            channel.clone().into(),
+            message.clone().into(),
+        ])
+        .into();
+
+        ids.iter()
+            .filter(|id| hub.send(**id, frame.clone()))
+            .count()
+    }
+
+    // Channels with at least one subscriber, optionally matching a pattern.
+    pub fn pubsub_channels(&self, kind: ChannelKind, pattern: Option<&[u8]>) -> Vec<Bytes> {
+        self.lock_pubsub()
+            .subscribers(kind)
+            .keys()
+            .filter(|channel| pattern.is_none_or(|pattern| glob::matches(pattern, channel, false)))
+            .cloned()
+            .collect()
+    }
+
+    pub fn pubsub_numsub(&self, kind: ChannelKind, channel: &[u8]) -> usize {
+        self.lock_pubsub()
+            .subscribers(kind)
+            .get(channel)
+            .map_or(0, HashSet::len)
+    }
+
+    // Patterns with at least one subscriber, not the subscriptions to them.
+    pub fn pubsub_numpat(&self) -> usize {
+        self.lock_pubsub().patterns.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish() {
+        let backend = Backend::new();
+        let mut alice = Subscriber::new(&backend);
+        let mut bob = Subscriber::new(&backend);
+
+        alice.subscribe(ChannelKind::Channel, "news.tech".into());
+        alice.subscribe(ChannelKind::Pattern, "news.*".into());
+        bob.subscribe(ChannelKind::Pattern, "sport.*".into());
+        assert_eq!(alice.count(ChannelKind::Channel), 2);
+
+        let receivers = backend.publish(&"news.tech".into(), &"hello".into());
+        assert_eq!(receivers, 2);
+        assert_eq!(
+            alice.try_message(),
+            Some(
+                Push::new(vec![
+                    b"message".into(),
+                    b"news.tech".into(),
+                    b"hello".into()
+                ])
+                .into()
+            )
+        );
+        assert_eq!(
+            alice.try_message(),
+            Some(
+                Push::new(vec![
+                    b"pmessage".into(),
+                    b"news.*".into(),
+                    b"news.tech".into(),
+                    b"hello".into()
+                ])
+                .into()
+            )
+        );
+        assert_eq!(bob.try_message(), None);
+
+        alice.unsubscribe(ChannelKind::Channel, b"news.tech");
+        assert_eq!(backend.publish(&"news.tech".into(), &"again".into()), 1);
+    }
+
+    #[test]
+    fn test_subscriber_drop() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Channel, "a".into());
+        subscriber.subscribe(ChannelKind::Channel, "b".into());
+        subscriber.subscribe(ChannelKind::Pattern, "*".into());
+
+        assert_eq!(backend.pubsub_channels(ChannelKind::Channel, None).len(), 2);
+        assert_eq!(
+            backend.pubsub_channels(ChannelKind::Channel, Some(b"a")),
+            vec![Bytes::from("a")]
+        );
+        assert_eq!(backend.pubsub_numsub(ChannelKind::Channel, b"a"), 1);
+        assert_eq!(backend.pubsub_numpat(), 1);
+
+        drop(subscriber);
+        assert!(backend
+            .pubsub_channels(ChannelKind::Channel, None)
+            .is_empty());
+        assert_eq!(backend.pubsub_numpat(), 0);
+        assert_eq!(backend.publish(&"a".into(), &"hello".into()), 0);
+    }
+
+    #[test]
+    fn test_spublish() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Shard, "orders".into());
+        subscriber.subscribe(ChannelKind::Pattern, "*".into());
+        assert_eq!(subscriber.count(ChannelKind::Shard), 1);
+        assert_eq!(subscriber.count(ChannelKind::Channel), 1);
+
+        assert_eq!(backend.spublish(&"orders".into(), &"new".into()), 1);
+        assert_eq!(
+            subscriber.try_message(),
+            Some(Push::new(vec![b"smessage".into(), b"orders".into(), b"new".into()]).into())
+        );
+        assert_eq!(subscriber.try_message(), None);
+
+        // classic and shard channels don't see each other's messages
+        assert_eq!(backend.publish(&"orders".into(), &"new".into()), 1);
+        assert!(backend
+            .pubsub_channels(ChannelKind::Channel, None)
+            .is_empty());
+        assert_eq!(backend.pubsub_numsub(ChannelKind::Shard, b"orders"), 1);
+    }
+}
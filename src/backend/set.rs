@@ -0,0 +1,293 @@
+use bytes::Bytes;
+use rand::seq::{IteratorRandom, SliceRandom};
+
+use super::{Backend, Set, Value};
+use crate::command::CommandError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+    Inter,
+    Union,
+    Diff,
+}
+
+impl Backend {
+    // Returns the number of members added.
+    pub fn sadd(&self, key: &[u8], members: Vec<Bytes>) -> Result<usize, CommandError> {
+        self.write(key, |set: &mut Set| {
+            let added = members
+                .into_iter()
+                .filter(|member| set.insert(member.clone()))
+                .count();
+            (added, added > 0)
+        })
+    }
+
+    pub fn srem(&self, key: &[u8], members: &[Bytes]) -> Result<usize, CommandError> {
+        let removed = self.update(key, |set: &mut Set| {
+            let removed = members.iter().filter(|member| set.remove(*member)).count();
+            (removed, removed > 0)
+        })?;
+
+        Ok(removed.unwrap_or(0))
+    }
+
+    pub fn smembers(&self, key: &[u8]) -> Result<Option<Vec<Bytes>>, CommandError> {
+        self.read(key, |set: &Set| set.iter().cloned().collect())
+    }
+
+    pub fn sismember(&self, key: &[u8], member: &[u8]) -> Result<bool, CommandError> {
+        let result = self.read(key, |set: &Set| set.contains(member))?;
+        Ok(result.unwrap_or(false))
+    }
+
+    pub fn smismember(&self, key: &[u8], members: &[Bytes]) -> Result<Vec<bool>, CommandError> {
+        let result = self.read(key, |set: &Set| {
+            members.iter().map(|member| set.contains(member)).collect()
+        })?;
+
+        Ok(result.unwrap_or_else(|| vec![false; members.len()]))
+    }
+
+    pub fn scard(&self, key: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |set: &Set| set.len())?;
+        Ok(len.unwrap_or(0))
+    }
+
+    // Removes up to `count` random members, None when the key does not exist.
+    pub fn spop(&self, key: &[u8], count: usize) -> Result<Option<Vec<Bytes>>, CommandError> {
+        self.update(key, |set: &mut Set| {
+            let popped = set
+                .iter()
+                .cloned()
+                .choose_multiple(&mut rand::thread_rng(), count);
+
+            for member in &popped {
+                set.remove(member);
+            }
+
+            let changed = !popped.is_empty();
+            (popped, changed)
+        })
+    }
+
+    // Returns up to `count` distinct random members, or exactly `-count`
+    // members that may repeat when count is negative.
+    pub fn srandmember(&self, key: &[u8], count: i64) -> Result<Vec<Bytes>, CommandError> {
+        let members = self.read(key, |set: &Set| {
+            let mut rng = rand::thread_rng();
+
+            match count {
+                count if count >= 0 => set
+                    .iter()
+                    .cloned()
+                    .choose_multiple(&mut rng, count as usize),
+                count => {
+                    let members = set.iter().collect::<Vec<_>>();
+                    (0..count.unsigned_abs())
+                        .filter_map(|_| members.choose(&mut rng).map(|m| (*m).clone()))
+                        .collect()
+                }
+            }
+        })?;
+
+        Ok(members.unwrap_or_default())
+    }
+
+    // Moves the member between sets, returning false when it is not in the
+    // source set.
+    pub fn smove(
+        &self,
+        source: &[u8],
+        destination: &[u8],
+        member: Bytes,
+    ) -> Result<bool, CommandError> {
+        // nothing is removed when the destination cannot take the member
+        self.read(destination, |_: &Set| ())?;
+
+        if source == destination {
+            return self.sismember(source, &member);
+        }
+
+        if self.srem(source, std::slice::from_ref(&member))? == 0 {
+            return Ok(false);
+        }
+
+        self.sadd(destination, vec![member])?;
+
+        Ok(true)
+    }
+
+    // Missing keys are empty sets, every key is still checked to hold a set.
+    pub fn set_op(&self, op: SetOp, keys: &[Bytes]) -> Result<Set, CommandError> {
+        let Some((first, others)) = keys.split_first() else {
+            return Ok(Set::new());
+        };
+
+        let mut result = self
+            .read(first, |set: &Set| set.clone())?
+            .unwrap_or_default();
+
+        for key in others {
+            let found = self.read(key, |set: &Set| match op {
+                SetOp::Inter => result.retain(|member| set.contains(member)),
+                SetOp::Union => result.extend(set.iter().cloned()),
+                SetOp::Diff => result.retain(|member| !set.contains(member)),
+            })?;
+
+            if found.is_none() && op == SetOp::Inter {
+                result.clear();
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Stores the result in the destination, replacing whatever it held.
+    // Returns the number of members stored.
+    pub fn set_op_store(
+        &self,
+        op: SetOp,
+        destination: &[u8],
+        keys: &[Bytes],
+    ) -> Result<usize, CommandError> {
+        let set = self.set_op(op, keys)?;
+        let len = set.len();
+
+        match len {
+            0 => {
+                self.remove(destination);
+            }
+            _ => {
+                self.expires.remove(destination);
+                self.keyspace
+                    .insert(Bytes::copy_from_slice(destination), Value::Set(set));
+                self.modified(destination);
+            }
+        }
+
+        Ok(len)
+    }
+
+    // Size of the intersection, counting stops at `limit` when it is not 0.
+    pub fn sintercard(&self, keys: &[Bytes], limit: usize) -> Result<usize, CommandError> {
+        let len = self.set_op(SetOp::Inter, keys)?.len();
+
+        match limit {
+            0 => Ok(len),
+            limit => Ok(len.min(limit)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::now_millis;
+
+    fn members(items: &[&'static str]) -> Vec<Bytes> {
+        items
+            .iter()
+            .map(|item| Bytes::from_static(item.as_bytes()))
+            .collect()
+    }
+
+    fn sorted(set: Set) -> Vec<Bytes> {
+        let mut members = set.into_iter().collect::<Vec<_>>();
+        members.sort();
+        members
+    }
+
+    #[test]
+    fn test_backend_sadd_srem() {
+        let backend = Backend::new();
+
+        assert_eq!(backend.sadd(b"set", members(&["a", "b", "a"])).unwrap(), 2);
+        assert_eq!(backend.sadd(b"set", members(&["b", "c"])).unwrap(), 1);
+        assert_eq!(
+            backend.smismember(b"set", &members(&["a", "x"])).unwrap(),
+            vec![true, false]
+        );
+        assert_eq!(backend.srem(b"set", &members(&["a", "x"])).unwrap(), 1);
+        assert_eq!(backend.scard(b"set").unwrap(), 2);
+
+        assert_eq!(backend.spop(b"set", 5).unwrap().unwrap().len(), 2);
+        assert!(!backend.exists(b"set"));
+        assert_eq!(backend.spop(b"set", 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_backend_srandmember() {
+        let backend = Backend::new();
+        backend.sadd(b"set", members(&["a", "b", "c"])).unwrap();
+
+        let mut distinct = backend.srandmember(b"set", 5).unwrap();
+        distinct.sort();
+        assert_eq!(distinct, members(&["a", "b", "c"]));
+
+        assert_eq!(backend.srandmember(b"set", -10).unwrap().len(), 10);
+        assert_eq!(backend.srandmember(b"set", 0).unwrap().len(), 0);
+        assert!(backend.srandmember(b"missing", -3).unwrap().is_empty());
+        assert_eq!(backend.scard(b"set").unwrap(), 3);
+    }
+
+    #[test]
+    fn test_backend_smove() {
+        let backend = Backend::new();
+        backend.sadd(b"src", members(&["a"])).unwrap();
+        backend.set(b"string", "value".into());
+
+        assert!(backend.smove(b"src", b"string", "a".into()).is_err());
+        assert!(backend.smove(b"src", b"dst", "a".into()).unwrap());
+        assert!(!backend.smove(b"src", b"dst", "a".into()).unwrap());
+        assert!(!backend.exists(b"src"));
+        assert!(backend.sismember(b"dst", b"a").unwrap());
+    }
+
+    #[test]
+    fn test_backend_set_op() {
+        let backend = Backend::new();
+        backend.sadd(b"a", members(&["1", "2", "3"])).unwrap();
+        backend.sadd(b"b", members(&["2", "3", "4"])).unwrap();
+        let keys = members(&["a", "b"]);
+
+        assert_eq!(
+            sorted(backend.set_op(SetOp::Inter, &keys).unwrap()),
+            members(&["2", "3"])
+        );
+        assert_eq!(
+            sorted(backend.set_op(SetOp::Union, &keys).unwrap()).len(),
+            4
+        );
+        assert_eq!(
+            sorted(backend.set_op(SetOp::Diff, &keys).unwrap()),
+            members(&["1"])
+        );
+        assert!(backend
+            .set_op(SetOp::Inter, &members(&["a", "missing", "b"]))
+            .unwrap()
+            .is_empty());
+        assert_eq!(backend.sintercard(&keys, 1).unwrap(), 1);
+
+        backend.set(b"string", "value".into());
+        assert!(backend
+            .set_op(SetOp::Union, &members(&["a", "string"]))
+            .is_err());
+
+        backend.expire_at(b"string", now_millis() + 10_000, &[]);
+        assert_eq!(
+            backend
+                .set_op_store(SetOp::Inter, b"string", &keys)
+                .unwrap(),
+            2
+        );
+        assert_eq!(backend.pttl(b"string"), Some(None));
+        assert_eq!(
+            backend
+                .set_op_store(SetOp::Diff, b"string", &members(&["x"]))
+                .unwrap(),
+            0
+        );
+        assert!(!backend.exists(b"string"));
+    }
+}
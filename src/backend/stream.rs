@@ -0,0 +1,604 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+
+use bytes::Bytes;
+
+use super::consumer_group::ConsumerGroup;
+use super::{now_millis, Backend};
+use crate::command::CommandError;
+
+// Entries per listpack node in Redis. Approximate trimming only evicts whole
+// nodes, so it removes entries in multiples of this.
+pub const STREAM_NODE_MAX_ENTRIES: usize = 100;
+
+// Field-value pairs of an entry, in the order they were added.
+pub type StreamFields = Vec<(Bytes, Bytes)>;
+pub type StreamEntry = (StreamId, StreamFields);
+
+// Entry IDs are a unix time in milliseconds and a sequence number for the
+// entries added in the same millisecond.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+    pub const MAX: StreamId = StreamId {
+        ms: u64::MAX,
+        seq: u64::MAX,
+    };
+
+    pub fn new(ms: u64, seq: u64) -> Self {
+        Self { ms, seq }
+    }
+
+    // Parses `ms-seq`, or `ms` alone taking `missing_seq` as the sequence.
+    pub fn parse(s: &[u8], missing_seq: u64) -> Result<Self, CommandError> {
+        let s = std::str::from_utf8(s).map_err(|_| invalid_stream_id())?;
+        let number = |s: &str| s.parse::<u64>().map_err(|_| invalid_stream_id());
+
+        match s.split_once('-') {
+            Some((ms, seq)) => Ok(Self::new(number(ms)?, number(seq)?)),
+            None => Ok(Self::new(number(s)?, missing_seq)),
+        }
+    }
+
+    pub fn next(self) -> Option<Self> {
+        match self.seq.checked_add(1) {
+            Some(seq) => Some(Self::new(self.ms, seq)),
+            None => Some(Self::new(self.ms.checked_add(1)?, 0)),
+        }
+    }
+
+    pub fn prev(self) -> Option<Self> {
+        match self.seq.checked_sub(1) {
+            Some(seq) => Some(Self::new(self.ms, seq)),
+            None => Some(Self::new(self.ms.checked_sub(1)?, u64::MAX)),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+pub fn invalid_stream_id() -> CommandError {
+    CommandError::InvalidArgument(
+        "Invalid stream ID specified as stream command argument".to_string(),
+    )
+}
+
+// The ID XADD was given: `*`, `ms-*` or a full ID.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum XAddId {
+    Auto,
+    AutoSeq(u64),
+    Explicit(StreamId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimStrategy {
+    MaxLen(u64),
+    MinId(StreamId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamTrim {
+    pub strategy: TrimStrategy,
+    // `~` only evicts whole nodes, up to `limit` entries
+    pub approximate: bool,
+    // Some(0) means no limit
+    pub limit: Option<u64>,
+}
+
+// Reply of XINFO STREAM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+    pub length: usize,
+    pub last_id: StreamId,
+    pub max_deleted_id: StreamId,
+    pub entries_added: u64,
+    // ID of the first entry, 0-0 when there is none
+    pub first_id: StreamId,
+    pub groups: usize,
+    pub first_entry: Option<StreamEntry>,
+    pub last_entry: Option<StreamEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stream {
+    entries: BTreeMap<StreamId, StreamFields>,
+    pub last_id: StreamId,
+    pub max_deleted_id: StreamId,
+    // entries ever added, including the deleted ones
+    pub entries_added: u64,
+    pub groups: BTreeMap<Bytes, ConsumerGroup>,
+}
+
+impl Stream {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&StreamId, &StreamFields)> {
+        self.entries.iter()
+    }
+
+    pub fn first_id(&self) -> Option<StreamId> {
+        self.entries.keys().next().copied()
+    }
+
+    pub fn get(&self, id: &StreamId) -> Option<&StreamFields> {
+        self.entries.get(id)
+    }
+
+    // Inserts an entry as is, used when loading persisted streams.
+    pub fn insert(&mut self, id: StreamId, fields: StreamFields) {
+        self.entries.insert(id, fields);
+    }
+
+    fn next_id(&self, id: XAddId) -> Result<StreamId, CommandError> {
+        let last = self.last_id;
+        let too_small = || {
+            CommandError::InvalidArgument(
+                "The ID specified in XADD is equal or smaller than the target stream top item"
+                    .to_string(),
+            )
+        };
+
+        match id {
+            XAddId::Auto => match now_millis() {
+                ms if ms > last.ms => Ok(StreamId::new(ms, 0)),
+                _ => last.next().ok_or_else(|| {
+                    CommandError::InvalidArgument(
+                        "The stream has exhausted the last possible ID, unable to add more items"
+                            .to_string(),
+                    )
+                }),
+            },
+            XAddId::AutoSeq(ms) if ms > last.ms => Ok(StreamId::new(ms, 0)),
+            XAddId::AutoSeq(ms) if ms == last.ms => match last.seq.checked_add(1) {
+                Some(seq) => Ok(StreamId::new(ms, seq)),
+                None => Err(too_small()),
+            },
+            XAddId::AutoSeq(_) => Err(too_small()),
+            XAddId::Explicit(StreamId::MIN) => Err(CommandError::InvalidArgument(
+                "The ID specified in XADD must be greater than 0-0".to_string(),
+            )),
+            XAddId::Explicit(id) if id <= last => Err(too_small()),
+            XAddId::Explicit(id) => Ok(id),
+        }
+    }
+
+    pub fn add(&mut self, id: XAddId, fields: StreamFields) -> Result<StreamId, CommandError> {
+        let id = self.next_id(id)?;
+
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        self.entries_added += 1;
+
+        Ok(id)
+    }
+
+    // Both bounds are inclusive. Reverse ranges start from the end.
+    pub fn range(
+        &self,
+        start: StreamId,
+        end: StreamId,
+        count: Option<usize>,
+        rev: bool,
+    ) -> Vec<StreamEntry> {
+        if start > end {
+            return Vec::new();
+        }
+
+        let range = self.entries.range(start..=end);
+        let count = count.unwrap_or(usize::MAX);
+        let entry = |(id, fields): (&StreamId, &StreamFields)| (*id, fields.clone());
+
+        match rev {
+            true => range.rev().take(count).map(entry).collect(),
+            false => range.take(count).map(entry).collect(),
+        }
+    }
+
+    // Entries with an ID greater than `id`, the way XREAD reads.
+    pub fn after(&self, id: StreamId, count: Option<usize>) -> Vec<StreamEntry> {
+        self.entries
+            .range((Bound::Excluded(id), Bound::Unbounded))
+            .take(count.unwrap_or(usize::MAX))
+            .map(|(id, fields)| (*id, fields.clone()))
+            .collect()
+    }
+
+    // Evicts the oldest entries, returning how many were removed.
+    pub fn trim(&mut self, trim: &StreamTrim) -> usize {
+        let mut remove = match trim.strategy {
+            TrimStrategy::MaxLen(max_len) => self.len().saturating_sub(max_len as usize),
+            TrimStrategy::MinId(min_id) => self.entries.range(..min_id).count(),
+        };
+
+        if trim.approximate {
+            let limit = match trim.limit {
+                Some(0) => usize::MAX,
+                Some(limit) => limit as usize,
+                None => 100 * STREAM_NODE_MAX_ENTRIES,
+            };
+
+            remove = remove.min(limit);
+            remove -= remove % STREAM_NODE_MAX_ENTRIES;
+        }
+
+        for _ in 0..remove {
+            self.entries.pop_first();
+        }
+
+        remove
+    }
+
+    pub fn delete(&mut self, ids: &[StreamId]) -> usize {
+        let mut deleted = 0;
+
+        for id in ids {
+            if self.entries.remove(id).is_some() {
+                self.max_deleted_id = self.max_deleted_id.max(*id);
+                deleted += 1;
+            }
+        }
+
+        deleted
+    }
+
+    // Whether entries were deleted after `id`, in which case the entries read
+    // by a group at `id` cannot be counted on to compute its lag.
+    pub(super) fn has_tombstones_after(&self, id: StreamId) -> bool {
+        !self.is_empty() && self.max_deleted_id != StreamId::MIN && self.max_deleted_id >= id
+    }
+
+    // How many entries were added up to `id`, when it can be told without
+    // counting them. Deletions in the middle of the stream make it unknown.
+    pub fn entries_read_at(&self, id: StreamId) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+
+        if self.is_empty() && id <= self.last_id {
+            return Some(self.entries_added);
+        }
+
+        match id.cmp(&self.last_id) {
+            Ordering::Equal => return Some(self.entries_added),
+            Ordering::Greater => return None,
+            Ordering::Less => {}
+        }
+
+        let first = self.first_id().unwrap_or_default();
+        if self.max_deleted_id != StreamId::MIN && self.max_deleted_id >= first {
+            return None;
+        }
+
+        let before_first = self.entries_added.saturating_sub(self.len() as u64);
+        match id.cmp(&first) {
+            Ordering::Less => Some(before_first),
+            Ordering::Equal => Some(before_first + 1),
+            Ordering::Greater => None,
+        }
+    }
+
+    // Entries the group has yet to read, None when it cannot be told.
+    pub fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+
+        let read = match group.entries_read {
+            Some(read) if !self.has_tombstones_after(group.last_id) => read,
+            _ => self.entries_read_at(group.last_id)?,
+        };
+
+        Some(self.entries_added.saturating_sub(read))
+    }
+
+    pub fn set_id(
+        &mut self,
+        id: StreamId,
+        entries_added: Option<u64>,
+        max_deleted_id: Option<StreamId>,
+    ) -> Result<(), CommandError> {
+        if self.entries.keys().next_back().is_some_and(|top| id < *top) {
+            return Err(CommandError::InvalidArgument(
+                "The ID specified in XSETID is smaller than the target stream top item".to_string(),
+            ));
+        }
+
+        if entries_added.is_some_and(|added| added < self.len() as u64) {
+            return Err(CommandError::InvalidArgument(
+                "The entries_added specified in XSETID is smaller than the target stream length"
+                    .to_string(),
+            ));
+        }
+
+        if max_deleted_id.is_some_and(|max_deleted_id| id < max_deleted_id) {
+            return Err(CommandError::InvalidArgument(
+                "The ID specified in XSETID is smaller than the provided max_deleted_entry_id"
+                    .to_string(),
+            ));
+        }
+
+        self.last_id = id;
+        if let Some(added) = entries_added {
+            self.entries_added = added;
+        }
+        if let Some(max_deleted_id) = max_deleted_id {
+            self.max_deleted_id = max_deleted_id;
+        }
+
+        Ok(())
+    }
+}
+
+impl Backend {
+    // Returns the ID of the new entry, None when NOMKSTREAM was given and the
+    // stream does not exist. Clients blocked reading the stream are signaled.
+    pub fn xadd(
+        &self,
+        key: &[u8],
+        id: XAddId,
+        fields: StreamFields,
+        nomkstream: bool,
+        trim: Option<&StreamTrim>,
+    ) -> Result<Option<StreamId>, CommandError> {
+        let add = |stream: &mut Stream| match stream.add(id, fields) {
+            Ok(id) => {
+                if let Some(trim) = trim {
+                    stream.trim(trim);
+                }
+                (Ok(id), true)
+            }
+            Err(e) => (Err(e), false),
+        };
+
+        // streams are kept once created, so a missing one is only created
+        // for a valid ID
+        if !nomkstream && !self.exists(key) {
+            Stream::default().next_id(id)?;
+        }
+
+        let id = match nomkstream {
+            true => match self.update(key, add)? {
+                Some(id) => id?,
+                None => return Ok(None),
+            },
+            false => self.write(key, add)??,
+        };
+
+        self.signal_ready(key);
+
+        Ok(Some(id))
+    }
+
+    pub fn xrange(
+        &self,
+        key: &[u8],
+        start: StreamId,
+        end: StreamId,
+        count: Option<usize>,
+        rev: bool,
+    ) -> Result<Vec<StreamEntry>, CommandError> {
+        let entries = self.read(key, |stream: &Stream| stream.range(start, end, count, rev))?;
+
+        Ok(entries.unwrap_or_default())
+    }
+
+    pub fn xlen(&self, key: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |stream: &Stream| stream.len())?;
+        Ok(len.unwrap_or(0))
+    }
+
+    pub fn xtrim(&self, key: &[u8], trim: &StreamTrim) -> Result<usize, CommandError> {
+        let removed = self.update(key, |stream: &mut Stream| {
+            let removed = stream.trim(trim);
+            (removed, removed > 0)
+        })?;
+        Ok(removed.unwrap_or(0))
+    }
+
+    pub fn xdel(&self, key: &[u8], ids: &[StreamId]) -> Result<usize, CommandError> {
+        let deleted = self.update(key, |stream: &mut Stream| {
+            let deleted = stream.delete(ids);
+            (deleted, deleted > 0)
+        })?;
+        Ok(deleted.unwrap_or(0))
+    }
+
+    pub fn xsetid(
+        &self,
+        key: &[u8],
+        id: StreamId,
+        entries_added: Option<u64>,
+        max_deleted_id: Option<StreamId>,
+    ) -> Result<(), CommandError> {
+        self.update(key, |stream: &mut Stream| {
+            let result = stream.set_id(id, entries_added, max_deleted_id);
+            let changed = result.is_ok();
+            (result, changed)
+        })?
+        .unwrap_or_else(|| Err(CommandError::InvalidArgument("no such key".to_string())))
+    }
+
+    // ID of the last entry ever added, None when the stream does not exist.
+    pub fn stream_last_id(&self, key: &[u8]) -> Result<Option<StreamId>, CommandError> {
+        self.read(key, |stream: &Stream| stream.last_id)
+    }
+
+    pub fn xinfo_stream(&self, key: &[u8]) -> Result<Option<StreamInfo>, CommandError> {
+        self.read(key, |stream: &Stream| {
+            let entry = |(id, fields): (&StreamId, &StreamFields)| (*id, fields.clone());
+
+            StreamInfo {
+                length: stream.len(),
+                last_id: stream.last_id,
+                max_deleted_id: stream.max_deleted_id,
+                entries_added: stream.entries_added,
+                first_id: stream.first_id().unwrap_or_default(),
+                groups: stream.groups.len(),
+                first_entry: stream.entries.iter().next().map(entry),
+                last_entry: stream.entries.iter().next_back().map(entry),
+            }
+        })
+    }
+
+    // Reads the entries after the given ID of each stream, leaving out the
+    // streams that have none.
+    pub fn xread(
+        &self,
+        streams: &[(Bytes, StreamId)],
+        count: Option<usize>,
+    ) -> Result<Vec<(Bytes, Vec<StreamEntry>)>, CommandError> {
+        let mut read = Vec::new();
+
+        for (key, id) in streams {
+            let entries = self.read(key, |stream: &Stream| stream.after(*id, count))?;
+
+            match entries {
+                Some(entries) if !entries.is_empty() => read.push((key.clone(), entries)),
+                _ => {}
+            }
+        }
+
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(field: &'static str) -> StreamFields {
+        vec![(Bytes::from_static(field.as_bytes()), "v".into())]
+    }
+
+    fn id(ms: u64, seq: u64) -> StreamId {
+        StreamId::new(ms, seq)
+    }
+
+    #[test]
+    fn test_stream_id_parse() {
+        assert_eq!(StreamId::parse(b"5-3", 0).unwrap(), id(5, 3));
+        assert_eq!(StreamId::parse(b"5", u64::MAX).unwrap(), id(5, u64::MAX));
+        assert!(StreamId::parse(b"5-", 0).is_err());
+        assert!(StreamId::parse(b"-1", 0).is_err());
+        assert!(StreamId::parse(b"a-1", 0).is_err());
+
+        assert_eq!(id(5, u64::MAX).next(), Some(id(6, 0)));
+        assert_eq!(id(5, 0).prev(), Some(id(4, u64::MAX)));
+        assert_eq!(StreamId::MAX.next(), None);
+        assert_eq!(id(5, 3).to_string(), "5-3");
+    }
+
+    #[test]
+    fn test_backend_xadd_ids() {
+        let backend = Backend::new();
+        let explicit = |ms, seq| XAddId::Explicit(id(ms, seq));
+
+        assert!(backend
+            .xadd(b"s", explicit(0, 0), fields("a"), false, None)
+            .is_err());
+        assert!(!backend.exists(b"s"));
+        assert_eq!(
+            backend
+                .xadd(b"s", explicit(1, 1), fields("a"), true, None)
+                .unwrap(),
+            None
+        );
+
+        let add = |id| backend.xadd(b"s", id, fields("a"), false, None);
+        assert_eq!(add(explicit(5, 1)).unwrap(), Some(id(5, 1)));
+        assert!(add(explicit(5, 1)).is_err());
+        assert!(add(XAddId::AutoSeq(4)).is_err());
+        assert_eq!(add(XAddId::AutoSeq(5)).unwrap(), Some(id(5, 2)));
+        assert_eq!(add(XAddId::AutoSeq(7)).unwrap(), Some(id(7, 0)));
+        assert!(add(XAddId::Auto).unwrap().unwrap().ms >= now_millis() - 1000);
+        assert_eq!(backend.xlen(b"s").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_backend_xrange_xdel() {
+        let backend = Backend::new();
+        for seq in 1..=4 {
+            let id = XAddId::Explicit(id(1, seq));
+            backend.xadd(b"s", id, fields("a"), false, None).unwrap();
+        }
+
+        let ids = |entries: Vec<StreamEntry>| entries.into_iter().map(|(id, _)| id).collect();
+        let range: Vec<_> = ids(backend
+            .xrange(b"s", id(1, 2), StreamId::MAX, None, false)
+            .unwrap());
+        assert_eq!(range, vec![id(1, 2), id(1, 3), id(1, 4)]);
+        let range: Vec<_> = ids(backend
+            .xrange(b"s", StreamId::MIN, id(1, 3), Some(2), true)
+            .unwrap());
+        assert_eq!(range, vec![id(1, 3), id(1, 2)]);
+
+        assert_eq!(backend.xdel(b"s", &[id(1, 4), id(9, 9)]).unwrap(), 1);
+        let read = backend.xread(&[("s".into(), id(1, 2))], None).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(ids(read[0].1.clone()), vec![id(1, 3)]);
+
+        // emptied streams are kept, with the last ID they had
+        assert_eq!(
+            backend.xdel(b"s", &[id(1, 1), id(1, 2), id(1, 3)]).unwrap(),
+            3
+        );
+        assert_eq!(backend.xlen(b"s").unwrap(), 0);
+        assert_eq!(backend.stream_last_id(b"s").unwrap(), Some(id(1, 4)));
+        assert!(backend
+            .xread(&[("s".into(), StreamId::MIN)], None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_stream_trim() {
+        let mut stream = Stream::default();
+        for ms in 1..=250 {
+            stream.add(XAddId::AutoSeq(ms), fields("a")).unwrap();
+        }
+
+        let trim = |strategy, approximate, limit| StreamTrim {
+            strategy,
+            approximate,
+            limit,
+        };
+
+        // approximate trimming evicts whole nodes only
+        assert_eq!(
+            stream.trim(&trim(TrimStrategy::MaxLen(100), true, None)),
+            100
+        );
+        assert_eq!(
+            stream.trim(&trim(TrimStrategy::MaxLen(100), true, Some(50))),
+            0
+        );
+        assert_eq!(
+            stream.trim(&trim(TrimStrategy::MaxLen(100), false, None)),
+            50
+        );
+        assert_eq!(stream.first_id(), Some(id(151, 0)));
+
+        let min_id = TrimStrategy::MinId(id(200, 0));
+        assert_eq!(stream.trim(&trim(min_id, false, None)), 49);
+        assert_eq!(stream.len(), 51);
+        assert_eq!(stream.entries_added, 250);
+    }
+}
@@ -0,0 +1,286 @@
+use bytes::{Bytes, BytesMut};
+use dashmap::mapref::entry::Entry;
+
+use super::{Backend, Value};
+use crate::command::CommandError;
+
+// Largest string SETRANGE and APPEND may produce, like Redis' default
+// proto-max-bulk-len.
+const MAX_STRING_LEN: usize = 512 * 1024 * 1024;
+
+// Strings are stored as bytes. Counters use the canonical decimal form, so a
+// value only is an integer when formatting it back gives the same bytes, the
+// way Redis decides whether a string can be int encoded.
+pub fn parse_int(value: &[u8]) -> Option<i64> {
+    let s = std::str::from_utf8(value).ok()?;
+    let int = s.parse::<i64>().ok()?;
+    (int.to_string() == s).then_some(int)
+}
+
+pub fn parse_float(value: &[u8]) -> Option<f64> {
+    std::str::from_utf8(value)
+        .ok()?
+        .parse::<f64>()
+        .ok()
+        .filter(|float| !float.is_nan())
+}
+
+impl Backend {
+    // Runs `f` on the string stored at key, None when the key does not exist,
+    // and stores the bytes it returns. The time to live is kept.
+    pub(super) fn write_string<R>(
+        &self,
+        key: &[u8],
+        f: impl FnOnce(Option<&Bytes>) -> Result<(Bytes, R), CommandError>,
+    ) -> Result<R, CommandError> {
+        self.expire_if_needed(key);
+
+        let result = match self.keyspace.entry(Bytes::copy_from_slice(key)) {
+            Entry::Occupied(mut entry) => match entry.get() {
+                Value::String(current) => {
+                    let (value, result) = f(Some(current))?;
+                    entry.insert(Value::String(value));
+                    result
+                }
+                _ => return Err(CommandError::WrongType),
+            },
+            Entry::Vacant(entry) => {
+                let (value, result) = f(None)?;
+                entry.insert(Value::String(value));
+                result
+            }
+        };
+
+        self.modified(key);
+
+        Ok(result)
+    }
+
+    // Missing keys count as 0. Returns the value after the increment.
+    pub fn incr_by(&self, key: &[u8], increment: i64) -> Result<i64, CommandError> {
+        self.write_string(key, |current| {
+            let current = match current {
+                Some(current) => parse_int(current).ok_or(CommandError::NotInteger)?,
+                None => 0,
+            };
+
+            let value = current.checked_add(increment).ok_or_else(|| {
+                CommandError::InvalidArgument("increment or decrement would overflow".to_string())
+            })?;
+
+            Ok((value.to_string().into(), value))
+        })
+    }
+
+    pub fn incr_by_float(&self, key: &[u8], increment: f64) -> Result<f64, CommandError> {
+        self.write_string(key, |current| {
+            let current = match current {
+                Some(current) => parse_float(current).ok_or(CommandError::NotFloat)?,
+                None => 0.0,
+            };
+
+            let value = current + increment;
+            if !value.is_finite() {
+                return Err(CommandError::InvalidArgument(
+                    "increment would produce NaN or Infinity".to_string(),
+                ));
+            }
+
+            Ok((value.to_string().into(), value))
+        })
+    }
+
+    // Returns the length of the string after appending.
+    pub fn append(&self, key: &[u8], value: &[u8]) -> Result<usize, CommandError> {
+        self.write_string(key, |current| {
+            let current = current.map_or(&[][..], |current| current);
+            check_length(current.len() + value.len())?;
+
+            let mut appended = BytesMut::with_capacity(current.len() + value.len());
+            appended.extend_from_slice(current);
+            appended.extend_from_slice(value);
+
+            let len = appended.len();
+            Ok((appended.freeze(), len))
+        })
+    }
+
+    pub fn strlen(&self, key: &[u8]) -> Result<usize, CommandError> {
+        Ok(self.get(key)?.map_or(0, |value| value.len()))
+    }
+
+    // Both offsets are inclusive, negative ones count from the end.
+    pub fn getrange(&self, key: &[u8], start: i64, end: i64) -> Result<Bytes, CommandError> {
+        let Some(value) = self.get(key)? else {
+            return Ok(Bytes::new());
+        };
+
+        let len = value.len() as i64;
+        if (start < 0 && end < 0 && start > end) || len == 0 {
+            return Ok(Bytes::new());
+        }
+
+        let start = if start < 0 {
+            (len + start).max(0)
+        } else {
+            start
+        };
+        let end = if end < 0 {
+            (len + end).max(0)
+        } else {
+            end.min(len - 1)
+        };
+
+        if start > end {
+            return Ok(Bytes::new());
+        }
+
+        Ok(value.slice(start as usize..=end as usize))
+    }
+
+    // Overwrites part of the string, padding it with zero bytes when the
+    // offset is past its end. Returns the length of the string afterwards.
+    pub fn setrange(&self, key: &[u8], offset: usize, value: &[u8]) -> Result<usize, CommandError> {
+        // nothing to write, missing keys are not created
+        if value.is_empty() {
+            return self.strlen(key);
+        }
+
+        check_length(offset + value.len())?;
+
+        self.write_string(key, |current| {
+            let current = current.map_or(&[][..], |current| current);
+
+            let mut updated = BytesMut::from(current);
+            if updated.len() < offset + value.len() {
+                updated.resize(offset + value.len(), 0);
+            }
+            updated[offset..offset + value.len()].copy_from_slice(value);
+
+            let len = updated.len();
+            Ok((updated.freeze(), len))
+        })
+    }
+
+    // Keys that do not hold a string are reported as missing.
+    pub fn mget(&self, keys: &[Bytes]) -> Vec<Option<Bytes>> {
+        keys.iter()
+            .map(|key| self.get(key).ok().flatten())
+            .collect()
+    }
+
+    // Callers hold the keyspace lock exclusively, so no client sees only part
+    // of the keys set.
+    pub fn mset(&self, pairs: &[(Bytes, Bytes)]) {
+        for (key, value) in pairs {
+            self.set(key, value.clone());
+        }
+    }
+
+    // Sets nothing when any of the keys exists.
+    pub fn msetnx(&self, pairs: &[(Bytes, Bytes)]) -> bool {
+        if pairs.iter().any(|(key, _)| self.exists(key)) {
+            return false;
+        }
+
+        self.mset(pairs);
+        true
+    }
+
+    pub fn getdel(&self, key: &[u8]) -> Result<Option<Bytes>, CommandError> {
+        let value = self.get(key)?;
+
+        if value.is_some() {
+            self.remove(key);
+        }
+
+        Ok(value)
+    }
+}
+
+fn check_length(len: usize) -> Result<(), CommandError> {
+    match len > MAX_STRING_LEN {
+        true => Err(CommandError::InvalidArgument(
+            "string exceeds maximum allowed size (proto-max-bulk-len)".to_string(),
+        )),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_int() {
+        assert_eq!(parse_int(b"-42"), Some(-42));
+        assert_eq!(parse_int(b"0"), Some(0));
+        assert_eq!(parse_int(b"+1"), None);
+        assert_eq!(parse_int(b"01"), None);
+        assert_eq!(parse_int(b" 1"), None);
+        assert_eq!(parse_int(b"9223372036854775808"), None);
+    }
+
+    #[test]
+    fn test_backend_incr_by() {
+        let backend = Backend::new();
+
+        assert_eq!(backend.incr_by(b"counter", 5).unwrap(), 5);
+        assert_eq!(backend.incr_by(b"counter", -7).unwrap(), -2);
+        assert_eq!(backend.get(b"counter").unwrap(), Some("-2".into()));
+        assert!(backend.incr_by(b"counter", i64::MIN).is_err());
+
+        assert_eq!(backend.incr_by_float(b"counter", 0.5).unwrap(), -1.5);
+        assert!(matches!(
+            backend.incr_by(b"counter", 1),
+            Err(CommandError::NotInteger)
+        ));
+        assert!(backend.incr_by_float(b"counter", f64::INFINITY).is_err());
+
+        backend.sadd(b"set", vec!["a".into()]).unwrap();
+        assert!(matches!(
+            backend.incr_by(b"set", 1),
+            Err(CommandError::WrongType)
+        ));
+    }
+
+    #[test]
+    fn test_backend_append_setrange_getrange() {
+        let backend = Backend::new();
+
+        assert_eq!(backend.append(b"key", b"Hello").unwrap(), 5);
+        assert_eq!(backend.append(b"key", b" World").unwrap(), 11);
+        assert_eq!(backend.getrange(b"key", 0, 4).unwrap(), "Hello");
+        assert_eq!(backend.getrange(b"key", -5, -1).unwrap(), "World");
+        assert_eq!(backend.getrange(b"key", 5, 1).unwrap(), "");
+        assert_eq!(backend.getrange(b"key", 0, 100).unwrap(), "Hello World");
+
+        assert_eq!(backend.setrange(b"key", 6, b"Redis").unwrap(), 11);
+        assert_eq!(backend.get(b"key").unwrap(), Some("Hello Redis".into()));
+
+        assert_eq!(backend.setrange(b"padded", 2, b"x").unwrap(), 3);
+        assert_eq!(backend.get(b"padded").unwrap(), Some("\0\0x".into()));
+        assert_eq!(backend.setrange(b"missing", 5, b"").unwrap(), 0);
+        assert!(!backend.exists(b"missing"));
+    }
+
+    #[test]
+    fn test_backend_mset_msetnx() {
+        let backend = Backend::new();
+        let pairs = vec![("a".into(), "1".into()), ("b".into(), "2".into())];
+
+        backend.mset(&pairs);
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+        assert_eq!(
+            backend.mget(&["a".into(), "set".into(), "c".into()]),
+            vec![Some("1".into()), None, None]
+        );
+
+        assert!(!backend.msetnx(&[("c".into(), "3".into()), ("a".into(), "x".into())]));
+        assert!(!backend.exists(b"c"));
+        assert!(backend.msetnx(&[("c".into(), "3".into())]));
+
+        assert_eq!(backend.getdel(b"c").unwrap(), Some("3".into()));
+        assert!(!backend.exists(b"c"));
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bytes::Bytes;
+
+use super::stream::Stream;
+use super::zset::ZSet;
+
+pub type Hash = HashMap<Bytes, Bytes>;
+pub type Set = HashSet<Bytes>;
+pub type List = VecDeque<Bytes>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(Bytes),
+    Hash(Hash),
+    Set(Set),
+    List(List),
+    ZSet(ZSet),
+    Stream(Stream),
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Hash(_) => "hash",
+            Value::Set(_) => "set",
+            Value::List(_) => "list",
+            Value::ZSet(_) => "zset",
+            Value::Stream(_) => "stream",
+        }
+    }
+}
+
+// Collection types stored in the keyspace. Commands access them through
+// `Backend::read` and `Backend::write`, which take care of the type checks.
+pub trait Collection: Default + Into<Value> {
+    fn from_value(value: &Value) -> Option<&Self>;
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self>;
+
+    fn is_empty(&self) -> bool;
+}
+
+impl From<Hash> for Value {
+    fn from(hash: Hash) -> Self {
+        Value::Hash(hash)
+    }
+}
+
+impl Collection for Hash {
+    fn from_value(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Hash(hash) => Some(hash),
+            _ => None,
+        }
+    }
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Hash(hash) => Some(hash),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+}
+
+impl From<Set> for Value {
+    fn from(set: Set) -> Self {
+        Value::Set(set)
+    }
+}
+
+impl Collection for Set {
+    fn from_value(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Set(set) => Some(set),
+            _ => None,
+        }
+    }
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Set(set) => Some(set),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+}
+
+impl From<List> for Value {
+    fn from(list: List) -> Self {
+        Value::List(list)
+    }
+}
+
+impl Collection for List {
+    fn from_value(value: &Value) -> Option<&Self> {
+        match value {
+            Value::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+}
+
+impl From<ZSet> for Value {
+    fn from(zset: ZSet) -> Self {
+        Value::ZSet(zset)
+    }
+}
+
+impl Collection for ZSet {
+    fn from_value(value: &Value) -> Option<&Self> {
+        match value {
+            Value::ZSet(zset) => Some(zset),
+            _ => None,
+        }
+    }
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::ZSet(zset) => Some(zset),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        ZSet::is_empty(self)
+    }
+}
+
+impl From<Stream> for Value {
+    fn from(stream: Stream) -> Self {
+        Value::Stream(stream)
+    }
+}
+
+impl Collection for Stream {
+    fn from_value(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Stream(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    fn from_value_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Stream(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    // Streams are kept without entries, like in Redis.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
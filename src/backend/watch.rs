@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+use bytes::Bytes;
+
+use super::Backend;
+
+// The version of a key watched by at least one client, bumped by every change
+// to the key. It is dropped with the last watcher, keys nobody watches are not
+// tracked.
+#[derive(Debug, Default)]
+pub struct WatchedKey {
+    watchers: usize,
+    version: u64,
+}
+
+// The keys a client watches, with the version each had when watched. EXEC
+// fails once any of them changed. Dropping it, like when the client
+// disconnects, unwatches them all.
+#[derive(Debug)]
+pub struct WatchedKeys {
+    keys: HashMap<Bytes, u64>,
+    backend: Backend,
+}
+
+impl WatchedKeys {
+    pub fn new(backend: &Backend) -> Self {
+        Self {
+            keys: HashMap::new(),
+            backend: backend.clone(),
+        }
+    }
+
+    pub fn watch(&mut self, key: Bytes) {
+        if self.keys.contains_key(&key) {
+            return;
+        }
+
+        // a key that already expired is dropped before its version is read,
+        // only expiring after WATCH counts as a change
+        self.backend.exists(&key);
+
+        let version = {
+            let mut watched = self.backend.watched.entry(key.clone()).or_default();
+            if watched.watchers == 0 {
+                self.backend.watching.fetch_add(1, Ordering::Relaxed);
+            }
+            watched.watchers += 1;
+            watched.version
+        };
+
+        self.keys.insert(key, version);
+    }
+
+    // Whether a watched key changed since WATCH, expiring included.
+    pub fn modified(&self) -> bool {
+        self.keys.iter().any(|(key, version)| {
+            self.backend.exists(key);
+
+            self.backend
+                .watched
+                .get(key)
+                .is_none_or(|watched| watched.version != *version)
+        })
+    }
+
+    pub fn clear(&mut self) {
+        for key in std::mem::take(&mut self.keys).into_keys() {
+            self.backend.unwatch(&key);
+        }
+    }
+}
+
+impl Drop for WatchedKeys {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl Backend {
+    fn unwatch(&self, key: &[u8]) {
+        let removed = self.watched.remove_if_mut(key, |_, watched| {
+            watched.watchers -= 1;
+            watched.watchers == 0
+        });
+
+        if removed.is_some() {
+            self.watching.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    // Called after every change to a key.
+    pub(super) fn touch_watched(&self, key: &[u8]) {
+        // most writes happen with no key watched at all
+        if self.watching.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        if let Some(mut watched) = self.watched.get_mut(key) {
+            watched.version += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{now_millis, ListEnd};
+
+    #[test]
+    fn test_watched_keys_modified() {
+        let backend = Backend::new();
+        backend.set(b"a", "1".into());
+
+        let mut watched = WatchedKeys::new(&backend);
+        watched.watch("a".into());
+        watched.watch("b".into());
+        assert!(!watched.modified());
+
+        // changes from before WATCH don't count
+        let mut other = WatchedKeys::new(&backend);
+        backend.set(b"a", "2".into());
+        other.watch("a".into());
+        assert!(watched.modified());
+        assert!(!other.modified());
+
+        backend.set(b"b", "1".into());
+        assert!(!other.modified());
+        watched.clear();
+        other.clear();
+        assert!(backend.watched.is_empty());
+        assert_eq!(backend.watching.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_watched_keys_expired() {
+        let backend = Backend::new();
+        backend.set(b"a", "1".into());
+        backend.expire_at(b"a", now_millis() + 10, &[]);
+
+        let mut watched = WatchedKeys::new(&backend);
+        watched.watch("a".into());
+        assert!(!watched.modified());
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(watched.modified());
+
+        drop(watched);
+        assert!(backend.watched.is_empty());
+    }
+
+    #[test]
+    fn test_watched_keys_unchanged_writes() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["a".into()]).unwrap();
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+        backend
+            .push(b"list", ListEnd::Right, vec!["a".into()])
+            .unwrap();
+
+        let mut watched = WatchedKeys::new(&backend);
+        for key in ["set", "hash", "list"] {
+            watched.watch(key.into());
+        }
+        let dirty = backend.dirty.load(Ordering::Relaxed);
+
+        // writes leaving the values as they were don't count
+        backend.sadd(b"set", vec!["a".into()]).unwrap();
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+        backend.lrem(b"list", 0, b"b").unwrap();
+        assert!(!watched.modified());
+        assert_eq!(backend.dirty.load(Ordering::Relaxed), dirty);
+
+        backend
+            .hset(b"hash", vec![("field".into(), "other".into())])
+            .unwrap();
+        assert!(watched.modified());
+    }
+}
@@ -0,0 +1,614 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
+
+use bytes::Bytes;
+
+use super::list::list_range;
+use super::{Backend, SetCondition};
+use crate::command::CommandError;
+
+// Scores are never NaN, so they can be totally ordered. Negative zero is
+// stored as zero, like Redis treats both as the same score.
+#[derive(Debug, Clone, Copy)]
+struct Score(f64);
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+type IndexBound = Bound<(Score, Bytes)>;
+
+// Members are indexed by score, then by member for equal scores, with a hash
+// lookup from member to score.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZSet {
+    scores: HashMap<Bytes, f64>,
+    index: BTreeSet<(Score, Bytes)>,
+}
+
+impl ZSet {
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn score(&self, member: &[u8]) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    // Returns the previous score of the member.
+    pub fn insert(&mut self, member: Bytes, score: f64) -> Option<f64> {
+        let score = score + 0.0;
+        let previous = self.scores.insert(member.clone(), score);
+
+        if let Some(previous) = previous {
+            self.index.remove(&(Score(previous), member.clone()));
+        }
+        self.index.insert((Score(score), member));
+
+        previous
+    }
+
+    pub fn remove(&mut self, member: &[u8]) -> Option<f64> {
+        let (member, score) = self.scores.remove_entry(member)?;
+        self.index.remove(&(Score(score), member));
+        Some(score)
+    }
+
+    // Members in ascending order of score.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&Bytes, f64)> + ExactSizeIterator {
+        self.index.iter().map(|(score, member)| (member, score.0))
+    }
+
+    pub fn rank(&self, member: &[u8]) -> Option<usize> {
+        let (member, score) = self.scores.get_key_value(member)?;
+        Some(self.index.range(..(Score(*score), member.clone())).count())
+    }
+
+    pub fn range_by_score(
+        &self,
+        min: ScoreBound,
+        max: ScoreBound,
+    ) -> impl DoubleEndedIterator<Item = (&Bytes, f64)> {
+        score_range(min, max)
+            .into_iter()
+            .flat_map(|range| self.index.range(range))
+            .map(|(score, member)| (member, score.0))
+    }
+
+    // Lexicographical ranges are only meaningful when all the members have
+    // the same score, as they are walked in score order.
+    pub fn range_by_lex<'a>(
+        &'a self,
+        min: &'a LexBound,
+        max: &'a LexBound,
+    ) -> impl DoubleEndedIterator<Item = (&'a Bytes, f64)> {
+        self.iter()
+            .filter(move |(member, _)| min.below(member) && max.above(member))
+    }
+}
+
+// One end of a score range, `(` makes it exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBound {
+    pub value: f64,
+    pub exclusive: bool,
+}
+
+impl ScoreBound {
+    pub fn parse(s: &str) -> Result<Self, CommandError> {
+        let (value, exclusive) = match s.strip_prefix('(') {
+            Some(value) => (value, true),
+            None => (s, false),
+        };
+
+        match parse_score(value) {
+            Ok(value) => Ok(Self { value, exclusive }),
+            Err(_) => Err(CommandError::InvalidArgument(
+                "min or max is not a float".to_string(),
+            )),
+        }
+    }
+}
+
+// The index range holding the scores between min and max, None when there
+// are none. Members sort after the empty member for the same score.
+fn score_range(min: ScoreBound, max: ScoreBound) -> Option<(IndexBound, IndexBound)> {
+    let lower = match min.exclusive {
+        true if min.value == f64::INFINITY => return None,
+        true => min.value.next_up(),
+        false => min.value,
+    };
+
+    let upper = match max.exclusive {
+        true => max.value,
+        false if max.value == f64::INFINITY => {
+            return Some((
+                Bound::Included((Score(lower), Bytes::new())),
+                Bound::Unbounded,
+            ));
+        }
+        false => max.value.next_up(),
+    };
+
+    match lower < upper {
+        true => Some((
+            Bound::Included((Score(lower), Bytes::new())),
+            Bound::Excluded((Score(upper), Bytes::new())),
+        )),
+        false => None,
+    }
+}
+
+// One end of a lexicographical range: `[` inclusive, `(` exclusive, `-` and
+// `+` the smallest and greatest members.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexBound {
+    Min,
+    Max,
+    Inclusive(Bytes),
+    Exclusive(Bytes),
+}
+
+impl LexBound {
+    pub fn parse(s: &Bytes) -> Result<Self, CommandError> {
+        match s.first() {
+            Some(b'-') if s.len() == 1 => Ok(LexBound::Min),
+            Some(b'+') if s.len() == 1 => Ok(LexBound::Max),
+            Some(b'[') => Ok(LexBound::Inclusive(s.slice(1..))),
+            Some(b'(') => Ok(LexBound::Exclusive(s.slice(1..))),
+            _ => Err(CommandError::InvalidArgument(
+                "min or max not valid string range item".to_string(),
+            )),
+        }
+    }
+
+    // Whether the member is past this bound used as a minimum.
+    fn below(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => true,
+            LexBound::Max => false,
+            LexBound::Inclusive(min) => member >= &min[..],
+            LexBound::Exclusive(min) => member > &min[..],
+        }
+    }
+
+    // Whether the member is before this bound used as a maximum.
+    fn above(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => false,
+            LexBound::Max => true,
+            LexBound::Inclusive(max) => member <= &max[..],
+            LexBound::Exclusive(max) => member < &max[..],
+        }
+    }
+}
+
+// Scores accept anything Redis does, including `inf`, but never NaN.
+pub fn parse_score(s: &str) -> Result<f64, CommandError> {
+    match s.parse::<f64>() {
+        Ok(score) if !score.is_nan() => Ok(score + 0.0),
+        _ => Err(CommandError::NotFloat),
+    }
+}
+
+// Only update members whose new score is greater or less than the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreComparison {
+    Greater,
+    Less,
+}
+
+impl ScoreComparison {
+    fn allows(comparison: Option<Self>, new: f64, current: f64) -> bool {
+        match comparison {
+            None => true,
+            Some(ScoreComparison::Greater) => new > current,
+            Some(ScoreComparison::Less) => new < current,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZRangeBy {
+    Rank(i64, i64),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+// A ZRANGE query. The bounds are always given as min then max, REV only
+// changes the order of the reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZRange {
+    pub by: ZRangeBy,
+    pub rev: bool,
+    pub offset: usize,
+    pub count: Option<usize>,
+}
+
+impl Backend {
+    // Returns the number of members added and the number of existing members
+    // whose score changed.
+    pub fn zadd(
+        &self,
+        key: &[u8],
+        condition: SetCondition,
+        comparison: Option<ScoreComparison>,
+        members: Vec<(f64, Bytes)>,
+    ) -> Result<(usize, usize), CommandError> {
+        self.write(key, |zset: &mut ZSet| {
+            let (mut added, mut updated) = (0, 0);
+
+            for (score, member) in members {
+                match zset.score(&member) {
+                    None if condition != SetCondition::IfExists => {
+                        zset.insert(member, score);
+                        added += 1;
+                    }
+                    Some(current)
+                        if condition != SetCondition::IfNotExists
+                            && current != score
+                            && ScoreComparison::allows(comparison, score, current) =>
+                    {
+                        zset.insert(member, score);
+                        updated += 1;
+                    }
+                    _ => {}
+                }
+            }
+
+            ((added, updated), added + updated > 0)
+        })
+    }
+
+    // Returns the new score of the member, None when the conditions prevented
+    // the increment.
+    pub fn zincrby(
+        &self,
+        key: &[u8],
+        condition: SetCondition,
+        comparison: Option<ScoreComparison>,
+        increment: f64,
+        member: Bytes,
+    ) -> Result<Option<f64>, CommandError> {
+        self.write(key, |zset: &mut ZSet| {
+            let current = zset.score(&member);
+            let score = current.unwrap_or(0.0) + increment;
+
+            if score.is_nan() {
+                let error = "resulting score is not a number (NaN)".to_string();
+                return (Err(CommandError::InvalidArgument(error)), false);
+            }
+
+            let allowed = match (condition, current) {
+                (SetCondition::IfNotExists, Some(_)) | (SetCondition::IfExists, None) => false,
+                (_, Some(current)) => ScoreComparison::allows(comparison, score, current),
+                (_, None) => true,
+            };
+
+            if !allowed {
+                return (Ok(None), false);
+            }
+
+            zset.insert(member, score);
+            (Ok(Some(score)), true)
+        })?
+    }
+
+    pub fn zrem(&self, key: &[u8], members: &[Bytes]) -> Result<usize, CommandError> {
+        let removed = self.update(key, |zset: &mut ZSet| {
+            let removed = members
+                .iter()
+                .filter(|member| zset.remove(member).is_some())
+                .count();
+            (removed, removed > 0)
+        })?;
+
+        Ok(removed.unwrap_or(0))
+    }
+
+    pub fn zscore(&self, key: &[u8], member: &[u8]) -> Result<Option<f64>, CommandError> {
+        let score = self.read(key, |zset: &ZSet| zset.score(member))?;
+        Ok(score.flatten())
+    }
+
+    pub fn zcard(&self, key: &[u8]) -> Result<usize, CommandError> {
+        let len = self.read(key, |zset: &ZSet| zset.len())?;
+        Ok(len.unwrap_or(0))
+    }
+
+    // Returns the rank of the member with its score, ranks counting from the
+    // highest score when `rev` is set.
+    pub fn zrank(
+        &self,
+        key: &[u8],
+        member: &[u8],
+        rev: bool,
+    ) -> Result<Option<(usize, f64)>, CommandError> {
+        let rank = self.read(key, |zset: &ZSet| {
+            let rank = zset.rank(member)?;
+            let score = zset.score(member)?;
+
+            match rev {
+                true => Some((zset.len() - 1 - rank, score)),
+                false => Some((rank, score)),
+            }
+        })?;
+
+        Ok(rank.flatten())
+    }
+
+    pub fn zcount(
+        &self,
+        key: &[u8],
+        min: ScoreBound,
+        max: ScoreBound,
+    ) -> Result<usize, CommandError> {
+        let count = self.read(key, |zset: &ZSet| zset.range_by_score(min, max).count())?;
+        Ok(count.unwrap_or(0))
+    }
+
+    pub fn zrange(&self, key: &[u8], range: &ZRange) -> Result<Vec<(Bytes, f64)>, CommandError> {
+        let members = self.read(key, |zset: &ZSet| {
+            let count = range.count.unwrap_or(usize::MAX);
+
+            let members: Box<dyn DoubleEndedIterator<Item = (&Bytes, f64)>> = match &range.by {
+                ZRangeBy::Rank(start, stop) => match list_range(*start, *stop, zset.len()) {
+                    // ranks count from the highest score with REV
+                    Some((start, stop)) if range.rev => {
+                        let len = zset.len();
+                        Box::new(zset.iter().skip(len - 1 - stop).take(stop - start + 1))
+                    }
+                    Some((start, stop)) => Box::new(zset.iter().skip(start).take(stop - start + 1)),
+                    None => Box::new(std::iter::empty()),
+                },
+                ZRangeBy::Score(min, max) => Box::new(zset.range_by_score(*min, *max)),
+                ZRangeBy::Lex(min, max) => Box::new(zset.range_by_lex(min, max)),
+            };
+
+            let members: Box<dyn Iterator<Item = (&Bytes, f64)>> = match range.rev {
+                true => Box::new(members.rev()),
+                false => members,
+            };
+
+            members
+                .skip(range.offset)
+                .take(count)
+                .map(|(member, score)| (member.clone(), score))
+                .collect()
+        })?;
+
+        Ok(members.unwrap_or_default())
+    }
+
+    // Pops up to `count` members with the lowest scores, or the highest ones
+    // when `max` is set.
+    pub fn zpop(
+        &self,
+        key: &[u8],
+        max: bool,
+        count: usize,
+    ) -> Result<Vec<(Bytes, f64)>, CommandError> {
+        let popped = self.update(key, |zset: &mut ZSet| {
+            let members: Vec<_> = match max {
+                true => zset
+                    .iter()
+                    .rev()
+                    .take(count)
+                    .map(|(m, s)| (m.clone(), s))
+                    .collect(),
+                false => zset
+                    .iter()
+                    .take(count)
+                    .map(|(m, s)| (m.clone(), s))
+                    .collect(),
+            };
+
+            for (member, _) in &members {
+                zset.remove(member);
+            }
+
+            let changed = !members.is_empty();
+            (members, changed)
+        })?;
+
+        Ok(popped.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound(s: &str) -> ScoreBound {
+        ScoreBound::parse(s).unwrap()
+    }
+
+    fn members(pairs: &[(f64, &'static str)]) -> Vec<(f64, Bytes)> {
+        pairs
+            .iter()
+            .map(|(score, member)| (*score, Bytes::from_static(member.as_bytes())))
+            .collect()
+    }
+
+    #[test]
+    fn test_zset_index() {
+        let mut zset = ZSet::default();
+        zset.insert("b".into(), 2.0);
+        zset.insert("a".into(), 2.0);
+        zset.insert("c".into(), -0.0);
+        assert_eq!(zset.insert("c".into(), 1.0), Some(0.0));
+
+        let order: Vec<_> = zset.iter().map(|(m, _)| m.clone()).collect();
+        assert_eq!(order, vec!["c", "a", "b"]);
+        assert_eq!(zset.rank(b"b"), Some(2));
+        assert_eq!(zset.remove(b"a"), Some(2.0));
+        assert_eq!(zset.len(), 2);
+        assert_eq!(zset.index.len(), 2);
+    }
+
+    #[test]
+    fn test_zset_range_by_score() {
+        let mut zset = ZSet::default();
+        for (score, member) in [(1.0, "a"), (2.0, "b"), (2.0, "c"), (f64::INFINITY, "d")] {
+            zset.insert(member.into(), score);
+        }
+
+        let range = |min, max| zset.range_by_score(bound(min), bound(max)).count();
+        assert_eq!(range("-inf", "+inf"), 4);
+        assert_eq!(range("2", "2"), 2);
+        assert_eq!(range("(1", "(inf"), 2);
+        assert_eq!(range("(2", "inf"), 1);
+        assert_eq!(range("(inf", "inf"), 0);
+        assert_eq!(range("3", "1"), 0);
+        assert_eq!(range("(2", "(2"), 0);
+        assert!(ScoreBound::parse("nan").is_err());
+        assert!(ScoreBound::parse("[1").is_err());
+    }
+
+    #[test]
+    fn test_backend_zadd_conditions() {
+        let backend = Backend::new();
+        let add = |condition, comparison, pairs| {
+            backend
+                .zadd(b"zset", condition, comparison, members(pairs))
+                .unwrap()
+        };
+
+        assert_eq!(
+            add(SetCondition::Always, None, &[(1.0, "a"), (2.0, "b")]),
+            (2, 0)
+        );
+        assert_eq!(
+            add(SetCondition::IfExists, None, &[(5.0, "a"), (1.0, "c")]),
+            (0, 1)
+        );
+        assert_eq!(
+            add(SetCondition::IfNotExists, None, &[(9.0, "a"), (3.0, "c")]),
+            (1, 0)
+        );
+        assert_eq!(
+            add(
+                SetCondition::Always,
+                Some(ScoreComparison::Greater),
+                &[(4.0, "a"), (6.0, "b")]
+            ),
+            (0, 1)
+        );
+        assert_eq!(backend.zscore(b"zset", b"a").unwrap(), Some(5.0));
+        assert_eq!(backend.zscore(b"zset", b"b").unwrap(), Some(6.0));
+
+        assert_eq!(
+            backend
+                .zincrby(
+                    b"zset",
+                    SetCondition::Always,
+                    Some(ScoreComparison::Less),
+                    1.0,
+                    "a".into()
+                )
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            backend
+                .zincrby(b"zset", SetCondition::Always, None, -1.5, "a".into())
+                .unwrap(),
+            Some(3.5)
+        );
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                members(&[(f64::INFINITY, "a")]),
+            )
+            .unwrap();
+        assert!(backend
+            .zincrby(
+                b"zset",
+                SetCondition::Always,
+                None,
+                f64::NEG_INFINITY,
+                "a".into()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_backend_zrange_zpop() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                members(&[(1.0, "a"), (2.0, "b"), (3.0, "c"), (4.0, "d")]),
+            )
+            .unwrap();
+
+        let range = |by, rev, offset, count| {
+            let range = ZRange {
+                by,
+                rev,
+                offset,
+                count,
+            };
+            backend
+                .zrange(b"zset", &range)
+                .unwrap()
+                .into_iter()
+                .map(|(member, _)| member)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(range(ZRangeBy::Rank(1, -2), false, 0, None), vec!["b", "c"]);
+        assert_eq!(range(ZRangeBy::Rank(0, 1), true, 0, None), vec!["d", "c"]);
+        assert_eq!(
+            range(ZRangeBy::Score(bound("(1"), bound("inf")), true, 1, Some(1)),
+            vec!["c"]
+        );
+        assert_eq!(
+            range(
+                ZRangeBy::Lex(
+                    LexBound::Exclusive("a".into()),
+                    LexBound::Inclusive("c".into())
+                ),
+                false,
+                0,
+                None
+            ),
+            vec!["b", "c"]
+        );
+        assert_eq!(backend.zrank(b"zset", b"c", true).unwrap(), Some((1, 3.0)));
+        assert_eq!(backend.zcount(b"zset", bound("2"), bound("(4")).unwrap(), 2);
+
+        assert_eq!(
+            backend.zpop(b"zset", true, 2).unwrap(),
+            vec![("d".into(), 4.0), ("c".into(), 3.0)]
+        );
+        assert_eq!(backend.zrem(b"zset", &["a".into(), "x".into()]).unwrap(), 1);
+        assert_eq!(backend.zpop(b"zset", false, 5).unwrap().len(), 1);
+        assert!(!backend.exists(b"zset"));
+    }
+}
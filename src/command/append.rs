@@ -0,0 +1,59 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Append {
+    key: Bytes,
+    value: Bytes,
+}
+
+impl CommandExecute for Append {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.append(&self.key, &self.value)?;
+        Ok((len as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Append {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "APPEND" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let value = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"append".into(), b"key".into(), b"abc".into()].into();
+        let cmd: Append = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 6.into());
+        assert_eq!(backend.get(b"key").unwrap(), Some("abcabc".into()));
+    }
+}
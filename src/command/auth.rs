@@ -0,0 +1,132 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// There are no ACLs, `requirepass` is the password of the only user.
+const DEFAULT_USER: &str = "default";
+
+#[derive(Debug)]
+pub struct Auth {
+    username: Option<String>,
+    password: String,
+}
+
+impl CommandExecute for Auth {
+    // The connection is marked as authenticated by the caller once this
+    // succeeds.
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let requirepass = backend.config().requirepass;
+
+        if self.username.is_none() && requirepass.is_none() {
+            return Err(CommandError::InvalidArgument(
+                "AUTH <password> called without any password configured for the default user. Are you sure your configuration is correct?".to_string(),
+            )
+            .into());
+        }
+
+        authenticate(&backend, self.username.as_deref(), &self.password)?;
+        Ok(OK.clone())
+    }
+}
+
+// Checks credentials against the default user, the only one there is. It has
+// no password until `requirepass` is set.
+pub(super) fn authenticate(
+    backend: &Backend,
+    username: Option<&str>,
+    password: &str,
+) -> Result<(), CommandError> {
+    let requirepass = backend.config().requirepass;
+    let user_matches = username.is_none_or(|u| u == DEFAULT_USER);
+    let password_matches = requirepass.is_none_or(|requirepass| requirepass == password);
+
+    match user_matches && password_matches {
+        true => Ok(()),
+        false => Err(CommandError::WrongPass),
+    }
+}
+
+impl TryFrom<Frame> for Auth {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "AUTH" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let first = parse.next_string()?;
+
+        let (username, password) = match parse.len() {
+            0 => (None, first),
+            _ => (Some(first), parse.next_string()?),
+        };
+        parse.finish()?;
+
+        Ok(Self { username, password })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+
+    fn auth(args: &[&str]) -> Result<Auth> {
+        let mut frame = vec![b"auth".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_auth_try_from_frame() {
+        let cmd = auth(&["secret"]).unwrap();
+        assert_eq!(cmd.username, None);
+        assert_eq!(cmd.password, "secret");
+
+        let cmd = auth(&["default", "secret"]).unwrap();
+        assert_eq!(cmd.username.as_deref(), Some("default"));
+
+        assert!(auth(&[]).is_err());
+        assert!(auth(&["a", "b", "c"]).is_err());
+    }
+
+    #[test]
+    fn test_auth_execute() {
+        let backend = Backend::new();
+        assert!(auth(&["secret"]).unwrap().execute(backend.clone()).is_err());
+        assert_eq!(
+            auth(&["default", "any"])
+                .unwrap()
+                .execute(backend.clone())
+                .unwrap(),
+            *OK
+        );
+
+        backend.set_config(ServerConfig {
+            requirepass: Some("secret".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            auth(&["secret"]).unwrap().execute(backend.clone()).unwrap(),
+            *OK
+        );
+        assert_eq!(
+            auth(&["wrong"])
+                .unwrap()
+                .execute(backend.clone())
+                .unwrap_err()
+                .to_string(),
+            "WRONGPASS invalid username-password pair or user is disabled."
+        );
+        assert!(auth(&["other", "secret"])
+            .unwrap()
+            .execute(backend)
+            .is_err());
+    }
+}
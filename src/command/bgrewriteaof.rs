@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::persistence::aof;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct BgRewriteAof;
+
+impl CommandExecute for BgRewriteAof {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match aof::bgrewrite(&backend) {
+            true => Ok("Background append only file rewriting started".into()),
+            false => Err(CommandError::InvalidArgument(
+                "Background append only file rewriting already in progress".to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for BgRewriteAof {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "BGREWRITEAOF" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_bgrewriteaof_try_from_frame() {
+        let frame: Frame = vec![b"bgrewriteaof".into()].into();
+        assert!(BgRewriteAof::try_from(frame).is_ok());
+
+        let frame: Frame = vec![b"bgrewriteaof".into(), b"now".into()].into();
+        assert!(BgRewriteAof::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_bgrewriteaof_already_in_progress() {
+        let backend = Backend::new();
+        backend.rewriting.store(true, Ordering::Release);
+
+        let frame: Frame = vec![b"bgrewriteaof".into()].into();
+        let cmd: BgRewriteAof = frame.try_into().unwrap();
+
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR Background append only file rewriting already in progress"
+        );
+    }
+}
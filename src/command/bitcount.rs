@@ -0,0 +1,97 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, BitUnit};
+use crate::resp::frame::Frame;
+
+pub(super) fn parse_bit_unit(s: &str) -> Result<BitUnit, CommandError> {
+    match s.to_uppercase().as_str() {
+        "BYTE" => Ok(BitUnit::Byte),
+        "BIT" => Ok(BitUnit::Bit),
+        _ => Err(CommandError::SyntaxError),
+    }
+}
+
+#[derive(Debug)]
+pub struct BitCount {
+    key: Bytes,
+    range: Option<(i64, i64, BitUnit)>,
+}
+
+impl CommandExecute for BitCount {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let count = backend.bitcount(&self.key, self.range)?;
+        Ok((count as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for BitCount {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "BITCOUNT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+
+        let range = match parse.len() {
+            0 => None,
+            1 => return Err(CommandError::SyntaxError.into()),
+            _ => {
+                let start = parse.next_int()?;
+                let end = parse.next_int()?;
+                let unit = match parse.len() {
+                    0 => BitUnit::Byte,
+                    _ => parse_bit_unit(&parse.next_string()?)?,
+                };
+                Some((start, end, unit))
+            }
+        };
+
+        if parse.len() > 0 {
+            return Err(ParseError::NotFinished.into());
+        }
+
+        Ok(Self { key, range })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitcount(args: &[&'static str]) -> Result<BitCount> {
+        let mut frames: Vec<Frame> = vec![b"bitcount".into(), b"key".into()];
+        frames.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frames).try_into()
+    }
+
+    #[test]
+    fn test_bitcount_try_from_frame() {
+        assert_eq!(bitcount(&[]).unwrap().range, None);
+        assert_eq!(
+            bitcount(&["1", "-1", "bit"]).unwrap().range,
+            Some((1, -1, BitUnit::Bit))
+        );
+        assert!(bitcount(&["1"]).is_err());
+        assert!(bitcount(&["1", "2", "word"]).is_err());
+    }
+
+    #[test]
+    fn test_bitcount_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "foobar".into());
+
+        let cmd = bitcount(&[]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 26.into());
+
+        let cmd = bitcount(&["1", "1"]).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 6.into());
+    }
+}
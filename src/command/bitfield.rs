@@ -0,0 +1,170 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::setbit::{parse_bit_offset, MAX_BIT_OFFSET};
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::{Backend, BitfieldOp, BitfieldOverflow, BitfieldType};
+use crate::resp::frame::Frame;
+
+// Offsets prefixed with '#' are multiplied by the width of the type. The
+// whole field has to fit in a bitmap.
+fn parse_offset(s: &str, ty: BitfieldType) -> Result<u64, CommandError> {
+    let offset = match s.strip_prefix('#') {
+        Some(index) => parse_bit_offset(index)?.saturating_mul(ty.bits as u64),
+        None => parse_bit_offset(s)?,
+    };
+
+    match offset + ty.bits as u64 - 1 <= MAX_BIT_OFFSET {
+        true => Ok(offset),
+        false => Err(CommandError::InvalidArgument(
+            "bit offset is not an integer or out of range".to_string(),
+        )),
+    }
+}
+
+#[derive(Debug)]
+pub struct BitField {
+    key: Bytes,
+    ops: Vec<BitfieldOp>,
+}
+
+impl CommandExecute for BitField {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let results = backend
+            .bitfield(&self.key, &self.ops)?
+            .into_iter()
+            .map(|result| result.map_or_else(|| NULL.clone(), Frame::from))
+            .collect::<Vec<_>>();
+
+        Ok(results.into())
+    }
+
+    // Only BITFIELD with SET or INCRBY operations is a write.
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        self.ops
+            .iter()
+            .any(|op| !matches!(op, BitfieldOp::Get { .. }))
+            .then(|| frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for BitField {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "BITFIELD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+
+        let mut ops = vec![];
+        let mut overflow = BitfieldOverflow::Wrap;
+
+        while parse.len() > 0 {
+            let subcommand = parse.next_string()?.to_uppercase();
+
+            if subcommand == "OVERFLOW" {
+                overflow = match parse.next_string()?.to_uppercase().as_str() {
+                    "WRAP" => BitfieldOverflow::Wrap,
+                    "SAT" => BitfieldOverflow::Sat,
+                    "FAIL" => BitfieldOverflow::Fail,
+                    _ => {
+                        return Err(CommandError::InvalidArgument(
+                            "Invalid OVERFLOW type specified".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                continue;
+            }
+
+            if !matches!(subcommand.as_str(), "GET" | "SET" | "INCRBY") {
+                return Err(CommandError::SyntaxError.into());
+            }
+
+            let ty = BitfieldType::parse(&parse.next_string()?)?;
+            let offset = parse_offset(&parse.next_string()?, ty)?;
+
+            ops.push(match subcommand.as_str() {
+                "GET" => BitfieldOp::Get { ty, offset },
+                "SET" => BitfieldOp::Set {
+                    ty,
+                    offset,
+                    value: parse.next_int()?,
+                    overflow,
+                },
+                _ => BitfieldOp::IncrBy {
+                    ty,
+                    offset,
+                    increment: parse.next_int()?,
+                    overflow,
+                },
+            });
+        }
+
+        Ok(Self { key, ops })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitfield(args: &[&'static str]) -> Result<BitField> {
+        let mut frames: Vec<Frame> = vec![b"bitfield".into(), b"key".into()];
+        frames.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frames).try_into()
+    }
+
+    #[test]
+    fn test_bitfield_try_from_frame() {
+        let cmd = bitfield(&[
+            "overflow", "fail", "incrby", "u8", "#2", "1", "get", "i4", "0",
+        ])
+        .unwrap();
+        let u8 = BitfieldType::parse("u8").unwrap();
+
+        assert_eq!(
+            cmd.ops[0],
+            BitfieldOp::IncrBy {
+                ty: u8,
+                offset: 16,
+                increment: 1,
+                overflow: BitfieldOverflow::Fail
+            }
+        );
+        assert!(cmd.propagate(&Frame::from(vec![])).is_some());
+
+        assert!(bitfield(&["get", "u64", "0"]).is_err());
+        assert!(bitfield(&["get", "i8", "-1"]).is_err());
+        assert!(bitfield(&["overflow", "clamp"]).is_err());
+        assert!(bitfield(&["set", "i8", "0"]).is_err());
+        assert!(bitfield(&["get", "i8", "0"])
+            .unwrap()
+            .propagate(&Frame::from(vec![]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_bitfield_execute() {
+        let backend = Backend::new();
+
+        let cmd = bitfield(&[
+            "overflow", "fail", "set", "u8", "0", "255", "incrby", "u8", "0", "1", "get", "u8", "0",
+        ])
+        .unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![0.into(), NULL.clone(), 255.into()].into()
+        );
+
+        let cmd = bitfield(&["get", "i8", "100"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), vec![0.into()].into());
+        assert_eq!(backend.strlen(b"key").unwrap(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, BitOp as Op};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct BitOp {
+    op: Op,
+    destination: Bytes,
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for BitOp {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.bitop(self.op, &self.destination, &self.keys)?;
+        Ok((len as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for BitOp {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "BITOP" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let op = match parse.next_string()?.to_uppercase().as_str() {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "XOR" => Op::Xor,
+            "NOT" => Op::Not,
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+
+        let destination = parse.next_bytes()?;
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        if op == Op::Not && keys.len() != 1 {
+            return Err(CommandError::InvalidArgument(
+                "BITOP NOT must be called with a single source key.".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            op,
+            destination,
+            keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitop(args: &[&'static str]) -> Result<BitOp> {
+        let mut frames: Vec<Frame> = vec![b"bitop".into()];
+        frames.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frames).try_into()
+    }
+
+    #[test]
+    fn test_bitop_try_from_frame() {
+        let cmd = bitop(&["xor", "dest", "a", "b"]).unwrap();
+        assert_eq!(cmd.op, Op::Xor);
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+
+        assert!(bitop(&["nand", "dest", "a"]).is_err());
+        assert_eq!(
+            bitop(&["not", "dest", "a", "b"]).unwrap_err().to_string(),
+            "ERR BITOP NOT must be called with a single source key."
+        );
+    }
+
+    #[test]
+    fn test_bitop_execute() {
+        let backend = Backend::new();
+        backend.set(b"a", "foobar".into());
+        backend.set(b"b", "abcdef".into());
+
+        let cmd = bitop(&["and", "dest", "a", "b"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 6.into());
+        assert_eq!(backend.get(b"dest").unwrap(), Some("`bc`ab".into()));
+    }
+}
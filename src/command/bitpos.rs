@@ -0,0 +1,105 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::bitcount::parse_bit_unit;
+use super::parse::{Parse, ParseError};
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, BitUnit};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct BitPos {
+    key: Bytes,
+    bit: bool,
+    start: Option<i64>,
+    end: Option<i64>,
+    unit: BitUnit,
+}
+
+impl CommandExecute for BitPos {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let position = backend.bitpos(&self.key, self.bit, self.start, self.end, self.unit)?;
+        Ok(position.into())
+    }
+}
+
+impl TryFrom<Frame> for BitPos {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "BITPOS" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let bit = match parse.next_string()?.as_str() {
+            "0" => false,
+            "1" => true,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "The bit argument must be 1 or 0.".to_string(),
+                )
+                .into())
+            }
+        };
+
+        let start = match parse.len() {
+            0 => None,
+            _ => Some(parse.next_int()?),
+        };
+        let end = match parse.len() {
+            0 => None,
+            _ => Some(parse.next_int()?),
+        };
+        let unit = match parse.len() {
+            0 => BitUnit::Byte,
+            _ => parse_bit_unit(&parse.next_string()?)?,
+        };
+
+        if parse.len() > 0 {
+            return Err(ParseError::NotFinished.into());
+        }
+
+        Ok(Self {
+            key,
+            bit,
+            start,
+            end,
+            unit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitpos(args: &[&'static str]) -> Result<BitPos> {
+        let mut frames: Vec<Frame> = vec![b"bitpos".into(), b"key".into()];
+        frames.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frames).try_into()
+    }
+
+    #[test]
+    fn test_bitpos_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", Bytes::from_static(&[0xff, 0xf0, 0x00]));
+
+        let cmd = bitpos(&["0"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 12.into());
+
+        let cmd = bitpos(&["1", "2", "-1", "byte"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), (-1).into());
+
+        let cmd = bitpos(&["0", "0", "15", "bit"]).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 12.into());
+
+        assert_eq!(
+            bitpos(&["2"]).unwrap_err().to_string(),
+            "ERR The bit argument must be 1 or 0."
+        );
+    }
+}
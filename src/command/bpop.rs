@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL_ARRAY};
+use crate::backend::{Backend, Block, BlockOp, ListEnd, Served};
+use crate::resp::frame::Frame;
+
+// Handles BLPOP and BRPOP, replying with the key an element was popped from
+// and the element.
+#[derive(Debug)]
+pub struct BPop {
+    keys: Vec<Bytes>,
+    end: ListEnd,
+    timeout: Option<Duration>,
+}
+
+// Timeouts are in seconds and may have a fractional part, zero blocks forever.
+pub(super) fn parse_timeout(timeout: &str) -> Result<Option<Duration>> {
+    let seconds = timeout
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite())
+        .ok_or_else(|| {
+            CommandError::InvalidArgument("timeout is not a float or out of range".to_string())
+        })?;
+
+    if seconds < 0.0 {
+        return Err(CommandError::InvalidArgument("timeout is negative".to_string()).into());
+    }
+
+    if seconds == 0.0 {
+        return Ok(None);
+    }
+
+    // finite timeouts can still be too long for a Duration
+    Duration::try_from_secs_f64(seconds)
+        .map(Some)
+        .map_err(|_| CommandError::InvalidArgument("timeout is out of range".to_string()).into())
+}
+
+fn pop_op(end: ListEnd) -> BlockOp {
+    Arc::new(move |backend: &Backend, key: &[u8]| {
+        let element = backend.pop(key, end, 1).ok()??.pop()?;
+        let command = match end {
+            ListEnd::Left => "LPOP",
+            ListEnd::Right => "RPOP",
+        };
+
+        Some(Served {
+            reply: vec![key.into(), element.into()].into(),
+            propagate: Some(vec![command.as_bytes().into(), key.into()].into()),
+        })
+    })
+}
+
+impl CommandExecute for BPop {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let op = pop_op(self.end);
+
+        match self.keys.iter().find_map(|key| op(&backend, key)) {
+            Some(served) => Ok(served.reply),
+            None => Ok(NULL_ARRAY.clone()),
+        }
+    }
+
+    fn block(&self, _backend: &Backend) -> Option<Block> {
+        Some(Block {
+            keys: self.keys.clone(),
+            op: pop_op(self.end),
+            timeout: self.timeout,
+            timeout_reply: NULL_ARRAY.clone(),
+        })
+    }
+}
+
+impl TryFrom<Frame> for BPop {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let end = match command.as_str() {
+            "BLPOP" => ListEnd::Left,
+            "BRPOP" => ListEnd::Right,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 1 {
+            keys.push(parse.next_bytes()?);
+        }
+        let timeout = parse_timeout(&parse.next_string()?)?;
+
+        Ok(Self { keys, end, timeout })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bpop_try_from_frame() {
+        let frame: Frame = vec![b"brpop".into(), b"a".into(), b"b".into(), b"0.5".into()].into();
+        let cmd: BPop = frame.try_into().unwrap();
+
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert_eq!(cmd.end, ListEnd::Right);
+        assert_eq!(cmd.timeout, Some(Duration::from_millis(500)));
+
+        let frame: Frame = vec![b"blpop".into(), b"a".into(), b"0".into()].into();
+        let cmd: BPop = frame.try_into().unwrap();
+        assert_eq!(cmd.timeout, None);
+
+        let frame: Frame = vec![b"blpop".into(), b"a".into(), b"-1".into()].into();
+        assert_eq!(
+            BPop::try_from(frame).unwrap_err().to_string(),
+            "ERR timeout is negative"
+        );
+
+        let frame: Frame = vec![b"blpop".into(), b"a".into(), b"soon".into()].into();
+        assert!(BPop::try_from(frame).is_err());
+
+        let frame: Frame = vec![b"blpop".into(), b"a".into(), b"1e300".into()].into();
+        assert_eq!(
+            BPop::try_from(frame).unwrap_err().to_string(),
+            "ERR timeout is out of range"
+        );
+    }
+
+    #[test]
+    fn test_bpop_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"blpop".into(), b"a".into(), b"b".into(), b"1".into()].into();
+        let cmd: BPop = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL_ARRAY);
+
+        backend
+            .push(b"b", ListEnd::Right, vec!["x".into(), "y".into()])
+            .unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap(),
+            vec![b"b".into(), b"x".into()].into()
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::config::{self, ServerConfig, PARAMETERS};
+use crate::glob;
+use crate::persistence::aof;
+use crate::resp::frame::Frame;
+
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Get(Vec<String>),
+    Set(Vec<(String, String)>),
+    ResetStat,
+    Rewrite,
+}
+
+#[derive(Debug)]
+pub struct Config {
+    subcommand: Subcommand,
+}
+
+fn set_failed(name: &str, reason: impl std::fmt::Display) -> anyhow::Error {
+    CommandError::InvalidArgument(format!(
+        "CONFIG SET failed (possibly related to argument '{}') - {}",
+        name, reason
+    ))
+    .into()
+}
+
+impl Config {
+    fn get(backend: &Backend, patterns: &[String]) -> Frame {
+        let config = backend.config();
+
+        let mut reply = Vec::new();
+        for name in PARAMETERS {
+            let matched = patterns
+                .iter()
+                .any(|pattern| glob::matches(pattern.as_bytes(), name.as_bytes(), true));
+
+            if let (true, Some(value)) = (matched, config.get(name)) {
+                reply.push(name.as_bytes().into());
+                reply.push(value.as_bytes().into());
+            }
+        }
+
+        reply.into()
+    }
+
+    // Parameters are all validated before any of them is applied, so a failed
+    // CONFIG SET leaves the config untouched.
+    fn set(backend: &Backend, pairs: &[(String, String)]) -> Result<Frame> {
+        let current = backend.config();
+        let mut config = current.clone();
+
+        for (i, (name, value)) in pairs.iter().enumerate() {
+            if !PARAMETERS.contains(&name.as_str()) {
+                return Err(CommandError::InvalidArgument(format!(
+                    "Unknown option or number of arguments for CONFIG SET - '{}'",
+                    name
+                ))
+                .into());
+            }
+
+            if pairs[..i].iter().any(|(other, _)| other == name) {
+                return Err(set_failed(name, "duplicate parameter"));
+            }
+
+            if !ServerConfig::is_mutable(name) {
+                return Err(set_failed(name, "can't set immutable config"));
+            }
+
+            config.set(name, value).map_err(|e| set_failed(name, e))?;
+        }
+
+        backend.set_config(config.clone());
+
+        if let Err(e) = apply(backend, &current, &config) {
+            backend.set_config(current);
+            return Err(set_failed("appendonly", e));
+        }
+
+        Ok(OK.clone())
+    }
+}
+
+// Makes the changed parameters take effect on the running server.
+fn apply(backend: &Backend, old: &ServerConfig, new: &ServerConfig) -> Result<()> {
+    if new.loglevel != old.loglevel {
+        config::set_log_level(new.loglevel);
+    }
+
+    if new.appendfsync != old.appendfsync {
+        if let Some(aof) = aof::lock(backend).as_mut() {
+            aof.set_fsync(new.appendfsync);
+        }
+    }
+
+    match (old.appendonly, new.appendonly) {
+        (false, true) => {
+            // the new AOF starts from a rewrite of the current dataset
+            aof::enable(backend)?;
+            aof::bgrewrite(backend);
+        }
+        (true, false) => aof::disable(backend)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+impl CommandExecute for Config {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match &self.subcommand {
+            Subcommand::Get(patterns) => Ok(Config::get(&backend, patterns)),
+            Subcommand::Set(pairs) => Config::set(&backend, pairs),
+            Subcommand::ResetStat => {
+                backend.stats.reset();
+                Ok(OK.clone())
+            }
+            Subcommand::Rewrite => match backend.config().rewrite() {
+                Ok(()) => Ok(OK.clone()),
+                Err(e) => Err(CommandError::InvalidArgument(e.to_string()).into()),
+            },
+        }
+    }
+}
+
+impl TryFrom<Frame> for Config {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "CONFIG" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let name = parse.next_string()?;
+        let mut args = Vec::new();
+        while parse.len() > 0 {
+            args.push(parse.next_string()?);
+        }
+
+        let subcommand = match name.to_uppercase().as_str() {
+            "GET" if !args.is_empty() => Subcommand::Get(args),
+            "SET" if !args.is_empty() && args.len() % 2 == 0 => Subcommand::Set(
+                args.chunks(2)
+                    .map(|pair| (pair[0].to_lowercase(), pair[1].clone()))
+                    .collect(),
+            ),
+            "RESETSTAT" if args.is_empty() => Subcommand::ResetStat,
+            "REWRITE" if args.is_empty() => Subcommand::Rewrite,
+            "GET" | "SET" | "RESETSTAT" | "REWRITE" => {
+                return Err(CommandError::WrongNumberOfArguments(format!(
+                    "config|{}",
+                    name.to_lowercase()
+                ))
+                .into())
+            }
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try CONFIG HELP.",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        Ok(Self { subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(args: &[&str]) -> Result<Config> {
+        let mut frame = vec![b"config".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_config_try_from_frame() {
+        let cmd = config(&["get", "port", "max*"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::Get(vec!["port".to_string(), "max*".to_string()])
+        );
+
+        let cmd = config(&["SET", "Timeout", "10"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::Set(vec![("timeout".to_string(), "10".to_string())])
+        );
+
+        assert_eq!(
+            config(&["set", "timeout"]).unwrap_err().to_string(),
+            "ERR wrong number of arguments for 'config|set' command"
+        );
+        assert!(config(&["get"]).is_err());
+        assert!(config(&["resetstat", "now"]).is_err());
+        assert!(config(&["nope"]).is_err());
+    }
+
+    #[test]
+    fn test_config_get() {
+        let backend = Backend::new();
+
+        let cmd = config(&["get", "maxm*", "PORT"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap(),
+            vec![
+                b"port".into(),
+                b"6379".into(),
+                b"maxmemory".into(),
+                b"0".into()
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_config_set() {
+        let backend = Backend::new();
+
+        let cmd = config(&["set", "timeout", "30", "maxmemory", "1mb"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.config().timeout, 30);
+        assert_eq!(backend.config().maxmemory, 1024 * 1024);
+
+        // nothing is applied when one of the parameters is invalid
+        let cmd = config(&["set", "timeout", "60", "maxmemory", "lots"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR CONFIG SET failed (possibly related to argument 'maxmemory') - argument must be a memory value"
+        );
+        assert_eq!(backend.config().timeout, 30);
+
+        let cmd = config(&["set", "port", "6380"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR CONFIG SET failed (possibly related to argument 'port') - can't set immutable config"
+        );
+
+        let cmd = config(&["set", "daemonize", "yes"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR Unknown option or number of arguments for CONFIG SET - 'daemonize'"
+        );
+    }
+
+    #[test]
+    fn test_config_rewrite_without_file() {
+        let cmd = config(&["rewrite"]).unwrap();
+        assert_eq!(
+            cmd.execute(Backend::new()).unwrap_err().to_string(),
+            "ERR The server is running without a config file"
+        );
+    }
+}
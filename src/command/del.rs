@@ -0,0 +1,91 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles DEL and UNLINK. UNLINK removes the keys right away but frees the
+// values on a blocking thread, so large collections don't stall the caller.
+#[derive(Debug)]
+pub struct Del {
+    keys: Vec<Bytes>,
+    lazy: bool,
+}
+
+impl CommandExecute for Del {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = self
+            .keys
+            .iter()
+            .filter_map(|key| backend.remove(key))
+            .collect::<Vec<_>>();
+
+        let count = removed.len() as i64;
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if self.lazy => {
+                handle.spawn_blocking(move || drop(removed));
+            }
+            _ => drop(removed),
+        }
+
+        Ok(count.into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Del {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let lazy = match command.as_str() {
+            "DEL" => false,
+            "UNLINK" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let mut keys = vec![parse.next_bytes()?];
+
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { keys, lazy })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_del_try_from_frame() {
+        let frame: Frame = vec![b"unlink".into(), b"a".into(), b"b".into()].into();
+        let cmd: Del = frame.try_into().unwrap();
+
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert!(cmd.lazy);
+    }
+
+    #[test]
+    fn test_del_execute() {
+        let backend = Backend::new();
+        backend.set(b"a", "value".into());
+        backend.sadd(b"b", vec!["member".into()]).unwrap();
+
+        let frame: Frame = vec![b"del".into(), b"a".into(), b"b".into(), b"c".into()].into();
+        let cmd: Del = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+        assert!(!backend.exists(b"a"));
+        assert!(!backend.exists(b"b"));
+    }
+}
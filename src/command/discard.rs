@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::network::Session;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Discard;
+
+impl Discard {
+    // Drops the queued commands and unwatches the watched keys.
+    pub fn discard(&self, session: &mut Session) -> Result<Frame> {
+        if session.transaction.take().is_none() {
+            return Err(CommandError::InvalidArgument("DISCARD without MULTI".to_string()).into());
+        }
+
+        session.watched.clear();
+        Ok(OK.clone())
+    }
+}
+
+impl CommandExecute for Discard {
+    // Transactions belong to the connection, which runs `discard` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("DISCARD is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Discard {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "DISCARD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Transaction;
+
+    #[test]
+    fn test_discard() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let frame: Frame = vec![b"discard".into()].into();
+        let cmd: Discard = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.discard(&mut session).unwrap_err().to_string(),
+            "ERR DISCARD without MULTI"
+        );
+
+        session.transaction = Some(Transaction::default());
+        assert_eq!(cmd.discard(&mut session).unwrap(), *OK);
+        assert!(session.transaction.is_none());
+    }
+}
@@ -38,12 +38,13 @@ impl TryFrom<Frame> for Echo {
 mod tests {
     use super::*;
     use crate::resp::RespDecode;
+    use bytes::Bytes;
     use std::io::Cursor;
 
     #[test]
     fn test_echo_try_from_frame() {
         let input = b"*2\r\n$4\r\necho\r\n$7\r\nmessage\r\n";
-        let mut buf = Cursor::new(&input[..]);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         let cmd = Echo::try_from(frame).unwrap();
 
@@ -53,7 +54,7 @@ mod tests {
     #[test]
     fn test_echo_try_from_frame_invalid_command() {
         let input = b"*2\r\n$3\r\nset\r\n$7\r\nmessage\r\n";
-        let mut buf = Cursor::new(&input[..]);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         let result = Echo::try_from(frame);
 
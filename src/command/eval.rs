@@ -0,0 +1,142 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug, PartialEq)]
+enum Source {
+    Body(Bytes),
+    // EVALSHA, of a cached script
+    Sha(String),
+}
+
+#[derive(Debug)]
+pub struct Eval {
+    source: Source,
+    keys: Vec<Bytes>,
+    args: Vec<Bytes>,
+}
+
+impl Eval {
+    // Runs the script, `call` running the commands it calls. Scripts run
+    // with EVAL are cached, EVALSHA can run them next.
+    pub fn eval(&self, backend: &Backend, call: impl FnMut(Frame) -> Frame) -> Result<Frame> {
+        let mut scripting = backend.scripting();
+        let sha = match &self.source {
+            Source::Body(body) => scripting.load(body)?,
+            Source::Sha(sha) => sha.clone(),
+        };
+
+        Ok(scripting.run(&sha, &self.keys, &self.args, call)?)
+    }
+}
+
+impl CommandExecute for Eval {
+    // The commands a script calls run as requests of their own, the request
+    // runs `eval` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(CommandError::InvalidArgument("EVAL is not allowed in this context".to_string()).into())
+    }
+
+    // no other command runs while a script does
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for Eval {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let source = match command.as_str() {
+            "EVAL" => Source::Body(parse.next_bytes()?),
+            "EVALSHA" => Source::Sha(parse.next_string()?),
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let numkeys = parse.next_int()?;
+        if numkeys < 0 {
+            return Err(CommandError::InvalidArgument(
+                "Number of keys can't be negative".to_string(),
+            )
+            .into());
+        }
+        if numkeys as usize > parse.len() {
+            return Err(CommandError::InvalidArgument(
+                "Number of keys can't be greater than number of args".to_string(),
+            )
+            .into());
+        }
+
+        let keys = (0..numkeys)
+            .map(|_| parse.next_bytes())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut args = Vec::new();
+        while parse.len() > 0 {
+            args.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { source, keys, args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripting;
+
+    fn eval(args: &[&str]) -> Result<Eval> {
+        Frame::from(
+            args.iter()
+                .map(|arg| Frame::from(arg.as_bytes()))
+                .collect::<Vec<_>>(),
+        )
+        .try_into()
+    }
+
+    #[test]
+    fn test_eval_try_from_frame() {
+        let cmd = eval(&["eval", "return 1", "1", "key", "arg"]).unwrap();
+        assert_eq!(cmd.source, Source::Body("return 1".into()));
+        assert_eq!(cmd.keys, vec![Bytes::from("key")]);
+        assert_eq!(cmd.args, vec![Bytes::from("arg")]);
+
+        assert_eq!(
+            eval(&["eval", "return 1", "2", "key"])
+                .unwrap_err()
+                .to_string(),
+            "ERR Number of keys can't be greater than number of args"
+        );
+        assert_eq!(
+            eval(&["evalsha", "abc", "-1"]).unwrap_err().to_string(),
+            "ERR Number of keys can't be negative"
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        let backend = Backend::new();
+        let sha = scripting::sha1_hex(b"return ARGV[1]");
+
+        let cmd = eval(&["evalsha", &sha, "0", "a"]).unwrap();
+        assert_eq!(
+            cmd.eval(&backend, |frame| frame).unwrap_err().to_string(),
+            "NOSCRIPT No matching script. Please use EVAL."
+        );
+
+        let reply = eval(&["eval", "return ARGV[1]", "0", "a"])
+            .unwrap()
+            .eval(&backend, |frame| frame);
+        assert_eq!(reply.unwrap(), b"a".into());
+
+        let cmd = eval(&["evalsha", &sha.to_uppercase(), "0", "b"]).unwrap();
+        assert_eq!(cmd.eval(&backend, |frame| frame).unwrap(), b"b".into());
+    }
+}
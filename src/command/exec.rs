@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::network::Session;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Exec;
+
+impl Exec {
+    // Runs the transaction, the watched keys are unwatched whatever the
+    // outcome.
+    pub fn exec(&self, backend: &Backend, session: &mut Session) -> Result<Frame> {
+        let Some(transaction) = session.transaction.take() else {
+            return Err(CommandError::InvalidArgument("EXEC without MULTI".to_string()).into());
+        };
+
+        let reply = transaction.exec(backend, &session.watched, session.protocol);
+        session.watched.clear();
+
+        reply
+    }
+}
+
+impl CommandExecute for Exec {
+    // Transactions belong to the connection, which runs `exec` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(CommandError::InvalidArgument("EXEC is not allowed in this context".to_string()).into())
+    }
+}
+
+impl TryFrom<Frame> for Exec {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "EXEC" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Transaction;
+
+    #[test]
+    fn test_exec() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let frame: Frame = vec![b"exec".into()].into();
+        let cmd: Exec = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.exec(&backend, &mut session).unwrap_err().to_string(),
+            "ERR EXEC without MULTI"
+        );
+
+        session.watched.watch("key".into());
+        session.transaction = Some(Transaction::default());
+        assert_eq!(
+            cmd.exec(&backend, &mut session).unwrap(),
+            Vec::<Frame>::new().into()
+        );
+        assert!(session.transaction.is_none());
+        assert!(!session.watched.modified());
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Exists {
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for Exists {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        // a key given several times is counted several times
+        let count = self.keys.iter().filter(|key| backend.exists(key)).count();
+        Ok((count as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for Exists {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "EXISTS" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let mut keys = vec![parse.next_bytes()?];
+
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { keys })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exists_try_from_frame() {
+        let frame: Frame = vec![b"exists".into(), b"a".into(), b"b".into()].into();
+        let cmd: Exists = frame.try_into().unwrap();
+
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_exists_try_from_frame_invalid_parts() {
+        let frame: Frame = vec![b"exists".into()].into();
+        let result: Result<Exists> = frame.try_into();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_exists_execute() {
+        let backend = Backend::new();
+        backend.set(b"a", "value".into());
+        backend
+            .hset(b"b", vec![("field".into(), "value".into())])
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"exists".into(),
+            b"a".into(),
+            b"b".into(),
+            b"c".into(),
+            b"a".into(),
+        ]
+        .into();
+        let cmd: Exists = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend).unwrap(), 3.into());
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{now_millis, Backend, ExpireCondition};
+use crate::resp::frame::Frame;
+
+// Handles EXPIRE, PEXPIRE, EXPIREAT and PEXPIREAT. Relative times are made
+// a unix timestamp in milliseconds once, when the command is parsed, so the
+// key and the AOF get the same deadline.
+#[derive(Debug)]
+pub struct Expire {
+    key: Bytes,
+    at: u64,
+    conditions: Vec<ExpireCondition>,
+}
+
+impl CommandExecute for Expire {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.expire_at(&self.key, self.at, &self.conditions) {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    // Always logged as PEXPIREAT, so replaying the AOF later does not extend
+    // the time to live.
+    fn propagate(&self, _frame: &Frame) -> Option<Frame> {
+        let mut command = vec![
+            b"PEXPIREAT".into(),
+            self.key.clone().into(),
+            self.at.to_string().as_bytes().into(),
+        ];
+
+        command.extend(self.conditions.iter().map(|condition| match condition {
+            ExpireCondition::IfNoExpiry => b"NX".into(),
+            ExpireCondition::IfHasExpiry => b"XX".into(),
+            ExpireCondition::IfGreater => b"GT".into(),
+            ExpireCondition::IfLess => b"LT".into(),
+        }));
+
+        Some(command.into())
+    }
+}
+
+impl TryFrom<Frame> for Expire {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_lowercase();
+
+        let (unit, absolute) = match command.as_str() {
+            "expire" => (1000, false),
+            "pexpire" => (1, false),
+            "expireat" => (1000, true),
+            "pexpireat" => (1, true),
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+        let millis = parse
+            .next_int()?
+            .checked_mul(unit)
+            .ok_or_else(|| CommandError::InvalidExpireTime(command.clone()))?;
+        let at = match absolute {
+            true => millis,
+            false => millis.saturating_add(now_millis() as i64),
+        };
+
+        let mut conditions = Vec::new();
+
+        while parse.len() > 0 {
+            let condition = match parse.next_string()?.to_uppercase().as_str() {
+                "NX" => ExpireCondition::IfNoExpiry,
+                "XX" => ExpireCondition::IfHasExpiry,
+                "GT" => ExpireCondition::IfGreater,
+                "LT" => ExpireCondition::IfLess,
+                option => {
+                    return Err(CommandError::InvalidArgument(format!(
+                        "Unsupported option {}",
+                        option
+                    ))
+                    .into())
+                }
+            };
+
+            if !conditions.contains(&condition) {
+                conditions.push(condition);
+            }
+        }
+
+        if conditions.contains(&ExpireCondition::IfNoExpiry) && conditions.len() > 1 {
+            return Err(CommandError::InvalidArgument(
+                "NX and XX, GT or LT options at the same time are not compatible".to_string(),
+            )
+            .into());
+        }
+
+        if conditions.contains(&ExpireCondition::IfGreater)
+            && conditions.contains(&ExpireCondition::IfLess)
+        {
+            return Err(CommandError::InvalidArgument(
+                "GT and LT options at the same time are not compatible".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            key,
+            at: at.max(0) as u64,
+            conditions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire_try_from_frame() {
+        let before = now_millis();
+        let frame: Frame = vec![b"pexpire".into(), b"key".into(), b"1500".into()].into();
+        let cmd: Expire = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "key");
+        assert!((before + 1500..=now_millis() + 1500).contains(&cmd.at));
+        assert!(cmd.conditions.is_empty());
+
+        let frame: Frame = vec![
+            b"expireat".into(),
+            b"key".into(),
+            b"100".into(),
+            b"gt".into(),
+        ]
+        .into();
+        let cmd: Expire = frame.try_into().unwrap();
+
+        assert_eq!(cmd.at, 100_000);
+        assert_eq!(cmd.conditions, vec![ExpireCondition::IfGreater]);
+    }
+
+    #[test]
+    fn test_expire_try_from_frame_invalid_options() {
+        let frame: Frame = vec![
+            b"expire".into(),
+            b"key".into(),
+            b"10".into(),
+            b"nx".into(),
+            b"xx".into(),
+        ]
+        .into();
+        let result: Result<Expire> = frame.try_into();
+        assert!(result.is_err());
+
+        let frame: Frame = vec![b"expire".into(), b"key".into(), b"ten".into()].into();
+        let result: Result<Expire> = frame.try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expire_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"expire".into(), b"key".into(), b"10".into()].into();
+        let cmd: Expire = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        backend.set(b"key", "value".into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert!(backend.pttl(b"key").unwrap().unwrap() > 9_000);
+
+        let frame: Frame = vec![b"expire".into(), b"key".into(), b"-1".into()].into();
+        let cmd: Expire = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.get(b"key").unwrap(), None);
+    }
+
+    #[test]
+    fn test_expire_propagate_absolute() {
+        let frame: Frame = vec![
+            b"expireat".into(),
+            b"key".into(),
+            b"100".into(),
+            b"gt".into(),
+        ]
+        .into();
+        let cmd: Expire = frame.clone().try_into().unwrap();
+
+        assert_eq!(
+            cmd.propagate(&frame),
+            Some(
+                vec![
+                    b"PEXPIREAT".into(),
+                    b"key".into(),
+                    b"100000".into(),
+                    b"GT".into()
+                ]
+                .into()
+            )
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::parse::Parse;
 use super::{CommandExecute, NULL};
@@ -7,13 +8,13 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct Get {
-    pub(crate) key: String,
+    pub(crate) key: Bytes,
 }
 
 impl CommandExecute for Get {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        match backend.get(&self.key) {
-            Some(value) => Ok(value),
+        match backend.get(&self.key)? {
+            Some(value) => Ok(value.into()),
             None => Ok(NULL.clone()),
         }
     }
@@ -30,7 +31,7 @@ impl TryFrom<Frame> for Get {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
+        let key = parse.next_bytes()?;
         parse.finish()?;
 
         Ok(Self { key })
@@ -48,9 +49,7 @@ mod tests {
         let frame: Frame = vec!["get".into(), "key".into()].into();
 
         let actual: Get = frame.try_into().unwrap();
-        let expected = Get {
-            key: "key".to_string(),
-        };
+        let expected = Get { key: "key".into() };
 
         assert_eq!(actual.key, expected.key);
     }
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::setbit::parse_bit_offset;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct GetBit {
+    key: Bytes,
+    offset: u64,
+}
+
+impl CommandExecute for GetBit {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.getbit(&self.key, self.offset)? {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for GetBit {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "GETBIT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let offset = parse_bit_offset(&parse.next_string()?)?;
+        parse.finish()?;
+
+        Ok(Self { key, offset })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_getbit_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", Bytes::from_static(&[0x40]));
+
+        let frame: Frame = vec![b"getbit".into(), b"key".into(), b"1".into()].into();
+        let cmd: GetBit = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+
+        let frame: Frame = vec![b"getbit".into(), b"key".into(), b"100".into()].into();
+        let cmd: GetBit = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct GetDel {
+    key: Bytes,
+}
+
+impl CommandExecute for GetDel {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.getdel(&self.key)? {
+            Some(value) => Ok(value.into()),
+            None => Ok(NULL.clone()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn propagate_reply(&self, entry: Frame, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::BulkString(_) => Some(entry),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Frame> for GetDel {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "GETDEL" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_getdel_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+
+        let frame: Frame = vec![b"getdel".into(), b"key".into()].into();
+        let cmd: GetDel = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"value".into());
+        assert!(!backend.exists(b"key"));
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::{now_millis, Backend};
+use crate::resp::frame::Frame;
+
+#[derive(Debug, PartialEq)]
+enum Expiration {
+    Ex(i64),
+    Px(i64),
+    ExAt(i64),
+    PxAt(i64),
+    Persist,
+}
+
+// GET that also sets or removes the time to live of the key.
+#[derive(Debug)]
+pub struct GetEx {
+    key: Bytes,
+    expiration: Option<Expiration>,
+}
+
+impl GetEx {
+    // Absolute expiration time in unix milliseconds, None for PERSIST.
+    fn expire_at(&self) -> Result<Option<u64>> {
+        let at = match self.expiration {
+            None | Some(Expiration::Persist) => return Ok(None),
+            Some(Expiration::Ex(seconds)) => seconds
+                .checked_mul(1000)
+                .and_then(|ms| ms.checked_add(now_millis() as i64)),
+            Some(Expiration::Px(ms)) => ms.checked_add(now_millis() as i64),
+            Some(Expiration::ExAt(seconds)) => seconds.checked_mul(1000),
+            Some(Expiration::PxAt(ms)) => Some(ms),
+        };
+
+        match at {
+            Some(at) => Ok(Some(at as u64)),
+            None => Err(CommandError::InvalidExpireTime("getex".to_string()).into()),
+        }
+    }
+}
+
+impl CommandExecute for GetEx {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let expire_at = self.expire_at()?;
+
+        let Some(value) = backend.get(&self.key)? else {
+            return Ok(NULL.clone());
+        };
+
+        match (&self.expiration, expire_at) {
+            (Some(Expiration::Persist), _) => {
+                backend.persist(&self.key);
+            }
+            (_, Some(at)) => {
+                backend.expire_at(&self.key, at, &[]);
+            }
+            _ => {}
+        }
+
+        Ok(value.into())
+    }
+
+    // Only GETEX with an option is a write.
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        self.expiration.as_ref().map(|_| frame.clone())
+    }
+
+    // Logged as the PERSIST or PEXPIREAT it amounts to, so replaying the AOF
+    // later does not extend relative times.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        if !matches!(reply, Frame::BulkString(_)) {
+            return None;
+        }
+
+        match self.expire_at().ok()? {
+            Some(at) => Some(
+                vec![
+                    b"PEXPIREAT".into(),
+                    self.key.clone().into(),
+                    at.to_string().as_bytes().into(),
+                ]
+                .into(),
+            ),
+            None => Some(vec![b"PERSIST".into(), self.key.clone().into()].into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for GetEx {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "GETEX" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+
+        let expiration = match parse.len() {
+            0 => None,
+            _ => {
+                let option = parse.next_string()?.to_uppercase();
+
+                match option.as_str() {
+                    "PERSIST" => Some(Expiration::Persist),
+                    "EX" | "PX" | "EXAT" | "PXAT" => {
+                        let time = parse.next_int()?;
+
+                        if time <= 0 {
+                            return Err(CommandError::InvalidExpireTime("getex".to_string()).into());
+                        }
+
+                        Some(match option.as_str() {
+                            "EX" => Expiration::Ex(time),
+                            "PX" => Expiration::Px(time),
+                            "EXAT" => Expiration::ExAt(time),
+                            _ => Expiration::PxAt(time),
+                        })
+                    }
+                    _ => return Err(CommandError::SyntaxError.into()),
+                }
+            }
+        };
+
+        if parse.len() > 0 {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        Ok(Self { key, expiration })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn getex(args: Vec<Frame>) -> Result<GetEx> {
+        Frame::from(args).try_into()
+    }
+
+    #[test]
+    fn test_getex_try_from_frame() {
+        let cmd = getex(vec![
+            b"getex".into(),
+            b"key".into(),
+            b"px".into(),
+            b"100".into(),
+        ])
+        .unwrap();
+        assert_eq!(cmd.expiration, Some(Expiration::Px(100)));
+
+        let cmd = getex(vec![b"getex".into(), b"key".into()]).unwrap();
+        assert_eq!(cmd.expiration, None);
+
+        let result = getex(vec![
+            b"getex".into(),
+            b"key".into(),
+            b"persist".into(),
+            b"ex".into(),
+            b"1".into(),
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_getex_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+
+        let frame: Frame = vec![b"getex".into(), b"key".into(), b"ex".into(), b"100".into()].into();
+        let cmd: GetEx = frame.clone().try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(reply, b"value".into());
+        assert!(backend.pttl(b"key").unwrap().is_some());
+
+        match cmd.propagate_reply(frame, &reply) {
+            Some(Frame::Array(array)) => assert_eq!(array.inner[0], b"PEXPIREAT".into()),
+            other => panic!("Expected PEXPIREAT, got {:?}", other),
+        }
+
+        let frame: Frame = vec![b"getex".into(), b"key".into(), b"persist".into()].into();
+        let cmd: GetEx = frame.try_into().unwrap();
+        cmd.execute(backend.clone()).unwrap();
+        assert_eq!(backend.pttl(b"key"), Some(None));
+    }
+}
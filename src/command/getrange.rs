@@ -0,0 +1,72 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct GetRange {
+    key: Bytes,
+    start: i64,
+    end: i64,
+}
+
+impl CommandExecute for GetRange {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let value = backend.getrange(&self.key, self.start, self.end)?;
+        Ok(value.into())
+    }
+}
+
+impl TryFrom<Frame> for GetRange {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "GETRANGE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let start = parse.next_int()?;
+        let end = parse.next_int()?;
+        parse.finish()?;
+
+        Ok(Self { key, start, end })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_getrange_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "This is a string".into());
+
+        let frame: Frame = vec![
+            b"getrange".into(),
+            b"key".into(),
+            b"-3".into(),
+            b"-1".into(),
+        ]
+        .into();
+        let cmd: GetRange = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"ing".into());
+
+        let frame: Frame = vec![
+            b"getrange".into(),
+            b"missing".into(),
+            b"0".into(),
+            b"-1".into(),
+        ]
+        .into();
+        let cmd: GetRange = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), b"".into());
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HDel {
+    key: Bytes,
+    fields: Vec<Bytes>,
+}
+
+impl CommandExecute for HDel {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = backend.hdel(&self.key, &self.fields)?;
+        Ok((removed as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for HDel {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HDEL" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut fields = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            fields.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, fields })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hdel_execute() {
+        let backend = Backend::new();
+        backend
+            .hset(
+                b"key",
+                vec![("a".into(), "1".into()), ("b".into(), "2".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"hdel".into(), b"key".into(), b"a".into(), b"x".into()].into();
+        let cmd: HDel = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.hlen(b"key").unwrap(), 1);
+
+        let frame: Frame = vec![b"hdel".into(), b"key".into()].into();
+        assert!(HDel::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::auth::authenticate;
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::network::Session;
+use crate::resp::frame::Frame;
+use crate::resp::map::Map;
+
+// The Redis version commands behave like, also written to RDB files.
+const REDIS_VERSION: &str = "7.2.0";
+
+#[derive(Debug)]
+pub struct Hello {
+    // None keeps the protocol in use
+    protocol: Option<u8>,
+    auth: Option<(String, String)>,
+    name: Option<Bytes>,
+}
+
+impl Hello {
+    // Authenticates and names the connection, then switches it to the
+    // protocol. The reply is already encoded with the new protocol.
+    pub fn hello(&self, backend: &Backend, session: &mut Session) -> Result<Frame> {
+        if let Some((username, password)) = &self.auth {
+            authenticate(backend, Some(username), password)?;
+            session.authenticated = true;
+        }
+
+        if !session.authenticated {
+            return Err(CommandError::NoAuthHello.into());
+        }
+
+        if let Some(name) = &self.name {
+            if name.iter().any(|b| !(b'!'..=b'~').contains(b)) {
+                return Err(CommandError::InvalidArgument(
+                    "Client names cannot contain spaces, newlines or special characters."
+                        .to_string(),
+                )
+                .into());
+            }
+
+            // an empty name removes it
+            session.name = (!name.is_empty()).then(|| name.clone());
+        }
+
+        if let Some(protocol) = self.protocol {
+            session.protocol = protocol;
+        }
+
+        let fields = [
+            ("server", Frame::from(b"redis")),
+            ("version", REDIS_VERSION.as_bytes().into()),
+            ("proto", (session.protocol as i64).into()),
+            ("id", (session.id as i64).into()),
+            ("mode", b"standalone".into()),
+            ("role", b"master".into()),
+            ("modules", Vec::new().into()),
+        ];
+
+        Ok(Map::new(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.as_bytes().into(), value))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .into())
+    }
+}
+
+impl CommandExecute for Hello {
+    // The protocol belongs to the connection, which runs `hello` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("HELLO is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Hello {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HELLO" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let protocol = match parse.len() {
+            0 => None,
+            _ => {
+                let version = parse.next_string()?.parse::<i64>().map_err(|_| {
+                    CommandError::InvalidArgument(
+                        "Protocol version is not an integer or out of range".to_string(),
+                    )
+                })?;
+
+                if !(2..=3).contains(&version) {
+                    return Err(CommandError::NoProto.into());
+                }
+
+                Some(version as u8)
+            }
+        };
+
+        let (mut auth, mut name) = (None, None);
+        while parse.len() > 0 {
+            let option = parse.next_string()?;
+
+            match option.to_uppercase().as_str() {
+                "AUTH" if parse.len() >= 2 => {
+                    auth = Some((parse.next_string()?, parse.next_string()?));
+                }
+                "SETNAME" if parse.len() >= 1 => name = Some(parse.next_bytes()?),
+                _ => {
+                    return Err(CommandError::InvalidArgument(format!(
+                        "Syntax error in HELLO option '{}'",
+                        option
+                    ))
+                    .into())
+                }
+            }
+        }
+
+        Ok(Self {
+            protocol,
+            auth,
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+
+    fn hello(args: &[&str]) -> Result<Hello> {
+        let mut frame = vec![b"hello".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    fn field(reply: &Frame, name: &str) -> Frame {
+        match reply {
+            Frame::Map(map) => map.inner[&Frame::from(name.as_bytes())].clone(),
+            _ => panic!("Expected Map"),
+        }
+    }
+
+    #[test]
+    fn test_hello_try_from_frame() {
+        let cmd = hello(&["3", "auth", "default", "secret", "setname", "app"]).unwrap();
+        assert_eq!(cmd.protocol, Some(3));
+        assert_eq!(
+            cmd.auth,
+            Some(("default".to_string(), "secret".to_string()))
+        );
+        assert_eq!(cmd.name, Some("app".into()));
+
+        assert_eq!(
+            hello(&["4"]).unwrap_err().to_string(),
+            "NOPROTO unsupported protocol version"
+        );
+        assert_eq!(
+            hello(&["three"]).unwrap_err().to_string(),
+            "ERR Protocol version is not an integer or out of range"
+        );
+        assert_eq!(
+            hello(&["3", "auth", "default"]).unwrap_err().to_string(),
+            "ERR Syntax error in HELLO option 'auth'"
+        );
+    }
+
+    #[test]
+    fn test_hello() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let reply = hello(&[]).unwrap().hello(&backend, &mut session).unwrap();
+        assert_eq!(field(&reply, "proto"), 2.into());
+        assert_eq!(field(&reply, "id"), (session.id as i64).into());
+
+        let reply = hello(&["3", "setname", "app"])
+            .unwrap()
+            .hello(&backend, &mut session)
+            .unwrap();
+        assert_eq!(field(&reply, "proto"), 3.into());
+        assert_eq!(session.protocol, 3);
+        assert_eq!(session.name, Some("app".into()));
+
+        assert!(hello(&["2", "setname", "my app"])
+            .unwrap()
+            .hello(&backend, &mut session)
+            .is_err());
+        assert_eq!(session.protocol, 3);
+    }
+
+    #[test]
+    fn test_hello_auth() {
+        let backend = Backend::new();
+        backend.set_config(ServerConfig {
+            requirepass: Some("secret".to_string()),
+            ..Default::default()
+        });
+        let mut session = Session::new(&backend);
+
+        assert!(hello(&["3"])
+            .unwrap()
+            .hello(&backend, &mut session)
+            .unwrap_err()
+            .to_string()
+            .starts_with("NOAUTH HELLO must be called"));
+        assert!(hello(&["3", "auth", "default", "wrong"])
+            .unwrap()
+            .hello(&backend, &mut session)
+            .is_err());
+        assert_eq!(session.protocol, 2);
+
+        hello(&["3", "auth", "default", "secret"])
+            .unwrap()
+            .hello(&backend, &mut session)
+            .unwrap();
+        assert!(session.authenticated);
+        assert_eq!(session.protocol, 3);
+    }
+}
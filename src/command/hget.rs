@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::parse::Parse;
 use super::{CommandExecute, NULL};
@@ -7,14 +8,14 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct HGet {
-    key: String,
-    field: String,
+    key: Bytes,
+    field: Bytes,
 }
 
 impl CommandExecute for HGet {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        match backend.hget(&self.key, &self.field) {
-            Some(value) => Ok(value),
+        match backend.hget(&self.key, &self.field)? {
+            Some(value) => Ok(value.into()),
             None => Ok(NULL.clone()),
         }
     }
@@ -31,8 +32,8 @@ impl TryFrom<Frame> for HGet {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
-        let field = parse.next_string()?;
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
         parse.finish()?;
 
         Ok(Self { key, field })
@@ -49,8 +50,8 @@ mod tests {
 
         let actual: HGet = frame.try_into().unwrap();
         let expected = HGet {
-            key: "key".to_string(),
-            field: "field".to_string(),
+            key: "key".into(),
+            field: "field".into(),
         };
 
         assert_eq!(actual.key, expected.key);
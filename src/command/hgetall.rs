@@ -1,31 +1,29 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{parse::Parse, CommandExecute, NULL};
 use crate::backend::Backend;
 use crate::resp::frame::Frame;
+use crate::resp::map::Map;
 
 #[derive(Debug)]
 pub struct HGetAll {
-    key: String,
+    key: Bytes,
 }
 
 impl CommandExecute for HGetAll {
+    // A Map, flattened into an Array for RESP2 clients.
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        let mut frame: Vec<Frame> = vec![];
-
-        match backend.hgetall(&self.key) {
-            Some(hmap) => {
-                for (field, value) in hmap {
-                    frame.push(field.as_bytes().into());
-                    frame.push(value);
-                }
-            }
-            None => {
-                return Ok(NULL.clone());
-            }
-        }
+        let Some(hash) = backend.hgetall(&self.key)? else {
+            return Ok(NULL.clone());
+        };
+
+        let fields = hash
+            .into_iter()
+            .map(|(field, value)| (field.into(), value.into()))
+            .collect();
 
-        Ok(frame.into())
+        Ok(Map::new(fields).into())
     }
 }
 
@@ -40,7 +38,7 @@ impl TryFrom<Frame> for HGetAll {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
+        let key = parse.next_bytes()?;
         parse.finish()?;
 
         Ok(Self { key })
@@ -56,9 +54,7 @@ mod tests {
         let frame: Frame = vec!["hgetall".into(), "key".into()].into();
 
         let actual: HGetAll = frame.try_into().unwrap();
-        let expected = HGetAll {
-            key: "key".to_string(),
-        };
+        let expected = HGetAll { key: "key".into() };
 
         assert_eq!(actual.key, expected.key);
     }
@@ -70,4 +66,25 @@ mod tests {
         let actual: Result<HGetAll> = frame.try_into();
         assert!(actual.is_err());
     }
+
+    #[test]
+    fn test_hgetall_execute() {
+        let backend = Backend::new();
+        let fields = vec![("b".into(), "2".into()), ("a".into(), "1".into())];
+        backend.hset(b"key", fields).unwrap();
+
+        let frame: Frame = vec!["hgetall".into(), "key".into()].into();
+        let cmd: HGetAll = frame.try_into().unwrap();
+        let reply = cmd.execute(backend.clone()).unwrap();
+
+        assert_eq!(
+            reply.clone().into_resp2(),
+            vec![b"a".into(), b"1".into(), b"b".into(), b"2".into()].into()
+        );
+        assert!(matches!(reply, Frame::Map(_)));
+
+        let frame: Frame = vec!["hgetall".into(), "nokey".into()].into();
+        let cmd: HGetAll = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
 }
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HIncrBy {
+    key: Bytes,
+    field: Bytes,
+    increment: i64,
+}
+
+impl CommandExecute for HIncrBy {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let value = backend.hincrby(&self.key, self.field.clone(), self.increment)?;
+        Ok(value.into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for HIncrBy {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HINCRBY" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
+        let increment = parse.next_int()?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            field,
+            increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hincrby_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![
+            b"hincrby".into(),
+            b"key".into(),
+            b"field".into(),
+            b"-3".into(),
+        ]
+        .into();
+        let cmd: HIncrBy = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), (-3).into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), (-6).into());
+
+        backend
+            .hset(b"key", vec![("text".into(), "abc".into())])
+            .unwrap();
+        let frame: Frame = vec![
+            b"hincrby".into(),
+            b"key".into(),
+            b"text".into(),
+            b"1".into(),
+        ]
+        .into();
+        let cmd: HIncrBy = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR hash value is not an integer"
+        );
+    }
+}
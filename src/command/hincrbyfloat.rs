@@ -0,0 +1,101 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{parse_score, Backend};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HIncrByFloat {
+    key: Bytes,
+    field: Bytes,
+    increment: f64,
+}
+
+impl CommandExecute for HIncrByFloat {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let value = backend.hincrbyfloat(&self.key, self.field.clone(), self.increment)?;
+        Ok(Bytes::from(value.to_string()).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // Replicas set the resulting value instead of repeating the increment,
+    // so float rounding can't make them drift apart.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::BulkString(_) => Some(
+                vec![
+                    b"HSET".into(),
+                    self.key.clone().into(),
+                    self.field.clone().into(),
+                    reply.clone(),
+                ]
+                .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Frame> for HIncrByFloat {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HINCRBYFLOAT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
+        let increment = parse_score(&parse.next_string()?)?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            field,
+            increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hincrbyfloat_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![
+            b"hincrbyfloat".into(),
+            b"key".into(),
+            b"field".into(),
+            b"10.5".into(),
+        ]
+        .into();
+        let cmd: HIncrByFloat = frame.clone().try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(reply, b"10.5".into());
+        assert_eq!(cmd.execute(backend).unwrap(), b"21".into());
+
+        assert_eq!(
+            cmd.propagate_reply(frame, &reply),
+            Some(
+                vec![
+                    b"HSET".into(),
+                    b"key".into(),
+                    b"field".into(),
+                    b"10.5".into()
+                ]
+                .into()
+            )
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HLen {
+    key: Bytes,
+}
+
+impl CommandExecute for HLen {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.hlen(&self.key)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for HLen {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HLEN" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hlen_execute() {
+        let backend = Backend::new();
+        backend
+            .hset(
+                b"key",
+                vec![("a".into(), "1".into()), ("b".into(), "2".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"hlen".into(), b"key".into()].into();
+        let cmd: HLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+
+        let frame: Frame = vec![b"hlen".into(), b"missing".into()].into();
+        let cmd: HLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
+use bytes::Bytes;
 
-use super::parse::Parse;
+use super::parse::{Parse, ParseError};
 use super::CommandExecute;
 use super::NULL;
 use crate::backend::Backend;
@@ -8,8 +9,8 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct Hmget {
-    pub(crate) key: String,
-    pub(crate) fields: Vec<String>,
+    pub(crate) key: Bytes,
+    pub(crate) fields: Vec<Bytes>,
 }
 
 impl CommandExecute for Hmget {
@@ -17,8 +18,8 @@ impl CommandExecute for Hmget {
         let mut result = Vec::with_capacity(self.fields.len());
 
         for field in &self.fields {
-            match backend.hget(&self.key, field) {
-                Some(value) => result.push(value),
+            match backend.hget(&self.key, field)? {
+                Some(value) => result.push(value.into()),
                 None => result.push(NULL.clone()),
             }
         }
@@ -38,12 +39,17 @@ impl TryFrom<Frame> for Hmget {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
+        let key = parse.next_bytes()?;
+
+        if parse.length() < 3 {
+            return Err(ParseError::EndOfParts.into());
+        }
+
         let fields_len = parse.length() - 2;
         let mut fields = Vec::with_capacity(fields_len);
 
         for _ in 0..fields_len {
-            let field = parse.next_string()?;
+            let field = parse.next_bytes()?;
             fields.push(field);
         }
 
@@ -61,7 +67,7 @@ mod tests {
     use std::io::Cursor;
 
     fn parse_cmd(input: &[u8]) -> Result<Hmget> {
-        let mut buf = Cursor::new(input);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         Hmget::try_from(frame)
     }
@@ -90,8 +96,12 @@ mod tests {
     fn test_hmget_execute() {
         let backend = Backend::new();
 
-        backend.hset("myhash", "field1", b"value1".into());
-        backend.hset("myhash", "field2", b"value2".into());
+        backend
+            .hset(b"myhash", vec![("field1".into(), "value1".into())])
+            .unwrap();
+        backend
+            .hset(b"myhash", vec![("field2".into(), "value2".into())])
+            .unwrap();
 
         let input = b"*5\r\n$5\r\nhmget\r\n$6\r\nmyhash\r\n$6\r\nfield1\r\n$6\r\nfield2\r\n$7\r\nnofield\r\n";
         let cmd = parse_cmd(&input[..]).unwrap();
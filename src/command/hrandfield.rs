@@ -0,0 +1,114 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Without a count a single field is replied, with one an array. Negative
+// counts may return the same field several times.
+#[derive(Debug)]
+pub struct HRandField {
+    key: Bytes,
+    count: Option<i64>,
+    with_values: bool,
+}
+
+impl CommandExecute for HRandField {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let mut fields = backend.hrandfield(&self.key, self.count.unwrap_or(1))?;
+
+        let frame = match self.count {
+            None => fields
+                .pop()
+                .map_or_else(|| NULL.clone(), |(field, _)| field.into()),
+            Some(_) => {
+                let mut frames = Vec::with_capacity(fields.len() * (1 + self.with_values as usize));
+
+                for (field, value) in fields {
+                    frames.push(field.into());
+                    if self.with_values {
+                        frames.push(value.into());
+                    }
+                }
+
+                frames.into()
+            }
+        };
+
+        Ok(frame)
+    }
+}
+
+impl TryFrom<Frame> for HRandField {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HRANDFIELD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let count = match parse.len() {
+            0 => None,
+            _ => Some(parse.next_int()?),
+        };
+
+        let with_values = match parse.len() {
+            0 => false,
+            _ if parse.next_string()?.to_uppercase() == "WITHVALUES" => true,
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            count,
+            with_values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hrandfield_execute() {
+        let backend = Backend::new();
+        backend
+            .hset(b"key", vec![("a".into(), "1".into())])
+            .unwrap();
+
+        let frame: Frame = vec![b"hrandfield".into(), b"key".into()].into();
+        let cmd: HRandField = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"a".into());
+
+        let frame: Frame = vec![
+            b"hrandfield".into(),
+            b"key".into(),
+            b"-2".into(),
+            b"withvalues".into(),
+        ]
+        .into();
+        let cmd: HRandField = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"a".into(), b"1".into(), b"a".into(), b"1".into()].into()
+        );
+
+        let frame: Frame = vec![b"hrandfield".into(), b"missing".into()].into();
+        let cmd: HRandField = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+
+    #[test]
+    fn test_hrandfield_try_from_frame_withvalues_needs_count() {
+        let frame: Frame = vec![b"hrandfield".into(), b"key".into(), b"withvalues".into()].into();
+        assert!(HRandField::try_from(frame).is_err());
+    }
+}
@@ -1,20 +1,25 @@
 use anyhow::Result;
+use bytes::Bytes;
 
-use super::{parse::Parse, CommandExecute};
+use super::parse::{Parse, ParseError};
+use super::CommandExecute;
 use crate::backend::Backend;
 use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct HSet {
-    key: String,
-    field: String,
-    value: Frame,
+    key: Bytes,
+    fields: Vec<(Bytes, Bytes)>,
 }
 
 impl CommandExecute for HSet {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        backend.hset(&self.key, &self.field, self.value.clone());
-        Ok(1.into())
+        let created = backend.hset(&self.key, self.fields.clone())?;
+        Ok((created as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
     }
 }
 
@@ -29,12 +34,19 @@ impl TryFrom<Frame> for HSet {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
-        let field = parse.next_string()?;
-        let value = parse.next()?;
-        parse.finish()?;
+        let key = parse.next_bytes()?;
+
+        // fields and values come in pairs, at least one of them
+        if parse.len() == 0 || parse.len() % 2 != 0 {
+            return Err(ParseError::EndOfParts.into());
+        }
+
+        let mut fields = Vec::with_capacity(parse.len() / 2);
+        while parse.len() > 0 {
+            fields.push((parse.next_bytes()?, parse.next_bytes()?));
+        }
 
-        Ok(Self { key, field, value })
+        Ok(Self { key, fields })
     }
 }
 
@@ -54,14 +66,39 @@ mod tests {
 
         let actual: HSet = frame.try_into().unwrap();
 
-        let expected = HSet {
-            key: "key".to_string(),
-            field: "field".to_string(),
-            value: b"value".into(),
-        };
+        assert_eq!(actual.key, "key");
+        assert_eq!(actual.fields, vec![("field".into(), "value".into())]);
+    }
+
+    #[test]
+    fn test_hset_execute_multiple_fields() {
+        let backend = Backend::new();
+        backend
+            .hset(b"key", vec![("a".into(), "old".into())])
+            .unwrap();
 
-        assert_eq!(actual.key, expected.key);
-        assert_eq!(actual.field, expected.field);
-        assert_eq!(actual.value, expected.value);
+        let frame: Frame = vec![
+            b"hset".into(),
+            b"key".into(),
+            b"a".into(),
+            b"1".into(),
+            b"b".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: HSet = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.hget(b"key", b"a").unwrap(), Some("1".into()));
+
+        let frame: Frame = vec![
+            b"hset".into(),
+            b"key".into(),
+            b"a".into(),
+            b"1".into(),
+            b"b".into(),
+        ]
+        .into();
+        assert!(HSet::try_from(frame).is_err());
     }
 }
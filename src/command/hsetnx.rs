@@ -0,0 +1,68 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HSetNx {
+    key: Bytes,
+    field: Bytes,
+    value: Bytes,
+}
+
+impl CommandExecute for HSetNx {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.hsetnx(&self.key, self.field.clone(), self.value.clone())? {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for HSetNx {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HSETNX" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
+        let value = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, field, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hsetnx_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![
+            b"hsetnx".into(),
+            b"key".into(),
+            b"field".into(),
+            b"value".into(),
+        ]
+        .into();
+        let cmd: HSetNx = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
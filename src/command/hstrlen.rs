@@ -0,0 +1,60 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HStrLen {
+    key: Bytes,
+    field: Bytes,
+}
+
+impl CommandExecute for HStrLen {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.hstrlen(&self.key, &self.field)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for HStrLen {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HSTRLEN" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, field })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hstrlen_execute() {
+        let backend = Backend::new();
+        backend
+            .hset(b"key", vec![("field".into(), "value".into())])
+            .unwrap();
+
+        let frame: Frame = vec![b"hstrlen".into(), b"key".into(), b"field".into()].into();
+        let cmd: HStrLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 5.into());
+
+        let frame: Frame = vec![b"hstrlen".into(), b"key".into(), b"other".into()].into();
+        let cmd: HStrLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct HVals {
+    key: Bytes,
+}
+
+impl CommandExecute for HVals {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let items = backend.hvals(&self.key)?;
+        Ok(items
+            .into_iter()
+            .map(Frame::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+impl TryFrom<Frame> for HVals {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "HVALS" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hvals_execute() {
+        let backend = Backend::new();
+        backend
+            .hset(b"key", vec![("a".into(), "1".into())])
+            .unwrap();
+
+        let frame: Frame = vec![b"hvals".into(), b"key".into()].into();
+        let cmd: HVals = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"1".into()].into()
+        );
+
+        let frame: Frame = vec![b"hvals".into(), b"missing".into()].into();
+        let cmd: HVals = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), Vec::<Frame>::new().into());
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles INCR, DECR, INCRBY and DECRBY, decrements are negative increments.
+#[derive(Debug)]
+pub struct Incr {
+    key: Bytes,
+    increment: i64,
+}
+
+impl CommandExecute for Incr {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let value = backend.incr_by(&self.key, self.increment)?;
+        Ok(value.into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Incr {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let key = match command.as_str() {
+            "INCR" | "DECR" | "INCRBY" | "DECRBY" => parse.next_bytes()?,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let increment = match command.as_str() {
+            "INCR" => 1,
+            "DECR" => -1,
+            "INCRBY" => parse.next_int()?,
+            _ => parse.next_int()?.checked_neg().ok_or_else(|| {
+                CommandError::InvalidArgument("decrement would overflow".to_string())
+            })?,
+        };
+        parse.finish()?;
+
+        Ok(Self { key, increment })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn incr(args: Vec<Frame>) -> Result<Incr> {
+        Frame::from(args).try_into()
+    }
+
+    #[test]
+    fn test_incr_try_from_frame() {
+        let cmd = incr(vec![b"decrby".into(), b"key".into(), b"5".into()]).unwrap();
+        assert_eq!(cmd.increment, -5);
+
+        let cmd = incr(vec![b"decr".into(), b"key".into()]).unwrap();
+        assert_eq!(cmd.increment, -1);
+
+        let result = incr(vec![
+            b"decrby".into(),
+            b"key".into(),
+            b"-9223372036854775808".into(),
+        ]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ERR decrement would overflow"
+        );
+    }
+
+    #[test]
+    fn test_incr_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "10".into());
+
+        let cmd = incr(vec![b"incr".into(), b"key".into()]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 11.into());
+
+        let cmd = incr(vec![
+            b"incrby".into(),
+            b"key".into(),
+            b"9223372036854775807".into(),
+        ])
+        .unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR increment or decrement would overflow"
+        );
+
+        backend.set(b"key", "ten".into());
+        let cmd = incr(vec![b"incr".into(), b"key".into()]).unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR value is not an integer or out of range"
+        );
+    }
+}
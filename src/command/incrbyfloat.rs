@@ -0,0 +1,95 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{parse_score, Backend};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct IncrByFloat {
+    key: Bytes,
+    increment: f64,
+}
+
+impl CommandExecute for IncrByFloat {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let value = backend.incr_by_float(&self.key, self.increment)?;
+        Ok(Bytes::from(value.to_string()).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // The resulting value is logged instead of the increment, like for
+    // HINCRBYFLOAT, keeping the time to live the increment did not touch.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::BulkString(_) => Some(
+                vec![
+                    b"SET".into(),
+                    self.key.clone().into(),
+                    reply.clone(),
+                    b"KEEPTTL".into(),
+                ]
+                .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Frame> for IncrByFloat {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "INCRBYFLOAT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let increment = parse_score(&parse.next_string()?)?;
+        parse.finish()?;
+
+        Ok(Self { key, increment })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incrbyfloat_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "10.50".into());
+
+        let frame: Frame = vec![b"incrbyfloat".into(), b"key".into(), b"0.1".into()].into();
+        let cmd: IncrByFloat = frame.clone().try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(reply, b"10.6".into());
+        assert_eq!(
+            cmd.propagate_reply(frame, &reply),
+            Some(
+                vec![
+                    b"SET".into(),
+                    b"key".into(),
+                    b"10.6".into(),
+                    b"KEEPTTL".into()
+                ]
+                .into()
+            )
+        );
+
+        backend.set(b"key", "abc".into());
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR value is not a valid float"
+        );
+    }
+}
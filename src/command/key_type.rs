@@ -0,0 +1,62 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct KeyType {
+    key: Bytes,
+}
+
+impl CommandExecute for KeyType {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let name = backend.key_type(&self.key).unwrap_or("none");
+        Ok(name.into())
+    }
+}
+
+impl TryFrom<Frame> for KeyType {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "TYPE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_try_from_frame() {
+        let frame: Frame = vec![b"type".into(), b"key".into()].into();
+        let cmd: KeyType = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "key");
+    }
+
+    #[test]
+    fn test_type_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"type".into(), b"key".into()].into();
+        let cmd: KeyType = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), "none".into());
+
+        backend.sadd(b"key", vec!["member".into()]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), "set".into());
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LastSave;
+
+impl CommandExecute for LastSave {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let last_save = backend.last_save.load(std::sync::atomic::Ordering::Relaxed);
+
+        Ok((last_save as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for LastSave {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LASTSAVE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lastsave_execute() {
+        let frame: Frame = vec![b"lastsave".into()].into();
+        let cmd: LastSave = frame.try_into().unwrap();
+
+        match cmd.execute(Backend::new()).unwrap() {
+            Frame::Integer(integer) => assert!(integer.inner > 0),
+            _ => panic!("Expected Integer"),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LIndex {
+    key: Bytes,
+    index: i64,
+}
+
+impl CommandExecute for LIndex {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.lindex(&self.key, self.index)? {
+            Some(element) => Ok(element.into()),
+            None => Ok(NULL.clone()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for LIndex {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LINDEX" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let index = parse.next_int()?;
+        parse.finish()?;
+
+        Ok(Self { key, index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    #[test]
+    fn test_lindex_execute() {
+        let backend = Backend::new();
+        backend
+            .push(b"list", ListEnd::Right, vec!["a".into(), "b".into()])
+            .unwrap();
+
+        let frame: Frame = vec![b"lindex".into(), b"list".into(), b"-1".into()].into();
+        let cmd: LIndex = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"b".into());
+
+        let frame: Frame = vec![b"lindex".into(), b"list".into(), b"2".into()].into();
+        let cmd: LIndex = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
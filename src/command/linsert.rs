@@ -0,0 +1,109 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, ListEnd};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LInsert {
+    key: Bytes,
+    // BEFORE inserts on the left of the pivot, AFTER on its right
+    side: ListEnd,
+    pivot: Bytes,
+    element: Bytes,
+}
+
+impl CommandExecute for LInsert {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.linsert(&self.key, self.side, &self.pivot, self.element.clone())?;
+        Ok(len.into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for LInsert {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LINSERT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let side = match parse.next_string()?.to_uppercase().as_str() {
+            "BEFORE" => ListEnd::Left,
+            "AFTER" => ListEnd::Right,
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+        let pivot = parse.next_bytes()?;
+        let element = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            side,
+            pivot,
+            element,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linsert_try_from_frame() {
+        let frame: Frame = vec![
+            b"linsert".into(),
+            b"list".into(),
+            b"after".into(),
+            b"a".into(),
+            b"b".into(),
+        ]
+        .into();
+        let cmd: LInsert = frame.try_into().unwrap();
+        assert_eq!(cmd.side, ListEnd::Right);
+
+        let frame: Frame = vec![
+            b"linsert".into(),
+            b"list".into(),
+            b"middle".into(),
+            b"a".into(),
+            b"b".into(),
+        ]
+        .into();
+        assert_eq!(
+            LInsert::try_from(frame).unwrap_err().to_string(),
+            "ERR syntax error"
+        );
+    }
+
+    #[test]
+    fn test_linsert_execute() {
+        let backend = Backend::new();
+        backend
+            .push(b"list", ListEnd::Right, vec!["a".into(), "c".into()])
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"linsert".into(),
+            b"list".into(),
+            b"before".into(),
+            b"c".into(),
+            b"b".into(),
+        ]
+        .into();
+        let cmd: LInsert = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.into());
+        assert_eq!(backend.lrange(b"list", 0, -1).unwrap(), vec!["a", "b", "c"]);
+    }
+}
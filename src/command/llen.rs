@@ -0,0 +1,56 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LLen {
+    key: Bytes,
+}
+
+impl CommandExecute for LLen {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        Ok((backend.llen(&self.key)? as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for LLen {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LLEN" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    #[test]
+    fn test_llen_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"llen".into(), b"list".into()].into();
+        let cmd: LLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        backend
+            .push(b"list", ListEnd::Left, vec!["a".into(), "b".into()])
+            .unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 2.into());
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::bpop::parse_timeout;
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::{Backend, Block, BlockOp, ListEnd, Served};
+use crate::resp::frame::Frame;
+
+// Handles LMOVE and BLMOVE.
+#[derive(Debug)]
+pub struct LMove {
+    source: Bytes,
+    destination: Bytes,
+    from: ListEnd,
+    to: ListEnd,
+    // BLMOVE only, None blocks forever
+    blocking: Option<Option<Duration>>,
+}
+
+fn parse_end(parse: &mut Parse) -> Result<ListEnd> {
+    match parse.next_string()?.to_uppercase().as_str() {
+        "LEFT" => Ok(ListEnd::Left),
+        "RIGHT" => Ok(ListEnd::Right),
+        _ => Err(CommandError::SyntaxError.into()),
+    }
+}
+
+fn end_name(end: ListEnd) -> &'static [u8] {
+    match end {
+        ListEnd::Left => b"LEFT",
+        ListEnd::Right => b"RIGHT",
+    }
+}
+
+impl LMove {
+    fn op(&self) -> BlockOp {
+        let destination = self.destination.clone();
+        let (from, to) = (self.from, self.to);
+
+        Arc::new(move |backend: &Backend, source: &[u8]| {
+            let element = backend.lmove(source, &destination, from, to).ok()??;
+
+            Some(Served {
+                reply: element.into(),
+                propagate: Some(
+                    vec![
+                        b"LMOVE".into(),
+                        source.into(),
+                        destination.clone().into(),
+                        end_name(from).into(),
+                        end_name(to).into(),
+                    ]
+                    .into(),
+                ),
+            })
+        })
+    }
+}
+
+impl CommandExecute for LMove {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let element = backend.lmove(&self.source, &self.destination, self.from, self.to)?;
+        Ok(element.map_or_else(|| NULL.clone(), Frame::from))
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn block(&self, _backend: &Backend) -> Option<Block> {
+        let timeout = self.blocking?;
+
+        Some(Block {
+            keys: vec![self.source.clone()],
+            op: self.op(),
+            timeout,
+            timeout_reply: NULL.clone(),
+        })
+    }
+}
+
+impl TryFrom<Frame> for LMove {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LMOVE" && command != "BLMOVE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let source = parse.next_bytes()?;
+        let destination = parse.next_bytes()?;
+        let from = parse_end(&mut parse)?;
+        let to = parse_end(&mut parse)?;
+
+        let blocking = match command.as_str() {
+            "BLMOVE" => Some(parse_timeout(&parse.next_string()?)?),
+            _ => None,
+        };
+        parse.finish()?;
+
+        Ok(Self {
+            source,
+            destination,
+            from,
+            to,
+            blocking,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lmove_try_from_frame() {
+        let frame: Frame = vec![
+            b"blmove".into(),
+            b"src".into(),
+            b"dst".into(),
+            b"right".into(),
+            b"left".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: LMove = frame.try_into().unwrap();
+
+        assert_eq!((cmd.from, cmd.to), (ListEnd::Right, ListEnd::Left));
+        assert_eq!(cmd.blocking, Some(Some(Duration::from_secs(2))));
+        assert!(cmd.block(&Backend::new()).is_some());
+
+        let frame: Frame = vec![
+            b"lmove".into(),
+            b"src".into(),
+            b"dst".into(),
+            b"up".into(),
+            b"left".into(),
+        ]
+        .into();
+        assert!(LMove::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_lmove_execute() {
+        let backend = Backend::new();
+        backend
+            .push(b"src", ListEnd::Right, vec!["a".into(), "b".into()])
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"lmove".into(),
+            b"src".into(),
+            b"dst".into(),
+            b"left".into(),
+            b"right".into(),
+        ]
+        .into();
+        let cmd: LMove = frame.try_into().unwrap();
+        assert!(cmd.block(&backend).is_none());
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"a".into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"b".into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL);
+        assert_eq!(backend.lrange(b"dst", 0, -1).unwrap(), vec!["a", "b"]);
+    }
+}
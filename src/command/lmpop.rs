@@ -0,0 +1,198 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::bpop::parse_timeout;
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL_ARRAY};
+use crate::backend::{Backend, Block, BlockOp, ListEnd, Served};
+use crate::resp::frame::Frame;
+
+// Handles LMPOP and BLMPOP, popping up to count elements from the first
+// non-empty list.
+#[derive(Debug)]
+pub struct LMPop {
+    keys: Vec<Bytes>,
+    end: ListEnd,
+    count: usize,
+    // BLMPOP only, None blocks forever
+    blocking: Option<Option<Duration>>,
+}
+
+impl LMPop {
+    fn op(&self) -> BlockOp {
+        let (end, count) = (self.end, self.count);
+
+        Arc::new(move |backend: &Backend, key: &[u8]| {
+            let elements = backend.pop(key, end, count).ok()??;
+            let command = match end {
+                ListEnd::Left => "LPOP",
+                ListEnd::Right => "RPOP",
+            };
+
+            let propagate = vec![
+                command.as_bytes().into(),
+                key.into(),
+                elements.len().to_string().as_bytes().into(),
+            ];
+            let elements = elements.into_iter().map(Frame::from).collect::<Vec<_>>();
+
+            Some(Served {
+                reply: vec![key.into(), elements.into()].into(),
+                propagate: Some(propagate.into()),
+            })
+        })
+    }
+}
+
+impl CommandExecute for LMPop {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let op = self.op();
+
+        match self.keys.iter().find_map(|key| op(&backend, key)) {
+            Some(served) => Ok(served.reply),
+            None => Ok(NULL_ARRAY.clone()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn block(&self, _backend: &Backend) -> Option<Block> {
+        let timeout = self.blocking?;
+
+        Some(Block {
+            keys: self.keys.clone(),
+            op: self.op(),
+            timeout,
+            timeout_reply: NULL_ARRAY.clone(),
+        })
+    }
+}
+
+impl TryFrom<Frame> for LMPop {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let blocking = match command.as_str() {
+            "LMPOP" => None,
+            "BLMPOP" => Some(parse_timeout(&parse.next_string()?)?),
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let numkeys = parse.next_int()?;
+        if numkeys <= 0 {
+            return Err(CommandError::InvalidArgument(
+                "numkeys should be greater than 0".to_string(),
+            )
+            .into());
+        }
+
+        let mut keys = Vec::with_capacity(numkeys as usize);
+        for _ in 0..numkeys {
+            keys.push(parse.next_bytes()?);
+        }
+
+        let end = match parse.next_string()?.to_uppercase().as_str() {
+            "LEFT" => ListEnd::Left,
+            "RIGHT" => ListEnd::Right,
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+
+        let count = match parse.len() {
+            0 => 1,
+            _ if parse.next_string()?.to_uppercase() != "COUNT" => {
+                return Err(CommandError::SyntaxError.into())
+            }
+            _ => match parse.next_int()? {
+                count if count > 0 => count as usize,
+                _ => {
+                    return Err(CommandError::InvalidArgument(
+                        "count should be greater than 0".to_string(),
+                    )
+                    .into())
+                }
+            },
+        };
+        parse.finish()?;
+
+        Ok(Self {
+            keys,
+            end,
+            count,
+            blocking,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lmpop_try_from_frame() {
+        let frame: Frame = vec![
+            b"blmpop".into(),
+            b"0".into(),
+            b"2".into(),
+            b"a".into(),
+            b"b".into(),
+            b"right".into(),
+            b"count".into(),
+            b"3".into(),
+        ]
+        .into();
+        let cmd: LMPop = frame.try_into().unwrap();
+
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert_eq!(cmd.end, ListEnd::Right);
+        assert_eq!(cmd.count, 3);
+        assert_eq!(cmd.blocking, Some(None));
+
+        let frame: Frame = vec![b"lmpop".into(), b"0".into(), b"a".into(), b"left".into()].into();
+        assert_eq!(
+            LMPop::try_from(frame).unwrap_err().to_string(),
+            "ERR numkeys should be greater than 0"
+        );
+
+        let frame: Frame = vec![b"lmpop".into(), b"1".into(), b"a".into(), b"up".into()].into();
+        assert!(LMPop::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_lmpop_execute() {
+        let backend = Backend::new();
+        backend
+            .push(
+                b"b",
+                ListEnd::Right,
+                vec!["x".into(), "y".into(), "z".into()],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"lmpop".into(),
+            b"2".into(),
+            b"a".into(),
+            b"b".into(),
+            b"left".into(),
+            b"count".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: LMPop = frame.try_into().unwrap();
+
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"b".into(), vec![b"x".into(), b"y".into()].into()].into()
+        );
+        cmd.execute(backend.clone()).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL_ARRAY);
+    }
+}
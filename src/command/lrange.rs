@@ -0,0 +1,86 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LRange {
+    key: Bytes,
+    start: i64,
+    stop: i64,
+}
+
+impl CommandExecute for LRange {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let elements = backend.lrange(&self.key, self.start, self.stop)?;
+        Ok(elements
+            .into_iter()
+            .map(Frame::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+impl TryFrom<Frame> for LRange {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LRANGE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let start = parse.next_int()?;
+        let stop = parse.next_int()?;
+        parse.finish()?;
+
+        Ok(Self { key, start, stop })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+    use crate::resp::RespEncode;
+
+    #[test]
+    fn test_lrange_try_from_frame() {
+        let frame: Frame = vec![b"lrange".into(), b"list".into(), b"0".into(), b"-1".into()].into();
+        let cmd: LRange = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "list");
+        assert_eq!((cmd.start, cmd.stop), (0, -1));
+
+        let frame: Frame = vec![b"lrange".into(), b"list".into(), b"0".into(), b"x".into()].into();
+        assert!(LRange::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_lrange_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame =
+            vec![b"lrange".into(), b"list".into(), b"-2".into(), b"-1".into()].into();
+        let cmd: LRange = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap().encode(), b"*0\r\n");
+
+        backend
+            .push(
+                b"list",
+                ListEnd::Right,
+                vec!["a".into(), "b".into(), "c".into()],
+            )
+            .unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap(),
+            vec![b"b".into(), b"c".into()].into()
+        );
+    }
+}
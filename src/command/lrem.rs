@@ -0,0 +1,76 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LRem {
+    key: Bytes,
+    count: i64,
+    element: Bytes,
+}
+
+impl CommandExecute for LRem {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = backend.lrem(&self.key, self.count, &self.element)?;
+        Ok((removed as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for LRem {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LREM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let count = parse.next_int()?;
+        let element = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            count,
+            element,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    #[test]
+    fn test_lrem_execute() {
+        let backend = Backend::new();
+        backend
+            .push(
+                b"list",
+                ListEnd::Right,
+                vec!["a".into(), "b".into(), "a".into()],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"lrem".into(), b"list".into(), b"1".into(), b"a".into()].into();
+        let cmd: LRem = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.lrange(b"list", 0, -1).unwrap(), vec!["b", "a"]);
+
+        let frame: Frame = vec![b"lrem".into(), b"missing".into(), b"0".into(), b"a".into()].into();
+        let cmd: LRem = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LSet {
+    key: Bytes,
+    index: i64,
+    element: Bytes,
+}
+
+impl CommandExecute for LSet {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        backend.lset(&self.key, self.index, self.element.clone())?;
+        Ok(OK.clone())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for LSet {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LSET" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let index = parse.next_int()?;
+        let element = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            index,
+            element,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    #[test]
+    fn test_lset_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"lset".into(), b"list".into(), b"0".into(), b"x".into()].into();
+        let cmd: LSet = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR no such key"
+        );
+
+        backend
+            .push(b"list", ListEnd::Right, vec!["a".into()])
+            .unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.lindex(b"list", 0).unwrap(), Some("x".into()));
+
+        let frame: Frame = vec![b"lset".into(), b"list".into(), b"1".into(), b"x".into()].into();
+        let cmd: LSet = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR index out of range"
+        );
+    }
+}
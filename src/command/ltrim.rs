@@ -0,0 +1,68 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct LTrim {
+    key: Bytes,
+    start: i64,
+    stop: i64,
+}
+
+impl CommandExecute for LTrim {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        backend.ltrim(&self.key, self.start, self.stop)?;
+        Ok(OK.clone())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for LTrim {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "LTRIM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let start = parse.next_int()?;
+        let stop = parse.next_int()?;
+        parse.finish()?;
+
+        Ok(Self { key, start, stop })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ListEnd;
+
+    #[test]
+    fn test_ltrim_execute() {
+        let backend = Backend::new();
+        backend
+            .push(
+                b"list",
+                ListEnd::Right,
+                vec!["a".into(), "b".into(), "c".into()],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"ltrim".into(), b"list".into(), b"0".into(), b"-2".into()].into();
+        let cmd: LTrim = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.lrange(b"list", 0, -1).unwrap(), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct MGet {
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for MGet {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let values = backend
+            .mget(&self.keys)
+            .into_iter()
+            .map(|value| value.map_or_else(|| NULL.clone(), Frame::from))
+            .collect::<Vec<_>>();
+
+        Ok(values.into())
+    }
+
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for MGet {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "MGET" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { keys })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mget_execute() {
+        let backend = Backend::new();
+        backend.set(b"a", "1".into());
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+
+        let frame: Frame = vec![b"mget".into(), b"a".into(), b"set".into(), b"b".into()].into();
+        let cmd: MGet = frame.try_into().unwrap();
+
+        assert_eq!(
+            cmd.execute(backend).unwrap(),
+            vec![b"1".into(), NULL.clone(), NULL.clone()].into()
+        );
+    }
+}
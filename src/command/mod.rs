@@ -1,31 +1,237 @@
+mod append;
+mod auth;
+mod bgrewriteaof;
+mod bitcount;
+mod bitfield;
+mod bitop;
+mod bitpos;
+mod bpop;
+mod config;
+mod del;
+mod discard;
 mod echo;
+mod eval;
+mod exec;
+mod exists;
+mod expire;
 mod get;
+mod getbit;
+mod getdel;
+mod getex;
+mod getrange;
+mod hdel;
+mod hello;
+mod hexists;
 mod hget;
 mod hgetall;
+mod hincrby;
+mod hincrbyfloat;
+mod hkeys;
+mod hlen;
 mod hmget;
+mod hrandfield;
 mod hset;
+mod hsetnx;
+mod hstrlen;
+mod hvals;
+mod incr;
+mod incrbyfloat;
+mod key_type;
+mod lastsave;
+mod lindex;
+mod linsert;
+mod llen;
+mod lmove;
+mod lmpop;
+mod lrange;
+mod lrem;
+mod lset;
+mod ltrim;
+mod mget;
+mod mset;
+mod multi;
 mod parse;
+mod persist;
+mod pfadd;
+mod pfcount;
+mod pfmerge;
+mod ping;
+mod pop;
+mod publish;
+mod pubsub;
+mod push;
 mod sadd;
+mod save;
+mod scard;
+mod script;
 mod set;
+mod setbit;
+mod setnx;
+mod setop;
+mod setrange;
+mod sintercard;
 mod sismember;
 mod smembers;
+mod smismember;
+mod smove;
+mod spop;
+mod srandmember;
+mod srem;
+mod strlen;
+mod subscribe;
+mod ttl;
+mod unsubscribe;
+mod unwatch;
+mod watch;
+mod xack;
+mod xadd;
+mod xautoclaim;
+mod xclaim;
+mod xdel;
+mod xgroup;
+mod xinfo;
+mod xlen;
+mod xpending;
+mod xrange;
+mod xread;
+mod xreadgroup;
+mod xsetid;
+mod xtrim;
+mod zadd;
+mod zcard;
+mod zcount;
+mod zincrby;
+mod zpop;
+mod zrange;
+mod zrank;
+mod zrem;
+mod zscore;
 
-use crate::backend::Backend;
+use crate::backend::{Backend, Block};
 use crate::resp::frame::Frame;
-use crate::resp::null::Null;
+use crate::resp::null_array::NullArray;
+use crate::resp::null_bulk_string::NullBulkString;
 use anyhow::Result;
 use enum_dispatch::enum_dispatch;
 use lazy_static::lazy_static;
-use parse::Parse;
+use parse::{Parse, ParseError};
+use thiserror::Error;
 
 lazy_static! {
     static ref OK: Frame = b"OK".into();
-    static ref NULL: Frame = Frame::Null(Null);
+    static ref NULL: Frame = Frame::NullBulkString(NullBulkString);
+    static ref NULL_ARRAY: Frame = Frame::NullArray(NullArray);
+}
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("ERR unknown command '{0}', with args beginning with: {1}")]
+    UnknownCommand(String, String),
+
+    #[error("ERR wrong number of arguments for '{0}' command")]
+    WrongNumberOfArguments(String),
+
+    #[error("WRONGTYPE Operation against a key holding the wrong kind of value")]
+    WrongType,
+
+    #[error("ERR syntax error")]
+    SyntaxError,
+
+    #[error("ERR value is not an integer or out of range")]
+    NotInteger,
+
+    #[error("ERR value is not a valid float")]
+    NotFloat,
+
+    #[error("WRONGTYPE Key is not a valid HyperLogLog string value.")]
+    NotHll,
+
+    #[error("INVALIDOBJ Corrupted HLL object detected")]
+    CorruptedHll,
+
+    #[error("ERR invalid expire time in '{0}' command")]
+    InvalidExpireTime(String),
+
+    #[error("ERR {0}")]
+    InvalidArgument(String),
+
+    #[error("NOGROUP {0}")]
+    NoGroup(String),
+
+    #[error("BUSYGROUP Consumer Group name already exists")]
+    BusyGroup,
+
+    #[error("ERR Can't execute '{0}': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context")]
+    SubscribeMode(String),
+
+    #[error("NOAUTH Authentication required.")]
+    NoAuth,
+
+    #[error("NOAUTH HELLO must be called with the client already authenticated, otherwise the HELLO <proto> AUTH <user> <pass> option can be used to authenticate the client and select the RESP protocol version at the same time")]
+    NoAuthHello,
+
+    #[error("NOPROTO unsupported protocol version")]
+    NoProto,
+
+    #[error("EXECABORT Transaction discarded because of previous errors.")]
+    ExecAbort,
+
+    #[error("NOSCRIPT No matching script. Please use EVAL.")]
+    NoScript,
+
+    #[error("ERR This Redis command is not allowed from script")]
+    NotAllowedFromScript,
+
+    #[error("BUSY Redis is busy running a script. You can only call SCRIPT KILL.")]
+    Busy,
+
+    #[error("NOTBUSY No scripts in execution right now.")]
+    NotBusy,
+
+    #[error("UNKILLABLE Sorry the script already executed write commands against the dataset. You can only wait the script termination.")]
+    Unkillable,
+
+    #[error("ERR Script killed by user with SCRIPT KILL...")]
+    ScriptKilled,
+
+    #[error("WRONGPASS invalid username-password pair or user is disabled.")]
+    WrongPass,
 }
 
 #[enum_dispatch]
 pub trait CommandExecute {
     fn execute(&self, backend: Backend) -> Result<Frame>;
+
+    // Commands that modify the dataset return the frame to append to the AOF,
+    // which usually is the frame they were parsed from.
+    fn propagate(&self, _frame: &Frame) -> Option<Frame> {
+        None
+    }
+
+    // Commands with random effects, like SPOP, replace the entry with what
+    // they actually did once their reply is known. None skips propagation.
+    fn propagate_reply(&self, entry: Frame, _reply: &Frame) -> Option<Frame> {
+        Some(entry)
+    }
+
+    // Blocking commands describe what to wait for when none of their keys can
+    // serve them yet. `execute` alone never blocks. The backend is there for
+    // commands that wait relative to the current data, like XREAD with `$`.
+    fn block(&self, _backend: &Backend) -> Option<Block> {
+        None
+    }
+
+    // Commands reading or writing several keys run with the keyspace locked
+    // exclusively, so no other command sees them half applied.
+    fn exclusive(&self) -> bool {
+        false
+    }
+
+    // Replies shaped differently under RESP3, like scores paired with their
+    // members, are reshaped from the RESP2 one.
+    fn resp3_reply(&self, reply: Frame) -> Frame {
+        reply
+    }
 }
 
 #[enum_dispatch(CommandExecute)]
@@ -33,14 +239,150 @@ pub trait CommandExecute {
 pub enum Command {
     Get(get::Get),
     Set(set::Set),
+    SetNx(setnx::SetNx),
+    GetDel(getdel::GetDel),
+    GetEx(getex::GetEx),
+    MGet(mget::MGet),
+    MSet(mset::MSet),
+    Incr(incr::Incr),
+    IncrByFloat(incrbyfloat::IncrByFloat),
+    Append(append::Append),
+    StrLen(strlen::StrLen),
+    GetRange(getrange::GetRange),
+    SetRange(setrange::SetRange),
+    SetBit(setbit::SetBit),
+    GetBit(getbit::GetBit),
+    BitCount(bitcount::BitCount),
+    BitPos(bitpos::BitPos),
+    BitOp(bitop::BitOp),
+    BitField(bitfield::BitField),
+    PfAdd(pfadd::PfAdd),
+    PfCount(pfcount::PfCount),
+    PfMerge(pfmerge::PfMerge),
     HGet(hget::HGet),
     HSet(hset::HSet),
     HGetAll(hgetall::HGetAll),
+    HDel(hdel::HDel),
+    HExists(hexists::HExists),
+    HLen(hlen::HLen),
+    HKeys(hkeys::HKeys),
+    HVals(hvals::HVals),
+    HIncrBy(hincrby::HIncrBy),
+    HIncrByFloat(hincrbyfloat::HIncrByFloat),
+    HSetNx(hsetnx::HSetNx),
+    HStrLen(hstrlen::HStrLen),
+    HRandField(hrandfield::HRandField),
     Echo(echo::Echo),
+    Ping(ping::Ping),
     Hmget(hmget::Hmget),
     Sadd(sadd::Sadd),
     Smembers(smembers::Smembers),
     Sismember(sismember::Sismember),
+    Expire(expire::Expire),
+    Ttl(ttl::Ttl),
+    Persist(persist::Persist),
+    Type(key_type::KeyType),
+    Exists(exists::Exists),
+    Del(del::Del),
+    Save(save::Save),
+    LastSave(lastsave::LastSave),
+    BgRewriteAof(bgrewriteaof::BgRewriteAof),
+    Config(config::Config),
+    Auth(auth::Auth),
+    Push(push::Push),
+    Pop(pop::Pop),
+    LRange(lrange::LRange),
+    LIndex(lindex::LIndex),
+    LSet(lset::LSet),
+    LRem(lrem::LRem),
+    LTrim(ltrim::LTrim),
+    LInsert(linsert::LInsert),
+    LLen(llen::LLen),
+    BPop(bpop::BPop),
+    LMove(lmove::LMove),
+    LMPop(lmpop::LMPop),
+    ZAdd(zadd::ZAdd),
+    ZIncrBy(zincrby::ZIncrBy),
+    ZRem(zrem::ZRem),
+    ZScore(zscore::ZScore),
+    ZCard(zcard::ZCard),
+    ZRange(zrange::ZRange),
+    ZRank(zrank::ZRank),
+    ZCount(zcount::ZCount),
+    ZPop(zpop::ZPop),
+    SRem(srem::SRem),
+    SCard(scard::SCard),
+    SMIsMember(smismember::SMIsMember),
+    SPop(spop::SPop),
+    SRandMember(srandmember::SRandMember),
+    SMove(smove::SMove),
+    SetOp(setop::SetOp),
+    SInterCard(sintercard::SInterCard),
+    XAdd(xadd::XAdd),
+    XRange(xrange::XRange),
+    XLen(xlen::XLen),
+    XTrim(xtrim::XTrim),
+    XDel(xdel::XDel),
+    XRead(xread::XRead),
+    XSetId(xsetid::XSetId),
+    XGroup(xgroup::XGroup),
+    XReadGroup(xreadgroup::XReadGroup),
+    XAck(xack::XAck),
+    XPending(xpending::XPending),
+    XClaim(xclaim::XClaim),
+    XAutoClaim(xautoclaim::XAutoClaim),
+    XInfo(xinfo::XInfo),
+    Subscribe(subscribe::Subscribe),
+    Unsubscribe(unsubscribe::Unsubscribe),
+    Publish(publish::Publish),
+    PubSub(pubsub::PubSub),
+    Hello(hello::Hello),
+    Multi(multi::Multi),
+    Exec(exec::Exec),
+    Discard(discard::Discard),
+    Watch(watch::Watch),
+    Unwatch(unwatch::Unwatch),
+    Eval(eval::Eval),
+    Script(script::Script),
+}
+
+impl Command {
+    // RESP2 clients with subscriptions can only manage them, or PING, as
+    // their connection carries nothing but messages.
+    pub fn allowed_while_subscribed(&self) -> bool {
+        matches!(
+            self,
+            Command::Subscribe(_) | Command::Unsubscribe(_) | Command::Ping(_)
+        )
+    }
+
+    // Inside MULTI everything but the commands handling the transaction
+    // itself is queued until EXEC.
+    pub fn queued_in_transaction(&self) -> bool {
+        !matches!(
+            self,
+            Command::Multi(_) | Command::Exec(_) | Command::Discard(_) | Command::Watch(_)
+        )
+    }
+
+    // Scripts can't handle the connection they run for, nor run scripts
+    // themselves.
+    pub fn allowed_in_script(&self) -> bool {
+        !matches!(
+            self,
+            Command::Auth(_)
+                | Command::Hello(_)
+                | Command::Subscribe(_)
+                | Command::Unsubscribe(_)
+                | Command::Multi(_)
+                | Command::Exec(_)
+                | Command::Discard(_)
+                | Command::Watch(_)
+                | Command::Unwatch(_)
+                | Command::Eval(_)
+                | Command::Script(_)
+        )
+    }
 }
 
 impl TryFrom<Frame> for Command {
@@ -48,23 +390,139 @@ impl TryFrom<Frame> for Command {
 
     fn try_from(frame: Frame) -> Result<Self> {
         let mut parse = Parse::try_new(frame.clone())?;
+        let name = parse.peek_string()?;
 
-        match parse.peek_string() {
-            Ok(command) => match command.to_uppercase().as_str() {
-                "GET" => Ok(Command::Get(frame.try_into()?)),
-                "SET" => Ok(Command::Set(frame.try_into()?)),
-                "HGET" => Ok(Command::HGet(frame.try_into()?)),
-                "HSET" => Ok(Command::HSet(frame.try_into()?)),
-                "HGETALL" => Ok(Command::HGetAll(frame.try_into()?)),
-                "ECHO" => Ok(Command::Echo(frame.try_into()?)),
-                "HMGET" => Ok(Command::Hmget(frame.try_into()?)),
-                "SADD" => Ok(Command::Sadd(frame.try_into()?)),
-                "SMEMBERS" => Ok(Command::Smembers(frame.try_into()?)),
-                "SISMEMBER" => Ok(Command::Sismember(frame.try_into()?)),
-                _ => anyhow::bail!("Invalid command"),
-            },
-            Err(_) => anyhow::bail!("Invalid command"),
-        }
+        let command = match name.to_uppercase().as_str() {
+            "GET" => frame.try_into().map(Command::Get),
+            "SET" => frame.try_into().map(Command::Set),
+            "SETNX" => frame.try_into().map(Command::SetNx),
+            "GETDEL" => frame.try_into().map(Command::GetDel),
+            "GETEX" => frame.try_into().map(Command::GetEx),
+            "MGET" => frame.try_into().map(Command::MGet),
+            "MSET" | "MSETNX" => frame.try_into().map(Command::MSet),
+            "INCR" | "DECR" | "INCRBY" | "DECRBY" => frame.try_into().map(Command::Incr),
+            "INCRBYFLOAT" => frame.try_into().map(Command::IncrByFloat),
+            "APPEND" => frame.try_into().map(Command::Append),
+            "STRLEN" => frame.try_into().map(Command::StrLen),
+            "GETRANGE" => frame.try_into().map(Command::GetRange),
+            "SETRANGE" => frame.try_into().map(Command::SetRange),
+            "SETBIT" => frame.try_into().map(Command::SetBit),
+            "GETBIT" => frame.try_into().map(Command::GetBit),
+            "BITCOUNT" => frame.try_into().map(Command::BitCount),
+            "BITPOS" => frame.try_into().map(Command::BitPos),
+            "BITOP" => frame.try_into().map(Command::BitOp),
+            "BITFIELD" => frame.try_into().map(Command::BitField),
+            "PFADD" => frame.try_into().map(Command::PfAdd),
+            "PFCOUNT" => frame.try_into().map(Command::PfCount),
+            "PFMERGE" => frame.try_into().map(Command::PfMerge),
+            "HGET" => frame.try_into().map(Command::HGet),
+            "HSET" => frame.try_into().map(Command::HSet),
+            "HGETALL" => frame.try_into().map(Command::HGetAll),
+            "HDEL" => frame.try_into().map(Command::HDel),
+            "HEXISTS" => frame.try_into().map(Command::HExists),
+            "HLEN" => frame.try_into().map(Command::HLen),
+            "HKEYS" => frame.try_into().map(Command::HKeys),
+            "HVALS" => frame.try_into().map(Command::HVals),
+            "HINCRBY" => frame.try_into().map(Command::HIncrBy),
+            "HINCRBYFLOAT" => frame.try_into().map(Command::HIncrByFloat),
+            "HSETNX" => frame.try_into().map(Command::HSetNx),
+            "HSTRLEN" => frame.try_into().map(Command::HStrLen),
+            "HRANDFIELD" => frame.try_into().map(Command::HRandField),
+            "ECHO" => frame.try_into().map(Command::Echo),
+            "PING" => frame.try_into().map(Command::Ping),
+            "HMGET" => frame.try_into().map(Command::Hmget),
+            "SADD" => frame.try_into().map(Command::Sadd),
+            "SMEMBERS" => frame.try_into().map(Command::Smembers),
+            "SISMEMBER" => frame.try_into().map(Command::Sismember),
+            "EXPIRE" | "PEXPIRE" | "EXPIREAT" | "PEXPIREAT" => {
+                frame.try_into().map(Command::Expire)
+            }
+            "TTL" | "PTTL" => frame.try_into().map(Command::Ttl),
+            "PERSIST" => frame.try_into().map(Command::Persist),
+            "TYPE" => frame.try_into().map(Command::Type),
+            "EXISTS" => frame.try_into().map(Command::Exists),
+            "DEL" | "UNLINK" => frame.try_into().map(Command::Del),
+            "SAVE" | "BGSAVE" => frame.try_into().map(Command::Save),
+            "LASTSAVE" => frame.try_into().map(Command::LastSave),
+            "BGREWRITEAOF" => frame.try_into().map(Command::BgRewriteAof),
+            "CONFIG" => frame.try_into().map(Command::Config),
+            "AUTH" => frame.try_into().map(Command::Auth),
+            "LPUSH" | "RPUSH" => frame.try_into().map(Command::Push),
+            "LPOP" | "RPOP" => frame.try_into().map(Command::Pop),
+            "LRANGE" => frame.try_into().map(Command::LRange),
+            "LINDEX" => frame.try_into().map(Command::LIndex),
+            "LSET" => frame.try_into().map(Command::LSet),
+            "LREM" => frame.try_into().map(Command::LRem),
+            "LTRIM" => frame.try_into().map(Command::LTrim),
+            "LINSERT" => frame.try_into().map(Command::LInsert),
+            "LLEN" => frame.try_into().map(Command::LLen),
+            "BLPOP" | "BRPOP" => frame.try_into().map(Command::BPop),
+            "LMOVE" | "BLMOVE" => frame.try_into().map(Command::LMove),
+            "LMPOP" | "BLMPOP" => frame.try_into().map(Command::LMPop),
+            "ZADD" => frame.try_into().map(Command::ZAdd),
+            "ZINCRBY" => frame.try_into().map(Command::ZIncrBy),
+            "ZREM" => frame.try_into().map(Command::ZRem),
+            "ZSCORE" => frame.try_into().map(Command::ZScore),
+            "ZCARD" => frame.try_into().map(Command::ZCard),
+            "ZRANGE" => frame.try_into().map(Command::ZRange),
+            "ZRANK" | "ZREVRANK" => frame.try_into().map(Command::ZRank),
+            "ZCOUNT" => frame.try_into().map(Command::ZCount),
+            "ZPOPMIN" | "ZPOPMAX" => frame.try_into().map(Command::ZPop),
+            "SREM" => frame.try_into().map(Command::SRem),
+            "SCARD" => frame.try_into().map(Command::SCard),
+            "SMISMEMBER" => frame.try_into().map(Command::SMIsMember),
+            "SPOP" => frame.try_into().map(Command::SPop),
+            "SRANDMEMBER" => frame.try_into().map(Command::SRandMember),
+            "SMOVE" => frame.try_into().map(Command::SMove),
+            "SINTER" | "SUNION" | "SDIFF" | "SINTERSTORE" | "SUNIONSTORE" | "SDIFFSTORE" => {
+                frame.try_into().map(Command::SetOp)
+            }
+            "SINTERCARD" => frame.try_into().map(Command::SInterCard),
+            "XADD" => frame.try_into().map(Command::XAdd),
+            "XRANGE" | "XREVRANGE" => frame.try_into().map(Command::XRange),
+            "XLEN" => frame.try_into().map(Command::XLen),
+            "XTRIM" => frame.try_into().map(Command::XTrim),
+            "XDEL" => frame.try_into().map(Command::XDel),
+            "XREAD" => frame.try_into().map(Command::XRead),
+            "XSETID" => frame.try_into().map(Command::XSetId),
+            "XGROUP" => frame.try_into().map(Command::XGroup),
+            "XREADGROUP" => frame.try_into().map(Command::XReadGroup),
+            "XACK" => frame.try_into().map(Command::XAck),
+            "XPENDING" => frame.try_into().map(Command::XPending),
+            "XCLAIM" => frame.try_into().map(Command::XClaim),
+            "XAUTOCLAIM" => frame.try_into().map(Command::XAutoClaim),
+            "XINFO" => frame.try_into().map(Command::XInfo),
+            "SUBSCRIBE" | "PSUBSCRIBE" | "SSUBSCRIBE" => frame.try_into().map(Command::Subscribe),
+            "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "SUNSUBSCRIBE" => {
+                frame.try_into().map(Command::Unsubscribe)
+            }
+            "PUBLISH" | "SPUBLISH" => frame.try_into().map(Command::Publish),
+            "PUBSUB" => frame.try_into().map(Command::PubSub),
+            "HELLO" => frame.try_into().map(Command::Hello),
+            "MULTI" => frame.try_into().map(Command::Multi),
+            "EXEC" => frame.try_into().map(Command::Exec),
+            "DISCARD" => frame.try_into().map(Command::Discard),
+            "WATCH" => frame.try_into().map(Command::Watch),
+            "UNWATCH" => frame.try_into().map(Command::Unwatch),
+            "EVAL" | "EVALSHA" => frame.try_into().map(Command::Eval),
+            "SCRIPT" => frame.try_into().map(Command::Script),
+            _ => {
+                let mut args = String::new();
+                parse.next()?;
+                while let Ok(arg) = parse.next_string() {
+                    args.push_str(&format!("'{}' ", arg));
+                }
+                return Err(CommandError::UnknownCommand(name, args).into());
+            }
+        };
+
+        command.map_err(|e| match e.downcast_ref::<ParseError>() {
+            Some(ParseError::EndOfParts) | Some(ParseError::NotFinished) => {
+                CommandError::WrongNumberOfArguments(name.to_lowercase()).into()
+            }
+            Some(ParseError::NotInteger(_)) => CommandError::NotInteger.into(),
+            _ => e,
+        })
     }
 }
 
@@ -72,6 +530,7 @@ impl TryFrom<Frame> for Command {
 mod tests {
     use super::*;
     use crate::resp::frame::Frame;
+    use crate::resp::RespEncode;
     use std::convert::TryInto;
 
     #[test]
@@ -79,9 +538,7 @@ mod tests {
         let frame: Frame = vec!["get".into(), "key".into()].into();
 
         let actual: Command = frame.try_into().unwrap();
-        let expected = get::Get {
-            key: "key".to_string(),
-        };
+        let expected = get::Get { key: "key".into() };
 
         match actual {
             Command::Get(actual) => {
@@ -91,6 +548,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_try_from_unknown_command() {
+        let frame: Frame = vec![b"foo".into(), b"bar".into()].into();
+        let result = Command::try_from(frame).unwrap_err();
+
+        assert_eq!(
+            result.to_string(),
+            "ERR unknown command 'foo', with args beginning with: 'bar' "
+        );
+    }
+
+    #[test]
+    fn test_command_try_from_wrong_number_of_arguments() {
+        let frame: Frame = vec![b"set".into(), b"key".into()].into();
+        let result = Command::try_from(frame).unwrap_err();
+        assert_eq!(
+            result.to_string(),
+            "ERR wrong number of arguments for 'set' command"
+        );
+
+        let frame: Frame = vec![b"hmget".into(), b"key".into()].into();
+        let result = Command::try_from(frame).unwrap_err();
+        assert_eq!(
+            result.to_string(),
+            "ERR wrong number of arguments for 'hmget' command"
+        );
+    }
+
     #[test]
     fn test_command_get_and_set() {
         let backend = Backend::new();
@@ -111,6 +596,22 @@ mod tests {
         assert_eq!(result, b"value".into());
     }
 
+    #[test]
+    fn test_command_get_empty_string() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"set".into(), b"key".into(), b"".into()].into();
+        let set_command: Command = frame.try_into().unwrap();
+        set_command.execute(backend.clone()).unwrap();
+
+        let frame: Frame = vec![b"get".into(), b"key".into()].into();
+        let get_command: Command = frame.try_into().unwrap();
+
+        let result = get_command.execute(backend.clone()).unwrap();
+        assert_eq!(result, b"".into());
+        assert_eq!(result.encode(), b"$0\r\n\r\n");
+    }
+
     #[test]
     fn test_command_hget_and_hset() {
         let backend = Backend::new();
@@ -137,6 +638,24 @@ mod tests {
         assert_eq!(result, b"value".into());
     }
 
+    #[test]
+    fn test_command_wrong_type() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"sadd".into(), b"key".into(), b"member".into()].into();
+        let sadd_command: Command = frame.try_into().unwrap();
+        sadd_command.execute(backend.clone()).unwrap();
+
+        let frame: Frame = vec![b"get".into(), b"key".into()].into();
+        let get_command: Command = frame.try_into().unwrap();
+        let result = get_command.execute(backend.clone()).unwrap_err();
+
+        assert_eq!(
+            result.to_string(),
+            "WRONGTYPE Operation against a key holding the wrong kind of value"
+        );
+    }
+
     #[test]
     fn test_command_hgetall() {
         let backend = Backend::new();
@@ -169,13 +688,13 @@ mod tests {
 
         let result = hgetall_command.execute(backend.clone()).unwrap();
 
-        match result {
+        match result.into_resp2() {
             Frame::Array(array) => {
                 assert_eq!(array.len(), 4);
-                // assert_eq!(array[0], b"field1".into());
-                // assert_eq!(array[1], b"value1".into());
-                // assert_eq!(array[2], b"field2".into());
-                // assert_eq!(array[3], b"value2".into());
+                assert_eq!(array[0], b"field1".into());
+                assert_eq!(array[1], b"value1".into());
+                assert_eq!(array[2], b"field2".into());
+                assert_eq!(array[3], b"value2".into());
             }
             _ => panic!("Expected Array"),
         }
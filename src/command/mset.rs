@@ -0,0 +1,111 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::{CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles MSET and MSETNX, which sets nothing when any of the keys exists.
+#[derive(Debug)]
+pub struct MSet {
+    pairs: Vec<(Bytes, Bytes)>,
+    nx: bool,
+}
+
+impl CommandExecute for MSet {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        if !self.nx {
+            backend.mset(&self.pairs);
+            return Ok(OK.clone());
+        }
+
+        match backend.msetnx(&self.pairs) {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for MSet {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let nx = match command.as_str() {
+            "MSET" => false,
+            "MSETNX" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        // keys and values come in pairs, at least one of them
+        if parse.len() == 0 || parse.len() % 2 != 0 {
+            return Err(ParseError::EndOfParts.into());
+        }
+
+        let mut pairs = Vec::with_capacity(parse.len() / 2);
+        while parse.len() > 0 {
+            pairs.push((parse.next_bytes()?, parse.next_bytes()?));
+        }
+
+        Ok(Self { pairs, nx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mset_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![
+            b"mset".into(),
+            b"a".into(),
+            b"1".into(),
+            b"b".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: MSet = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.get(b"b").unwrap(), Some("2".into()));
+
+        let frame: Frame = vec![b"mset".into(), b"a".into()].into();
+        assert!(MSet::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_msetnx_execute() {
+        let backend = Backend::new();
+        backend.set(b"b", "old".into());
+
+        let frame: Frame = vec![
+            b"msetnx".into(),
+            b"a".into(),
+            b"1".into(),
+            b"b".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: MSet = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+        assert!(!backend.exists(b"a"));
+
+        backend.remove(b"b");
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.get(b"a").unwrap(), Some("1".into()));
+    }
+}
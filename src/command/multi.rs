@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::network::{Session, Transaction};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Multi;
+
+impl Multi {
+    // Commands are queued from now on, until EXEC or DISCARD.
+    pub fn multi(&self, session: &mut Session) -> Result<Frame> {
+        if session.transaction.is_some() {
+            return Err(
+                CommandError::InvalidArgument("MULTI calls can not be nested".to_string()).into(),
+            );
+        }
+
+        session.transaction = Some(Transaction::default());
+        Ok(OK.clone())
+    }
+}
+
+impl CommandExecute for Multi {
+    // Transactions belong to the connection, which runs `multi` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("MULTI is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Multi {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "MULTI" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let frame: Frame = vec![b"multi".into()].into();
+        let cmd: Multi = frame.try_into().unwrap();
+        assert_eq!(cmd.multi(&mut session).unwrap(), *OK);
+        assert!(session.transaction.is_some());
+
+        assert_eq!(
+            cmd.multi(&mut session).unwrap_err().to_string(),
+            "ERR MULTI calls can not be nested"
+        );
+    }
+}
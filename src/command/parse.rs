@@ -1,5 +1,6 @@
 use crate::resp::frame::Frame;
 use anyhow::Result;
+use bytes::Bytes;
 use std::ops::Deref;
 use std::vec::IntoIter;
 use thiserror::Error;
@@ -15,6 +16,9 @@ pub enum ParseError {
     #[error("Not finished")]
     NotFinished,
 
+    #[error("Not an integer: {0}")]
+    NotInteger(String),
+
     #[error("From utf8 error: {0}")]
     FromUtf8Error(#[from] std::string::FromUtf8Error),
 }
@@ -58,15 +62,26 @@ impl Parse {
         self.parts.next().ok_or(ParseError::EndOfParts)
     }
 
-    pub fn next_string(&mut self) -> Result<String, ParseError> {
+    // Binary safe arguments, large ones sliced from the request buffer.
+    pub fn next_bytes(&mut self) -> Result<Bytes, ParseError> {
         let frame = self.next()?;
         match frame {
             Frame::SimpleString(s) => Ok(s.inner),
-            Frame::BulkString(s) => Ok(String::from_utf8(s.inner)?),
+            Frame::BulkString(s) => Ok(s.inner),
             _ => Err(ParseError::InvalidType(format!("for string {:?}", frame))),
         }
     }
 
+    pub fn next_string(&mut self) -> Result<String, ParseError> {
+        let bytes = self.next_bytes()?;
+        Ok(String::from_utf8(bytes.to_vec())?)
+    }
+
+    pub fn next_int(&mut self) -> Result<i64, ParseError> {
+        let s = self.next_string()?;
+        s.parse().map_err(|_| ParseError::NotInteger(s))
+    }
+
     pub fn finish(&mut self) -> Result<(), ParseError> {
         if self.parts.next().is_none() {
             Ok(())
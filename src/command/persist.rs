@@ -0,0 +1,71 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Persist {
+    key: Bytes,
+}
+
+impl CommandExecute for Persist {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.persist(&self.key) {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Persist {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PERSIST" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::now_millis;
+
+    #[test]
+    fn test_persist_try_from_frame() {
+        let frame: Frame = vec![b"persist".into(), b"key".into()].into();
+        let cmd: Persist = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "key");
+    }
+
+    #[test]
+    fn test_persist_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"persist".into(), b"key".into()].into();
+        let cmd: Persist = frame.try_into().unwrap();
+
+        backend.set(b"key", "value".into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        backend.expire_at(b"key", now_millis() + 10_000, &[]);
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.pttl(b"key"), Some(None));
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct PfAdd {
+    key: Bytes,
+    elements: Vec<Bytes>,
+}
+
+impl CommandExecute for PfAdd {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.pfadd(&self.key, &self.elements)? {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for PfAdd {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PFADD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut elements = vec![];
+        while parse.len() > 0 {
+            elements.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, elements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pfadd_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"pfadd".into(), b"hll".into(), b"a".into(), b"b".into()].into();
+        let cmd: PfAdd = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+        assert!(backend.get(b"hll").unwrap().unwrap().starts_with(b"HYLL"));
+
+        backend.set(b"string", "value".into());
+        let frame: Frame = vec![b"pfadd".into(), b"string".into(), b"a".into()].into();
+        let cmd: PfAdd = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "WRONGTYPE Key is not a valid HyperLogLog string value."
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct PfCount {
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for PfCount {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let count = backend.pfcount(&self.keys)?;
+        Ok((count as i64).into())
+    }
+
+    // caching the count writes to the key
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for PfCount {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PFCOUNT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { keys })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pfcount_execute() {
+        let backend = Backend::new();
+        backend
+            .pfadd(b"a", &["x".into(), "y".into(), "z".into()])
+            .unwrap();
+        backend.pfadd(b"b", &["z".into(), "w".into()]).unwrap();
+
+        let frame: Frame = vec![b"pfcount".into(), b"a".into()].into();
+        let cmd: PfCount = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.into());
+
+        let frame: Frame = vec![b"pfcount".into(), b"a".into(), b"b".into(), b"c".into()].into();
+        let cmd: PfCount = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 4.into());
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct PfMerge {
+    destination: Bytes,
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for PfMerge {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        backend.pfmerge(&self.destination, &self.keys)?;
+        Ok(OK.clone())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+impl TryFrom<Frame> for PfMerge {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PFMERGE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let destination = parse.next_bytes()?;
+        let mut keys = vec![];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { destination, keys })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pfmerge_execute() {
+        let backend = Backend::new();
+        backend.pfadd(b"a", &["x".into(), "y".into()]).unwrap();
+        backend.pfadd(b"b", &["y".into(), "z".into()]).unwrap();
+        backend.pfadd(b"dest", &["w".into()]).unwrap();
+
+        let frame: Frame = vec![b"pfmerge".into(), b"dest".into(), b"a".into(), b"b".into()].into();
+        let cmd: PfMerge = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.pfcount(&["dest".into()]).unwrap(), 4);
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Ping {
+    message: Option<Bytes>,
+}
+
+impl Ping {
+    // RESP2 clients in subscribe mode can't tell a reply from a message, so
+    // PING replies the way messages look instead.
+    pub fn pong_subscribed(&self) -> Frame {
+        let message = self.message.clone().unwrap_or_default();
+        vec![b"pong".into(), message.into()].into()
+    }
+}
+
+impl CommandExecute for Ping {
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        match &self.message {
+            Some(message) => Ok(message.clone().into()),
+            None => Ok("PONG".into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for Ping {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PING" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let message = match parse.length() {
+            1 => None,
+            _ => Some(parse.next_bytes()?),
+        };
+        parse.finish()?;
+
+        Ok(Self { message })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ping_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"ping".into()].into();
+        let cmd: Ping = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), "PONG".into());
+        assert_eq!(
+            cmd.pong_subscribed(),
+            vec![b"pong".into(), b"".into()].into()
+        );
+
+        let frame: Frame = vec![b"PING".into(), b"hello".into()].into();
+        let cmd: Ping = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), b"hello".into());
+        assert_eq!(
+            cmd.pong_subscribed(),
+            vec![b"pong".into(), b"hello".into()].into()
+        );
+
+        let frame: Frame = vec![b"ping".into(), b"a".into(), b"b".into()].into();
+        let result: Result<Ping> = frame.try_into();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL, NULL_ARRAY};
+use crate::backend::{Backend, ListEnd};
+use crate::resp::frame::Frame;
+
+// Handles LPOP and RPOP. Without a count a single element is replied, with
+// one an array of up to count elements.
+#[derive(Debug)]
+pub struct Pop {
+    key: Bytes,
+    end: ListEnd,
+    count: Option<usize>,
+}
+
+impl CommandExecute for Pop {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let popped = backend.pop(&self.key, self.end, self.count.unwrap_or(1))?;
+
+        let frame = match (popped, self.count) {
+            (None, None) => NULL.clone(),
+            (None, Some(_)) => NULL_ARRAY.clone(),
+            (Some(mut elements), None) => elements.pop().map_or_else(|| NULL.clone(), Frame::from),
+            (Some(elements), Some(_)) => elements
+                .into_iter()
+                .map(Frame::from)
+                .collect::<Vec<_>>()
+                .into(),
+        };
+
+        Ok(frame)
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Pop {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let end = match command.as_str() {
+            "LPOP" => ListEnd::Left,
+            "RPOP" => ListEnd::Right,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+
+        let count = match parse.len() {
+            0 => None,
+            _ => {
+                let count = parse.next_int()?;
+                if count < 0 {
+                    return Err(CommandError::InvalidArgument(
+                        "value is out of range, must be positive".to_string(),
+                    )
+                    .into());
+                }
+                Some(count as usize)
+            }
+        };
+        parse.finish()?;
+
+        Ok(Self { key, end, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pop_try_from_frame() {
+        let frame: Frame = vec![b"rpop".into(), b"list".into(), b"2".into()].into();
+        let cmd: Pop = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "list");
+        assert_eq!(cmd.end, ListEnd::Right);
+        assert_eq!(cmd.count, Some(2));
+
+        let frame: Frame = vec![b"lpop".into(), b"list".into(), b"-1".into()].into();
+        assert!(Pop::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_pop_execute() {
+        let backend = Backend::new();
+        backend
+            .push(
+                b"list",
+                ListEnd::Right,
+                vec!["a".into(), "b".into(), "c".into()],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"lpop".into(), b"list".into()].into();
+        let cmd: Pop = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"a".into());
+
+        let frame: Frame = vec![b"rpop".into(), b"list".into(), b"5".into()].into();
+        let cmd: Pop = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"c".into(), b"b".into()].into()
+        );
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL_ARRAY);
+        let frame: Frame = vec![b"lpop".into(), b"list".into()].into();
+        let cmd: Pop = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Messages are not part of the dataset, they are never appended to the AOF.
+#[derive(Debug)]
+pub struct Publish {
+    channel: Bytes,
+    message: Bytes,
+    // SPUBLISH, to a shard channel
+    shard: bool,
+}
+
+impl CommandExecute for Publish {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let receivers = match self.shard {
+            true => backend.spublish(&self.channel, &self.message),
+            false => backend.publish(&self.channel, &self.message),
+        };
+        Ok((receivers as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for Publish {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let shard = match command.as_str() {
+            "PUBLISH" => false,
+            "SPUBLISH" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let channel = parse.next_bytes()?;
+        let message = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            channel,
+            message,
+            shard,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{ChannelKind, Subscriber};
+
+    #[test]
+    fn test_publish_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"publish".into(), b"news".into(), b"hello".into()].into();
+        let cmd: Publish = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Pattern, "n*".into());
+        assert_eq!(cmd.execute(backend).unwrap(), 1.into());
+        assert!(subscriber.try_message().is_some());
+    }
+
+    #[test]
+    fn test_spublish_execute() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Channel, "news".into());
+
+        let frame: Frame = vec![b"spublish".into(), b"news".into(), b"hello".into()].into();
+        let cmd: Publish = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        subscriber.subscribe(ChannelKind::Shard, "news".into());
+        assert_eq!(cmd.execute(backend).unwrap(), 1.into());
+    }
+}
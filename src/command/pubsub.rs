@@ -0,0 +1,166 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, ChannelKind};
+use crate::resp::frame::Frame;
+
+// CHANNELS and NUMSUB list classic channels, or shard channels for their
+// SHARD variants.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Channels(ChannelKind, Option<Bytes>),
+    NumSub(ChannelKind, Vec<Bytes>),
+    NumPat,
+}
+
+#[derive(Debug)]
+pub struct PubSub {
+    subcommand: Subcommand,
+}
+
+impl CommandExecute for PubSub {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match &self.subcommand {
+            Subcommand::Channels(kind, pattern) => Ok(backend
+                .pubsub_channels(*kind, pattern.as_deref())
+                .into_iter()
+                .map(Frame::from)
+                .collect::<Vec<_>>()
+                .into()),
+            // channels and counts flattened, in the order they were asked
+            Subcommand::NumSub(kind, channels) => Ok(channels
+                .iter()
+                .flat_map(|channel| {
+                    let count = backend.pubsub_numsub(*kind, channel) as i64;
+                    [channel.clone().into(), count.into()]
+                })
+                .collect::<Vec<_>>()
+                .into()),
+            Subcommand::NumPat => Ok((backend.pubsub_numpat() as i64).into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for PubSub {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "PUBSUB" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let name = parse.next_string()?;
+        let wrong_arity =
+            || CommandError::WrongNumberOfArguments(format!("pubsub|{}", name.to_lowercase()));
+
+        let subcommand = match name.to_uppercase().as_str() {
+            name @ ("CHANNELS" | "SHARDCHANNELS") => {
+                let kind = match name {
+                    "CHANNELS" => ChannelKind::Channel,
+                    _ => ChannelKind::Shard,
+                };
+
+                match parse.len() {
+                    0 => Subcommand::Channels(kind, None),
+                    1 => Subcommand::Channels(kind, Some(parse.next_bytes()?)),
+                    _ => return Err(wrong_arity().into()),
+                }
+            }
+            name @ ("NUMSUB" | "SHARDNUMSUB") => {
+                let kind = match name {
+                    "NUMSUB" => ChannelKind::Channel,
+                    _ => ChannelKind::Shard,
+                };
+
+                let mut channels = Vec::new();
+                while parse.len() > 0 {
+                    channels.push(parse.next_bytes()?);
+                }
+                Subcommand::NumSub(kind, channels)
+            }
+            "NUMPAT" => match parse.len() {
+                0 => Subcommand::NumPat,
+                _ => return Err(wrong_arity().into()),
+            },
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try PUBSUB HELP.",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        Ok(Self { subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{ChannelKind, Subscriber};
+
+    fn pubsub(args: &[&str]) -> Result<PubSub> {
+        let mut frame = vec![b"pubsub".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_pubsub_try_from_frame() {
+        let cmd = pubsub(&["channels", "a*"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::Channels(ChannelKind::Channel, Some("a*".into()))
+        );
+
+        let cmd = pubsub(&["shardnumsub", "a"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::NumSub(ChannelKind::Shard, vec!["a".into()])
+        );
+
+        assert_eq!(
+            pubsub(&["numpat", "x"]).unwrap_err().to_string(),
+            "ERR wrong number of arguments for 'pubsub|numpat' command"
+        );
+        assert_eq!(
+            pubsub(&["nope"]).unwrap_err().to_string(),
+            "ERR unknown subcommand 'nope'. Try PUBSUB HELP."
+        );
+    }
+
+    #[test]
+    fn test_pubsub_execute() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Channel, "ab".into());
+        subscriber.subscribe(ChannelKind::Channel, "cd".into());
+        subscriber.subscribe(ChannelKind::Pattern, "a*".into());
+        subscriber.subscribe(ChannelKind::Shard, "cd".into());
+
+        let reply = pubsub(&["channels", "a*"])
+            .unwrap()
+            .execute(backend.clone());
+        assert_eq!(reply.unwrap(), vec![Frame::from(b"ab")].into());
+
+        let reply = pubsub(&["numsub", "cd", "x"])
+            .unwrap()
+            .execute(backend.clone());
+        assert_eq!(
+            reply.unwrap(),
+            vec![b"cd".into(), 1.into(), b"x".into(), 0.into()].into()
+        );
+
+        let reply = pubsub(&["shardchannels"]).unwrap().execute(backend.clone());
+        assert_eq!(reply.unwrap(), vec![Frame::from(b"cd")].into());
+
+        let reply = pubsub(&["numpat"]).unwrap().execute(backend);
+        assert_eq!(reply.unwrap(), 1.into());
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, ListEnd};
+use crate::resp::frame::Frame;
+
+// Handles LPUSH and RPUSH.
+#[derive(Debug)]
+pub struct Push {
+    key: Bytes,
+    end: ListEnd,
+    elements: Vec<Bytes>,
+}
+
+impl CommandExecute for Push {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.push(&self.key, self.end, self.elements.clone())?;
+        Ok((len as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for Push {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let end = match command.as_str() {
+            "LPUSH" => ListEnd::Left,
+            "RPUSH" => ListEnd::Right,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+        let mut elements = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            elements.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, end, elements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_try_from_frame() {
+        let frame: Frame = vec![b"rpush".into(), b"list".into(), b"a".into(), b"b".into()].into();
+        let cmd: Push = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "list");
+        assert_eq!(cmd.end, ListEnd::Right);
+        assert_eq!(cmd.elements, vec!["a", "b"]);
+
+        let frame: Frame = vec![b"lpush".into(), b"list".into()].into();
+        assert!(Push::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_push_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"lpush".into(), b"list".into(), b"a".into(), b"b".into()].into();
+        let cmd: Push = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+        assert_eq!(backend.lrange(b"list", 0, -1).unwrap(), vec!["b", "a"]);
+
+        backend.set(b"string", "value".into());
+        let frame: Frame = vec![b"rpush".into(), b"string".into(), b"a".into()].into();
+        let cmd: Push = frame.try_into().unwrap();
+        assert!(cmd.execute(backend).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::parse::Parse;
 use super::CommandExecute;
@@ -7,16 +8,18 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct Sadd {
-    pub(crate) key: String,
-    pub(crate) field: String,
+    pub(crate) key: Bytes,
+    pub(crate) members: Vec<Bytes>,
 }
 
 impl CommandExecute for Sadd {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        match backend.sadd(&self.key, &self.field) {
-            true => Ok(1.into()),
-            false => Ok(0.into()),
-        }
+        let added = backend.sadd(&self.key, self.members.clone())?;
+        Ok((added as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
     }
 }
 
@@ -31,11 +34,13 @@ impl TryFrom<Frame> for Sadd {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
-        let field = parse.next_string()?;
-        parse.finish()?;
+        let key = parse.next_bytes()?;
+        let mut members = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            members.push(parse.next_bytes()?);
+        }
 
-        Ok(Self { key, field })
+        Ok(Self { key, members })
     }
 }
 
@@ -47,7 +52,7 @@ mod tests {
     use std::io::Cursor;
 
     fn parse_cmd(input: &[u8]) -> Result<Sadd> {
-        let mut buf = Cursor::new(input);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         Sadd::try_from(frame)
     }
@@ -58,7 +63,7 @@ mod tests {
         let cmd = parse_cmd(input).unwrap();
 
         assert_eq!(cmd.key, "key");
-        assert_eq!(cmd.field, "value");
+        assert_eq!(cmd.members, vec!["value"]);
     }
 
     #[test]
@@ -75,8 +80,12 @@ mod tests {
         let cmd = parse_cmd(input).unwrap();
 
         let backend = Backend::new();
-        let result = cmd.execute(backend);
+        let result = cmd.execute(backend.clone());
 
         assert_eq!(result.unwrap(), 1.into());
+
+        let input = b"*4\r\n$4\r\nSADD\r\n$3\r\nkey\r\n$5\r\nvalue\r\n$5\r\nother\r\n";
+        let cmd = parse_cmd(input).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 1.into());
     }
 }
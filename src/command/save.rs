@@ -0,0 +1,103 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::persistence;
+use crate::resp::frame::Frame;
+
+// Handles SAVE and BGSAVE.
+#[derive(Debug)]
+pub struct Save {
+    background: bool,
+}
+
+impl CommandExecute for Save {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        if !self.background {
+            if backend.saving.load(std::sync::atomic::Ordering::Acquire) {
+                return Err(CommandError::InvalidArgument(
+                    "Background save already in progress".to_string(),
+                )
+                .into());
+            }
+
+            persistence::save(&backend)?;
+            return Ok(OK.clone());
+        }
+
+        match persistence::bgsave(&backend) {
+            true => Ok("Background saving started".into()),
+            false => Err(CommandError::InvalidArgument(
+                "Background save already in progress".to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+impl TryFrom<Frame> for Save {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let background = match command.as_str() {
+            "SAVE" => false,
+            "BGSAVE" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        // BGSAVE SCHEDULE is accepted, a save never waits on a rewrite here
+        if background && parse.len() > 0 && parse.next_string()?.to_uppercase() != "SCHEDULE" {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        parse.finish()?;
+
+        Ok(Self { background })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+
+    #[test]
+    fn test_save_try_from_frame() {
+        let frame: Frame = vec![b"bgsave".into(), b"schedule".into()].into();
+        let cmd: Save = frame.try_into().unwrap();
+        assert!(cmd.background);
+
+        let frame: Frame = vec![b"save".into(), b"now".into()].into();
+        let result: Result<Save> = frame.try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_save_execute() {
+        let backend = Backend::new();
+        let dir = std::env::temp_dir();
+        let dbfilename = format!("simple-redis-save-{}.rdb", std::process::id());
+
+        backend.set_config(ServerConfig {
+            dir: dir.clone(),
+            dbfilename: dbfilename.clone(),
+            save: vec![],
+            ..Default::default()
+        });
+        backend.set(b"key", "value".into());
+
+        let frame: Frame = vec![b"save".into()].into();
+        let cmd: Save = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(backend.dirty.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+        let path = dir.join(dbfilename);
+        assert!(path.exists());
+        std::fs::remove_file(path).unwrap();
+    }
+}
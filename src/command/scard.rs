@@ -0,0 +1,53 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SCard {
+    key: Bytes,
+}
+
+impl CommandExecute for SCard {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.scard(&self.key)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for SCard {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SCARD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scard_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"scard".into(), b"set".into()].into();
+        let cmd: SCard = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        backend.sadd(b"set", vec!["a".into(), "b".into()]).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 2.into());
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Load(Bytes),
+    Exists(Vec<String>),
+    Flush,
+    Kill,
+}
+
+#[derive(Debug)]
+pub struct Script {
+    subcommand: Subcommand,
+}
+
+impl Script {
+    pub fn is_kill(&self) -> bool {
+        self.subcommand == Subcommand::Kill
+    }
+
+    // SCRIPT KILL can't wait for the keyspace lock the script holds, the
+    // request runs it before taking the lock.
+    pub fn kill(&self, backend: &Backend) -> Result<Frame> {
+        backend.running_script().kill()?;
+        Ok(OK.clone())
+    }
+}
+
+impl CommandExecute for Script {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match &self.subcommand {
+            Subcommand::Load(body) => {
+                let sha = backend.scripting().load(body)?;
+                Ok(sha.as_bytes().into())
+            }
+            Subcommand::Exists(shas) => {
+                let scripting = backend.scripting();
+                Ok(shas
+                    .iter()
+                    .map(|sha| Frame::from(scripting.exists(sha) as i64))
+                    .collect::<Vec<_>>()
+                    .into())
+            }
+            Subcommand::Flush => {
+                backend.scripting().flush();
+                Ok(OK.clone())
+            }
+            Subcommand::Kill => self.kill(&backend),
+        }
+    }
+}
+
+impl TryFrom<Frame> for Script {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SCRIPT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let name = parse.next_string()?;
+        let wrong_arity =
+            || CommandError::WrongNumberOfArguments(format!("script|{}", name.to_lowercase()));
+
+        let subcommand = match name.to_uppercase().as_str() {
+            "LOAD" => match parse.len() {
+                1 => Subcommand::Load(parse.next_bytes()?),
+                _ => return Err(wrong_arity().into()),
+            },
+            "EXISTS" => {
+                if parse.len() == 0 {
+                    return Err(wrong_arity().into());
+                }
+
+                let mut shas = Vec::new();
+                while parse.len() > 0 {
+                    shas.push(parse.next_string()?);
+                }
+                Subcommand::Exists(shas)
+            }
+            // scripts are flushed right away either way
+            "FLUSH" => match parse.len() {
+                0 => Subcommand::Flush,
+                1 => match parse.next_string()?.to_uppercase().as_str() {
+                    "ASYNC" | "SYNC" => Subcommand::Flush,
+                    _ => return Err(CommandError::SyntaxError.into()),
+                },
+                _ => return Err(wrong_arity().into()),
+            },
+            "KILL" => match parse.len() {
+                0 => Subcommand::Kill,
+                _ => return Err(wrong_arity().into()),
+            },
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try SCRIPT HELP.",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        Ok(Self { subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripting;
+
+    fn script(args: &[&str]) -> Result<Script> {
+        let mut frame = vec![b"script".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_script_try_from_frame() {
+        let cmd = script(&["exists", "a", "b"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::Exists(vec!["a".to_string(), "b".to_string()])
+        );
+
+        assert_eq!(
+            script(&["flush", "async"]).unwrap().subcommand,
+            Subcommand::Flush
+        );
+        assert!(script(&["kill"]).unwrap().is_kill());
+        assert_eq!(
+            script(&["load"]).unwrap_err().to_string(),
+            "ERR wrong number of arguments for 'script|load' command"
+        );
+        assert_eq!(
+            script(&["nope"]).unwrap_err().to_string(),
+            "ERR unknown subcommand 'nope'. Try SCRIPT HELP."
+        );
+    }
+
+    #[test]
+    fn test_script_execute() {
+        let backend = Backend::new();
+        let sha = scripting::sha1_hex(b"return 1");
+
+        let reply = script(&["load", "return 1"])
+            .unwrap()
+            .execute(backend.clone());
+        assert_eq!(reply.unwrap(), sha.as_bytes().into());
+
+        let reply = script(&["exists", &sha, "nope"])
+            .unwrap()
+            .execute(backend.clone());
+        assert_eq!(reply.unwrap(), vec![1.into(), 0.into()].into());
+
+        assert!(script(&["load", "return +"])
+            .unwrap()
+            .execute(backend.clone())
+            .is_err());
+
+        script(&["flush"])
+            .unwrap()
+            .execute(backend.clone())
+            .unwrap();
+        assert!(!backend.scripting().exists(&sha));
+
+        assert_eq!(
+            script(&["kill"])
+                .unwrap()
+                .execute(backend.clone())
+                .unwrap_err()
+                .to_string(),
+            "NOTBUSY No scripts in execution right now."
+        );
+    }
+}
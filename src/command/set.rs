@@ -1,19 +1,97 @@
 use anyhow::Result;
+use bytes::Bytes;
 
-use super::{parse::Parse, CommandExecute, OK};
-use crate::backend::Backend;
+use super::{parse::Parse, CommandError, CommandExecute, NULL, OK};
+use crate::backend::{now_millis, Backend, SetCondition, SetExpiry};
 use crate::resp::frame::Frame;
 
+#[derive(Debug, PartialEq)]
+enum Expiration {
+    Ex(i64),
+    Px(i64),
+    ExAt(i64),
+    PxAt(i64),
+    KeepTtl,
+}
+
 #[derive(Debug)]
 pub struct Set {
-    key: String,
-    value: Frame,
+    key: Bytes,
+    value: Bytes,
+    condition: SetCondition,
+    expiration: Option<Expiration>,
+    // the expiration as a deadline, made absolute once when parsed so the
+    // key and the AOF get the same one
+    expiry: SetExpiry,
+    get: bool,
+}
+
+impl Expiration {
+    fn expiry(expiration: &Option<Self>) -> Result<SetExpiry> {
+        let at = match *expiration {
+            None => return Ok(SetExpiry::Clear),
+            Some(Expiration::KeepTtl) => return Ok(SetExpiry::Keep),
+            Some(Expiration::Ex(seconds)) => seconds
+                .checked_mul(1000)
+                .and_then(|ms| ms.checked_add(now_millis() as i64)),
+            Some(Expiration::Px(ms)) => ms.checked_add(now_millis() as i64),
+            Some(Expiration::ExAt(seconds)) => seconds.checked_mul(1000),
+            Some(Expiration::PxAt(ms)) => Some(ms),
+        };
+
+        match at {
+            Some(at) => Ok(SetExpiry::At(at as u64)),
+            None => Err(CommandError::InvalidExpireTime("set".to_string()).into()),
+        }
+    }
 }
 
 impl CommandExecute for Set {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        backend.set(self.key.clone(), self.value.clone());
-        Ok(OK.clone())
+        let expiry = self.expiry;
+
+        // SET .. GET must not overwrite a value it cannot return
+        if self.get && backend.key_type(&self.key).is_some_and(|t| t != "string") {
+            return Err(CommandError::WrongType.into());
+        }
+
+        let (written, previous) =
+            backend.set_with(&self.key, self.value.clone(), self.condition, expiry)?;
+
+        if self.get {
+            return Ok(previous.map_or_else(|| NULL.clone(), Frame::from));
+        }
+
+        match written {
+            true => Ok(OK.clone()),
+            false => Ok(NULL.clone()),
+        }
+    }
+
+    // Relative expiration times are logged as absolute ones, so replaying the
+    // AOF later does not extend them.
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        let at = match (&self.expiration, self.expiry) {
+            (Some(Expiration::Ex(_)) | Some(Expiration::Px(_)), SetExpiry::At(at)) => at,
+            _ => return Some(frame.clone()),
+        };
+
+        let mut command = vec![
+            b"SET".into(),
+            self.key.clone().into(),
+            self.value.clone().into(),
+        ];
+
+        match self.condition {
+            SetCondition::IfNotExists => command.push(b"NX".into()),
+            SetCondition::IfExists => command.push(b"XX".into()),
+            SetCondition::Always => {}
+        }
+
+        command.push(b"PXAT".into());
+        command.push(at.to_string().as_bytes().into());
+
+        Some(command.into())
     }
 }
 
@@ -28,11 +106,51 @@ impl TryFrom<Frame> for Set {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
-        let value = parse.next()?;
-        parse.finish()?;
+        let key = parse.next_bytes()?;
+        let value = parse.next_bytes()?;
+
+        let mut condition = SetCondition::Always;
+        let mut expiration = None;
+        let mut get = false;
+
+        while parse.len() > 0 {
+            let option = parse.next_string()?.to_uppercase();
+
+            match option.as_str() {
+                "NX" if condition == SetCondition::Always => {
+                    condition = SetCondition::IfNotExists;
+                }
+                "XX" if condition == SetCondition::Always => {
+                    condition = SetCondition::IfExists;
+                }
+                "GET" => get = true,
+                "KEEPTTL" if expiration.is_none() => expiration = Some(Expiration::KeepTtl),
+                "EX" | "PX" | "EXAT" | "PXAT" if expiration.is_none() => {
+                    let time = parse.next_int()?;
+
+                    if time <= 0 {
+                        return Err(CommandError::InvalidExpireTime("set".to_string()).into());
+                    }
 
-        Ok(Self { key, value })
+                    expiration = Some(match option.as_str() {
+                        "EX" => Expiration::Ex(time),
+                        "PX" => Expiration::Px(time),
+                        "EXAT" => Expiration::ExAt(time),
+                        _ => Expiration::PxAt(time),
+                    });
+                }
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        Ok(Self {
+            key,
+            value,
+            condition,
+            expiry: Expiration::expiry(&expiration)?,
+            expiration,
+            get,
+        })
     }
 }
 
@@ -47,13 +165,72 @@ mod tests {
         let frame: Frame = vec!["set".into(), "key".into(), "value".into()].into();
 
         let actual: Set = frame.try_into().unwrap();
-        let expected = Set {
-            key: "key".to_string(),
-            value: "value".into(),
-        };
 
-        assert_eq!(actual.key, expected.key);
-        assert_eq!(actual.value, expected.value);
+        assert_eq!(actual.key, "key");
+        assert_eq!(actual.value, "value");
+        assert_eq!(actual.condition, SetCondition::Always);
+        assert_eq!(actual.expiration, None);
+        assert!(!actual.get);
+    }
+
+    #[test]
+    fn test_set_try_from_frame_with_options() {
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"value".into(),
+            b"nx".into(),
+            b"px".into(),
+            b"1000".into(),
+            b"get".into(),
+        ]
+        .into();
+
+        let actual: Set = frame.try_into().unwrap();
+
+        assert_eq!(actual.condition, SetCondition::IfNotExists);
+        assert_eq!(actual.expiration, Some(Expiration::Px(1000)));
+        assert!(actual.get);
+    }
+
+    #[test]
+    fn test_set_try_from_frame_invalid_options() {
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"value".into(),
+            b"nx".into(),
+            b"xx".into(),
+        ]
+        .into();
+        let actual: Result<Set> = frame.try_into();
+        assert!(actual.is_err());
+
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"value".into(),
+            b"ex".into(),
+            b"10".into(),
+            b"keepttl".into(),
+        ]
+        .into();
+        let actual: Result<Set> = frame.try_into();
+        assert!(actual.is_err());
+
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"value".into(),
+            b"ex".into(),
+            b"0".into(),
+        ]
+        .into();
+        let actual: Result<Set> = frame.try_into();
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            "ERR invalid expire time in 'set' command"
+        );
     }
 
     #[test]
@@ -71,4 +248,49 @@ mod tests {
         let actual: Result<Set> = frame.try_into();
         assert!(actual.is_err());
     }
+
+    #[test]
+    fn test_set_execute_with_options() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![b"set".into(), b"key".into(), b"value".into(), b"xx".into()].into();
+        let cmd: Set = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL);
+
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"value".into(),
+            b"ex".into(),
+            b"100".into(),
+        ]
+        .into();
+        let cmd: Set = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert!(backend.pttl(b"key").unwrap().is_some());
+
+        let frame: Frame = vec![
+            b"set".into(),
+            b"key".into(),
+            b"other".into(),
+            b"keepttl".into(),
+            b"get".into(),
+        ]
+        .into();
+        let cmd: Set = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"value".into());
+        assert!(backend.pttl(b"key").unwrap().is_some());
+
+        let frame: Frame = vec![b"set".into(), b"key".into(), b"last".into()].into();
+        let cmd: Set = frame.try_into().unwrap();
+        cmd.execute(backend.clone()).unwrap();
+        assert_eq!(backend.pttl(b"key"), Some(None));
+
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+        let frame: Frame =
+            vec![b"set".into(), b"set".into(), b"value".into(), b"get".into()].into();
+        let cmd: Set = frame.try_into().unwrap();
+        assert!(cmd.execute(backend.clone()).is_err());
+        assert_eq!(backend.key_type(b"set"), Some("set"));
+    }
 }
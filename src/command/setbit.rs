@@ -0,0 +1,110 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Bitmaps are capped at 512MB, like Redis strings.
+pub(super) const MAX_BIT_OFFSET: u64 = 4 * 1024 * 1024 * 1024 - 1;
+
+pub(super) fn parse_bit_offset(s: &str) -> Result<u64, CommandError> {
+    s.parse::<u64>()
+        .ok()
+        .filter(|offset| *offset <= MAX_BIT_OFFSET)
+        .ok_or_else(|| {
+            CommandError::InvalidArgument(
+                "bit offset is not an integer or out of range".to_string(),
+            )
+        })
+}
+
+#[derive(Debug)]
+pub struct SetBit {
+    key: Bytes,
+    offset: u64,
+    bit: bool,
+}
+
+impl CommandExecute for SetBit {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match backend.setbit(&self.key, self.offset, self.bit)? {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SetBit {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SETBIT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let offset = parse_bit_offset(&parse.next_string()?)?;
+        let bit = match parse.next_string()?.as_str() {
+            "0" => false,
+            "1" => true,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "bit is not an integer or out of range".to_string(),
+                )
+                .into())
+            }
+        };
+        parse.finish()?;
+
+        Ok(Self { key, offset, bit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setbit(offset: &'static str, bit: &'static str) -> Result<SetBit> {
+        let frame: Frame = vec![
+            b"setbit".into(),
+            b"key".into(),
+            offset.as_bytes().into(),
+            bit.as_bytes().into(),
+        ]
+        .into();
+        frame.try_into()
+    }
+
+    #[test]
+    fn test_setbit_try_from_frame() {
+        assert_eq!(setbit("7", "1").unwrap().offset, 7);
+        assert_eq!(
+            setbit("-1", "1").unwrap_err().to_string(),
+            "ERR bit offset is not an integer or out of range"
+        );
+        assert!(setbit("4294967296", "1").is_err());
+        assert_eq!(
+            setbit("7", "2").unwrap_err().to_string(),
+            "ERR bit is not an integer or out of range"
+        );
+    }
+
+    #[test]
+    fn test_setbit_execute() {
+        let backend = Backend::new();
+        let cmd = setbit("7", "1").unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.get(b"key").unwrap(), Some(Bytes::from_static(&[1])));
+    }
+}
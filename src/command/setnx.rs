@@ -0,0 +1,67 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, SetCondition, SetExpiry};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SetNx {
+    key: Bytes,
+    value: Bytes,
+}
+
+impl CommandExecute for SetNx {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let (written, _) = backend.set_with(
+            &self.key,
+            self.value.clone(),
+            SetCondition::IfNotExists,
+            SetExpiry::Clear,
+        )?;
+
+        match written {
+            true => Ok(1.into()),
+            false => Ok(0.into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SetNx {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SETNX" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let value = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setnx_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"setnx".into(), b"key".into(), b"value".into()].into();
+        let cmd: SetNx = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, SetOp as Op};
+use crate::resp::frame::Frame;
+
+// Handles SINTER, SUNION and SDIFF, and their STORE variants which write the
+// result to a destination key instead of replying with it.
+#[derive(Debug)]
+pub struct SetOp {
+    op: Op,
+    destination: Option<Bytes>,
+    keys: Vec<Bytes>,
+}
+
+impl CommandExecute for SetOp {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        if let Some(destination) = &self.destination {
+            let len = backend.set_op_store(self.op, destination, &self.keys)?;
+            return Ok((len as i64).into());
+        }
+
+        let set = backend.set_op(self.op, &self.keys)?;
+        Ok(set.into_iter().map(Frame::from).collect::<Vec<_>>().into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        self.destination.as_ref().map(|_| frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SetOp {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let (op, store) = match command.as_str() {
+            "SINTER" => (Op::Inter, false),
+            "SUNION" => (Op::Union, false),
+            "SDIFF" => (Op::Diff, false),
+            "SINTERSTORE" => (Op::Inter, true),
+            "SUNIONSTORE" => (Op::Union, true),
+            "SDIFFSTORE" => (Op::Diff, true),
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let destination = match store {
+            true => Some(parse.next_bytes()?),
+            false => None,
+        };
+
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self {
+            op,
+            destination,
+            keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setop_try_from_frame() {
+        let frame: Frame = vec![
+            b"sdiffstore".into(),
+            b"dst".into(),
+            b"a".into(),
+            b"b".into(),
+        ]
+        .into();
+        let cmd: SetOp = frame.try_into().unwrap();
+
+        assert_eq!(cmd.op, Op::Diff);
+        assert_eq!(cmd.destination, Some("dst".into()));
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert!(cmd.propagate(&vec![].into()).is_some());
+
+        let frame: Frame = vec![b"sunionstore".into(), b"dst".into()].into();
+        assert!(SetOp::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_setop_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"a", vec!["1".into(), "2".into()]).unwrap();
+        backend.sadd(b"b", vec!["2".into(), "3".into()]).unwrap();
+
+        let frame: Frame = vec![b"sinter".into(), b"a".into(), b"b".into()].into();
+        let cmd: SetOp = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"2".into()].into()
+        );
+
+        let frame: Frame =
+            vec![b"sunionstore".into(), b"a".into(), b"a".into(), b"b".into()].into();
+        let cmd: SetOp = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.into());
+        assert_eq!(backend.scard(b"a").unwrap(), 3);
+    }
+}
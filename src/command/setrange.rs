@@ -0,0 +1,83 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SetRange {
+    key: Bytes,
+    offset: usize,
+    value: Bytes,
+}
+
+impl CommandExecute for SetRange {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.setrange(&self.key, self.offset, &self.value)?;
+        Ok((len as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SetRange {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SETRANGE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let offset = parse.next_int()?;
+        let value = parse.next_bytes()?;
+        parse.finish()?;
+
+        if offset < 0 {
+            return Err(CommandError::InvalidArgument("offset is out of range".to_string()).into());
+        }
+
+        Ok(Self {
+            key,
+            offset: offset as usize,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setrange_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "Hello World".into());
+
+        let frame: Frame = vec![
+            b"setrange".into(),
+            b"key".into(),
+            b"6".into(),
+            b"Redis".into(),
+        ]
+        .into();
+        let cmd: SetRange = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 11.into());
+        assert_eq!(backend.get(b"key").unwrap(), Some("Hello Redis".into()));
+
+        let frame: Frame =
+            vec![b"setrange".into(), b"key".into(), b"-1".into(), b"x".into()].into();
+        assert_eq!(
+            SetRange::try_from(frame).unwrap_err().to_string(),
+            "ERR offset is out of range"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SInterCard {
+    keys: Vec<Bytes>,
+    // 0 means no limit
+    limit: usize,
+}
+
+impl CommandExecute for SInterCard {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.sintercard(&self.keys, self.limit)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for SInterCard {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SINTERCARD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let numkeys = parse.next_int()?;
+        if numkeys <= 0 {
+            return Err(CommandError::InvalidArgument(
+                "numkeys should be greater than 0".to_string(),
+            )
+            .into());
+        }
+
+        if numkeys as usize > parse.len() {
+            return Err(CommandError::InvalidArgument(
+                "Number of keys can't be greater than number of args".to_string(),
+            )
+            .into());
+        }
+
+        let mut keys = Vec::with_capacity(numkeys as usize);
+        for _ in 0..numkeys {
+            keys.push(parse.next_bytes()?);
+        }
+
+        let limit = match parse.len() {
+            0 => 0,
+            _ if parse.next_string()?.to_uppercase() != "LIMIT" => {
+                return Err(CommandError::SyntaxError.into())
+            }
+            _ => match parse.next_int()? {
+                limit if limit >= 0 => limit as usize,
+                _ => {
+                    return Err(CommandError::InvalidArgument(
+                        "LIMIT can't be negative".to_string(),
+                    )
+                    .into())
+                }
+            },
+        };
+        parse.finish()?;
+
+        Ok(Self { keys, limit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sintercard(args: &[&str]) -> Result<SInterCard> {
+        let mut frame = vec![b"sintercard".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_sintercard_try_from_frame() {
+        let cmd = sintercard(&["2", "a", "b", "limit", "5"]).unwrap();
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert_eq!(cmd.limit, 5);
+
+        assert!(sintercard(&["0", "a"]).is_err());
+        assert!(sintercard(&["3", "a", "b"]).is_err());
+        assert!(sintercard(&["1", "a", "limit", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_sintercard_execute() {
+        let backend = Backend::new();
+        backend
+            .sadd(b"a", vec!["1".into(), "2".into(), "3".into()])
+            .unwrap();
+        backend.sadd(b"b", vec!["1".into(), "2".into()]).unwrap();
+
+        let cmd = sintercard(&["2", "a", "b"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+
+        let cmd = sintercard(&["2", "a", "b", "limit", "1"]).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 1.into());
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::parse::Parse;
 use super::CommandExecute;
@@ -7,13 +8,13 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct Sismember {
-    key: String,
-    field: String,
+    key: Bytes,
+    field: Bytes,
 }
 
 impl CommandExecute for Sismember {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        match backend.sismember(&self.key, &self.field) {
+        match backend.sismember(&self.key, &self.field)? {
             true => Ok(1.into()),
             false => Ok(0.into()),
         }
@@ -31,8 +32,8 @@ impl TryFrom<Frame> for Sismember {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
-        let field = parse.next_string()?;
+        let key = parse.next_bytes()?;
+        let field = parse.next_bytes()?;
         parse.finish()?;
 
         Ok(Self { key, field })
@@ -47,7 +48,7 @@ mod tests {
     use std::io::Cursor;
 
     fn parse_cmd(input: &[u8]) -> Result<Sismember> {
-        let mut buf = Cursor::new(input);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         Sismember::try_from(frame)
     }
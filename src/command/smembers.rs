@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::parse::Parse;
 use super::CommandExecute;
@@ -7,17 +8,17 @@ use crate::resp::frame::Frame;
 
 #[derive(Debug)]
 pub struct Smembers {
-    pub(crate) key: String,
+    pub(crate) key: Bytes,
 }
 
 impl CommandExecute for Smembers {
     fn execute(&self, backend: Backend) -> Result<Frame> {
-        let result = backend.smembers(&self.key);
+        let result = backend.smembers(&self.key)?;
 
         match result {
             Some(set) => Ok(set
-                .iter()
-                .map(|s| s.as_bytes().into())
+                .into_iter()
+                .map(Frame::from)
                 .collect::<Vec<Frame>>()
                 .into()),
             None => Ok(Vec::<Frame>::new().into()),
@@ -36,7 +37,7 @@ impl TryFrom<Frame> for Smembers {
             anyhow::bail!("Invalid command");
         }
 
-        let key = parse.next_string()?;
+        let key = parse.next_bytes()?;
         parse.finish()?;
 
         Ok(Self { key })
@@ -51,7 +52,7 @@ mod tests {
     use std::io::Cursor;
 
     fn parse_cmd(input: &[u8]) -> Result<Smembers> {
-        let mut buf = Cursor::new(input);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         Smembers::try_from(frame)
     }
@@ -67,7 +68,7 @@ mod tests {
     #[test]
     fn test_smembers_try_from_frame_invalid_command() {
         let input = b"*2\r\n$3\r\nSET\r\n$3\r\nkey\r\n";
-        let mut buf = Cursor::new(&input[..]);
+        let mut buf = Cursor::new(Bytes::copy_from_slice(input));
         let frame = Frame::decode(&mut buf).unwrap();
         let result = Smembers::try_from(frame);
 
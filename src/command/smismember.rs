@@ -0,0 +1,70 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SMIsMember {
+    key: Bytes,
+    members: Vec<Bytes>,
+}
+
+impl CommandExecute for SMIsMember {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let found = backend.smismember(&self.key, &self.members)?;
+
+        Ok(found
+            .into_iter()
+            .map(|found| Frame::from(found as i64))
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+impl TryFrom<Frame> for SMIsMember {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SMISMEMBER" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut members = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            members.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, members })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smismember_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["a".into()]).unwrap();
+
+        let frame: Frame = vec![
+            b"smismember".into(),
+            b"set".into(),
+            b"a".into(),
+            b"b".into(),
+        ]
+        .into();
+        let cmd: SMIsMember = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap(),
+            vec![1.into(), 0.into()].into()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SMove {
+    source: Bytes,
+    destination: Bytes,
+    member: Bytes,
+}
+
+impl CommandExecute for SMove {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let moved = backend.smove(&self.source, &self.destination, self.member.clone())?;
+        Ok((moved as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SMove {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SMOVE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let source = parse.next_bytes()?;
+        let destination = parse.next_bytes()?;
+        let member = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            source,
+            destination,
+            member,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smove_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"src", vec!["a".into()]).unwrap();
+
+        let frame: Frame = vec![b"smove".into(), b"src".into(), b"dst".into(), b"a".into()].into();
+        let cmd: SMove = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+        assert!(backend.sismember(b"dst", b"a").unwrap());
+    }
+}
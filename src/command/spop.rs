@@ -0,0 +1,121 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Without a count a single member is replied, with one an array of up to
+// count members.
+#[derive(Debug)]
+pub struct SPop {
+    key: Bytes,
+    count: Option<usize>,
+}
+
+impl CommandExecute for SPop {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let popped = backend.spop(&self.key, self.count.unwrap_or(1))?;
+
+        let frame = match (popped, self.count) {
+            (None, None) => NULL.clone(),
+            (Some(mut members), None) => members.pop().map_or_else(|| NULL.clone(), Frame::from),
+            (members, Some(_)) => members
+                .unwrap_or_default()
+                .into_iter()
+                .map(Frame::from)
+                .collect::<Vec<_>>()
+                .into(),
+        };
+
+        Ok(frame)
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // The members are picked at random, replaying SPOP would pop others.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        let mut entry = vec![b"SREM".into(), self.key.clone().into()];
+
+        match reply {
+            Frame::BulkString(_) => entry.push(reply.clone()),
+            Frame::Array(members) if !members.inner.is_empty() => {
+                entry.extend(members.inner.iter().cloned())
+            }
+            _ => return None,
+        }
+
+        Some(entry.into())
+    }
+}
+
+impl TryFrom<Frame> for SPop {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SPOP" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+
+        let count = match parse.len() {
+            0 => None,
+            _ => match parse.next_int()? {
+                count if count >= 0 => Some(count as usize),
+                _ => {
+                    return Err(CommandError::InvalidArgument(
+                        "value is out of range, must be positive".to_string(),
+                    )
+                    .into())
+                }
+            },
+        };
+        parse.finish()?;
+
+        Ok(Self { key, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spop_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["a".into(), "b".into()]).unwrap();
+
+        let frame: Frame = vec![b"spop".into(), b"set".into(), b"5".into()].into();
+        let cmd: SPop = frame.try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        match &reply {
+            Frame::Array(members) => assert_eq!(members.inner.len(), 2),
+            _ => panic!("Expected Array"),
+        }
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), vec![].into());
+
+        let frame: Frame = vec![b"spop".into(), b"set".into()].into();
+        let cmd: SPop = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+
+    #[test]
+    fn test_spop_propagate_reply() {
+        let frame: Frame = vec![b"spop".into(), b"set".into()].into();
+        let cmd: SPop = frame.clone().try_into().unwrap();
+
+        assert_eq!(
+            cmd.propagate_reply(frame.clone(), &b"a".into()),
+            Some(vec![b"SREM".into(), b"set".into(), b"a".into()].into())
+        );
+        assert_eq!(cmd.propagate_reply(frame, &NULL), None);
+    }
+}
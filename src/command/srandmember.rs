@@ -0,0 +1,80 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Without a count a single member is replied, with one an array. Negative
+// counts may return the same member several times.
+#[derive(Debug)]
+pub struct SRandMember {
+    key: Bytes,
+    count: Option<i64>,
+}
+
+impl CommandExecute for SRandMember {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let mut members = backend.srandmember(&self.key, self.count.unwrap_or(1))?;
+
+        let frame = match self.count {
+            None => members.pop().map_or_else(|| NULL.clone(), Frame::from),
+            Some(_) => members
+                .into_iter()
+                .map(Frame::from)
+                .collect::<Vec<_>>()
+                .into(),
+        };
+
+        Ok(frame)
+    }
+}
+
+impl TryFrom<Frame> for SRandMember {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SRANDMEMBER" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let count = match parse.len() {
+            0 => None,
+            _ => Some(parse.next_int()?),
+        };
+        parse.finish()?;
+
+        Ok(Self { key, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srandmember_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["a".into()]).unwrap();
+
+        let frame: Frame = vec![b"srandmember".into(), b"set".into()].into();
+        let cmd: SRandMember = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), b"a".into());
+
+        let frame: Frame = vec![b"srandmember".into(), b"set".into(), b"-3".into()].into();
+        let cmd: SRandMember = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![b"a".into(), b"a".into(), b"a".into()].into()
+        );
+
+        let frame: Frame = vec![b"srandmember".into(), b"missing".into()].into();
+        let cmd: SRandMember = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
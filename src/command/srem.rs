@@ -0,0 +1,64 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct SRem {
+    key: Bytes,
+    members: Vec<Bytes>,
+}
+
+impl CommandExecute for SRem {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = backend.srem(&self.key, &self.members)?;
+        Ok((removed as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for SRem {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "SREM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut members = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            members.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, members })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srem_execute() {
+        let backend = Backend::new();
+        backend.sadd(b"set", vec!["a".into(), "b".into()]).unwrap();
+
+        let frame: Frame = vec![b"srem".into(), b"set".into(), b"a".into(), b"x".into()].into();
+        let cmd: SRem = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.scard(b"set").unwrap(), 1);
+
+        let frame: Frame = vec![b"srem".into(), b"set".into()].into();
+        assert!(SRem::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct StrLen {
+    key: Bytes,
+}
+
+impl CommandExecute for StrLen {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.strlen(&self.key)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for StrLen {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "STRLEN" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strlen_execute() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+
+        let frame: Frame = vec![b"strlen".into(), b"key".into()].into();
+        let cmd: StrLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 5.into());
+
+        let frame: Frame = vec![b"strlen".into(), b"missing".into()].into();
+        let cmd: StrLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
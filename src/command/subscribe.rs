@@ -0,0 +1,131 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, ChannelKind, Subscriber};
+use crate::resp::frame::Frame;
+use crate::resp::push::Push;
+
+#[derive(Debug)]
+pub struct Subscribe {
+    kind: ChannelKind,
+    channels: Vec<Bytes>,
+}
+
+// What (un)subscribing to a channel is confirmed with, along with how many
+// subscriptions the client is left with.
+pub(super) fn confirmation(name: &str, channel: Frame, count: usize) -> Frame {
+    Push::new(vec![name.as_bytes().into(), channel, (count as i64).into()]).into()
+}
+
+impl Subscribe {
+    // One confirmation per channel, even when already subscribed to it.
+    pub fn subscribe(&self, subscriber: &mut Subscriber) -> Vec<Frame> {
+        let name = match self.kind {
+            ChannelKind::Channel => "subscribe",
+            ChannelKind::Pattern => "psubscribe",
+            ChannelKind::Shard => "ssubscribe",
+        };
+
+        self.channels
+            .iter()
+            .map(|channel| {
+                subscriber.subscribe(self.kind, channel.clone());
+                confirmation(name, channel.clone().into(), subscriber.count(self.kind))
+            })
+            .collect()
+    }
+}
+
+impl CommandExecute for Subscribe {
+    // Subscriptions belong to the connection, which runs `subscribe` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("SUBSCRIBE is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Subscribe {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let kind = match command.as_str() {
+            "SUBSCRIBE" => ChannelKind::Channel,
+            "PSUBSCRIBE" => ChannelKind::Pattern,
+            "SSUBSCRIBE" => ChannelKind::Shard,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let mut channels = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            channels.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { kind, channels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_try_from_frame() {
+        let frame: Frame = vec![b"psubscribe".into(), b"a*".into(), b"b*".into()].into();
+        let cmd: Subscribe = frame.try_into().unwrap();
+        assert_eq!(cmd.kind, ChannelKind::Pattern);
+        assert_eq!(cmd.channels, vec![Bytes::from("a*"), Bytes::from("b*")]);
+
+        let frame: Frame = vec![b"subscribe".into()].into();
+        assert!(Subscribe::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+
+        let frame: Frame = vec![b"subscribe".into(), b"a".into(), b"a".into()].into();
+        let cmd: Subscribe = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.subscribe(&mut subscriber),
+            vec![
+                confirmation("subscribe", b"a".into(), 1),
+                confirmation("subscribe", b"a".into(), 1),
+            ]
+        );
+
+        let frame: Frame = vec![b"psubscribe".into(), b"a".into()].into();
+        let cmd: Subscribe = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.subscribe(&mut subscriber),
+            vec![confirmation("psubscribe", b"a".into(), 2)]
+        );
+        assert_eq!(backend.pubsub_numpat(), 1);
+    }
+
+    #[test]
+    fn test_ssubscribe() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Channel, "a".into());
+
+        // shard channels are counted apart from the others
+        let frame: Frame = vec![b"ssubscribe".into(), b"a".into()].into();
+        let cmd: Subscribe = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.subscribe(&mut subscriber),
+            vec![confirmation("ssubscribe", b"a".into(), 1)]
+        );
+        assert_eq!(
+            backend.pubsub_channels(ChannelKind::Shard, None),
+            vec![Bytes::from("a")]
+        );
+    }
+}
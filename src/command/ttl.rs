@@ -0,0 +1,85 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles TTL and PTTL.
+#[derive(Debug)]
+pub struct Ttl {
+    key: Bytes,
+    millis: bool,
+}
+
+impl CommandExecute for Ttl {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let ttl = match backend.pttl(&self.key) {
+            None => -2,
+            Some(None) => -1,
+            Some(Some(ttl)) if self.millis => ttl as i64,
+            Some(Some(ttl)) => ((ttl + 500) / 1000) as i64,
+        };
+
+        Ok(ttl.into())
+    }
+}
+
+impl TryFrom<Frame> for Ttl {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let millis = match command.as_str() {
+            "TTL" => false,
+            "PTTL" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, millis })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::now_millis;
+
+    #[test]
+    fn test_ttl_try_from_frame() {
+        let frame: Frame = vec![b"pttl".into(), b"key".into()].into();
+        let cmd: Ttl = frame.try_into().unwrap();
+
+        assert_eq!(cmd.key, "key");
+        assert!(cmd.millis);
+    }
+
+    #[test]
+    fn test_ttl_try_from_frame_invalid_command() {
+        let frame: Frame = vec![b"get".into(), b"key".into()].into();
+        let result: Result<Ttl> = frame.try_into();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ttl_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"ttl".into(), b"key".into()].into();
+        let cmd: Ttl = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), (-2).into());
+
+        backend.set(b"key", "value".into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), (-1).into());
+
+        backend.expire_at(b"key", now_millis() + 10_000, &[]);
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 10.into());
+    }
+}
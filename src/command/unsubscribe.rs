@@ -0,0 +1,122 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::subscribe::confirmation;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, ChannelKind, Subscriber};
+use crate::resp::frame::Frame;
+use crate::resp::null::Null;
+
+#[derive(Debug)]
+pub struct Unsubscribe {
+    kind: ChannelKind,
+    // empty to unsubscribe from all of them
+    channels: Vec<Bytes>,
+}
+
+impl Unsubscribe {
+    // One confirmation per channel, a single one with no channel when there
+    // was nothing to unsubscribe from.
+    pub fn unsubscribe(&self, subscriber: &mut Subscriber) -> Vec<Frame> {
+        let name = match self.kind {
+            ChannelKind::Channel => "unsubscribe",
+            ChannelKind::Pattern => "punsubscribe",
+            ChannelKind::Shard => "sunsubscribe",
+        };
+
+        let channels = match self.channels.is_empty() {
+            true => subscriber.subscriptions(self.kind),
+            false => self.channels.clone(),
+        };
+
+        if channels.is_empty() {
+            return vec![confirmation(
+                name,
+                Frame::Null(Null),
+                subscriber.count(self.kind),
+            )];
+        }
+
+        channels
+            .into_iter()
+            .map(|channel| {
+                subscriber.unsubscribe(self.kind, &channel);
+                confirmation(name, channel.into(), subscriber.count(self.kind))
+            })
+            .collect()
+    }
+}
+
+impl CommandExecute for Unsubscribe {
+    // Subscriptions belong to the connection, which runs `unsubscribe`
+    // instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("UNSUBSCRIBE is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Unsubscribe {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let kind = match command.as_str() {
+            "UNSUBSCRIBE" => ChannelKind::Channel,
+            "PUNSUBSCRIBE" => ChannelKind::Pattern,
+            "SUNSUBSCRIBE" => ChannelKind::Shard,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let mut channels = Vec::new();
+        while parse.len() > 0 {
+            channels.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { kind, channels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsubscribe(args: &[&'static str]) -> Unsubscribe {
+        let mut frame = vec![b"unsubscribe".into()];
+        frame.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frame).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let backend = Backend::new();
+        let mut subscriber = Subscriber::new(&backend);
+        subscriber.subscribe(ChannelKind::Channel, "a".into());
+        subscriber.subscribe(ChannelKind::Channel, "b".into());
+        subscriber.subscribe(ChannelKind::Pattern, "c*".into());
+
+        assert_eq!(
+            unsubscribe(&["x"]).unsubscribe(&mut subscriber),
+            vec![confirmation("unsubscribe", b"x".into(), 3)]
+        );
+        assert_eq!(
+            unsubscribe(&[]).unsubscribe(&mut subscriber),
+            vec![
+                confirmation("unsubscribe", b"a".into(), 2),
+                confirmation("unsubscribe", b"b".into(), 1),
+            ]
+        );
+        assert_eq!(
+            unsubscribe(&[]).unsubscribe(&mut subscriber),
+            vec![confirmation("unsubscribe", Frame::Null(Null), 1)]
+        );
+        assert!(backend
+            .pubsub_channels(ChannelKind::Channel, None)
+            .is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+
+use super::parse::Parse;
+use super::{CommandExecute, OK};
+use crate::backend::Backend;
+use crate::network::Session;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Unwatch;
+
+impl Unwatch {
+    pub fn unwatch(&self, session: &mut Session) -> Result<Frame> {
+        session.watched.clear();
+        Ok(OK.clone())
+    }
+}
+
+impl CommandExecute for Unwatch {
+    // Only run when queued in a transaction, EXEC unwatches everything by
+    // then.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Ok(OK.clone())
+    }
+}
+
+impl TryFrom<Frame> for Unwatch {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "UNWATCH" {
+            anyhow::bail!("Invalid command");
+        }
+
+        parse.finish()?;
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwatch() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+        session.watched.watch("key".into());
+
+        let frame: Frame = vec![b"unwatch".into()].into();
+        let cmd: Unwatch = frame.try_into().unwrap();
+        assert_eq!(cmd.unwatch(&mut session).unwrap(), *OK);
+
+        backend.set(b"key", "1".into());
+        assert!(!session.watched.modified());
+    }
+}
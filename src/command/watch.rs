@@ -0,0 +1,89 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::Backend;
+use crate::network::Session;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct Watch {
+    keys: Vec<Bytes>,
+}
+
+impl Watch {
+    // The next EXEC fails if any of the keys changes meanwhile.
+    pub fn watch(&self, session: &mut Session) -> Result<Frame> {
+        if session.transaction.is_some() {
+            return Err(CommandError::InvalidArgument(
+                "WATCH inside MULTI is not allowed".to_string(),
+            )
+            .into());
+        }
+
+        for key in &self.keys {
+            session.watched.watch(key.clone());
+        }
+
+        Ok(OK.clone())
+    }
+}
+
+impl CommandExecute for Watch {
+    // Watched keys belong to the connection, which runs `watch` instead.
+    fn execute(&self, _backend: Backend) -> Result<Frame> {
+        Err(
+            CommandError::InvalidArgument("WATCH is not allowed in this context".to_string())
+                .into(),
+        )
+    }
+}
+
+impl TryFrom<Frame> for Watch {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "WATCH" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let mut keys = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            keys.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { keys })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Transaction;
+
+    #[test]
+    fn test_watch() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let frame: Frame = vec![b"watch".into(), b"a".into(), b"b".into()].into();
+        let cmd: Watch = frame.try_into().unwrap();
+        assert_eq!(cmd.watch(&mut session).unwrap(), *OK);
+
+        backend.set(b"b", "1".into());
+        assert!(session.watched.modified());
+
+        session.transaction = Some(Transaction::default());
+        assert_eq!(
+            cmd.watch(&mut session).unwrap_err().to_string(),
+            "ERR WATCH inside MULTI is not allowed"
+        );
+
+        let frame: Frame = vec![b"watch".into()].into();
+        assert!(Watch::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XAck {
+    key: Bytes,
+    group: Bytes,
+    ids: Vec<StreamId>,
+}
+
+impl CommandExecute for XAck {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let acknowledged = backend.xack(&self.key, &self.group, &self.ids)?;
+        Ok((acknowledged as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for XAck {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XACK" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+        let mut ids = vec![StreamId::parse(&parse.next_bytes()?, 0)?];
+        while parse.len() > 0 {
+            ids.push(StreamId::parse(&parse.next_bytes()?, 0)?);
+        }
+
+        Ok(Self { key, group, ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{GroupReadId, XAddId};
+
+    #[test]
+    fn test_xack_execute() {
+        let backend = Backend::new();
+        let id = XAddId::Explicit(StreamId::new(1, 1));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(b"s", id, fields, false, None).unwrap();
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+        let streams = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"c", &streams, None, false)
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"xack".into(),
+            b"s".into(),
+            b"g".into(),
+            b"1-1".into(),
+            b"1-2".into(),
+        ]
+        .into();
+        let cmd: XAck = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+        assert_eq!(backend.xpending_summary(b"s", b"g").unwrap().count, 0);
+
+        let frame: Frame = vec![b"xack".into(), b"s".into(), b"g".into()].into();
+        assert!(XAck::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::xtrim::{parse_trim, trim_args};
+use super::{CommandExecute, NULL};
+use crate::backend::{invalid_stream_id, Backend, StreamFields, StreamId, StreamTrim, XAddId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XAdd {
+    key: Bytes,
+    id: XAddId,
+    fields: StreamFields,
+    nomkstream: bool,
+    trim: Option<StreamTrim>,
+}
+
+fn parse_id(id: &[u8]) -> Result<XAddId> {
+    match id {
+        b"*" => Ok(XAddId::Auto),
+        _ => match id.strip_suffix(b"-*") {
+            Some(ms) => {
+                let ms = std::str::from_utf8(ms)
+                    .ok()
+                    .and_then(|ms| ms.parse().ok())
+                    .ok_or_else(invalid_stream_id)?;
+                Ok(XAddId::AutoSeq(ms))
+            }
+            None => Ok(XAddId::Explicit(StreamId::parse(id, 0)?)),
+        },
+    }
+}
+
+impl CommandExecute for XAdd {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let id = backend.xadd(
+            &self.key,
+            self.id,
+            self.fields.clone(),
+            self.nomkstream,
+            self.trim.as_ref(),
+        )?;
+
+        Ok(id.map_or_else(|| NULL.clone(), |id| id.to_string().as_bytes().into()))
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // Generated IDs are propagated as the ID the entry got, so replaying the
+    // AOF gives the same stream.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        if !matches!(reply, Frame::BulkString(_)) {
+            return None;
+        }
+
+        let mut command = vec![b"XADD".into(), self.key.clone().into()];
+        if self.nomkstream {
+            command.push(b"NOMKSTREAM".into());
+        }
+        if let Some(trim) = &self.trim {
+            command.extend(trim_args(trim));
+        }
+
+        command.push(reply.clone());
+        for (field, value) in &self.fields {
+            command.push(field.clone().into());
+            command.push(value.clone().into());
+        }
+
+        Some(command.into())
+    }
+}
+
+impl TryFrom<Frame> for XAdd {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XADD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let (mut nomkstream, mut trim) = (false, None);
+
+        while let Ok(option) = parse.peek_string() {
+            match option.to_uppercase().as_str() {
+                "NOMKSTREAM" => {
+                    parse.next()?;
+                    nomkstream = true;
+                }
+                "MAXLEN" | "MINID" => trim = Some(parse_trim(&mut parse)?),
+                _ => break,
+            }
+        }
+
+        let id = parse_id(&parse.next_bytes()?)?;
+
+        if parse.len() == 0 || parse.len() % 2 != 0 {
+            return Err(ParseError::EndOfParts.into());
+        }
+
+        let mut fields = Vec::with_capacity(parse.len() / 2);
+        while parse.len() > 0 {
+            fields.push((parse.next_bytes()?, parse.next_bytes()?));
+        }
+
+        Ok(Self {
+            key,
+            id,
+            fields,
+            nomkstream,
+            trim,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TrimStrategy;
+
+    #[test]
+    fn test_xadd_try_from_frame() {
+        let frame: Frame = vec![
+            b"xadd".into(),
+            b"s".into(),
+            b"nomkstream".into(),
+            b"maxlen".into(),
+            b"~".into(),
+            b"100".into(),
+            b"5-*".into(),
+            b"f".into(),
+            b"v".into(),
+        ]
+        .into();
+        let cmd: XAdd = frame.try_into().unwrap();
+
+        assert!(cmd.nomkstream);
+        assert_eq!(cmd.id, XAddId::AutoSeq(5));
+        assert_eq!(cmd.trim.unwrap().strategy, TrimStrategy::MaxLen(100));
+        assert_eq!(cmd.fields, vec![("f".into(), "v".into())]);
+
+        let frame: Frame = vec![b"xadd".into(), b"s".into(), b"*".into(), b"f".into()].into();
+        assert!(XAdd::try_from(frame).is_err());
+
+        let frame: Frame = vec![
+            b"xadd".into(),
+            b"s".into(),
+            b"1-x".into(),
+            b"f".into(),
+            b"v".into(),
+        ]
+        .into();
+        assert_eq!(
+            XAdd::try_from(frame).unwrap_err().to_string(),
+            "ERR Invalid stream ID specified as stream command argument"
+        );
+    }
+
+    #[test]
+    fn test_xadd_execute() {
+        let backend = Backend::new();
+
+        let frame: Frame = vec![
+            b"xadd".into(),
+            b"s".into(),
+            b"*".into(),
+            b"f".into(),
+            b"v".into(),
+        ]
+        .into();
+        let entry = frame.clone();
+        let cmd: XAdd = frame.try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(backend.xlen(b"s").unwrap(), 1);
+        assert_eq!(
+            cmd.propagate_reply(entry, &reply).unwrap(),
+            vec![b"XADD".into(), b"s".into(), reply, b"f".into(), b"v".into()].into()
+        );
+
+        let frame: Frame = vec![
+            b"xadd".into(),
+            b"missing".into(),
+            b"nomkstream".into(),
+            b"*".into(),
+            b"f".into(),
+            b"v".into(),
+        ]
+        .into();
+        let cmd: XAdd = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL);
+        assert!(!backend.exists(b"missing"));
+    }
+}
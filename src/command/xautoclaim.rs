@@ -0,0 +1,231 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::xclaim::{claimed_ids, parse_min_idle};
+use super::xrange::parse_bound;
+use super::xreadgroup::group_entries;
+use super::{CommandError, CommandExecute};
+use crate::backend::{now_millis, Backend, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XAutoClaim {
+    key: Bytes,
+    group: Bytes,
+    consumer: Bytes,
+    min_idle: u64,
+    start: StreamId,
+    count: usize,
+    justid: bool,
+}
+
+impl CommandExecute for XAutoClaim {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let claimed = backend.xautoclaim(
+            &self.key,
+            &self.group,
+            &self.consumer,
+            self.min_idle,
+            self.start,
+            self.count,
+            self.justid,
+        )?;
+
+        let entries = match self.justid {
+            true => claimed
+                .claimed
+                .into_iter()
+                .map(|(id, _)| Frame::from(id.to_string().as_bytes()))
+                .collect::<Vec<_>>()
+                .into(),
+            false => group_entries(claimed.claimed),
+        };
+        let deleted = claimed
+            .deleted
+            .into_iter()
+            .map(|id| Frame::from(id.to_string().as_bytes()))
+            .collect::<Vec<_>>();
+
+        Ok(vec![
+            claimed.cursor.to_string().as_bytes().into(),
+            entries,
+            deleted.into(),
+        ]
+        .into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // Propagated as an XCLAIM of the claimed entries and of the deleted ones,
+    // which replaying drops from the pending entries list again.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        let Frame::Array(reply) = reply else {
+            return None;
+        };
+
+        let mut ids = claimed_ids(reply.inner.get(1)?);
+        ids.extend(claimed_ids(reply.inner.get(2)?));
+        if ids.is_empty() {
+            return None;
+        }
+
+        let mut command = vec![
+            b"XCLAIM".into(),
+            self.key.clone().into(),
+            self.group.clone().into(),
+            self.consumer.clone().into(),
+            b"0".into(),
+        ];
+        command.extend(ids);
+        command.push(b"TIME".into());
+        command.push(now_millis().to_string().as_bytes().into());
+        if self.justid {
+            command.push(b"JUSTID".into());
+        }
+
+        Some(command.into())
+    }
+}
+
+impl TryFrom<Frame> for XAutoClaim {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XAUTOCLAIM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+        let consumer = parse.next_bytes()?;
+        let min_idle = parse_min_idle(&mut parse, "XAUTOCLAIM")?;
+        let start = parse_bound(&parse.next_bytes()?, true)?;
+        let (mut count, mut justid) = (100, false);
+
+        while parse.len() > 0 {
+            match parse.next_string()?.to_uppercase().as_str() {
+                "COUNT" => {
+                    // ten times the count are scanned, so it is bounded
+                    count = match parse.next_int()? {
+                        n if !(1..=i64::MAX / 10).contains(&n) => {
+                            return Err(CommandError::InvalidArgument(
+                                "COUNT must be > 0".to_string(),
+                            )
+                            .into())
+                        }
+                        n => n as usize,
+                    };
+                }
+                "JUSTID" => justid = true,
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        Ok(Self {
+            key,
+            group,
+            consumer,
+            min_idle,
+            start,
+            count,
+            justid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{GroupReadId, XAddId};
+
+    #[test]
+    fn test_xautoclaim_try_from_frame() {
+        let frame: Frame = vec![
+            b"xautoclaim".into(),
+            b"s".into(),
+            b"g".into(),
+            b"bob".into(),
+            b"10".into(),
+            b"(1-1".into(),
+            b"count".into(),
+            b"5".into(),
+        ]
+        .into();
+        let cmd: XAutoClaim = frame.try_into().unwrap();
+
+        assert_eq!(cmd.start, StreamId::new(1, 2));
+        assert_eq!(cmd.count, 5);
+        assert!(!cmd.justid);
+
+        let frame: Frame = vec![
+            b"xautoclaim".into(),
+            b"s".into(),
+            b"g".into(),
+            b"bob".into(),
+            b"10".into(),
+            b"0".into(),
+            b"count".into(),
+            b"0".into(),
+        ]
+        .into();
+        assert_eq!(
+            XAutoClaim::try_from(frame).unwrap_err().to_string(),
+            "ERR COUNT must be > 0"
+        );
+    }
+
+    #[test]
+    fn test_xautoclaim_execute() {
+        let backend = Backend::new();
+        for seq in 1..=2 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("f".into(), "v".into())];
+            backend.xadd(b"s", id, fields, false, None).unwrap();
+        }
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+        let streams = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &streams, None, false)
+            .unwrap();
+        backend.xdel(b"s", &[StreamId::new(1, 2)]).unwrap();
+
+        let frame: Frame = vec![
+            b"xautoclaim".into(),
+            b"s".into(),
+            b"g".into(),
+            b"bob".into(),
+            b"0".into(),
+            b"-".into(),
+            b"justid".into(),
+        ]
+        .into();
+        let cmd: XAutoClaim = frame.clone().try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(
+            reply,
+            vec![
+                b"0-0".into(),
+                vec![Frame::from(b"1-1")].into(),
+                vec![Frame::from(b"1-2")].into(),
+            ]
+            .into()
+        );
+
+        match cmd.propagate_reply(frame, &reply).unwrap() {
+            Frame::Array(entry) => {
+                assert_eq!(entry.inner[0], b"XCLAIM".into());
+                assert_eq!(entry.inner[5..7], [b"1-1".into(), b"1-2".into()]);
+            }
+            _ => panic!("Expected Array"),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::xreadgroup::group_entries;
+use super::{CommandError, CommandExecute};
+use crate::backend::{now_millis, Backend, ClaimOptions, ClaimTime, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XClaim {
+    key: Bytes,
+    group: Bytes,
+    consumer: Bytes,
+    min_idle: u64,
+    ids: Vec<StreamId>,
+    options: ClaimOptions,
+}
+
+// Parses the milliseconds argument of XCLAIM and XAUTOCLAIM, negative ones
+// counting as 0.
+pub(super) fn parse_min_idle(parse: &mut Parse, command: &str) -> Result<u64> {
+    let min_idle = parse.next_string()?.parse::<i64>().map_err(|_| {
+        CommandError::InvalidArgument(format!("Invalid min-idle-time argument for {}", command))
+    })?;
+
+    Ok(min_idle.max(0) as u64)
+}
+
+// The IDs of the entries a claim replied with, as entries or IDs alone.
+pub(super) fn claimed_ids(entries: &Frame) -> Vec<Frame> {
+    let Frame::Array(entries) = entries else {
+        return Vec::new();
+    };
+
+    entries
+        .inner
+        .iter()
+        .filter_map(|entry| match entry {
+            Frame::Array(entry) => entry.inner.first().cloned(),
+            id => Some(id.clone()),
+        })
+        .collect()
+}
+
+impl XClaim {
+    fn entry(&self, min_idle: Frame, ids: Vec<Frame>, time: Option<u64>) -> Frame {
+        let mut command = vec![
+            b"XCLAIM".into(),
+            self.key.clone().into(),
+            self.group.clone().into(),
+            self.consumer.clone().into(),
+            min_idle,
+        ];
+        command.extend(ids);
+
+        if let Some(time) = time {
+            command.push(b"TIME".into());
+            command.push(time.to_string().as_bytes().into());
+        }
+        if let Some(retry_count) = self.options.retry_count {
+            command.push(b"RETRYCOUNT".into());
+            command.push(retry_count.to_string().as_bytes().into());
+        }
+        if self.options.force {
+            command.push(b"FORCE".into());
+        }
+        if self.options.justid {
+            command.push(b"JUSTID".into());
+        }
+        if let Some(last_id) = self.options.last_id {
+            command.push(b"LASTID".into());
+            command.push(last_id.to_string().as_bytes().into());
+        }
+
+        command.into()
+    }
+}
+
+impl CommandExecute for XClaim {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let claimed = backend.xclaim(
+            &self.key,
+            &self.group,
+            &self.consumer,
+            self.min_idle,
+            &self.ids,
+            &self.options,
+        )?;
+
+        match self.options.justid {
+            true => Ok(claimed
+                .into_iter()
+                .map(|(id, _)| Frame::from(id.to_string().as_bytes()))
+                .collect::<Vec<_>>()
+                .into()),
+            false => Ok(group_entries(claimed)),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // Idle times change by the time the AOF is replayed, so the entries that
+    // were claimed are propagated as claimed with no minimum idle time and
+    // the delivery time they got. When none was, nothing is claimed on replay
+    // either, while deleted entries are still dropped and LASTID still set.
+    fn propagate_reply(&self, _entry: Frame, reply: &Frame) -> Option<Frame> {
+        if !matches!(reply, Frame::Array(_)) {
+            return None;
+        }
+
+        let claimed = claimed_ids(reply);
+        if claimed.is_empty() {
+            let ids = self.ids.iter().map(|id| id.to_string().as_bytes().into());
+            let never = i64::MAX.to_string();
+            return Some(self.entry(never.as_bytes().into(), ids.collect(), None));
+        }
+
+        let time = self.options.delivery_time(now_millis());
+        Some(self.entry(b"0".into(), claimed, Some(time)))
+    }
+}
+
+impl TryFrom<Frame> for XClaim {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XCLAIM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+        let consumer = parse.next_bytes()?;
+        let min_idle = parse_min_idle(&mut parse, "XCLAIM")?;
+
+        // IDs go on until the first option
+        let mut ids = vec![StreamId::parse(&parse.next_bytes()?, 0)?];
+        while let Ok(arg) = parse.peek_string() {
+            match StreamId::parse(arg.as_bytes(), 0) {
+                Ok(id) => ids.push(id),
+                Err(_) => break,
+            }
+            parse.next()?;
+        }
+
+        let mut options = ClaimOptions::default();
+        let int_option = |parse: &mut Parse, name: &str| {
+            parse.next_string()?.parse::<i64>().map_err(|_| {
+                anyhow::Error::from(CommandError::InvalidArgument(format!(
+                    "Invalid {} option argument for XCLAIM",
+                    name
+                )))
+            })
+        };
+
+        while parse.len() > 0 {
+            let option = parse.next_string()?;
+            match option.to_uppercase().as_str() {
+                "IDLE" => options.time = Some(ClaimTime::Idle(int_option(&mut parse, "IDLE")?)),
+                "TIME" => options.time = Some(ClaimTime::Unix(int_option(&mut parse, "TIME")?)),
+                "RETRYCOUNT" => {
+                    let count = int_option(&mut parse, "RETRYCOUNT")?;
+                    options.retry_count = Some(count.max(0) as u64);
+                }
+                "FORCE" => options.force = true,
+                "JUSTID" => options.justid = true,
+                "LASTID" => options.last_id = Some(StreamId::parse(&parse.next_bytes()?, 0)?),
+                _ => {
+                    return Err(CommandError::InvalidArgument(format!(
+                        "Unrecognized XCLAIM option '{}'",
+                        option
+                    ))
+                    .into())
+                }
+            }
+        }
+
+        Ok(Self {
+            key,
+            group,
+            consumer,
+            min_idle,
+            ids,
+            options,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{GroupReadId, XAddId};
+
+    fn xclaim(args: &[&str]) -> Result<XClaim> {
+        let mut frame = vec![b"xclaim".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_xclaim_try_from_frame() {
+        let cmd = xclaim(&[
+            "s",
+            "g",
+            "bob",
+            "-5",
+            "1-1",
+            "2",
+            "idle",
+            "100",
+            "retrycount",
+            "3",
+            "justid",
+        ])
+        .unwrap();
+
+        assert_eq!(cmd.min_idle, 0);
+        assert_eq!(cmd.ids, vec![StreamId::new(1, 1), StreamId::new(2, 0)]);
+        assert_eq!(cmd.options.time, Some(ClaimTime::Idle(100)));
+        assert_eq!(cmd.options.retry_count, Some(3));
+        assert!(cmd.options.justid);
+
+        assert_eq!(
+            xclaim(&["s", "g", "bob", "0", "1-1", "nope"])
+                .unwrap_err()
+                .to_string(),
+            "ERR Unrecognized XCLAIM option 'nope'"
+        );
+        assert!(xclaim(&["s", "g", "bob", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_xclaim_execute() {
+        let backend = Backend::new();
+        let id = XAddId::Explicit(StreamId::new(1, 1));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(b"s", id, fields, false, None).unwrap();
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+        let streams = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &streams, None, false)
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"xclaim".into(),
+            b"s".into(),
+            b"g".into(),
+            b"bob".into(),
+            b"0".into(),
+            b"1-1".into(),
+            b"justid".into(),
+        ]
+        .into();
+        let cmd: XClaim = frame.clone().try_into().unwrap();
+
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(reply, vec![Frame::from(b"1-1")].into());
+
+        match cmd.propagate_reply(frame, &reply).unwrap() {
+            Frame::Array(entry) => {
+                assert_eq!(entry.inner[4..6], [b"0".into(), b"1-1".into()]);
+                assert_eq!(entry.inner[6], b"TIME".into());
+                assert_eq!(entry.inner.last(), Some(&b"JUSTID".into()));
+            }
+            _ => panic!("Expected Array"),
+        }
+
+        let pending = backend
+            .xpending(b"s", b"g", StreamId::MIN, StreamId::MAX, 10, None, None)
+            .unwrap();
+        assert_eq!(pending[0].consumer, "bob");
+        assert_eq!(pending[0].delivery_count, 1);
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XDel {
+    key: Bytes,
+    ids: Vec<StreamId>,
+}
+
+impl CommandExecute for XDel {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let deleted = backend.xdel(&self.key, &self.ids)?;
+        Ok((deleted as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for XDel {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XDEL" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut ids = vec![StreamId::parse(&parse.next_bytes()?, 0)?];
+        while parse.len() > 0 {
+            ids.push(StreamId::parse(&parse.next_bytes()?, 0)?);
+        }
+
+        Ok(Self { key, ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    #[test]
+    fn test_xdel_execute() {
+        let backend = Backend::new();
+        let id = XAddId::Explicit(StreamId::new(1, 1));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(b"s", id, fields, false, None).unwrap();
+
+        let frame: Frame = vec![b"xdel".into(), b"s".into(), b"1-1".into(), b"2".into()].into();
+        let cmd: XDel = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.xlen(b"s").unwrap(), 0);
+        assert!(backend.exists(b"s"));
+
+        let frame: Frame = vec![b"xdel".into(), b"s".into()].into();
+        assert!(XDel::try_from(frame).is_err());
+    }
+}
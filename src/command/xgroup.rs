@@ -0,0 +1,235 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::{Backend, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    // IDs are None for `$`, the last ID of the stream
+    Create {
+        id: Option<StreamId>,
+        mkstream: bool,
+        entries_read: Option<u64>,
+    },
+    SetId {
+        id: Option<StreamId>,
+        entries_read: Option<u64>,
+    },
+    Destroy,
+    CreateConsumer(Bytes),
+    DelConsumer(Bytes),
+}
+
+#[derive(Debug)]
+pub struct XGroup {
+    key: Bytes,
+    group: Bytes,
+    subcommand: Subcommand,
+}
+
+fn parse_id(id: &[u8]) -> Result<Option<StreamId>> {
+    match id {
+        b"$" => Ok(None),
+        id => Ok(Some(StreamId::parse(id, 0)?)),
+    }
+}
+
+// ENTRIESREAD takes -1 for an unknown number of entries read.
+fn parse_entries_read(parse: &mut Parse) -> Result<Option<u64>> {
+    match parse.next_int()? {
+        -1 => Ok(None),
+        read if read < 0 => Err(CommandError::InvalidArgument(
+            "value for ENTRIESREAD must be positive or -1".to_string(),
+        )
+        .into()),
+        read => Ok(Some(read as u64)),
+    }
+}
+
+impl CommandExecute for XGroup {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let (key, group) = (&self.key, &self.group);
+
+        match &self.subcommand {
+            Subcommand::Create {
+                id,
+                mkstream,
+                entries_read,
+            } => {
+                backend.xgroup_create(key, group, *id, *mkstream, *entries_read)?;
+                Ok(OK.clone())
+            }
+            Subcommand::SetId { id, entries_read } => {
+                backend.xgroup_setid(key, group, *id, *entries_read)?;
+                Ok(OK.clone())
+            }
+            Subcommand::Destroy => {
+                let destroyed = backend.xgroup_destroy(key, group)?;
+                Ok((destroyed as i64).into())
+            }
+            Subcommand::CreateConsumer(consumer) => {
+                let created = backend.xgroup_createconsumer(key, group, consumer)?;
+                Ok((created as i64).into())
+            }
+            Subcommand::DelConsumer(consumer) => {
+                let pending = backend.xgroup_delconsumer(key, group, consumer)?;
+                Ok((pending as i64).into())
+            }
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for XGroup {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XGROUP" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let name = parse.next_string()?;
+        let arity = match name.to_uppercase().as_str() {
+            "CREATE" | "SETID" => 3..=6,
+            "DESTROY" => 2..=2,
+            "CREATECONSUMER" | "DELCONSUMER" => 3..=3,
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try XGROUP HELP.",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        if !arity.contains(&parse.len()) {
+            return Err(CommandError::WrongNumberOfArguments(format!(
+                "xgroup|{}",
+                name.to_lowercase()
+            ))
+            .into());
+        }
+
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+
+        let subcommand = match name.to_uppercase().as_str() {
+            "CREATE" | "SETID" => {
+                let create = name.eq_ignore_ascii_case("CREATE");
+                let id = parse_id(&parse.next_bytes()?)?;
+                let (mut mkstream, mut entries_read) = (false, None);
+
+                while parse.len() > 0 {
+                    match parse.next_string()?.to_uppercase().as_str() {
+                        "MKSTREAM" if create => mkstream = true,
+                        "ENTRIESREAD" => entries_read = parse_entries_read(&mut parse)?,
+                        _ => return Err(CommandError::SyntaxError.into()),
+                    }
+                }
+
+                match create {
+                    true => Subcommand::Create {
+                        id,
+                        mkstream,
+                        entries_read,
+                    },
+                    false => Subcommand::SetId { id, entries_read },
+                }
+            }
+            "DESTROY" => Subcommand::Destroy,
+            "CREATECONSUMER" => Subcommand::CreateConsumer(parse.next_bytes()?),
+            _ => Subcommand::DelConsumer(parse.next_bytes()?),
+        };
+
+        if parse.len() > 0 {
+            return Err(ParseError::NotFinished.into());
+        }
+
+        Ok(Self {
+            key,
+            group,
+            subcommand,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xgroup(args: &[&str]) -> Result<XGroup> {
+        let mut frame = vec![b"xgroup".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_xgroup_try_from_frame() {
+        let cmd = xgroup(&["create", "s", "g", "$", "mkstream", "entriesread", "3"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::Create {
+                id: None,
+                mkstream: true,
+                entries_read: Some(3),
+            }
+        );
+
+        let cmd = xgroup(&["SETID", "s", "g", "1-1"]).unwrap();
+        assert_eq!(
+            cmd.subcommand,
+            Subcommand::SetId {
+                id: Some(StreamId::new(1, 1)),
+                entries_read: None,
+            }
+        );
+
+        assert!(xgroup(&["setid", "s", "g", "0", "mkstream"]).is_err());
+        assert!(xgroup(&["destroy", "s"]).is_err());
+        assert_eq!(
+            xgroup(&["nope", "s", "g"]).unwrap_err().to_string(),
+            "ERR unknown subcommand 'nope'. Try XGROUP HELP."
+        );
+    }
+
+    #[test]
+    fn test_xgroup_execute() {
+        let backend = Backend::new();
+
+        let cmd = xgroup(&["create", "s", "g", "$"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically."
+        );
+
+        let cmd = xgroup(&["create", "s", "g", "$", "mkstream"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "BUSYGROUP Consumer Group name already exists"
+        );
+
+        let cmd = xgroup(&["createconsumer", "s", "g", "alice"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        let cmd = xgroup(&["delconsumer", "s", "missing", "alice"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "NOGROUP No such consumer group 'missing' for key name 's'"
+        );
+
+        let cmd = xgroup(&["destroy", "s", "g"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::xrange::stream_entry;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, StreamEntry, StreamId};
+use crate::resp::frame::Frame;
+use crate::resp::map::Map;
+use crate::resp::null::Null;
+
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Stream,
+    Groups,
+    Consumers(Bytes),
+}
+
+// Replies are maps, flattened into arrays for RESP2 clients.
+#[derive(Debug)]
+pub struct XInfo {
+    key: Bytes,
+    subcommand: Subcommand,
+}
+
+fn map(fields: Vec<(&str, Frame)>) -> Frame {
+    let fields = fields
+        .into_iter()
+        .map(|(name, value)| (Frame::from(name.as_bytes()), value))
+        .collect::<BTreeMap<_, _>>();
+
+    Map::new(fields).into()
+}
+
+fn id_frame(id: StreamId) -> Frame {
+    id.to_string().as_bytes().into()
+}
+
+fn optional(value: Option<u64>) -> Frame {
+    value.map_or(Frame::Null(Null), |value| (value as i64).into())
+}
+
+fn entry_frame(entry: Option<StreamEntry>) -> Frame {
+    entry.map_or(Frame::Null(Null), |(id, fields)| stream_entry(id, fields))
+}
+
+fn no_such_key() -> CommandError {
+    CommandError::InvalidArgument("no such key".to_string())
+}
+
+impl CommandExecute for XInfo {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        match &self.subcommand {
+            Subcommand::Stream => {
+                let info = backend.xinfo_stream(&self.key)?.ok_or_else(no_such_key)?;
+
+                Ok(map(vec![
+                    ("length", (info.length as i64).into()),
+                    ("last-generated-id", id_frame(info.last_id)),
+                    ("max-deleted-entry-id", id_frame(info.max_deleted_id)),
+                    ("entries-added", (info.entries_added as i64).into()),
+                    ("recorded-first-entry-id", id_frame(info.first_id)),
+                    ("groups", (info.groups as i64).into()),
+                    ("first-entry", entry_frame(info.first_entry)),
+                    ("last-entry", entry_frame(info.last_entry)),
+                ]))
+            }
+            Subcommand::Groups => {
+                let groups = backend.xinfo_groups(&self.key)?.ok_or_else(no_such_key)?;
+
+                Ok(groups
+                    .into_iter()
+                    .map(|group| {
+                        map(vec![
+                            ("name", group.name.into()),
+                            ("consumers", (group.consumers as i64).into()),
+                            ("pending", (group.pending as i64).into()),
+                            ("last-delivered-id", id_frame(group.last_id)),
+                            ("entries-read", optional(group.entries_read)),
+                            ("lag", optional(group.lag)),
+                        ])
+                    })
+                    .collect::<Vec<_>>()
+                    .into())
+            }
+            Subcommand::Consumers(group) => {
+                let consumers = backend
+                    .xinfo_consumers(&self.key, group)?
+                    .ok_or_else(no_such_key)?;
+
+                // consumers that never read nor claimed are inactive for -1
+                Ok(consumers
+                    .into_iter()
+                    .map(|consumer| {
+                        let inactive = consumer.inactive.map_or(-1, |inactive| inactive as i64);
+                        map(vec![
+                            ("name", consumer.name.into()),
+                            ("pending", (consumer.pending as i64).into()),
+                            ("idle", (consumer.idle as i64).into()),
+                            ("inactive", inactive.into()),
+                        ])
+                    })
+                    .collect::<Vec<_>>()
+                    .into())
+            }
+        }
+    }
+}
+
+impl TryFrom<Frame> for XInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XINFO" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let name = parse.next_string()?;
+        let (key, subcommand) = match name.to_uppercase().as_str() {
+            "STREAM" => (parse.next_bytes()?, Subcommand::Stream),
+            "GROUPS" => (parse.next_bytes()?, Subcommand::Groups),
+            "CONSUMERS" => {
+                let key = parse.next_bytes()?;
+                (key, Subcommand::Consumers(parse.next_bytes()?))
+            }
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try XINFO HELP.",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        // the FULL form of XINFO STREAM is not supported
+        if parse.len() > 0 {
+            return match subcommand {
+                Subcommand::Stream => Err(CommandError::SyntaxError.into()),
+                _ => Err(ParseError::NotFinished.into()),
+            };
+        }
+
+        Ok(Self { key, subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{GroupReadId, XAddId};
+
+    fn xinfo(args: &[&str]) -> Result<XInfo> {
+        let mut frame = vec![b"xinfo".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    fn field(reply: &Frame, name: &str) -> Frame {
+        match reply {
+            Frame::Map(map) => map.inner[&Frame::from(name.as_bytes())].clone(),
+            _ => panic!("Expected Map"),
+        }
+    }
+
+    #[test]
+    fn test_xinfo_try_from_frame() {
+        let cmd = xinfo(&["consumers", "s", "g"]).unwrap();
+        assert_eq!(cmd.subcommand, Subcommand::Consumers("g".into()));
+
+        assert!(xinfo(&["stream", "s", "full"]).is_err());
+        assert!(xinfo(&["groups"]).is_err());
+        assert!(xinfo(&["nope", "s"]).is_err());
+    }
+
+    #[test]
+    fn test_xinfo_execute() {
+        let backend = Backend::new();
+        for seq in 1..=3 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("f".into(), "v".into())];
+            backend.xadd(b"s", id, fields, false, None).unwrap();
+        }
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+        let streams = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &streams, Some(1), false)
+            .unwrap();
+
+        let reply = xinfo(&["stream", "s"])
+            .unwrap()
+            .execute(backend.clone())
+            .unwrap();
+        assert_eq!(field(&reply, "length"), 3.into());
+        assert_eq!(field(&reply, "groups"), 1.into());
+        assert_eq!(field(&reply, "last-generated-id"), b"1-3".into());
+
+        let reply = xinfo(&["groups", "s"])
+            .unwrap()
+            .execute(backend.clone())
+            .unwrap();
+        let Frame::Array(groups) = reply else {
+            panic!("Expected Array");
+        };
+        assert_eq!(field(&groups.inner[0], "entries-read"), 1.into());
+        assert_eq!(field(&groups.inner[0], "lag"), 2.into());
+        assert_eq!(field(&groups.inner[0], "pending"), 1.into());
+
+        let reply = xinfo(&["consumers", "s", "g"])
+            .unwrap()
+            .execute(backend.clone())
+            .unwrap();
+        let Frame::Array(consumers) = reply else {
+            panic!("Expected Array");
+        };
+        assert_eq!(field(&consumers.inner[0], "name"), b"alice".into());
+
+        let cmd = xinfo(&["consumers", "s", "missing"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap_err().to_string(),
+            "NOGROUP No such consumer group 'missing' for key name 's'"
+        );
+        let cmd = xinfo(&["stream", "missing"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR no such key"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XLen {
+    key: Bytes,
+}
+
+impl CommandExecute for XLen {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.xlen(&self.key)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for XLen {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XLEN" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    #[test]
+    fn test_xlen_execute() {
+        let backend = Backend::new();
+        let fields = vec![("f".into(), "v".into())];
+        backend
+            .xadd(b"s", XAddId::Auto, fields, false, None)
+            .unwrap();
+
+        let frame: Frame = vec![b"xlen".into(), b"s".into()].into();
+        let cmd: XLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+
+        let frame: Frame = vec![b"xlen".into(), b"missing".into()].into();
+        let cmd: XLen = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
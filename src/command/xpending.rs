@@ -0,0 +1,227 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::xrange::parse_bound;
+use super::{CommandError, CommandExecute, NULL, NULL_ARRAY};
+use crate::backend::{Backend, StreamId};
+use crate::resp::frame::Frame;
+
+// The extended form, listing the pending entries of a range.
+#[derive(Debug, PartialEq)]
+struct PendingRange {
+    min_idle: Option<u64>,
+    start: StreamId,
+    end: StreamId,
+    count: usize,
+    consumer: Option<Bytes>,
+}
+
+#[derive(Debug)]
+pub struct XPending {
+    key: Bytes,
+    group: Bytes,
+    range: Option<PendingRange>,
+}
+
+fn id_frame(id: StreamId) -> Frame {
+    id.to_string().as_bytes().into()
+}
+
+impl XPending {
+    fn summary(&self, backend: Backend) -> Result<Frame> {
+        let summary = backend.xpending_summary(&self.key, &self.group)?;
+
+        if summary.count == 0 {
+            return Ok(vec![0.into(), NULL.clone(), NULL.clone(), NULL_ARRAY.clone()].into());
+        }
+
+        // the counts of the consumers are bulk strings, like in Redis
+        let consumers = summary
+            .consumers
+            .into_iter()
+            .map(|(name, count)| {
+                Frame::from(vec![name.into(), count.to_string().as_bytes().into()])
+            })
+            .collect::<Vec<_>>();
+
+        Ok(vec![
+            (summary.count as i64).into(),
+            summary.first.map_or_else(|| NULL.clone(), id_frame),
+            summary.last.map_or_else(|| NULL.clone(), id_frame),
+            consumers.into(),
+        ]
+        .into())
+    }
+}
+
+impl CommandExecute for XPending {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let Some(range) = &self.range else {
+            return self.summary(backend);
+        };
+
+        let pending = backend.xpending(
+            &self.key,
+            &self.group,
+            range.start,
+            range.end,
+            range.count,
+            range.consumer.as_deref(),
+            range.min_idle,
+        )?;
+
+        Ok(pending
+            .into_iter()
+            .map(|info| {
+                Frame::from(vec![
+                    id_frame(info.id),
+                    info.consumer.into(),
+                    (info.idle as i64).into(),
+                    (info.delivery_count as i64).into(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+impl TryFrom<Frame> for XPending {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XPENDING" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let group = parse.next_bytes()?;
+
+        if parse.len() == 0 {
+            return Ok(Self {
+                key,
+                group,
+                range: None,
+            });
+        }
+
+        let min_idle = match parse.peek_string()?.eq_ignore_ascii_case("IDLE") {
+            true => {
+                parse.next()?;
+                Some(parse.next_int()?.max(0) as u64)
+            }
+            false => None,
+        };
+
+        if parse.len() < 3 {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        let start = parse_bound(&parse.next_bytes()?, true)?;
+        let end = parse_bound(&parse.next_bytes()?, false)?;
+        let count = parse.next_int()?.max(0) as usize;
+        let consumer = match parse.len() {
+            0 => None,
+            1 => Some(parse.next_bytes()?),
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+
+        Ok(Self {
+            key,
+            group,
+            range: Some(PendingRange {
+                min_idle,
+                start,
+                end,
+                count,
+                consumer,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{GroupReadId, XAddId};
+
+    fn xpending(args: &[&str]) -> Result<XPending> {
+        let mut frame = vec![b"xpending".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_xpending_try_from_frame() {
+        let cmd = xpending(&["s", "g", "idle", "100", "-", "(2", "10", "alice"]).unwrap();
+        assert_eq!(
+            cmd.range,
+            Some(PendingRange {
+                min_idle: Some(100),
+                start: StreamId::MIN,
+                end: StreamId::new(2, u64::MAX - 1),
+                count: 10,
+                consumer: Some("alice".into()),
+            })
+        );
+
+        assert!(xpending(&["s", "g", "-", "+"]).is_err());
+        assert!(xpending(&["s", "g", "idle", "100"]).is_err());
+    }
+
+    #[test]
+    fn test_xpending_execute() {
+        let backend = Backend::new();
+        for seq in 1..=2 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("f".into(), "v".into())];
+            backend.xadd(b"s", id, fields, false, None).unwrap();
+        }
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+
+        let cmd = xpending(&["s", "g"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![0.into(), NULL.clone(), NULL.clone(), NULL_ARRAY.clone()].into()
+        );
+
+        let streams = [("s".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"g", b"alice", &streams, None, false)
+            .unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![
+                2.into(),
+                b"1-1".into(),
+                b"1-2".into(),
+                vec![Frame::from(vec![b"alice".into(), b"2".into()])].into(),
+            ]
+            .into()
+        );
+
+        let cmd = xpending(&["s", "g", "-", "+", "1"]).unwrap();
+        match cmd.execute(backend.clone()).unwrap() {
+            Frame::Array(pending) => {
+                assert_eq!(pending.inner.len(), 1);
+                let Frame::Array(entry) = &pending.inner[0] else {
+                    panic!("Expected Array");
+                };
+                assert_eq!(entry.inner[0], b"1-1".into());
+                assert_eq!(entry.inner[3], 1.into());
+            }
+            _ => panic!("Expected Array"),
+        }
+
+        let cmd = xpending(&["s", "missing"]).unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "NOGROUP No such key 's' or consumer group 'missing'"
+        );
+    }
+}
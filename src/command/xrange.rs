@@ -0,0 +1,176 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL_ARRAY};
+use crate::backend::{Backend, StreamEntry, StreamFields, StreamId};
+use crate::resp::frame::Frame;
+
+// Handles XRANGE and XREVRANGE, the latter taking the end of the range first.
+#[derive(Debug)]
+pub struct XRange {
+    key: Bytes,
+    start: StreamId,
+    end: StreamId,
+    count: Option<usize>,
+    rev: bool,
+}
+
+// Each entry is its ID followed by its fields and values, in a flat array.
+pub(super) fn stream_entry(id: StreamId, fields: StreamFields) -> Frame {
+    let fields = fields
+        .into_iter()
+        .flat_map(|(field, value)| [field.into(), value.into()])
+        .collect::<Vec<Frame>>();
+
+    Frame::from(vec![id.to_string().as_bytes().into(), fields.into()])
+}
+
+pub(super) fn stream_entries(entries: Vec<StreamEntry>) -> Frame {
+    entries
+        .into_iter()
+        .map(|(id, fields)| stream_entry(id, fields))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+// `-` and `+` are the smallest and greatest IDs, and a `(` prefix excludes
+// the ID. A missing sequence is taken as the first one for the start of the
+// range and the last one for its end.
+pub(super) fn parse_bound(bound: &[u8], is_start: bool) -> Result<StreamId, CommandError> {
+    match bound {
+        b"-" => return Ok(StreamId::MIN),
+        b"+" => return Ok(StreamId::MAX),
+        _ => {}
+    }
+
+    let missing_seq = if is_start { 0 } else { u64::MAX };
+
+    let Some(bound) = bound.strip_prefix(b"(") else {
+        return StreamId::parse(bound, missing_seq);
+    };
+
+    let id = StreamId::parse(bound, missing_seq)?;
+    let (id, name) = match is_start {
+        true => (id.next(), "start"),
+        false => (id.prev(), "end"),
+    };
+
+    id.ok_or_else(|| CommandError::InvalidArgument(format!("invalid {} ID for the interval", name)))
+}
+
+impl CommandExecute for XRange {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        if self.count == Some(0) {
+            return Ok(NULL_ARRAY.clone());
+        }
+
+        let entries = backend.xrange(&self.key, self.start, self.end, self.count, self.rev)?;
+        Ok(stream_entries(entries))
+    }
+}
+
+impl TryFrom<Frame> for XRange {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let rev = match command.as_str() {
+            "XRANGE" => false,
+            "XREVRANGE" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+        let (first, second) = (parse.next_bytes()?, parse.next_bytes()?);
+        let (start, end) = match rev {
+            true => (parse_bound(&second, true)?, parse_bound(&first, false)?),
+            false => (parse_bound(&first, true)?, parse_bound(&second, false)?),
+        };
+
+        let count = match parse.len() {
+            0 => None,
+            2 if parse.next_string()?.eq_ignore_ascii_case("COUNT") => {
+                Some(parse.next_int()?.max(0) as usize)
+            }
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+
+        Ok(Self {
+            key,
+            start,
+            end,
+            count,
+            rev,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    #[test]
+    fn test_xrange_try_from_frame() {
+        let frame: Frame = vec![b"xrevrange".into(), b"s".into(), b"(5".into(), b"3".into()].into();
+        let cmd: XRange = frame.try_into().unwrap();
+
+        assert_eq!(cmd.start, StreamId::new(3, 0));
+        assert_eq!(cmd.end, StreamId::new(5, u64::MAX - 1));
+        assert!(cmd.rev);
+
+        let frame: Frame = vec![b"xrange".into(), b"s".into(), b"(+".into(), b"+".into()].into();
+        assert!(XRange::try_from(frame).is_err());
+
+        let frame: Frame = vec![
+            b"xrange".into(),
+            b"s".into(),
+            b"(18446744073709551615-18446744073709551615".into(),
+            b"+".into(),
+        ]
+        .into();
+        assert_eq!(
+            XRange::try_from(frame).unwrap_err().to_string(),
+            "ERR invalid start ID for the interval"
+        );
+    }
+
+    #[test]
+    fn test_xrange_execute() {
+        let backend = Backend::new();
+        for seq in 1..=3 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("f".into(), seq.to_string().into())];
+            backend.xadd(b"s", id, fields, false, None).unwrap();
+        }
+
+        let frame: Frame = vec![
+            b"xrevrange".into(),
+            b"s".into(),
+            b"+".into(),
+            b"-".into(),
+            b"count".into(),
+            b"1".into(),
+        ]
+        .into();
+        let cmd: XRange = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![Frame::from(vec![
+                b"1-3".into(),
+                vec![b"f".into(), b"3".into()].into()
+            ])]
+            .into()
+        );
+
+        let frame: Frame = vec![b"xrange".into(), b"s".into(), b"(1-1".into(), b"1".into()].into();
+        let cmd: XRange = frame.try_into().unwrap();
+        match cmd.execute(backend).unwrap() {
+            Frame::Array(entries) => assert_eq!(entries.inner.len(), 2),
+            _ => panic!("Expected Array"),
+        }
+    }
+}
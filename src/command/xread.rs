@@ -0,0 +1,264 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::xrange::stream_entries;
+use super::{CommandError, CommandExecute, NULL_ARRAY};
+use crate::backend::{Backend, Block, BlockOp, Served, StreamEntry, StreamId};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XRead {
+    keys: Vec<Bytes>,
+    // None for `$`, the last ID of the stream when the command runs
+    ids: Vec<Option<StreamId>>,
+    count: Option<usize>,
+    // BLOCK only, None blocks forever
+    blocking: Option<Option<Duration>>,
+}
+
+// Streams with entries, as an array of key and entries pairs.
+fn streams_frame(read: Vec<(Bytes, Vec<StreamEntry>)>) -> Frame {
+    read.into_iter()
+        .map(|(key, entries)| Frame::from(vec![key.into(), stream_entries(entries)]))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+// Parses the BLOCK milliseconds, None for 0 which blocks forever. Shared
+// with XREADGROUP.
+pub(super) fn parse_block(parse: &mut Parse) -> Result<Option<Duration>> {
+    let ms = parse.next_string()?.parse::<i64>().map_err(|_| {
+        CommandError::InvalidArgument("timeout is not an integer or out of range".to_string())
+    })?;
+
+    if ms < 0 {
+        return Err(CommandError::InvalidArgument("timeout is negative".to_string()).into());
+    }
+
+    Ok((ms > 0).then(|| Duration::from_millis(ms as u64)))
+}
+
+// Splits the arguments after STREAMS into the keys and their IDs.
+pub(super) fn parse_streams(parse: &mut Parse, command: &str) -> Result<(Vec<Bytes>, Vec<Bytes>)> {
+    if parse.len() == 0 || !parse.len().is_multiple_of(2) {
+        return Err(CommandError::InvalidArgument(format!(
+            "Unbalanced '{}' list of streams: for each stream key an ID or '$' must be specified.",
+            command
+        ))
+        .into());
+    }
+
+    let streams = parse.len() / 2;
+    let mut args = (0..streams * 2)
+        .map(|_| parse.next_bytes())
+        .collect::<Result<Vec<_>, _>>()?;
+    let ids = args.split_off(streams);
+
+    Ok((args, ids))
+}
+
+impl XRead {
+    fn streams(&self, backend: &Backend) -> Result<Vec<(Bytes, StreamId)>> {
+        self.keys
+            .iter()
+            .zip(&self.ids)
+            .map(|(key, id)| {
+                let id = match id {
+                    Some(id) => *id,
+                    None => backend.stream_last_id(key)?.unwrap_or_default(),
+                };
+                Ok((key.clone(), id))
+            })
+            .collect()
+    }
+
+    fn op(streams: Vec<(Bytes, StreamId)>, count: Option<usize>) -> BlockOp {
+        Arc::new(move |backend: &Backend, key: &[u8]| {
+            let (key, id) = streams.iter().find(|(k, _)| k == key)?;
+            let read = backend.xread(&[(key.clone(), *id)], count).ok()?;
+
+            if read.is_empty() {
+                return None;
+            }
+
+            Some(Served {
+                reply: streams_frame(read),
+                propagate: None,
+            })
+        })
+    }
+}
+
+impl CommandExecute for XRead {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let read = backend.xread(&self.streams(&backend)?, self.count)?;
+
+        match read.is_empty() {
+            true => Ok(NULL_ARRAY.clone()),
+            false => Ok(streams_frame(read)),
+        }
+    }
+
+    // `$` is resolved when blocking, so only entries added afterwards serve
+    // the client. Reads that have entries already, or fail, are executed.
+    fn block(&self, backend: &Backend) -> Option<Block> {
+        let timeout = self.blocking?;
+        let streams = self.streams(backend).ok()?;
+
+        match backend.xread(&streams, self.count) {
+            Ok(read) if read.is_empty() => Some(Block {
+                keys: self.keys.clone(),
+                op: Self::op(streams, self.count),
+                timeout,
+                timeout_reply: NULL_ARRAY.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Frame> for XRead {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XREAD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let (mut count, mut blocking) = (None, None);
+
+        loop {
+            match parse.next_string()?.to_uppercase().as_str() {
+                "COUNT" => {
+                    let n = parse.next_int()?;
+                    count = (n > 0).then_some(n as usize);
+                }
+                "BLOCK" => blocking = Some(parse_block(&mut parse)?),
+                "STREAMS" => break,
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        let (keys, ids) = parse_streams(&mut parse, "xread")?;
+        let ids = ids
+            .iter()
+            .map(|id| match id.as_ref() {
+                b"$" => Ok(None),
+                id => Ok(Some(StreamId::parse(id, 0)?)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            keys,
+            ids,
+            count,
+            blocking,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BlockResult, XAddId};
+
+    fn add(backend: &Backend, key: &[u8], seq: u64) {
+        let id = XAddId::Explicit(StreamId::new(1, seq));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(key, id, fields, false, None).unwrap();
+    }
+
+    #[test]
+    fn test_xread_try_from_frame() {
+        let frame: Frame = vec![
+            b"xread".into(),
+            b"count".into(),
+            b"2".into(),
+            b"block".into(),
+            b"0".into(),
+            b"streams".into(),
+            b"a".into(),
+            b"b".into(),
+            b"1-1".into(),
+            b"$".into(),
+        ]
+        .into();
+        let cmd: XRead = frame.try_into().unwrap();
+
+        assert_eq!(cmd.keys, vec!["a", "b"]);
+        assert_eq!(cmd.ids, vec![Some(StreamId::new(1, 1)), None]);
+        assert_eq!(cmd.count, Some(2));
+        assert_eq!(cmd.blocking, Some(None));
+
+        let frame: Frame = vec![b"xread".into(), b"streams".into(), b"a".into()].into();
+        assert!(XRead::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_xread_execute() {
+        let backend = Backend::new();
+        add(&backend, b"a", 1);
+        add(&backend, b"a", 2);
+
+        let frame: Frame = vec![
+            b"xread".into(),
+            b"streams".into(),
+            b"a".into(),
+            b"b".into(),
+            b"1-1".into(),
+            b"0".into(),
+        ]
+        .into();
+        let cmd: XRead = frame.try_into().unwrap();
+
+        let entry = Frame::from(vec![b"1-2".into(), vec![b"f".into(), b"v".into()].into()]);
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![Frame::from(vec![b"a".into(), vec![entry].into()])].into()
+        );
+        assert!(cmd.block(&backend).is_none());
+
+        let frame: Frame =
+            vec![b"xread".into(), b"streams".into(), b"a".into(), b"$".into()].into();
+        let cmd: XRead = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL_ARRAY);
+    }
+
+    #[test]
+    fn test_xread_block() {
+        let backend = Backend::new();
+        add(&backend, b"a", 1);
+
+        let frame: Frame = vec![
+            b"xread".into(),
+            b"block".into(),
+            b"100".into(),
+            b"streams".into(),
+            b"a".into(),
+            b"$".into(),
+        ]
+        .into();
+        let cmd: XRead = frame.try_into().unwrap();
+        let block = cmd.block(&backend).unwrap();
+        assert_eq!(block.timeout, Some(Duration::from_millis(100)));
+
+        let BlockResult::Blocked(_, mut receiver) = backend.block(block.keys, block.op) else {
+            panic!("Expected Blocked");
+        };
+
+        add(&backend, b"a", 2);
+        assert!(backend.serve_blocked().is_empty());
+
+        match receiver.try_recv().unwrap() {
+            Frame::Array(streams) => assert_eq!(streams.inner.len(), 1),
+            _ => panic!("Expected Array"),
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::xrange::stream_entry;
+use super::xread::{parse_block, parse_streams};
+use super::{CommandError, CommandExecute, NULL_ARRAY};
+use crate::backend::{Backend, Block, BlockOp, GroupEntry, GroupReadId, Served, StreamId};
+use crate::resp::frame::Frame;
+use crate::resp::simple_error::SimpleError;
+
+#[derive(Debug)]
+pub struct XReadGroup {
+    group: Bytes,
+    consumer: Bytes,
+    keys: Vec<Bytes>,
+    ids: Vec<GroupReadId>,
+    count: Option<usize>,
+    // BLOCK only, None blocks forever
+    blocking: Option<Option<Duration>>,
+    noack: bool,
+}
+
+// Entries read by a group, the pending ones deleted from the stream with
+// null fields.
+pub(super) fn group_entries(entries: Vec<GroupEntry>) -> Frame {
+    entries
+        .into_iter()
+        .map(|(id, fields)| match fields {
+            Some(fields) => stream_entry(id, fields),
+            None => Frame::from(vec![id.to_string().as_bytes().into(), NULL_ARRAY.clone()]),
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn streams_frame(read: Vec<(Bytes, Vec<GroupEntry>)>) -> Frame {
+    read.into_iter()
+        .map(|(key, entries)| Frame::from(vec![key.into(), group_entries(entries)]))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+impl XReadGroup {
+    // The non-blocking XREADGROUP a served client propagates.
+    fn served_entry(&self, key: &[u8]) -> Frame {
+        let mut command = vec![
+            b"XREADGROUP".into(),
+            b"GROUP".into(),
+            self.group.clone().into(),
+            self.consumer.clone().into(),
+        ];
+
+        if let Some(count) = self.count {
+            command.push(b"COUNT".into());
+            command.push(count.to_string().as_bytes().into());
+        }
+        if self.noack {
+            command.push(b"NOACK".into());
+        }
+
+        command.extend([b"STREAMS".into(), key.into(), b">".into()]);
+        command.into()
+    }
+
+    fn op(&self) -> BlockOp {
+        let (group, consumer) = (self.group.clone(), self.consumer.clone());
+        let (count, noack) = (self.count, self.noack);
+        let keys = self.keys.clone();
+        let entries = keys
+            .iter()
+            .map(|key| self.served_entry(key))
+            .collect::<Vec<_>>();
+
+        Arc::new(move |backend: &Backend, key: &[u8]| {
+            let index = keys.iter().position(|k| k == key)?;
+            let streams = [(keys[index].clone(), GroupReadId::New)];
+
+            // a group destroyed while waiting fails the client, like in Redis
+            match backend.xreadgroup(&group, &consumer, &streams, count, noack) {
+                Ok(read) if read.is_empty() => None,
+                Ok(read) => Some(Served {
+                    reply: streams_frame(read),
+                    propagate: Some(entries[index].clone()),
+                }),
+                Err(err) => Some(Served {
+                    reply: Frame::SimpleError(SimpleError::new(err)),
+                    propagate: None,
+                }),
+            }
+        })
+    }
+}
+
+impl CommandExecute for XReadGroup {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let streams = self
+            .keys
+            .iter()
+            .cloned()
+            .zip(self.ids.iter().copied())
+            .collect::<Vec<_>>();
+        let read = backend.xreadgroup(
+            &self.group,
+            &self.consumer,
+            &streams,
+            self.count,
+            self.noack,
+        )?;
+
+        match read.is_empty() {
+            true => Ok(NULL_ARRAY.clone()),
+            false => Ok(streams_frame(read)),
+        }
+    }
+
+    // Delivering entries changes the group, BLOCK is ignored when replayed.
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+
+    // Only reads of new entries wait, and only when all the groups exist and
+    // none of them has undelivered entries.
+    fn block(&self, backend: &Backend) -> Option<Block> {
+        let timeout = self.blocking?;
+
+        if self.ids.iter().any(|id| *id != GroupReadId::New) {
+            return None;
+        }
+
+        for key in &self.keys {
+            if !backend.has_group(key, &self.group).ok()?
+                || backend.has_undelivered(key, &self.group).ok()?
+            {
+                return None;
+            }
+        }
+
+        Some(Block {
+            keys: self.keys.clone(),
+            op: self.op(),
+            timeout,
+            timeout_reply: NULL_ARRAY.clone(),
+        })
+    }
+}
+
+impl TryFrom<Frame> for XReadGroup {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XREADGROUP" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let (mut group, mut count, mut blocking, mut noack) = (None, None, None, false);
+
+        loop {
+            match parse.next_string()?.to_uppercase().as_str() {
+                "GROUP" => group = Some((parse.next_bytes()?, parse.next_bytes()?)),
+                "COUNT" => {
+                    let n = parse.next_int()?;
+                    count = (n > 0).then_some(n as usize);
+                }
+                "BLOCK" => blocking = Some(parse_block(&mut parse)?),
+                "NOACK" => noack = true,
+                "STREAMS" => break,
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        let Some((group, consumer)) = group else {
+            return Err(CommandError::InvalidArgument(
+                "Missing GROUP option for XREADGROUP".to_string(),
+            )
+            .into());
+        };
+
+        let (keys, ids) = parse_streams(&mut parse, "xreadgroup")?;
+        let ids = ids
+            .iter()
+            .map(|id| match id.as_ref() {
+                b">" => Ok(GroupReadId::New),
+                b"$" => Err(CommandError::InvalidArgument(
+                    "The $ ID is meaningless in the context of XREADGROUP: you want to read the history of this consumer by specifying a proper ID, or use the > ID to get new messages. The $ ID would just return an empty result set."
+                        .to_string(),
+                )
+                .into()),
+                id => Ok(GroupReadId::Pending(StreamId::parse(id, 0)?)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            group,
+            consumer,
+            keys,
+            ids,
+            count,
+            blocking,
+            noack,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BlockResult, XAddId};
+
+    fn add(backend: &Backend, seq: u64) {
+        let id = XAddId::Explicit(StreamId::new(1, seq));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(b"s", id, fields, false, None).unwrap();
+    }
+
+    fn xreadgroup(args: &[&'static str]) -> XReadGroup {
+        let mut frame = vec![b"xreadgroup".into()];
+        frame.extend(args.iter().map(|arg| Frame::from(arg.as_bytes())));
+        Frame::from(frame).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_xreadgroup_try_from_frame() {
+        let cmd = xreadgroup(&[
+            "group", "g", "alice", "count", "2", "noack", "block", "0", "streams", "a", "b", ">",
+            "1-1",
+        ]);
+
+        assert_eq!(
+            (cmd.group.as_ref(), cmd.consumer.as_ref()),
+            (&b"g"[..], &b"alice"[..])
+        );
+        assert_eq!(
+            cmd.ids,
+            vec![GroupReadId::New, GroupReadId::Pending(StreamId::new(1, 1))]
+        );
+        assert_eq!(cmd.count, Some(2));
+        assert_eq!(cmd.blocking, Some(None));
+        assert!(cmd.noack);
+
+        let frame: Frame = vec![
+            b"xreadgroup".into(),
+            b"streams".into(),
+            b"a".into(),
+            b">".into(),
+        ]
+        .into();
+        assert!(XReadGroup::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_xreadgroup_execute() {
+        let backend = Backend::new();
+        add(&backend, 1);
+        backend
+            .xgroup_create(b"s", b"g", Some(StreamId::MIN), false, None)
+            .unwrap();
+
+        let entry = Frame::from(vec![b"1-1".into(), vec![b"f".into(), b"v".into()].into()]);
+        let cmd = xreadgroup(&["group", "g", "alice", "streams", "s", ">"]);
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![Frame::from(vec![b"s".into(), vec![entry.clone()].into()])].into()
+        );
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *NULL_ARRAY);
+
+        let cmd = xreadgroup(&["group", "g", "alice", "streams", "s", "0"]);
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![Frame::from(vec![b"s".into(), vec![entry].into()])].into()
+        );
+
+        let cmd = xreadgroup(&["group", "missing", "alice", "streams", "s", ">"]);
+        assert!(cmd
+            .execute(backend)
+            .unwrap_err()
+            .to_string()
+            .starts_with("NOGROUP"));
+    }
+
+    #[test]
+    fn test_xreadgroup_block() {
+        let backend = Backend::new();
+        add(&backend, 1);
+        backend
+            .xgroup_create(b"s", b"g", None, false, None)
+            .unwrap();
+
+        let cmd = xreadgroup(&["group", "g", "alice", "block", "0", "streams", "s", ">"]);
+        let block = cmd.block(&backend).unwrap();
+        let BlockResult::Blocked(_, mut receiver) = backend.block(block.keys, block.op) else {
+            panic!("Expected Blocked");
+        };
+
+        add(&backend, 2);
+        let propagated = backend.serve_blocked();
+        assert_eq!(propagated, vec![cmd.served_entry(b"s")]);
+
+        match receiver.try_recv().unwrap() {
+            Frame::Array(streams) => assert_eq!(streams.inner.len(), 1),
+            _ => panic!("Expected Array"),
+        }
+        assert_eq!(backend.xpending_summary(b"s", b"g").unwrap().count, 1);
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, OK};
+use crate::backend::{Backend, StreamId};
+use crate::resp::frame::Frame;
+
+// Sets the last ID of a stream, used by AOF rewrites to restore what deleted
+// entries leave behind.
+#[derive(Debug)]
+pub struct XSetId {
+    key: Bytes,
+    id: StreamId,
+    entries_added: Option<u64>,
+    max_deleted_id: Option<StreamId>,
+}
+
+impl CommandExecute for XSetId {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        backend.xsetid(&self.key, self.id, self.entries_added, self.max_deleted_id)?;
+        Ok(OK.clone())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for XSetId {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XSETID" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let id = StreamId::parse(&parse.next_bytes()?, 0)?;
+        let (mut entries_added, mut max_deleted_id) = (None, None);
+
+        while parse.len() > 0 {
+            match parse.next_string()?.to_uppercase().as_str() {
+                "ENTRIESADDED" => {
+                    let added = parse.next_int()?;
+                    if added < 0 {
+                        return Err(CommandError::InvalidArgument(
+                            "entries_added must be positive".to_string(),
+                        )
+                        .into());
+                    }
+                    entries_added = Some(added as u64);
+                }
+                "MAXDELETEDID" => {
+                    max_deleted_id = Some(StreamId::parse(&parse.next_bytes()?, 0)?);
+                }
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        Ok(Self {
+            key,
+            id,
+            entries_added,
+            max_deleted_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    #[test]
+    fn test_xsetid_execute() {
+        let backend = Backend::new();
+        let id = XAddId::Explicit(StreamId::new(5, 0));
+        let fields = vec![("f".into(), "v".into())];
+        backend.xadd(b"s", id, fields, false, None).unwrap();
+
+        let frame: Frame = vec![
+            b"xsetid".into(),
+            b"s".into(),
+            b"9".into(),
+            b"entriesadded".into(),
+            b"3".into(),
+        ]
+        .into();
+        let cmd: XSetId = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), *OK);
+        assert_eq!(
+            backend.stream_last_id(b"s").unwrap(),
+            Some(StreamId::new(9, 0))
+        );
+
+        let frame: Frame = vec![b"xsetid".into(), b"s".into(), b"4".into()].into();
+        let cmd: XSetId = frame.try_into().unwrap();
+        assert!(cmd.execute(backend.clone()).is_err());
+
+        let frame: Frame = vec![b"xsetid".into(), b"missing".into(), b"4".into()].into();
+        let cmd: XSetId = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend).unwrap_err().to_string(),
+            "ERR no such key"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, StreamId, StreamTrim, TrimStrategy};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct XTrim {
+    key: Bytes,
+    trim: StreamTrim,
+}
+
+// Parses `<MAXLEN | MINID> [= | ~] threshold [LIMIT count]`, shared with
+// XADD.
+pub(super) fn parse_trim(parse: &mut Parse) -> Result<StreamTrim> {
+    let strategy = parse.next_string()?.to_uppercase();
+
+    let approximate = match parse.peek_string()?.as_str() {
+        "~" => true,
+        "=" => false,
+        _ => return parse_threshold(parse, &strategy, false),
+    };
+    parse.next()?;
+
+    parse_threshold(parse, &strategy, approximate)
+}
+
+fn parse_threshold(parse: &mut Parse, strategy: &str, approximate: bool) -> Result<StreamTrim> {
+    let threshold = parse.next_bytes()?;
+
+    let strategy = match strategy {
+        "MAXLEN" => {
+            let max_len = std::str::from_utf8(&threshold)
+                .ok()
+                .and_then(|s| s.parse::<i64>().ok())
+                .ok_or(CommandError::NotInteger)?;
+
+            if max_len < 0 {
+                return Err(CommandError::InvalidArgument(
+                    "The MAXLEN argument must be >= 0.".to_string(),
+                )
+                .into());
+            }
+
+            TrimStrategy::MaxLen(max_len as u64)
+        }
+        "MINID" => TrimStrategy::MinId(StreamId::parse(&threshold, 0)?),
+        _ => return Err(CommandError::SyntaxError.into()),
+    };
+
+    let limit = match parse.peek_string() {
+        Ok(option) if option.eq_ignore_ascii_case("LIMIT") => {
+            parse.next()?;
+            let limit = parse.next_int()?;
+
+            if limit < 0 {
+                return Err(CommandError::InvalidArgument(
+                    "The LIMIT argument must be >= 0.".to_string(),
+                )
+                .into());
+            }
+
+            if !approximate {
+                return Err(CommandError::InvalidArgument(
+                    "syntax error, LIMIT cannot be used without the special ~ option".to_string(),
+                )
+                .into());
+            }
+
+            Some(limit as u64)
+        }
+        _ => None,
+    };
+
+    Ok(StreamTrim {
+        strategy,
+        approximate,
+        limit,
+    })
+}
+
+// The arguments `parse_trim` reads back the trim from.
+pub(super) fn trim_args(trim: &StreamTrim) -> Vec<Frame> {
+    let (strategy, threshold) = match trim.strategy {
+        TrimStrategy::MaxLen(max_len) => ("MAXLEN", max_len.to_string()),
+        TrimStrategy::MinId(min_id) => ("MINID", min_id.to_string()),
+    };
+
+    let mut args = vec![
+        strategy.as_bytes().into(),
+        if trim.approximate { b"~" } else { b"=" }.into(),
+        threshold.as_bytes().into(),
+    ];
+
+    if let Some(limit) = trim.limit {
+        args.push(b"LIMIT".into());
+        args.push(limit.to_string().as_bytes().into());
+    }
+
+    args
+}
+
+impl CommandExecute for XTrim {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = backend.xtrim(&self.key, &self.trim)?;
+        Ok((removed as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for XTrim {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "XTRIM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let trim = parse_trim(&mut parse)?;
+
+        if parse.len() > 0 {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        Ok(Self { key, trim })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    #[test]
+    fn test_xtrim_try_from_frame() {
+        let frame: Frame = vec![
+            b"xtrim".into(),
+            b"s".into(),
+            b"minid".into(),
+            b"~".into(),
+            b"5".into(),
+            b"limit".into(),
+            b"10".into(),
+        ]
+        .into();
+        let cmd: XTrim = frame.try_into().unwrap();
+
+        assert_eq!(cmd.trim.strategy, TrimStrategy::MinId(StreamId::new(5, 0)));
+        assert!(cmd.trim.approximate);
+        assert_eq!(cmd.trim.limit, Some(10));
+
+        let frame: Frame = vec![
+            b"xtrim".into(),
+            b"s".into(),
+            b"maxlen".into(),
+            b"5".into(),
+            b"limit".into(),
+            b"10".into(),
+        ]
+        .into();
+        assert_eq!(
+            XTrim::try_from(frame).unwrap_err().to_string(),
+            "ERR syntax error, LIMIT cannot be used without the special ~ option"
+        );
+
+        let frame: Frame =
+            vec![b"xtrim".into(), b"s".into(), b"maxlen".into(), b"-1".into()].into();
+        assert!(XTrim::try_from(frame).is_err());
+    }
+
+    #[test]
+    fn test_xtrim_execute() {
+        let backend = Backend::new();
+        for seq in 1..=5 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("f".into(), "v".into())];
+            backend.xadd(b"s", id, fields, false, None).unwrap();
+        }
+
+        let frame: Frame = vec![
+            b"xtrim".into(),
+            b"s".into(),
+            b"maxlen".into(),
+            b"=".into(),
+            b"2".into(),
+        ]
+        .into();
+        let cmd: XTrim = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.into());
+        assert_eq!(backend.xlen(b"s").unwrap(), 2);
+        assert_eq!(cmd.execute(backend).unwrap(), 0.into());
+    }
+}
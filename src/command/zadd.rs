@@ -0,0 +1,187 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::{Parse, ParseError};
+use super::{CommandError, CommandExecute, NULL};
+use crate::backend::{parse_score, Backend, ScoreComparison, SetCondition};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZAdd {
+    key: Bytes,
+    condition: SetCondition,
+    comparison: Option<ScoreComparison>,
+    // CH, reply with the number of members added or updated
+    changed: bool,
+    incr: bool,
+    members: Vec<(f64, Bytes)>,
+}
+
+impl CommandExecute for ZAdd {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        if self.incr {
+            let (increment, member) = self.members[0].clone();
+            let score = backend.zincrby(
+                &self.key,
+                self.condition,
+                self.comparison,
+                increment,
+                member,
+            )?;
+
+            return Ok(score.map_or_else(|| NULL.clone(), Frame::from));
+        }
+
+        let (added, updated) = backend.zadd(
+            &self.key,
+            self.condition,
+            self.comparison,
+            self.members.clone(),
+        )?;
+
+        match self.changed {
+            true => Ok(((added + updated) as i64).into()),
+            false => Ok((added as i64).into()),
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for ZAdd {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZADD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        if parse.len() < 2 {
+            return Err(ParseError::EndOfParts.into());
+        }
+
+        let (mut nx, mut xx, mut gt, mut lt, mut changed, mut incr) =
+            (false, false, false, false, false, false);
+
+        while let Ok(option) = parse.peek_string() {
+            match option.to_uppercase().as_str() {
+                "NX" => nx = true,
+                "XX" => xx = true,
+                "GT" => gt = true,
+                "LT" => lt = true,
+                "CH" => changed = true,
+                "INCR" => incr = true,
+                _ => break,
+            }
+            parse.next()?;
+        }
+
+        if parse.len() == 0 || parse.len() % 2 != 0 {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        if nx && xx {
+            return Err(CommandError::InvalidArgument(
+                "XX and NX options at the same time are not compatible".to_string(),
+            )
+            .into());
+        }
+
+        if (gt && lt) || (nx && (gt || lt)) {
+            return Err(CommandError::InvalidArgument(
+                "GT, LT, and/or NX options at the same time are not compatible".to_string(),
+            )
+            .into());
+        }
+
+        if incr && parse.len() > 2 {
+            return Err(CommandError::InvalidArgument(
+                "INCR option supports a single increment-element pair".to_string(),
+            )
+            .into());
+        }
+
+        let mut members = Vec::with_capacity(parse.len() / 2);
+        while parse.len() > 0 {
+            let score = parse_score(&parse.next_string()?)?;
+            members.push((score, parse.next_bytes()?));
+        }
+
+        let condition = match (nx, xx) {
+            (true, _) => SetCondition::IfNotExists,
+            (_, true) => SetCondition::IfExists,
+            _ => SetCondition::Always,
+        };
+
+        let comparison = match (gt, lt) {
+            (true, _) => Some(ScoreComparison::Greater),
+            (_, true) => Some(ScoreComparison::Less),
+            _ => None,
+        };
+
+        Ok(Self {
+            key,
+            condition,
+            comparison,
+            changed,
+            incr,
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zadd(args: &[&str]) -> Result<ZAdd> {
+        let mut frame = vec![b"zadd".into(), b"zset".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_zadd_try_from_frame() {
+        let cmd = zadd(&["xx", "gt", "ch", "1", "a", "-inf", "b"]).unwrap();
+
+        assert_eq!(cmd.condition, SetCondition::IfExists);
+        assert_eq!(cmd.comparison, Some(ScoreComparison::Greater));
+        assert!(cmd.changed);
+        assert_eq!(
+            cmd.members,
+            vec![(1.0, "a".into()), (f64::NEG_INFINITY, "b".into())]
+        );
+
+        assert!(zadd(&["1"]).is_err());
+        assert!(zadd(&["nx", "xx", "1", "a"]).is_err());
+        assert!(zadd(&["nx", "gt", "1", "a"]).is_err());
+        assert!(zadd(&["incr", "1", "a", "2", "b"]).is_err());
+        assert_eq!(
+            zadd(&["nan", "a"]).unwrap_err().to_string(),
+            "ERR value is not a valid float"
+        );
+    }
+
+    #[test]
+    fn test_zadd_execute() {
+        let backend = Backend::new();
+
+        let cmd = zadd(&["1", "a", "2", "b"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+
+        let cmd = zadd(&["ch", "3", "a", "2", "b", "1", "c"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 2.into());
+
+        let cmd = zadd(&["incr", "2.5", "a"]).unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 5.5.into());
+
+        let cmd = zadd(&["nx", "incr", "1", "a"]).unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
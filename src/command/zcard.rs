@@ -0,0 +1,54 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZCard {
+    key: Bytes,
+}
+
+impl CommandExecute for ZCard {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let len = backend.zcard(&self.key)?;
+        Ok((len as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for ZCard {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZCARD" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zcard_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"zcard".into(), b"zset".into()].into();
+        let cmd: ZCard = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 0.into());
+
+        backend.set(b"zset", "value".into());
+        assert!(cmd.execute(backend).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{Backend, ScoreBound};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZCount {
+    key: Bytes,
+    min: ScoreBound,
+    max: ScoreBound,
+}
+
+impl CommandExecute for ZCount {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let count = backend.zcount(&self.key, self.min, self.max)?;
+        Ok((count as i64).into())
+    }
+}
+
+impl TryFrom<Frame> for ZCount {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZCOUNT" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let min = ScoreBound::parse(&parse.next_string()?)?;
+        let max = ScoreBound::parse(&parse.next_string()?)?;
+        parse.finish()?;
+
+        Ok(Self { key, min, max })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    #[test]
+    fn test_zcount_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                vec![(1.0, "a".into()), (2.0, "b".into()), (3.0, "c".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![
+            b"zcount".into(),
+            b"zset".into(),
+            b"(1".into(),
+            b"+inf".into(),
+        ]
+        .into();
+        let cmd: ZCount = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), 2.into());
+
+        let frame: Frame = vec![b"zcount".into(), b"zset".into(), b"x".into(), b"1".into()].into();
+        assert_eq!(
+            ZCount::try_from(frame).unwrap_err().to_string(),
+            "ERR min or max is not a float"
+        );
+    }
+}
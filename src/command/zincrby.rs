@@ -0,0 +1,81 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::{parse_score, Backend, SetCondition};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZIncrBy {
+    key: Bytes,
+    increment: f64,
+    member: Bytes,
+}
+
+impl CommandExecute for ZIncrBy {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let score = backend.zincrby(
+            &self.key,
+            SetCondition::Always,
+            None,
+            self.increment,
+            self.member.clone(),
+        )?;
+
+        // an unconditional increment always applies
+        Ok(score.unwrap_or_default().into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for ZIncrBy {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZINCRBY" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let increment = parse_score(&parse.next_string()?)?;
+        let member = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            increment,
+            member,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zincrby_execute() {
+        let backend = Backend::new();
+        let frame: Frame = vec![
+            b"zincrby".into(),
+            b"zset".into(),
+            b"1.5".into(),
+            b"a".into(),
+        ]
+        .into();
+        let cmd: ZIncrBy = frame.try_into().unwrap();
+
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.5.into());
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 3.0.into());
+
+        let frame: Frame = vec![b"zincrby".into(), b"zset".into(), b"x".into(), b"a".into()].into();
+        assert!(ZIncrBy::try_from(frame).is_err());
+    }
+}
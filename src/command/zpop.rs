@@ -0,0 +1,113 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::zrange::{score_pairs, scored_members};
+use super::{CommandError, CommandExecute};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles ZPOPMIN and ZPOPMAX.
+#[derive(Debug)]
+pub struct ZPop {
+    key: Bytes,
+    max: bool,
+    // None pops a single member, replied without nesting under RESP3
+    count: Option<usize>,
+}
+
+impl CommandExecute for ZPop {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let popped = backend.zpop(&self.key, self.max, self.count.unwrap_or(1))?;
+        Ok(scored_members(popped, true))
+    }
+
+    fn resp3_reply(&self, reply: Frame) -> Frame {
+        match self.count {
+            Some(_) => score_pairs(reply),
+            None => reply,
+        }
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for ZPop {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let max = match command.as_str() {
+            "ZPOPMIN" => false,
+            "ZPOPMAX" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+
+        let count = match parse.len() {
+            0 => None,
+            _ => match parse.next_int()? {
+                count if count >= 0 => Some(count as usize),
+                _ => {
+                    return Err(CommandError::InvalidArgument(
+                        "value is out of range, must be positive".to_string(),
+                    )
+                    .into())
+                }
+            },
+        };
+        parse.finish()?;
+
+        Ok(Self { key, max, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    #[test]
+    fn test_zpop_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                vec![(1.0, "a".into()), (2.0, "b".into()), (3.0, "c".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"zpopmax".into(), b"zset".into(), b"2".into()].into();
+        let cmd: ZPop = frame.try_into().unwrap();
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(
+            reply,
+            vec![b"c".into(), 3.0.into(), b"b".into(), 2.0.into()].into()
+        );
+        assert_eq!(
+            cmd.resp3_reply(reply),
+            vec![
+                vec![b"c".into(), 3.0.into()].into(),
+                vec![b"b".into(), 2.0.into()].into()
+            ]
+            .into()
+        );
+
+        let frame: Frame = vec![b"zpopmin".into(), b"zset".into()].into();
+        let cmd: ZPop = frame.try_into().unwrap();
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(reply, vec![b"a".into(), 1.0.into()].into());
+        assert_eq!(cmd.resp3_reply(reply.clone()), reply);
+        assert_eq!(cmd.execute(backend).unwrap(), vec![].into());
+
+        let frame: Frame = vec![b"zpopmin".into(), b"zset".into(), b"-1".into()].into();
+        assert!(ZPop::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute};
+use crate::backend::{Backend, LexBound, ScoreBound, ZRange as Range, ZRangeBy};
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZRange {
+    key: Bytes,
+    range: Range,
+    with_scores: bool,
+}
+
+// Members optionally followed by their scores, in a flat array.
+pub(super) fn scored_members(members: Vec<(Bytes, f64)>, with_scores: bool) -> Frame {
+    let mut frames = Vec::with_capacity(members.len() * (1 + with_scores as usize));
+
+    for (member, score) in members {
+        frames.push(member.into());
+        if with_scores {
+            frames.push(score.into());
+        }
+    }
+
+    frames.into()
+}
+
+// RESP3 pairs each member with its score, as an array of its own.
+pub(super) fn score_pairs(reply: Frame) -> Frame {
+    match reply {
+        Frame::Array(array) => array
+            .inner
+            .chunks(2)
+            .map(|pair| Frame::from(pair.to_vec()))
+            .collect::<Vec<_>>()
+            .into(),
+        reply => reply,
+    }
+}
+
+impl CommandExecute for ZRange {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let members = backend.zrange(&self.key, &self.range)?;
+        Ok(scored_members(members, self.with_scores))
+    }
+
+    fn resp3_reply(&self, reply: Frame) -> Frame {
+        match self.with_scores {
+            true => score_pairs(reply),
+            false => reply,
+        }
+    }
+}
+
+fn syntax_error(message: &str) -> CommandError {
+    CommandError::InvalidArgument(format!("syntax error, {}", message))
+}
+
+fn parse_rank(s: &Bytes) -> Result<i64, CommandError> {
+    std::str::from_utf8(s)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(CommandError::NotInteger)
+}
+
+fn parse_score_bound(s: &Bytes) -> Result<ScoreBound, CommandError> {
+    ScoreBound::parse(std::str::from_utf8(s).unwrap_or_default())
+}
+
+impl TryFrom<Frame> for ZRange {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZRANGE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let start = parse.next_bytes()?;
+        let stop = parse.next_bytes()?;
+
+        let (mut by_score, mut by_lex, mut rev, mut with_scores) = (false, false, false, false);
+        let mut limit = None;
+
+        while parse.len() > 0 {
+            match parse.next_string()?.to_uppercase().as_str() {
+                "BYSCORE" => by_score = true,
+                "BYLEX" => by_lex = true,
+                "REV" => rev = true,
+                "WITHSCORES" => with_scores = true,
+                "LIMIT" => limit = Some((parse.next_int()?, parse.next_int()?)),
+                _ => return Err(CommandError::SyntaxError.into()),
+            }
+        }
+
+        if by_score && by_lex {
+            return Err(CommandError::SyntaxError.into());
+        }
+
+        if limit.is_some() && !by_score && !by_lex {
+            return Err(syntax_error(
+                "LIMIT is only supported in combination with either BYSCORE or BYLEX",
+            )
+            .into());
+        }
+
+        if with_scores && by_lex {
+            return Err(syntax_error("WITHSCORES not supported in combination with BYLEX").into());
+        }
+
+        // with REV the score and lex ranges are given from max to min
+        let (min, max) = match rev {
+            true => (stop, start),
+            false => (start, stop),
+        };
+
+        let by = match (by_score, by_lex) {
+            (true, _) => ZRangeBy::Score(parse_score_bound(&min)?, parse_score_bound(&max)?),
+            (_, true) => ZRangeBy::Lex(LexBound::parse(&min)?, LexBound::parse(&max)?),
+            _ if rev => ZRangeBy::Rank(parse_rank(&max)?, parse_rank(&min)?),
+            _ => ZRangeBy::Rank(parse_rank(&min)?, parse_rank(&max)?),
+        };
+
+        // a negative offset selects nothing, a negative count everything
+        let (offset, count) = match limit {
+            Some((offset, count)) => (
+                usize::try_from(offset).unwrap_or(usize::MAX),
+                usize::try_from(count).ok(),
+            ),
+            None => (0, None),
+        };
+
+        Ok(Self {
+            key,
+            range: Range {
+                by,
+                rev,
+                offset,
+                count,
+            },
+            with_scores,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    fn zrange(args: &[&str]) -> Result<ZRange> {
+        let mut frame = vec![b"zrange".into(), b"zset".into()];
+        frame.extend(args.iter().map(|arg| arg.as_bytes().into()));
+        Frame::from(frame).try_into()
+    }
+
+    #[test]
+    fn test_zrange_try_from_frame() {
+        let cmd = zrange(&["(5", "1", "byscore", "rev", "limit", "1", "-1"]).unwrap();
+        assert_eq!(
+            cmd.range,
+            Range {
+                by: ZRangeBy::Score(
+                    ScoreBound {
+                        value: 1.0,
+                        exclusive: false
+                    },
+                    ScoreBound {
+                        value: 5.0,
+                        exclusive: true
+                    }
+                ),
+                rev: true,
+                offset: 1,
+                count: None,
+            }
+        );
+
+        assert!(zrange(&["0", "-1", "limit", "0", "1"]).is_err());
+        assert!(zrange(&["-", "+", "bylex", "withscores"]).is_err());
+        assert!(zrange(&["a", "+", "bylex"]).is_err());
+        assert!(zrange(&["0", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_zrange_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                vec![(1.0, "a".into()), (2.0, "b".into()), (3.0, "c".into())],
+            )
+            .unwrap();
+
+        let cmd = zrange(&["0", "1", "rev", "withscores"]).unwrap();
+        let reply = cmd.execute(backend.clone()).unwrap();
+        assert_eq!(
+            reply,
+            vec![b"c".into(), 3.0.into(), b"b".into(), 2.0.into()].into()
+        );
+        assert_eq!(
+            cmd.resp3_reply(reply),
+            vec![
+                vec![b"c".into(), 3.0.into()].into(),
+                vec![b"b".into(), 2.0.into()].into()
+            ]
+            .into()
+        );
+
+        let cmd = zrange(&["[b", "+", "bylex", "limit", "1", "5"]).unwrap();
+        let reply = cmd.execute(backend).unwrap();
+        assert_eq!(reply, vec![b"c".into()].into());
+        assert_eq!(cmd.resp3_reply(reply.clone()), reply);
+    }
+}
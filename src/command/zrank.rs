@@ -0,0 +1,103 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandError, CommandExecute, NULL, NULL_ARRAY};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+// Handles ZRANK and ZREVRANK.
+#[derive(Debug)]
+pub struct ZRank {
+    key: Bytes,
+    member: Bytes,
+    rev: bool,
+    with_score: bool,
+}
+
+impl CommandExecute for ZRank {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let rank = backend.zrank(&self.key, &self.member, self.rev)?;
+
+        let frame = match (rank, self.with_score) {
+            (Some((rank, score)), true) => vec![(rank as i64).into(), score.into()].into(),
+            (Some((rank, _)), false) => (rank as i64).into(),
+            (None, true) => NULL_ARRAY.clone(),
+            (None, false) => NULL.clone(),
+        };
+
+        Ok(frame)
+    }
+}
+
+impl TryFrom<Frame> for ZRank {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        let rev = match command.as_str() {
+            "ZRANK" => false,
+            "ZREVRANK" => true,
+            _ => anyhow::bail!("Invalid command"),
+        };
+
+        let key = parse.next_bytes()?;
+        let member = parse.next_bytes()?;
+
+        let with_score = match parse.len() {
+            0 => false,
+            _ if parse.next_string()?.to_uppercase() == "WITHSCORE" => true,
+            _ => return Err(CommandError::SyntaxError.into()),
+        };
+        parse.finish()?;
+
+        Ok(Self {
+            key,
+            member,
+            rev,
+            with_score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    #[test]
+    fn test_zrank_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                vec![(1.0, "a".into()), (2.0, "b".into()), (3.0, "c".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"zrank".into(), b"zset".into(), b"b".into()].into();
+        let cmd: ZRank = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+
+        let frame: Frame = vec![
+            b"zrevrank".into(),
+            b"zset".into(),
+            b"c".into(),
+            b"withscore".into(),
+        ]
+        .into();
+        let cmd: ZRank = frame.try_into().unwrap();
+        assert_eq!(
+            cmd.execute(backend.clone()).unwrap(),
+            vec![0.into(), 3.0.into()].into()
+        );
+
+        let frame: Frame = vec![b"zrank".into(), b"zset".into(), b"x".into()].into();
+        let cmd: ZRank = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::CommandExecute;
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZRem {
+    key: Bytes,
+    members: Vec<Bytes>,
+}
+
+impl CommandExecute for ZRem {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let removed = backend.zrem(&self.key, &self.members)?;
+        Ok((removed as i64).into())
+    }
+
+    fn propagate(&self, frame: &Frame) -> Option<Frame> {
+        Some(frame.clone())
+    }
+}
+
+impl TryFrom<Frame> for ZRem {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZREM" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let mut members = vec![parse.next_bytes()?];
+        while parse.len() > 0 {
+            members.push(parse.next_bytes()?);
+        }
+
+        Ok(Self { key, members })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    #[test]
+    fn test_zrem_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(
+                b"zset",
+                SetCondition::Always,
+                None,
+                vec![(1.0, "a".into()), (2.0, "b".into())],
+            )
+            .unwrap();
+
+        let frame: Frame = vec![b"zrem".into(), b"zset".into(), b"a".into(), b"x".into()].into();
+        let cmd: ZRem = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.into());
+        assert_eq!(backend.zcard(b"zset").unwrap(), 1);
+
+        let frame: Frame = vec![b"zrem".into(), b"zset".into()].into();
+        assert!(ZRem::try_from(frame).is_err());
+    }
+}
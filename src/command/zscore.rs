@@ -0,0 +1,61 @@
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::parse::Parse;
+use super::{CommandExecute, NULL};
+use crate::backend::Backend;
+use crate::resp::frame::Frame;
+
+#[derive(Debug)]
+pub struct ZScore {
+    key: Bytes,
+    member: Bytes,
+}
+
+impl CommandExecute for ZScore {
+    fn execute(&self, backend: Backend) -> Result<Frame> {
+        let score = backend.zscore(&self.key, &self.member)?;
+        Ok(score.map_or_else(|| NULL.clone(), Frame::from))
+    }
+}
+
+impl TryFrom<Frame> for ZScore {
+    type Error = anyhow::Error;
+
+    fn try_from(frame: Frame) -> Result<Self> {
+        let mut parse = Parse::try_new(frame)?;
+        let command = parse.next_string()?.to_uppercase();
+
+        if command != "ZSCORE" {
+            anyhow::bail!("Invalid command");
+        }
+
+        let key = parse.next_bytes()?;
+        let member = parse.next_bytes()?;
+        parse.finish()?;
+
+        Ok(Self { key, member })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SetCondition;
+
+    #[test]
+    fn test_zscore_execute() {
+        let backend = Backend::new();
+        backend
+            .zadd(b"zset", SetCondition::Always, None, vec![(1.5, "a".into())])
+            .unwrap();
+
+        let frame: Frame = vec![b"zscore".into(), b"zset".into(), b"a".into()].into();
+        let cmd: ZScore = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend.clone()).unwrap(), 1.5.into());
+
+        let frame: Frame = vec![b"zscore".into(), b"zset".into(), b"b".into()].into();
+        let cmd: ZScore = frame.try_into().unwrap();
+        assert_eq!(cmd.execute(backend).unwrap(), *NULL);
+    }
+}
@@ -0,0 +1,779 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::{warn, Level, Metadata};
+
+use crate::persistence::{format_save_rules, parse_save_rules, AppendFsync, SaveRule};
+
+// Parameters supported by the config file, the command line and CONFIG.
+pub const PARAMETERS: &[&str] = &[
+    "bind",
+    "port",
+    "maxclients",
+    "timeout",
+    "requirepass",
+    "save",
+    "appendonly",
+    "appendfilename",
+    "appendfsync",
+    "maxmemory",
+    "loglevel",
+    "databases",
+    "dir",
+    "dbfilename",
+];
+
+// Parameters that can only be set on startup.
+const IMMUTABLE: &[&str] = &["bind", "port", "appendfilename", "databases"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Debug,
+    Verbose,
+    Notice,
+    Warning,
+    Nothing,
+}
+
+impl FromStr for LogLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "debug" => Ok(LogLevel::Debug),
+            "verbose" => Ok(LogLevel::Verbose),
+            "notice" => Ok(LogLevel::Notice),
+            "warning" => Ok(LogLevel::Warning),
+            "nothing" => Ok(LogLevel::Nothing),
+            _ => anyhow::bail!("argument(s) must be one of the following: debug, verbose, notice, warning, nothing"),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Verbose => "verbose",
+            LogLevel::Notice => "notice",
+            LogLevel::Warning => "warning",
+            LogLevel::Nothing => "nothing",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Notice as u8);
+
+// Applies the log level to the tracing filter installed by `log_enabled`.
+// Callsites cache whether they are enabled the first time they fire, the
+// cache is rebuilt for them to follow the new level.
+pub fn set_log_level(level: LogLevel) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+    tracing::callsite::rebuild_interest_cache();
+}
+
+// Tracing filter following the live `loglevel`, so CONFIG SET takes effect
+// without a restart.
+pub fn log_enabled(metadata: &Metadata<'_>) -> bool {
+    let max = match LOG_LEVEL.load(Ordering::Relaxed) {
+        l if l == LogLevel::Debug as u8 => Level::TRACE,
+        l if l == LogLevel::Verbose as u8 => Level::DEBUG,
+        l if l == LogLevel::Notice as u8 => Level::INFO,
+        l if l == LogLevel::Warning as u8 => Level::WARN,
+        _ => return false,
+    };
+
+    *metadata.level() <= max
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub bind: Vec<String>,
+    pub port: u16,
+    pub maxclients: u64,
+    // seconds a client may stay idle before it is disconnected, 0 to disable
+    pub timeout: u64,
+    pub requirepass: Option<String>,
+    pub save: Vec<SaveRule>,
+    pub appendonly: bool,
+    pub appendfilename: String,
+    pub appendfsync: AppendFsync,
+    pub maxmemory: u64,
+    pub loglevel: LogLevel,
+    pub databases: u64,
+    pub dir: PathBuf,
+    pub dbfilename: String,
+    // file the config was loaded from, which CONFIG REWRITE updates
+    pub config_file: Option<PathBuf>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: vec!["*".to_string(), "-::*".to_string()],
+            port: 6379,
+            maxclients: 10000,
+            timeout: 0,
+            requirepass: None,
+            save: vec![
+                SaveRule {
+                    seconds: 3600,
+                    changes: 1,
+                },
+                SaveRule {
+                    seconds: 300,
+                    changes: 100,
+                },
+                SaveRule {
+                    seconds: 60,
+                    changes: 10000,
+                },
+            ],
+            appendonly: false,
+            appendfilename: "appendonly.aof".to_string(),
+            appendfsync: AppendFsync::EverySec,
+            maxmemory: 0,
+            loglevel: LogLevel::Notice,
+            databases: 16,
+            dir: PathBuf::from("."),
+            dbfilename: "dump.rdb".to_string(),
+            config_file: None,
+        }
+    }
+}
+
+// Command line flags, named after the config file directives like
+// `redis-server [/path/to/redis.conf] [--port 6380]`. Flags override the file.
+#[derive(Debug, Default, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Path to a redis.conf style config file
+    pub config: Option<PathBuf>,
+
+    #[arg(long, num_args = 1.., value_name = "ADDR")]
+    pub bind: Option<Vec<String>>,
+    #[arg(long)]
+    pub port: Option<String>,
+    #[arg(long)]
+    pub maxclients: Option<String>,
+    #[arg(long)]
+    pub timeout: Option<String>,
+    #[arg(long)]
+    pub requirepass: Option<String>,
+    #[arg(long, allow_hyphen_values = true)]
+    pub save: Option<String>,
+    #[arg(long)]
+    pub appendonly: Option<String>,
+    #[arg(long)]
+    pub appendfilename: Option<String>,
+    #[arg(long)]
+    pub appendfsync: Option<String>,
+    #[arg(long)]
+    pub maxmemory: Option<String>,
+    #[arg(long)]
+    pub loglevel: Option<String>,
+    #[arg(long)]
+    pub databases: Option<String>,
+    #[arg(long)]
+    pub dir: Option<String>,
+    #[arg(long)]
+    pub dbfilename: Option<String>,
+}
+
+impl ServerConfig {
+    pub fn from_cli(cli: &Cli) -> Result<Self> {
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+
+        let flags = [
+            ("bind", cli.bind.as_ref().map(|addrs| addrs.join(" "))),
+            ("port", cli.port.clone()),
+            ("maxclients", cli.maxclients.clone()),
+            ("timeout", cli.timeout.clone()),
+            ("requirepass", cli.requirepass.clone()),
+            ("save", cli.save.clone()),
+            ("appendonly", cli.appendonly.clone()),
+            ("appendfilename", cli.appendfilename.clone()),
+            ("appendfsync", cli.appendfsync.clone()),
+            ("maxmemory", cli.maxmemory.clone()),
+            ("loglevel", cli.loglevel.clone()),
+            ("databases", cli.databases.clone()),
+            ("dir", cli.dir.clone()),
+            ("dbfilename", cli.dbfilename.clone()),
+        ];
+
+        for (name, value) in flags {
+            if let Some(value) = value {
+                config
+                    .set(name, &value)
+                    .map_err(|e| anyhow::anyhow!("Invalid --{} '{}': {}", name, value, e))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut config = Self::parse(&contents)?;
+        config.config_file = Some(fs::canonicalize(path)?);
+
+        Ok(config)
+    }
+
+    // Parses the `redis.conf` format: one directive per line followed by its
+    // arguments, which may be quoted. Directives this server does not support
+    // are skipped so a stock redis.conf can be used as is.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut config = Self::default();
+        let mut save_seen = false;
+
+        for (i, line) in contents.lines().enumerate() {
+            let args = split_args(line)
+                .map_err(|e| anyhow::anyhow!("line {}: {}: {}", i + 1, e, line.trim()))?;
+
+            let Some((name, values)) = args.split_first() else {
+                continue;
+            };
+            let name = name.to_lowercase();
+
+            if !PARAMETERS.contains(&name.as_str()) {
+                warn!(
+                    "Skipping unsupported directive '{}' at line {}",
+                    name,
+                    i + 1
+                );
+                continue;
+            }
+
+            let result = match name.as_str() {
+                // every save line adds a rule, the first one replaces the
+                // default rules
+                "save" => {
+                    if !save_seen {
+                        config.save.clear();
+                        save_seen = true;
+                    }
+
+                    parse_save_rules(&values.join(" ")).map(|rules| config.save.extend(rules))
+                }
+                "bind" => config.set(&name, &values.join(" ")),
+                _ if values.len() != 1 => Err(anyhow::anyhow!("wrong number of arguments")),
+                _ => config.set(&name, &values[0]),
+            };
+
+            result.map_err(|e| anyhow::anyhow!("line {}: {}: {}", i + 1, e, line.trim()))?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name.to_lowercase().as_str() {
+            "bind" => {
+                let addrs = value
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+
+                for addr in &addrs {
+                    parse_bind_addr(addr)?;
+                }
+
+                self.bind = addrs;
+            }
+            "port" => self.port = value.parse()?,
+            "maxclients" => {
+                let maxclients = value.parse()?;
+                if maxclients == 0 {
+                    anyhow::bail!("argument must be between 1 and 4294967295 inclusive");
+                }
+                self.maxclients = maxclients;
+            }
+            "timeout" => self.timeout = value.parse()?,
+            "requirepass" => {
+                self.requirepass = match value {
+                    "" => None,
+                    password => Some(password.to_string()),
+                }
+            }
+            "save" => self.save = parse_save_rules(value)?,
+            "appendonly" => self.appendonly = parse_bool(value)?,
+            "appendfilename" => {
+                if value.is_empty() || value.contains('/') {
+                    anyhow::bail!("appendfilename can't be a path, just a filename");
+                }
+                self.appendfilename = value.to_string();
+            }
+            "appendfsync" => self.appendfsync = value.parse()?,
+            "maxmemory" => self.maxmemory = parse_memory(value)?,
+            "loglevel" => self.loglevel = value.parse()?,
+            "databases" => {
+                let databases = value.parse()?;
+                if databases == 0 {
+                    anyhow::bail!("argument must be between 1 and 2147483647 inclusive");
+                }
+                self.databases = databases;
+            }
+            "dir" => {
+                let dir = PathBuf::from(value);
+                if !dir.is_dir() {
+                    anyhow::bail!("No such file or directory");
+                }
+                self.dir = dir;
+            }
+            "dbfilename" => {
+                if value.is_empty() || value.contains('/') {
+                    anyhow::bail!("dbfilename can't be a path, just a filename");
+                }
+                self.dbfilename = value.to_string();
+            }
+            _ => anyhow::bail!("Unknown option '{}'", name),
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "bind" => self.bind.join(" "),
+            "port" => self.port.to_string(),
+            "maxclients" => self.maxclients.to_string(),
+            "timeout" => self.timeout.to_string(),
+            "requirepass" => self.requirepass.clone().unwrap_or_default(),
+            "save" => format_save_rules(&self.save),
+            "appendonly" => format_bool(self.appendonly),
+            "appendfilename" => self.appendfilename.clone(),
+            "appendfsync" => self.appendfsync.to_string(),
+            "maxmemory" => self.maxmemory.to_string(),
+            "loglevel" => self.loglevel.to_string(),
+            "databases" => self.databases.to_string(),
+            "dir" => fs::canonicalize(&self.dir)
+                .unwrap_or_else(|_| self.dir.clone())
+                .display()
+                .to_string(),
+            "dbfilename" => self.dbfilename.clone(),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    pub fn is_mutable(name: &str) -> bool {
+        !IMMUTABLE.contains(&name)
+    }
+
+    pub fn rdb_path(&self) -> PathBuf {
+        self.dir.join(&self.dbfilename)
+    }
+
+    pub fn aof_path(&self) -> PathBuf {
+        self.dir.join(&self.appendfilename)
+    }
+
+    // Addresses to listen on, with whether failing to bind them is fatal.
+    // Addresses prefixed with `-` are optional, like in Redis.
+    pub fn listen_addrs(&self) -> Vec<(SocketAddr, bool)> {
+        self.bind
+            .iter()
+            .filter_map(|addr| parse_bind_addr(addr).ok())
+            .map(|(ip, required)| (SocketAddr::new(ip, self.port), required))
+            .collect()
+    }
+
+    // Writes the live config back to the file it was loaded from. Lines of
+    // supported directives are updated in place, everything else in the file
+    // is kept as is and changed parameters missing from it are appended.
+    pub fn rewrite(&self) -> Result<()> {
+        let Some(path) = &self.config_file else {
+            anyhow::bail!("The server is running without a config file");
+        };
+
+        let contents = fs::read_to_string(path).unwrap_or_default();
+        let defaults = Self::default();
+        let mut written = HashSet::new();
+        let mut lines = Vec::new();
+
+        for line in contents.lines() {
+            let name = split_args(line)
+                .ok()
+                .and_then(|args| args.first().map(|name| name.to_lowercase()));
+
+            match name {
+                Some(name) if PARAMETERS.contains(&name.as_str()) => {
+                    if written.insert(name.clone()) {
+                        lines.extend(self.directive_lines(&name));
+                    }
+                }
+                _ => lines.push(line.to_string()),
+            }
+        }
+
+        let missing = PARAMETERS
+            .iter()
+            .filter(|name| !written.contains(**name))
+            .filter(|name| self.get(name) != defaults.get(name))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            lines.push("# Generated by CONFIG REWRITE".to_string());
+
+            for name in missing {
+                lines.extend(self.directive_lines(name));
+            }
+        }
+
+        let mut data = lines.join("\n");
+        data.push('\n');
+
+        let tmp = path.with_file_name(format!("redis.conf.tmp-{}", std::process::id()));
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    fn directive_lines(&self, name: &str) -> Vec<String> {
+        match name {
+            "save" if self.save.is_empty() => vec!["save \"\"".to_string()],
+            "save" => self
+                .save
+                .iter()
+                .map(|rule| format!("save {} {}", rule.seconds, rule.changes))
+                .collect(),
+            "bind" => vec![format!("bind {}", self.bind.join(" "))],
+            "requirepass" if self.requirepass.is_none() => vec![],
+            name => vec![format!(
+                "{} {}",
+                name,
+                quote(&self.get(name).unwrap_or_default())
+            )],
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => anyhow::bail!("argument must be 'yes' or 'no'"),
+    }
+}
+
+fn format_bool(value: bool) -> String {
+    match value {
+        true => "yes".to_string(),
+        false => "no".to_string(),
+    }
+}
+
+fn parse_bind_addr(addr: &str) -> Result<(IpAddr, bool)> {
+    let (addr, required) = match addr.strip_prefix('-') {
+        Some(addr) => (addr, false),
+        None => (addr, true),
+    };
+
+    let ip = match addr {
+        "*" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        "::*" => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        addr => addr
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid bind address '{}'", addr))?,
+    };
+
+    Ok((ip, required))
+}
+
+// Memory sizes accept the units of redis.conf: 1k is 1000 bytes while 1kb is
+// 1024 bytes, and so on for m, mb, g and gb.
+pub fn parse_memory(value: &str) -> Result<u64> {
+    let value = value.to_lowercase();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+
+    let (number, unit) = value.split_at(digits);
+    let multiplier = match unit {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        _ => anyhow::bail!("argument must be a memory value"),
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| anyhow::anyhow!("argument must be a memory value"))
+}
+
+// Splits a config line into arguments the way Redis does: blank lines and
+// comments have none, arguments are separated by spaces and may be quoted
+// with "double quotes" (supporting escapes) or 'single quotes'.
+pub fn split_args(line: &str) -> Result<Vec<String>> {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(vec![]);
+    }
+
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+
+        let mut arg = String::new();
+
+        match first {
+            '"' => {
+                chars.next();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => arg.push('\n'),
+                            Some('r') => arg.push('\r'),
+                            Some('t') => arg.push('\t'),
+                            Some('b') => arg.push('\u{8}'),
+                            Some('a') => arg.push('\u{7}'),
+                            Some('x') => {
+                                let hex = chars.by_ref().take(2).collect::<String>();
+                                let byte = u8::from_str_radix(&hex, 16)
+                                    .map_err(|_| anyhow::anyhow!("Invalid escape"))?;
+                                arg.push(byte as char);
+                            }
+                            Some(c) => arg.push(c),
+                            None => anyhow::bail!("Unbalanced quotes"),
+                        },
+                        Some(c) => arg.push(c),
+                        None => anyhow::bail!("Unbalanced quotes"),
+                    }
+                }
+            }
+            '\'' => {
+                chars.next();
+
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            arg.push('\'');
+                        }
+                        Some(c) => arg.push(c),
+                        None => anyhow::bail!("Unbalanced quotes"),
+                    }
+                }
+            }
+            _ => {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    arg.push(c);
+                }
+            }
+        }
+
+        // a closing quote must be followed by a space or the end of the line
+        if first == '"' || first == '\'' {
+            if let Some(c) = chars.peek() {
+                if !c.is_whitespace() {
+                    anyhow::bail!("Unbalanced quotes");
+                }
+            }
+        }
+
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_graphic() && c != '"' && c != '\'' && c != '\\');
+
+    if plain {
+        return value.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+    struct CountEvents(Arc<AtomicUsize>);
+
+    impl<S: Subscriber> Layer<S> for CountEvents {
+        fn on_event(&self, _: &Event<'_>, _: Context<'_, S>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_set_log_level_fired_callsites() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let subscriber = tracing_subscriber::registry().with(
+            CountEvents(count.clone())
+                .with_filter(tracing_subscriber::filter::filter_fn(log_enabled)),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let info = || tracing::info!("info");
+            let debug = || tracing::debug!("debug");
+
+            set_log_level(LogLevel::Notice);
+            info();
+            debug();
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+
+            set_log_level(LogLevel::Warning);
+            info();
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+
+            set_log_level(LogLevel::Verbose);
+            info();
+            debug();
+            assert_eq!(count.load(Ordering::SeqCst), 3);
+
+            set_log_level(LogLevel::Notice);
+        });
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert!(split_args("  # comment").unwrap().is_empty());
+        assert_eq!(split_args("port 6380").unwrap(), vec!["port", "6380"]);
+        assert_eq!(
+            split_args(r#"requirepass "pa ss\"\x41" 'it\'s'"#).unwrap(),
+            vec!["requirepass", "pa ss\"A", "it's"]
+        );
+        assert_eq!(split_args(r#"save """#).unwrap(), vec!["save", ""]);
+        assert!(split_args(r#"requirepass "open"#).is_err());
+        assert!(split_args(r#"requirepass "a"b"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("100").unwrap(), 100);
+        assert_eq!(parse_memory("1k").unwrap(), 1000);
+        assert_eq!(parse_memory("1KB").unwrap(), 1024);
+        assert_eq!(parse_memory("2gb").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert!(parse_memory("1tb").is_err());
+        assert!(parse_memory("mb").is_err());
+    }
+
+    #[test]
+    fn test_config_parse() {
+        let config = ServerConfig::parse(
+            r#"
+# a stock redis.conf has many more directives
+daemonize no
+bind 127.0.0.1 -::1
+port 6380
+requirepass "secret word"
+save 900 1
+save 300 10
+appendonly yes
+maxmemory 100mb
+loglevel warning
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.bind, vec!["127.0.0.1", "-::1"]);
+        assert_eq!(config.port, 6380);
+        assert_eq!(config.requirepass.as_deref(), Some("secret word"));
+        assert_eq!(config.get("save").unwrap(), "900 1 300 10");
+        assert!(config.appendonly);
+        assert_eq!(config.maxmemory, 100 * 1024 * 1024);
+        assert_eq!(config.loglevel, LogLevel::Warning);
+        assert_eq!(
+            config.listen_addrs(),
+            vec![
+                ("127.0.0.1:6380".parse().unwrap(), true),
+                ("[::1]:6380".parse().unwrap(), false)
+            ]
+        );
+
+        assert!(ServerConfig::parse("port nope").is_err());
+        assert!(ServerConfig::parse("port 1 2").is_err());
+    }
+
+    #[test]
+    fn test_config_from_cli() {
+        let cli = Cli::parse_from(["simple-redis", "--port", "7000", "--save", ""]);
+        let config = ServerConfig::from_cli(&cli).unwrap();
+
+        assert_eq!(config.port, 7000);
+        assert!(config.save.is_empty());
+
+        let cli = Cli::parse_from(["simple-redis", "--appendfsync", "sometimes"]);
+        assert!(ServerConfig::from_cli(&cli).is_err());
+    }
+
+    #[test]
+    fn test_config_rewrite() {
+        let path = std::env::temp_dir().join(format!("simple-redis-{}.conf", std::process::id()));
+        fs::write(
+            &path,
+            "# keep me\nport 6380\nsave 900 1\nsave 300 10\ndaemonize no\n",
+        )
+        .unwrap();
+
+        let mut config = ServerConfig::from_file(&path).unwrap();
+        config.set("save", "60 5").unwrap();
+        config.set("requirepass", "p w").unwrap();
+        config.rewrite().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# keep me\nport 6380\nsave 60 5\ndaemonize no\n# Generated by CONFIG REWRITE\nrequirepass \"p w\"\n"
+        );
+        assert_eq!(ServerConfig::from_file(&path).unwrap(), config);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
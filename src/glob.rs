@@ -0,0 +1,128 @@
+// Glob-style pattern matching as done by Redis for CONFIG GET, KEYS and
+// pattern subscriptions: `*` matches any sequence, `?` any single byte,
+// `[abc]`, `[^abc]` and `[a-z]` a class of bytes, and `\` escapes the next one.
+pub fn matches(pattern: &[u8], string: &[u8], nocase: bool) -> bool {
+    let eq = |a: u8, b: u8| match nocase {
+        true => a.eq_ignore_ascii_case(&b),
+        false => a == b,
+    };
+
+    let (mut p, mut s) = (0, 0);
+    // where to resume after the last `*` when the rest does not match
+    let mut backtrack = None;
+
+    while s < string.len() {
+        let matched = match pattern.get(p) {
+            Some(b'*') => {
+                while pattern.get(p) == Some(&b'*') {
+                    p += 1;
+                }
+
+                if p == pattern.len() {
+                    return true;
+                }
+
+                backtrack = Some((p, s));
+                continue;
+            }
+            Some(b'?') => Some(p + 1),
+            Some(b'[') => match_class(pattern, p, string[s], nocase),
+            Some(b'\\') if p + 1 < pattern.len() => eq(pattern[p + 1], string[s]).then_some(p + 2),
+            Some(&c) => eq(c, string[s]).then_some(p + 1),
+            None => None,
+        };
+
+        match (matched, backtrack) {
+            (Some(next), _) => {
+                p = next;
+                s += 1;
+            }
+            (None, Some((star_p, star_s))) => {
+                p = star_p;
+                s = star_s + 1;
+                backtrack = Some((star_p, star_s + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+// Matches a byte against the class starting at `pattern[start]`, returning the
+// position after the class when it matches.
+fn match_class(pattern: &[u8], start: usize, c: u8, nocase: bool) -> Option<usize> {
+    let fold = |b: u8| match nocase {
+        true => b.to_ascii_lowercase(),
+        false => b,
+    };
+
+    let c = fold(c);
+    let mut p = start + 1;
+    let negate = pattern.get(p) == Some(&b'^');
+    if negate {
+        p += 1;
+    }
+
+    let mut matched = false;
+
+    loop {
+        match pattern.get(p) {
+            // an unterminated class ends with the pattern
+            None => break,
+            Some(b']') => {
+                p += 1;
+                break;
+            }
+            Some(b'\\') if p + 1 < pattern.len() => {
+                matched |= fold(pattern[p + 1]) == c;
+                p += 2;
+            }
+            Some(&from) if pattern.get(p + 1) == Some(&b'-') && p + 2 < pattern.len() => {
+                let (from, to) = (fold(from), fold(pattern[p + 2]));
+                let (low, high) = if from <= to { (from, to) } else { (to, from) };
+                matched |= low <= c && c <= high;
+                p += 3;
+            }
+            Some(&b) => {
+                matched |= fold(b) == c;
+                p += 1;
+            }
+        }
+    }
+
+    (matched != negate).then_some(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches(b"*", b"", false));
+        assert!(matches(b"*", b"anything", false));
+        assert!(matches(b"h?llo", b"hello", false));
+        assert!(!matches(b"h?llo", b"hllo", false));
+        assert!(matches(b"h*llo", b"heeeello", false));
+        assert!(matches(b"*max*", b"maxmemory", false));
+        assert!(matches(b"a*b*c", b"abxbxc", false));
+        assert!(!matches(b"a*b*c", b"abxbx", false));
+        assert!(matches(b"h\\*llo", b"h*llo", false));
+        assert!(!matches(b"h\\*llo", b"hello", false));
+        assert!(matches(b"PORT", b"port", true));
+        assert!(!matches(b"PORT", b"port", false));
+    }
+
+    #[test]
+    fn test_matches_class() {
+        assert!(matches(b"h[ae]llo", b"hallo", false));
+        assert!(!matches(b"h[ae]llo", b"hillo", false));
+        assert!(matches(b"h[^e]llo", b"hallo", false));
+        assert!(!matches(b"h[^e]llo", b"hello", false));
+        assert!(matches(b"h[a-c]llo", b"hbllo", false));
+        assert!(matches(b"h[c-a]llo", b"hbllo", false));
+        assert!(!matches(b"h[a-c]llo", b"hdllo", false));
+        assert!(matches(b"h[A-C]llo", b"hbllo", true));
+    }
+}
@@ -1,4 +1,8 @@
 pub mod backend;
 pub mod command;
+pub mod config;
+pub mod glob;
 pub mod network;
+pub mod persistence;
 pub mod resp;
+pub mod scripting;
@@ -1,21 +1,100 @@
 use anyhow::Result;
+use clap::Parser;
+use simple_redis::config::{self, Cli, ServerConfig};
+use simple_redis::persistence::{self, aof};
 use simple_redis::{backend::Backend, network::stream_handle};
-use std::net::Ipv4Addr;
+use socket2::{Domain, Socket, Type};
 use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{info, warn};
+use tracing_subscriber::{filter, prelude::*};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(filter::filter_fn(config::log_enabled)))
+        .init();
+
+    let config = ServerConfig::from_cli(&Cli::parse())?;
+    config::set_log_level(config.loglevel);
+
+    match &config.config_file {
+        Some(path) => info!("Configuration loaded from {}", path.display()),
+        None => warn!("No config file specified, using the default config"),
+    }
+
+    let mut listeners = Vec::new();
+    for (addr, required) in config.listen_addrs() {
+        match listen(addr) {
+            Ok(listener) => {
+                info!("Listening on {}", addr);
+                listeners.push(listener);
+            }
+            Err(e) if !required => warn!("Could not bind optional address {}: {}", addr, e),
+            Err(e) => return Err(anyhow::anyhow!("Could not bind {}: {}", addr, e)),
+        }
+    }
+
+    if listeners.is_empty() {
+        anyhow::bail!("No address to listen on");
+    }
 
     let backend = Backend::new();
+    backend.set_config(config);
+
+    persistence::load(&backend)?;
 
-    let addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, 6379));
-    info!("Listening on {}", addr);
+    let expire_backend = backend.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(100));
+
+        loop {
+            interval.tick().await;
+            expire_backend.active_expire_cycle();
+
+            if let Err(e) = aof::propagate_expired(&expire_backend) {
+                warn!("AOF write error: {:?}", e);
+            }
+        }
+    });
 
-    let listener = TcpListener::bind(addr).await?;
+    tokio::spawn(persistence::save_cron(backend.clone()));
+    tokio::spawn(aof::fsync_cron(backend.clone()));
+
+    let servers = listeners
+        .into_iter()
+        .map(|listener| tokio::spawn(serve(listener, backend.clone())))
+        .collect::<Vec<_>>();
+
+    tokio::select! {
+        (result, _, _) = futures::future::select_all(servers) => result?,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Received SIGINT, shutting down");
+
+            persistence::shutdown(&backend)
+        }
+    }
+}
+
+// Binds like Redis does, with IPv6 sockets restricted to IPv6 so the default
+// `bind * -::*` can listen on both families on the same port.
+fn listen(addr: SocketAddr) -> Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, None)?;
+
+    if addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(511)?;
+
+    Ok(TcpListener::from_std(socket.into())?)
+}
 
+async fn serve(listener: TcpListener, backend: Backend) -> Result<()> {
     loop {
         let (stream, raddr) = listener.accept().await?;
         info!("Accepted connection from {}", raddr);
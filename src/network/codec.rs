@@ -1,13 +1,15 @@
 use crate::resp::frame::Frame;
 use crate::resp::{RespDecode, RespEncode, RespError};
 use anyhow::Result;
-use bytes::Buf;
 use bytes::BytesMut;
 use std::io::Cursor;
 use tokio_util::codec::{Decoder, Encoder};
 
-#[derive(Debug)]
-pub struct RespFrameCodec;
+#[derive(Debug, Default)]
+pub struct RespFrameCodec {
+    // how far a partly received array was already checked
+    progress: Option<(i64, u64)>,
+}
 
 impl Decoder for RespFrameCodec {
     type Item = Frame;
@@ -16,11 +18,13 @@ impl Decoder for RespFrameCodec {
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>> {
         let mut cursor = Cursor::new(&buf[..]);
 
-        match Frame::decode(&mut cursor) {
-            Ok(frame) => {
+        match Frame::check_resume(&mut cursor, &mut self.progress) {
+            Ok(()) => {
+                // split the complete frame off the buffer, its large strings
+                // are then sliced from it instead of being copied
                 let len = cursor.position() as usize;
-                buf.advance(len);
-                Ok(Some(frame))
+                let mut frame = Cursor::new(buf.split_to(len).freeze());
+                Ok(Some(Frame::decode(&mut frame)?))
             }
             Err(RespError::Incomplete) => Ok(None),
             Err(e) => Err(e.into()),
@@ -36,3 +40,58 @@ impl Encoder<Frame> for RespFrameCodec {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec_decode_slices_big_strings() {
+        let mut codec = RespFrameCodec::default();
+        let value = "v".repeat(64 * 1024);
+        let data = format!(
+            "*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$65536\r\n{}\r\n*1\r\n",
+            value
+        );
+        let mut buf = BytesMut::from(data.as_bytes());
+        let range = buf.as_ptr_range();
+
+        let frame = codec.decode(&mut buf).unwrap().unwrap();
+
+        match frame {
+            Frame::Array(array) => match (&array[1], &array[2]) {
+                (Frame::BulkString(key), Frame::BulkString(value)) => {
+                    assert_eq!(key.inner, "key");
+                    assert!(!range.contains(&key.inner.as_ptr()));
+                    assert_eq!(value.inner.len(), 64 * 1024);
+                    assert!(range.contains(&value.inner.as_ptr()));
+                }
+                _ => panic!("Expected BulkStrings"),
+            },
+            _ => panic!("Expected Array"),
+        }
+
+        assert_eq!(&buf[..], b"*1\r\n");
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert_eq!(&buf[..], b"*1\r\n");
+    }
+
+    #[test]
+    fn test_codec_decode_across_reads() {
+        let mut codec = RespFrameCodec::default();
+        let mut buf = BytesMut::from(&b"*2\r\n$3\r\nGET\r\n$3\r\nk"[..]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+
+        buf.extend_from_slice(b"ey\r\n");
+        let frame = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(frame, vec![b"GET".into(), b"key".into()].into());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_codec_decode_bulk_too_long() {
+        let mut buf = BytesMut::from(&b"*2\r\n$3\r\nGET\r\n$9999999999\r\n"[..]);
+        let error = RespFrameCodec::default().decode(&mut buf).unwrap_err();
+        assert_eq!(error.to_string(), "Protocol error: invalid bulk length");
+    }
+}
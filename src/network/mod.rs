@@ -1,37 +1,891 @@
 mod codec;
 mod request;
+mod session;
+mod transaction;
 
 use crate::backend::Backend;
-use crate::command::Command;
+use crate::command::{Command, CommandError};
 use crate::resp::frame::Frame;
+use crate::resp::simple_error::SimpleError;
 use anyhow::Result;
-use codec::RespFrameCodec;
+use bytes::BytesMut;
+pub use codec::RespFrameCodec;
 use futures::SinkExt;
-use request::RespRequest;
+use request::{Reply, RespRequest};
+pub use session::Session;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::runtime::{Handle, RuntimeFlavor};
 use tokio_stream::StreamExt;
-use tokio_util::codec::Framed;
-use tracing::info;
+use tokio_util::codec::{Decoder, Framed};
+use tracing::{debug, info};
+pub use transaction::Transaction;
 
-pub async fn stream_handle(stream: TcpStream, backend: Backend) -> Result<()> {
-    let mut framed = Framed::new(stream, RespFrameCodec);
+// Replies are queued with `feed` and flushed once every command the client
+// already pipelined has been executed, instead of flushing after each reply.
+pub async fn stream_handle(mut stream: TcpStream, backend: Backend) -> Result<()> {
+    let _client = ConnectedClient::new(&backend);
+    let stats = &backend.stats;
+    stats
+        .total_connections_received
+        .fetch_add(1, Ordering::Relaxed);
+
+    let maxclients = backend.with_config(|config| config.maxclients);
+    if stats.connected_clients.load(Ordering::Relaxed) > maxclients {
+        stats.rejected_connections.fetch_add(1, Ordering::Relaxed);
+        stream
+            .write_all(b"-ERR max number of clients reached\r\n")
+            .await?;
+        return Ok(());
+    }
+
+    // replies are batched already, Nagle would only delay them
+    stream.set_nodelay(true)?;
+    let mut framed = Framed::new(stream, RespFrameCodec::default());
+    let mut session = Session::new(&backend);
 
     loop {
-        match framed.next().await {
-            Some(Ok(frame)) => {
-                info!("Received frame: {:?}", frame);
-                let response = request_handle(frame, backend.clone()).await?;
-                framed.send(response).await?;
+        // subscribed clients wait for messages, they are never idle
+        let timeout = match session.subscribed() {
+            true => 0,
+            false => backend.with_config(|config| config.timeout),
+        };
+        let idle = async {
+            match timeout {
+                0 => std::future::pending().await,
+                seconds => tokio::time::sleep(Duration::from_secs(seconds)).await,
+            }
+        };
+
+        let frame = tokio::select! {
+            next = framed.next() => match next {
+                Some(Ok(frame)) => frame,
+                Some(Err(e)) => return protocol_error(&mut framed, e).await,
+                None => return Ok(()),
+            },
+            Some(message) = session.message() => {
+                framed.feed(session.for_protocol(message)).await?;
+                while let Some(message) = session.try_message() {
+                    framed.feed(session.for_protocol(message)).await?;
+                }
+                framed.flush().await?;
+                continue;
+            }
+            _ = idle => {
+                info!("Closing idle client after {} seconds", timeout);
+                return Ok(());
             }
-            Some(Err(e)) => return Err(e),
-            None => return Ok(()),
+        };
+
+        // only the name, the arguments may be passwords
+        debug!("Received command: {}", command_name(&frame));
+        let Some(responses) =
+            watched_request_handle(&mut framed, frame, backend.clone(), &mut session).await?
+        else {
+            return Ok(());
+        };
+        for response in responses {
+            framed.feed(response).await?;
         }
+
+        loop {
+            let frame = match buffered_frame(&mut framed) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => return protocol_error(&mut framed, e).await,
+            };
+            debug!("Received command: {}", command_name(&frame));
+            let Some(responses) =
+                watched_request_handle(&mut framed, frame, backend.clone(), &mut session).await?
+            else {
+                return Ok(());
+            };
+            for response in responses {
+                framed.feed(response).await?;
+            }
+        }
+
+        framed.flush().await?;
+    }
+}
+
+// Decodes a frame the client already pipelined, without reading the socket.
+// The codec keeps how far it checked the buffer, so it is the framed one.
+fn buffered_frame(framed: &mut Framed<TcpStream, RespFrameCodec>) -> Result<Option<Frame>> {
+    let mut buf = std::mem::take(framed.read_buffer_mut());
+    let frame = framed.codec_mut().decode(&mut buf);
+    *framed.read_buffer_mut() = buf;
+    frame
+}
+
+// Malformed requests are replied to, along with the replies already queued,
+// before closing the connection, as the rest of the stream can't be parsed.
+async fn protocol_error(
+    framed: &mut Framed<TcpStream, RespFrameCodec>,
+    error: anyhow::Error,
+) -> Result<()> {
+    info!("Closing client after protocol error: {}", error);
+    framed.send(error_frame(error)).await
+}
+
+// Counts the client as connected for as long as it is alive.
+struct ConnectedClient<'a>(&'a Backend);
+
+impl<'a> ConnectedClient<'a> {
+    fn new(backend: &'a Backend) -> Self {
+        backend
+            .stats
+            .connected_clients
+            .fetch_add(1, Ordering::Relaxed);
+        Self(backend)
+    }
+}
+
+impl Drop for ConnectedClient<'_> {
+    fn drop(&mut self) {
+        self.0
+            .stats
+            .connected_clients
+            .fetch_sub(1, Ordering::Relaxed);
     }
 }
 
-pub async fn request_handle(frame: Frame, backend: Backend) -> Result<Frame> {
-    let command = Command::try_from(frame)?;
-    let request = RespRequest::new(command, backend);
-    let response = request.execute()?;
+// Most commands reply once, (un)subscribing replies once per channel.
+pub async fn request_handle(
+    frame: Frame,
+    backend: Backend,
+    session: &mut Session,
+) -> Result<Vec<Frame>> {
+    backend
+        .stats
+        .total_commands_processed
+        .fetch_add(1, Ordering::Relaxed);
+
+    let command = Command::try_from(frame.clone()).and_then(|command| {
+        // HELLO can authenticate too, with its AUTH option
+        let is_auth = matches!(command, Command::Auth(_) | Command::Hello(_));
+
+        if !session.authenticated && !is_auth {
+            return Err(CommandError::NoAuth.into());
+        }
+
+        if session.subscribe_mode() && !command.allowed_while_subscribed() {
+            return Err(CommandError::SubscribeMode(command_name(&frame)).into());
+        }
+
+        // a script running for too long can only be killed
+        let is_kill = matches!(&command, Command::Script(script) if script.is_kill());
+        if backend.running_script().busy() && !is_auth && !is_kill {
+            return Err(CommandError::Busy.into());
+        }
+
+        Ok(command)
+    });
+
+    // a command refused while queueing, like an unknown one, dooms the
+    // transaction, EXEC will refuse it
+    if let (Err(_), Some(transaction)) = (&command, &mut session.transaction) {
+        transaction.abort();
+    }
+
+    let response = command.and_then(|command| {
+        let is_auth = matches!(command, Command::Auth(_) | Command::Hello(_));
+        let is_kill = matches!(&command, Command::Script(script) if script.is_kill());
+
+        if let Some(transaction) = &mut session.transaction {
+            if command.queued_in_transaction() {
+                return Ok(Reply::Frame(transaction.queue(command, frame)));
+            }
+        }
+
+        // these change the connection rather than the dataset
+        match &command {
+            Command::Subscribe(subscribe) => {
+                let subscriber = session.subscriber(&backend);
+                return Ok(Reply::Frames(subscribe.subscribe(subscriber)));
+            }
+            Command::Unsubscribe(unsubscribe) => {
+                let subscriber = session.subscriber(&backend);
+                return Ok(Reply::Frames(unsubscribe.unsubscribe(subscriber)));
+            }
+            Command::Ping(ping) if session.subscribe_mode() => {
+                return Ok(Reply::Frame(ping.pong_subscribed()))
+            }
+            Command::Script(script) if is_kill => return script.kill(&backend).map(Reply::Frame),
+            Command::Hello(hello) => return hello.hello(&backend, session).map(Reply::Frame),
+            Command::Multi(multi) => return multi.multi(session).map(Reply::Frame),
+            Command::Exec(exec) => {
+                return off_runtime(|| exec.exec(&backend, session)).map(Reply::Frame)
+            }
+            Command::Discard(discard) => return discard.discard(session).map(Reply::Frame),
+            Command::Watch(watch) => return watch.watch(session).map(Reply::Frame),
+            Command::Unwatch(unwatch) => return unwatch.unwatch(session).map(Reply::Frame),
+            _ => {}
+        }
+
+        let is_script = matches!(command, Command::Eval(_));
+        let request = RespRequest::new(command, frame, backend.clone());
+        let reply = match is_script {
+            true => off_runtime(|| request.execute())?,
+            false => execute(&request, &backend)?,
+        };
+        let reply = match reply {
+            Reply::Frame(frame) => Reply::Frame(request.for_protocol(session.protocol, frame)),
+            reply => reply,
+        };
+
+        if is_auth {
+            session.authenticated = true;
+        }
+
+        Ok(reply)
+    });
+
+    let responses = match response {
+        Ok(Reply::Frame(frame)) => vec![frame],
+        Ok(Reply::Frames(frames)) => frames,
+        Ok(Reply::Blocked(client)) => vec![client.wait().await],
+        Err(e) => vec![error_frame(e)],
+    };
+
+    Ok(responses
+        .into_iter()
+        .map(|response| session.for_protocol(response))
+        .collect())
+}
+
+// The keyspace lock may be held for long by a script. A request that can't
+// take it right away is replied BUSY when the script ran for too long, else
+// waits for it off the runtime.
+fn execute(request: &RespRequest, backend: &Backend) -> Result<Reply> {
+    if let Some(reply) = request.try_execute() {
+        return reply;
+    }
+
+    if backend.running_script().busy() {
+        return Err(CommandError::Busy.into());
+    }
+
+    off_runtime(|| request.execute())
+}
+
+// Runs `f`, which may block for long, like a script or a request waiting on
+// one. The worker running it hands its other connections over meanwhile, for
+// them to be replied BUSY or SCRIPT KILL the script.
+fn off_runtime<R>(f: impl FnOnce() -> R) -> R {
+    match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(f),
+        _ => f(),
+    }
+}
+
+// The name a command was called with, as error messages quote it.
+fn command_name(frame: &Frame) -> String {
+    match frame {
+        Frame::Array(array) => match array.first() {
+            Some(Frame::BulkString(name)) => String::from_utf8_lossy(&name.inner).to_lowercase(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+// Runs a request while watching the connection, so a client that disconnects
+// while blocked is removed from the keys it waits on. Data the client sends
+// meanwhile is kept for the following requests. None when it disconnected.
+async fn watched_request_handle(
+    framed: &mut Framed<TcpStream, RespFrameCodec>,
+    frame: Frame,
+    backend: Backend,
+    session: &mut Session,
+) -> Result<Option<Vec<Frame>>> {
+    let request = request_handle(frame, backend, session);
+    tokio::pin!(request);
+
+    let mut pending = BytesMut::new();
+
+    let response = loop {
+        tokio::select! {
+            biased;
+            response = &mut request => break Some(response?),
+            read = framed.get_mut().read_buf(&mut pending) => {
+                if read? == 0 {
+                    break None;
+                }
+            }
+        }
+    };
+
+    framed.read_buffer_mut().extend_from_slice(&pending);
+
     Ok(response)
 }
+
+// Errors are replied to the client instead of closing the connection. Typed
+// command errors already carry their Redis error code, anything else is
+// reported as a generic `ERR`.
+fn error_frame(error: anyhow::Error) -> Frame {
+    let message = match error.downcast_ref::<CommandError>() {
+        Some(e) => e.to_string(),
+        None => format!("ERR {}", error),
+    };
+
+    let message = message.replace(['\r', '\n'], " ");
+    Frame::SimpleError(SimpleError::new(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resp::null::Null;
+    use crate::resp::null_array::NullArray;
+    use crate::resp::null_bulk_string::NullBulkString;
+
+    #[tokio::test]
+    async fn test_request_handle_unknown_command() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"foo".into(), b"bar".into()].into();
+        let result = request_handle(frame, backend.clone(), &mut Session::new(&backend))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR unknown command 'foo', with args beginning with: 'bar' "
+            ))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_wrong_number_of_arguments() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"get".into()].into();
+        let result = request_handle(frame, backend.clone(), &mut Session::new(&backend))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR wrong number of arguments for 'get' command"
+            ))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_keeps_valid_reply() {
+        let backend = Backend::new();
+        let frame: Frame = vec![b"echo".into(), b"hello".into()].into();
+        let result = request_handle(frame, backend.clone(), &mut Session::new(&backend))
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec![b"hello".into()]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_pipelined_replies() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            stream_handle(stream, Backend::new()).await
+        });
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$1\r\nv\r\n*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n*1\r\n$3\r\nFOO\r\n")
+            .await
+            .unwrap();
+
+        let expected =
+            b"$2\r\nOK\r\n$1\r\nv\r\n-ERR unknown command 'FOO', with args beginning with: \r\n";
+        let mut buf = vec![0; expected.len()];
+        client.read_exact(&mut buf).await.unwrap();
+
+        assert_eq!(buf, expected);
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_requires_auth() {
+        let backend = Backend::new();
+        backend.set_config(crate::config::ServerConfig {
+            requirepass: Some("secret".to_string()),
+            ..Default::default()
+        });
+        let mut session = Session::new(&backend);
+
+        let frame: Frame = vec![b"get".into(), b"key".into()].into();
+        let result = request_handle(frame.clone(), backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "NOAUTH Authentication required."
+            ))]
+        );
+
+        let auth: Frame = vec![b"auth".into(), b"wrong".into()].into();
+        request_handle(auth, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert!(!session.authenticated);
+
+        let auth: Frame = vec![b"auth".into(), b"secret".into()].into();
+        request_handle(auth, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert!(session.authenticated);
+
+        let result = request_handle(frame, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![Frame::NullBulkString(NullBulkString)]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_max_clients() {
+        use tokio::io::AsyncReadExt;
+
+        let backend = Backend::new();
+        backend.set_config(crate::config::ServerConfig {
+            maxclients: 1,
+            ..Default::default()
+        });
+        // another client is already connected
+        let _client = ConnectedClient::new(&backend);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server_backend = backend.clone();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            stream_handle(stream, server_backend).await
+        });
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let mut buf = Vec::new();
+        client.read_to_end(&mut buf).await.unwrap();
+
+        assert_eq!(buf, b"-ERR max number of clients reached\r\n");
+        assert_eq!(
+            backend.stats.rejected_connections.load(Ordering::Relaxed),
+            1
+        );
+        assert_eq!(backend.stats.connected_clients.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_blocked_client() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let backend = Backend::new();
+        let server_backend = backend.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(stream_handle(stream, server_backend.clone()));
+            }
+        });
+
+        let mut blocked = TcpStream::connect(addr).await.unwrap();
+        blocked
+            .write_all(b"*3\r\n$5\r\nBLPOP\r\n$4\r\nlist\r\n$1\r\n0\r\n")
+            .await
+            .unwrap();
+        while backend.blocked_clients() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let mut pusher = TcpStream::connect(addr).await.unwrap();
+        pusher
+            .write_all(b"*3\r\n$5\r\nRPUSH\r\n$4\r\nlist\r\n$1\r\na\r\n")
+            .await
+            .unwrap();
+
+        let expected = b"*2\r\n$4\r\nlist\r\n$1\r\na\r\n";
+        let mut buf = vec![0; expected.len()];
+        blocked.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, expected);
+
+        blocked
+            .write_all(b"*3\r\n$5\r\nBRPOP\r\n$4\r\nlist\r\n$4\r\n0.01\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 5];
+        blocked.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, b"*-1\r\n");
+        assert_eq!(backend.blocked_clients(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_subscribe_mode() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let subscribe: Frame = vec![b"subscribe".into(), b"a".into(), b"b".into()].into();
+        let result = request_handle(subscribe, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                vec![b"subscribe".into(), b"a".into(), 1.into()].into(),
+                vec![b"subscribe".into(), b"b".into(), 2.into()].into(),
+            ]
+        );
+
+        let get: Frame = vec![b"GET".into(), b"key".into()].into();
+        let result = request_handle(get.clone(), backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR Can't execute 'get': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context"
+            ))]
+        );
+
+        let ping: Frame = vec![b"ping".into()].into();
+        let result = request_handle(ping.clone(), backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![vec![b"pong".into(), b"".into()].into()]);
+
+        // RESP3 clients tell messages apart, they keep running commands
+        session.protocol = 3;
+        let result = request_handle(ping, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["PONG".into()]);
+
+        let result = request_handle(get.clone(), backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![Frame::Null(Null)]);
+
+        session.protocol = 2;
+        let unsubscribe: Frame = vec![b"unsubscribe".into()].into();
+        request_handle(unsubscribe, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        let result = request_handle(get, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![Frame::NullBulkString(NullBulkString)]);
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_hello() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let hset: Frame = vec![b"hset".into(), b"key".into(), b"f".into(), b"v".into()].into();
+        request_handle(hset, backend.clone(), &mut session)
+            .await
+            .unwrap();
+
+        let hgetall: Frame = vec![b"hgetall".into(), b"key".into()].into();
+        let result = request_handle(hgetall.clone(), backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![vec![b"f".into(), b"v".into()].into()]);
+
+        let hello: Frame = vec![b"hello".into(), b"3".into()].into();
+        let result = request_handle(hello, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert!(matches!(result[..], [Frame::Map(_)]));
+        assert_eq!(session.protocol, 3);
+
+        let result = request_handle(hgetall, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert!(matches!(result[..], [Frame::Map(_)]));
+
+        let get: Frame = vec![b"get".into(), b"nokey".into()].into();
+        let result = request_handle(get, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![Frame::Null(Null)]);
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_transaction() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        async fn run(backend: &Backend, session: &mut Session, args: &[&str]) -> Vec<Frame> {
+            let frame: Frame = args
+                .iter()
+                .map(|arg| Frame::from(arg.as_bytes()))
+                .collect::<Vec<_>>()
+                .into();
+            request_handle(frame, backend.clone(), session)
+                .await
+                .unwrap()
+        }
+
+        run(&backend, &mut session, &["multi"]).await;
+        let result = run(&backend, &mut session, &["set", "key", "a"]).await;
+        assert_eq!(result, vec!["QUEUED".into()]);
+        run(&backend, &mut session, &["nope"]).await;
+        let result = run(&backend, &mut session, &["exec"]).await;
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "EXECABORT Transaction discarded because of previous errors."
+            ))]
+        );
+        assert!(!backend.exists(b"key"));
+
+        run(&backend, &mut session, &["watch", "key"]).await;
+        run(&backend, &mut session, &["multi"]).await;
+        run(&backend, &mut session, &["incr", "key"]).await;
+        backend.set(b"key", "1".into());
+        let result = run(&backend, &mut session, &["exec"]).await;
+        assert_eq!(result, vec![Frame::NullArray(NullArray)]);
+
+        run(&backend, &mut session, &["multi"]).await;
+        run(&backend, &mut session, &["incr", "key"]).await;
+        let result = run(&backend, &mut session, &["exec"]).await;
+        assert_eq!(result, vec![vec![2.into()].into()]);
+
+        // refused connection commands leave the transaction alone
+        run(&backend, &mut session, &["multi"]).await;
+        let result = run(&backend, &mut session, &["multi"]).await;
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR MULTI calls can not be nested"
+            ))]
+        );
+        let result = run(&backend, &mut session, &["watch", "key"]).await;
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR WATCH inside MULTI is not allowed"
+            ))]
+        );
+        run(&backend, &mut session, &["incr", "key"]).await;
+        let result = run(&backend, &mut session, &["exec"]).await;
+        assert_eq!(result, vec![vec![3.into()].into()]);
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_transaction_serves_blocked_after() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        async fn run(backend: &Backend, session: &mut Session, args: &[&str]) -> Vec<Frame> {
+            let frame: Frame = args
+                .iter()
+                .map(|arg| Frame::from(arg.as_bytes()))
+                .collect::<Vec<_>>()
+                .into();
+            request_handle(frame, backend.clone(), session)
+                .await
+                .unwrap()
+        }
+
+        let blpop: Frame = vec![b"blpop".into(), b"q".into(), b"0".into()].into();
+        let request = RespRequest::new(blpop.clone().try_into().unwrap(), blpop, backend.clone());
+        let Ok(Reply::Blocked(client)) = request.execute() else {
+            panic!("Expected Blocked");
+        };
+
+        // the transaction pops what it pushed, the client keeps waiting
+        run(&backend, &mut session, &["multi"]).await;
+        run(&backend, &mut session, &["rpush", "q", "x"]).await;
+        run(&backend, &mut session, &["lpop", "q"]).await;
+        let result = run(&backend, &mut session, &["exec"]).await;
+        assert_eq!(result, vec![vec![1.into(), b"x".into()].into()]);
+        assert_eq!(backend.blocked_clients(), 1);
+
+        run(&backend, &mut session, &["rpush", "q", "y"]).await;
+        assert_eq!(client.wait().await, vec![b"q".into(), b"y".into()].into());
+    }
+
+    #[tokio::test]
+    async fn test_request_handle_eval() {
+        let backend = Backend::new();
+        let mut session = Session::new(&backend);
+
+        let script = "redis.call('set', KEYS[1], ARGV[1]) return redis.call('incr', KEYS[1])";
+        let eval: Frame = vec![
+            b"eval".into(),
+            script.as_bytes().into(),
+            b"1".into(),
+            b"key".into(),
+            b"41".into(),
+        ]
+        .into();
+        let result = request_handle(eval, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![42.into()]);
+
+        // the script pops what it pushed, the blocked client keeps waiting
+        let blpop: Frame = vec![b"blpop".into(), b"q".into(), b"0".into()].into();
+        let request = RespRequest::new(blpop.clone().try_into().unwrap(), blpop, backend.clone());
+        let Ok(Reply::Blocked(client)) = request.execute() else {
+            panic!("Expected Blocked");
+        };
+        let script = "redis.call('rpush', KEYS[1], 'y') return redis.call('lpop', KEYS[1])";
+        let eval: Frame = vec![
+            b"eval".into(),
+            script.as_bytes().into(),
+            b"1".into(),
+            b"q".into(),
+        ]
+        .into();
+        let result = request_handle(eval, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(result, vec![b"y".into()]);
+        assert_eq!(backend.blocked_clients(), 1);
+
+        let eval: Frame = vec![
+            b"eval".into(),
+            b"return redis.call('rpush', KEYS[1], 'z')".into(),
+            b"1".into(),
+            b"q".into(),
+        ]
+        .into();
+        request_handle(eval, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(client.wait().await, vec![b"q".into(), b"z".into()].into());
+
+        let eval: Frame = vec![
+            b"eval".into(),
+            b"return redis.pcall('multi')".into(),
+            b"0".into(),
+        ]
+        .into();
+        let result = request_handle(eval, backend.clone(), &mut session)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![Frame::SimpleError(SimpleError::new(
+                "ERR This Redis command is not allowed from script"
+            ))]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_stream_handle_script_kill_with_waiting_clients() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let backend = Backend::new();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(stream_handle(stream, backend.clone()));
+            }
+        });
+
+        let mut script = TcpStream::connect(addr).await.unwrap();
+        script
+            .write_all(b"*3\r\n$4\r\nEVAL\r\n$17\r\nwhile true do end\r\n$1\r\n0\r\n")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // more clients wait on the script than there are workers
+        let mut waiting = Vec::new();
+        for _ in 0..4 {
+            let mut client = TcpStream::connect(addr).await.unwrap();
+            client
+                .write_all(b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n")
+                .await
+                .unwrap();
+            waiting.push(client);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut killer = TcpStream::connect(addr).await.unwrap();
+        killer
+            .write_all(b"*2\r\n$6\r\nSCRIPT\r\n$4\r\nKILL\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 8];
+        tokio::time::timeout(Duration::from_secs(5), killer.read_exact(&mut buf))
+            .await
+            .expect("SCRIPT KILL replied while clients wait")
+            .unwrap();
+        assert_eq!(buf, b"$2\r\nOK\r\n");
+
+        let expected = b"-ERR Script killed by user with SCRIPT KILL...\r\n";
+        let mut buf = vec![0; expected.len()];
+        script.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, expected);
+
+        for mut client in waiting {
+            let mut buf = vec![0; 5];
+            client.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, b"$-1\r\n");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_pubsub() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let backend = Backend::new();
+        let server_backend = backend.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(stream_handle(stream, server_backend.clone()));
+            }
+        });
+
+        let mut subscriber = TcpStream::connect(addr).await.unwrap();
+        subscriber
+            .write_all(b"*2\r\n$10\r\nPSUBSCRIBE\r\n$2\r\nn*\r\n")
+            .await
+            .unwrap();
+        let expected = b"*3\r\n$10\r\npsubscribe\r\n$2\r\nn*\r\n:1\r\n";
+        let mut buf = vec![0; expected.len()];
+        subscriber.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, expected);
+
+        let mut publisher = TcpStream::connect(addr).await.unwrap();
+        publisher
+            .write_all(b"*3\r\n$7\r\nPUBLISH\r\n$4\r\nnews\r\n$2\r\nhi\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 4];
+        publisher.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, b":1\r\n");
+
+        let expected = b"*4\r\n$8\r\npmessage\r\n$2\r\nn*\r\n$4\r\nnews\r\n$2\r\nhi\r\n";
+        let mut buf = vec![0; expected.len()];
+        subscriber.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, expected);
+
+        drop(subscriber);
+        while backend.pubsub_numpat() > 0 {
+            tokio::task::yield_now().await;
+        }
+    }
+}
@@ -1,20 +1,282 @@
-use crate::backend::Backend;
-use crate::command::{Command, CommandExecute};
+use super::error_frame;
+use crate::backend::{Backend, Block, BlockResult};
+use crate::command::{Command, CommandError, CommandExecute};
+use crate::persistence::aof::{self, Aof};
 use crate::resp::frame::Frame;
 use anyhow::Result;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use tokio::sync::oneshot;
 
 #[derive(Debug)]
 pub struct RespRequest {
     command: Command,
+    frame: Frame,
     backend: Backend,
 }
 
+#[derive(Debug)]
+pub enum Reply {
+    Frame(Frame),
+    // replies to commands answering several times, like SUBSCRIBE
+    Frames(Vec<Frame>),
+    Blocked(BlockedClient),
+}
+
 impl RespRequest {
-    pub fn new(command: Command, backend: Backend) -> Self {
-        Self { command, backend }
+    pub fn new(command: Command, frame: Frame, backend: Backend) -> Self {
+        Self {
+            command,
+            frame,
+            backend,
+        }
+    }
+
+    // Writes are appended to the AOF after they succeed. The AOF lock is held
+    // while executing them, so the log keeps the order they were applied in.
+    pub fn execute(&self) -> Result<Reply> {
+        let reply = self
+            .backend
+            .with_keyspace_lock(self.command.exclusive(), || self.execute_locked(None));
+        self.executed(reply)
+    }
+
+    // Like `execute`, but None right away when another request holds the
+    // keyspace lock.
+    pub fn try_execute(&self) -> Option<Result<Reply>> {
+        let reply = self
+            .backend
+            .try_with_keyspace_lock(self.command.exclusive(), || self.execute_locked(None))?;
+        Some(self.executed(reply))
+    }
+
+    // The reply to a client of `protocol`, see `CommandExecute::resp3_reply`.
+    pub fn for_protocol(&self, protocol: u8, reply: Frame) -> Frame {
+        match protocol {
+            2 => reply,
+            _ => self.command.resp3_reply(reply),
+        }
+    }
+
+    // Scripts serve the clients blocked on keys they gave data to once done.
+    fn executed(&self, reply: Result<Reply>) -> Result<Reply> {
+        if let Command::Eval(_) = &self.command {
+            serve_ready(&self.backend)?;
+        }
+        reply
+    }
+
+    // Runs a request MULTI queued or a script called, EXEC or the script
+    // already holding the keyspace lock. Its writes are collected in
+    // `propagated`, to be appended as one transaction. Blocking commands
+    // don't block there, they time out right away when no data is available.
+    pub fn execute_queued(&self, propagated: &mut Vec<Frame>) -> Result<Frame> {
+        match self.execute_locked(Some(propagated))? {
+            Reply::Frame(frame) => Ok(frame),
+            Reply::Frames(frames) => Ok(frames.into()),
+            Reply::Blocked(client) => Ok(client.timeout_reply.clone()),
+        }
+    }
+
+    fn execute_locked(&self, collected: Option<&mut Vec<Frame>>) -> Result<Reply> {
+        if let Command::Eval(eval) = &self.command {
+            let mut writes = Vec::new();
+            let reply = eval.eval(&self.backend, |frame| {
+                let reply = script_call(&self.backend, frame, &mut writes);
+                if !writes.is_empty() {
+                    self.backend.running_script().wrote();
+                }
+                reply
+            });
+
+            // the writes made before a script failed are kept too
+            match collected {
+                Some(collected) => collected.append(&mut writes),
+                None => aof::append_transaction(&self.backend, &writes)?,
+            }
+            return reply.map(Reply::Frame);
+        }
+
+        if let Some(block) = self.command.block(&self.backend) {
+            return self.execute_blocking(block, collected);
+        }
+
+        let Some(entry) = self.command.propagate(&self.frame) else {
+            return self.command.execute(self.backend.clone()).map(Reply::Frame);
+        };
+
+        let mut guard = None;
+        let mut sink = self.sink(collected, &mut guard);
+
+        let response = self.command.execute(self.backend.clone())?;
+        sink.expired(&self.backend)?;
+        if let Some(entry) = self.command.propagate_reply(entry, &response) {
+            sink.push(&entry)?;
+        }
+        self.serve_blocked(&mut sink)?;
+
+        Ok(Reply::Frame(response))
+    }
+
+    // Clients served right away propagate the equivalent non-blocking
+    // command, the others are parked until a write serves them.
+    fn execute_blocking(&self, block: Block, collected: Option<&mut Vec<Frame>>) -> Result<Reply> {
+        let mut guard = None;
+        let mut sink = self.sink(collected, &mut guard);
+
+        match self.backend.block(block.keys, block.op) {
+            BlockResult::Served(served) => {
+                sink.expired(&self.backend)?;
+                if let Some(entry) = &served.propagate {
+                    sink.push(entry)?;
+                }
+                self.serve_blocked(&mut sink)?;
+
+                Ok(Reply::Frame(served.reply))
+            }
+            BlockResult::Blocked(id, receiver) => Ok(Reply::Blocked(BlockedClient {
+                id,
+                receiver,
+                timeout: block.timeout,
+                timeout_reply: block.timeout_reply,
+                backend: self.backend.clone(),
+            })),
+        }
+    }
+
+    // The writes are collected when `collected` is given, else appended to
+    // the AOF, locked in `guard` while the request runs so the log keeps the
+    // order they were applied in.
+    fn sink<'a, 'b: 'a>(
+        &'b self,
+        collected: Option<&'a mut Vec<Frame>>,
+        guard: &'a mut Option<MutexGuard<'b, Option<Aof>>>,
+    ) -> Sink<'a> {
+        if let Some(collected) = collected {
+            return Sink::Collect(collected);
+        }
+
+        let aof = aof::lock(&self.backend);
+        match aof.is_some() {
+            true => Sink::Aof(guard.insert(aof).as_mut()),
+            false => Sink::Aof(None),
+        }
+    }
+
+    // Serves the clients blocked on keys this write gave data to. Inside a
+    // transaction or script they are served once it is done, see
+    // `serve_ready`.
+    fn serve_blocked(&self, sink: &mut Sink) -> Result<()> {
+        match sink {
+            Sink::Collect(_) => Ok(()),
+            Sink::Aof(_) => serve_blocked(&self.backend, sink),
+        }
     }
+}
+
+// Serves the clients blocked on keys a transaction or script gave data to,
+// once it applied all its writes and released the keyspace lock, so they
+// never see it half applied.
+pub fn serve_ready(backend: &Backend) -> Result<()> {
+    if backend.blocked_clients() == 0 {
+        return Ok(());
+    }
+
+    backend.with_keyspace_lock(false, || {
+        let mut aof = aof::lock(backend);
+        serve_blocked(backend, &mut Sink::Aof(aof.as_mut()))
+    })
+}
+
+fn serve_blocked(backend: &Backend, sink: &mut Sink) -> Result<()> {
+    if backend.blocked_clients() == 0 {
+        return Ok(());
+    }
+
+    for entry in backend.serve_blocked() {
+        sink.push(&entry)?;
+    }
+
+    Ok(())
+}
+
+// Where the writes of a request are propagated to.
+enum Sink<'a> {
+    // the AOF, None while it is disabled
+    Aof(Option<&'a mut Aof>),
+    // the writes of a transaction or script, appended once it is done
+    Collect(&'a mut Vec<Frame>),
+}
+
+impl Sink<'_> {
+    fn push(&mut self, entry: &Frame) -> Result<()> {
+        match self {
+            Sink::Aof(Some(aof)) => aof.append(entry),
+            Sink::Aof(None) => Ok(()),
+            Sink::Collect(entries) => {
+                entries.push(entry.clone());
+                Ok(())
+            }
+        }
+    }
+
+    // Keys expired while the request ran are logged before its writes.
+    // Collected writes log them when they are appended.
+    fn expired(&mut self, backend: &Backend) -> Result<()> {
+        match self {
+            Sink::Aof(aof) => aof::append_expired(backend, aof.as_deref_mut()),
+            Sink::Collect(_) => Ok(()),
+        }
+    }
+}
+
+// Runs a command called by a script as a request of its own, serving blocked
+// clients like any other. EVAL is not propagated, only the writes it made
+// are, collected in `writes`.
+fn script_call(backend: &Backend, frame: Frame, writes: &mut Vec<Frame>) -> Frame {
+    match Command::try_from(frame.clone()) {
+        Ok(command) if command.allowed_in_script() => {
+            RespRequest::new(command, frame, backend.clone())
+                .execute_queued(writes)
+                .unwrap_or_else(error_frame)
+        }
+        Ok(_) => error_frame(CommandError::NotAllowedFromScript.into()),
+        Err(e) => error_frame(e),
+    }
+}
+
+// A client parked by a blocking command. Dropping it, like when the client
+// disconnects, removes it from the keys it waits on.
+#[derive(Debug)]
+pub struct BlockedClient {
+    id: u64,
+    receiver: oneshot::Receiver<Frame>,
+    timeout: Option<Duration>,
+    timeout_reply: Frame,
+    backend: Backend,
+}
+
+impl BlockedClient {
+    pub async fn wait(mut self) -> Frame {
+        let reply = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, &mut self.receiver).await.ok(),
+            None => Some((&mut self.receiver).await),
+        };
+
+        if let Some(Ok(reply)) = reply {
+            return reply;
+        }
+
+        // the client may have been served after the timeout fired
+        self.backend.unblock(self.id);
+        self.receiver
+            .try_recv()
+            .unwrap_or_else(|_| self.timeout_reply.clone())
+    }
+}
 
-    pub fn execute(&self) -> Result<Frame> {
-        self.command.execute(self.backend.clone())
+impl Drop for BlockedClient {
+    fn drop(&mut self) {
+        self.backend.unblock(self.id);
     }
 }
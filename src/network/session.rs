@@ -0,0 +1,76 @@
+use bytes::Bytes;
+
+use super::Transaction;
+use crate::backend::{Backend, Subscriber, WatchedKeys};
+use crate::resp::frame::Frame;
+
+// State of a client connection that outlives a single command.
+#[derive(Debug)]
+pub struct Session {
+    pub id: u64,
+    pub authenticated: bool,
+    // RESP version replies are encoded with, RESP2 unless negotiated
+    pub protocol: u8,
+    // set with HELLO SETNAME
+    pub name: Option<Bytes>,
+    // Pub/Sub subscriptions, from the first (un)subscribe on
+    pub subscriber: Option<Subscriber>,
+    // commands queued since MULTI
+    pub transaction: Option<Transaction>,
+    pub watched: WatchedKeys,
+}
+
+impl Session {
+    // Clients only need to authenticate when a password is configured as
+    // they connect.
+    pub fn new(backend: &Backend) -> Self {
+        Self {
+            id: backend.next_client_id(),
+            authenticated: backend.config().requirepass.is_none(),
+            protocol: 2,
+            name: None,
+            subscriber: None,
+            transaction: None,
+            watched: WatchedKeys::new(backend),
+        }
+    }
+
+    pub fn subscriber(&mut self, backend: &Backend) -> &mut Subscriber {
+        self.subscriber
+            .get_or_insert_with(|| Subscriber::new(backend))
+    }
+
+    pub fn subscribed(&self) -> bool {
+        self.subscriber
+            .as_ref()
+            .is_some_and(|subscriber| !subscriber.is_empty())
+    }
+
+    // RESP2 has no push type, so a subscribed client can't tell messages
+    // from replies and may only manage its subscriptions.
+    pub fn subscribe_mode(&self) -> bool {
+        self.protocol == 2 && self.subscribed()
+    }
+
+    // Waits for the next message published to the client, forever when it
+    // never subscribed.
+    pub async fn message(&mut self) -> Option<Frame> {
+        match &mut self.subscriber {
+            Some(subscriber) => subscriber.message().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    pub fn try_message(&mut self) -> Option<Frame> {
+        self.subscriber.as_mut()?.try_message()
+    }
+
+    // Replies are built with RESP3 types, encoded as RESP2 unless the client
+    // negotiated RESP3.
+    pub fn for_protocol(&self, frame: Frame) -> Frame {
+        match self.protocol {
+            2 => frame.into_resp2(),
+            _ => frame.into_resp3(),
+        }
+    }
+}
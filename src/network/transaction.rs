@@ -0,0 +1,127 @@
+use anyhow::Result;
+
+use super::error_frame;
+use super::request::{serve_ready, RespRequest};
+use crate::backend::{Backend, WatchedKeys};
+use crate::command::{Command, CommandError};
+use crate::persistence::aof;
+use crate::resp::frame::Frame;
+use crate::resp::null_array::NullArray;
+
+// Commands queued between MULTI and EXEC. An error while queueing, like an
+// unknown command, aborts the whole transaction.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    requests: Vec<(Command, Frame)>,
+    aborted: bool,
+}
+
+impl Transaction {
+    pub fn queue(&mut self, command: Command, frame: Frame) -> Frame {
+        self.requests.push((command, frame));
+        "QUEUED".into()
+    }
+
+    pub fn abort(&mut self) {
+        self.aborted = true;
+    }
+
+    // Runs the queued commands holding the keyspace lock exclusively, so no
+    // other client runs in between or sees the transaction half applied. The
+    // reply is nil when a watched key changed since WATCH, nothing runs then.
+    // Errors of single commands are replied in their place, the others still
+    // run. Their writes are appended to the AOF together, as a transaction.
+    // The clients blocked on keys it gave data to are served after it.
+    pub fn exec(self, backend: &Backend, watched: &WatchedKeys, protocol: u8) -> Result<Frame> {
+        if self.aborted {
+            return Err(CommandError::ExecAbort.into());
+        }
+
+        let reply = backend.with_keyspace_lock(true, || {
+            if watched.modified() {
+                return Ok(Frame::NullArray(NullArray));
+            }
+
+            let mut propagated = Vec::new();
+            let replies = self
+                .requests
+                .into_iter()
+                .map(|(command, frame)| {
+                    let request = RespRequest::new(command, frame, backend.clone());
+                    let reply = request
+                        .execute_queued(&mut propagated)
+                        .unwrap_or_else(error_frame);
+                    request.for_protocol(protocol, reply)
+                })
+                .collect::<Vec<_>>();
+
+            aof::append_transaction(backend, &propagated)?;
+            Ok(replies.into())
+        });
+
+        serve_ready(backend)?;
+        reply
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(transaction: &mut Transaction, args: &[&str]) {
+        let frame: Frame = args
+            .iter()
+            .map(|arg| Frame::from(arg.as_bytes()))
+            .collect::<Vec<_>>()
+            .into();
+        transaction.queue(frame.clone().try_into().unwrap(), frame);
+    }
+
+    #[test]
+    fn test_transaction_exec() {
+        let backend = Backend::new();
+        let watched = WatchedKeys::new(&backend);
+
+        let mut transaction = Transaction::default();
+        queue(&mut transaction, &["set", "key", "a"]);
+        queue(&mut transaction, &["lpush", "key", "b"]);
+        queue(&mut transaction, &["append", "key", "b"]);
+        queue(&mut transaction, &["blpop", "list", "0"]);
+
+        let reply = transaction.exec(&backend, &watched, 2).unwrap();
+        let Frame::Array(replies) = reply else {
+            panic!("Expected Array");
+        };
+        assert_eq!(replies[0], b"OK".into());
+        assert!(matches!(replies[1], Frame::SimpleError(_)));
+        assert_eq!(replies[2], 2.into());
+        assert_eq!(replies[3], Frame::NullArray(NullArray));
+        assert_eq!(backend.get(b"key").unwrap(), Some("ab".into()));
+    }
+
+    #[test]
+    fn test_transaction_exec_watched() {
+        let backend = Backend::new();
+        let mut watched = WatchedKeys::new(&backend);
+        watched.watch("key".into());
+
+        let mut transaction = Transaction::default();
+        queue(&mut transaction, &["set", "key", "a"]);
+        backend.set(b"key", "b".into());
+
+        let reply = transaction.exec(&backend, &watched, 2).unwrap();
+        assert_eq!(reply, Frame::NullArray(NullArray));
+        assert_eq!(backend.get(b"key").unwrap(), Some("b".into()));
+
+        let mut transaction = Transaction::default();
+        queue(&mut transaction, &["set", "key", "a"]);
+        transaction.abort();
+        assert_eq!(
+            transaction
+                .exec(&backend, &watched, 2)
+                .unwrap_err()
+                .to_string(),
+            "EXECABORT Transaction discarded because of previous errors."
+        );
+    }
+}
@@ -0,0 +1,634 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Cursor, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+use tracing::{info, warn};
+
+use super::rdb::Entry;
+use crate::backend::{Backend, Stream, StreamId, Value};
+use crate::command::{Command, CommandExecute};
+use crate::resp::frame::Frame;
+use crate::resp::{RespDecode, RespEncode, RespError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppendFsync {
+    Always,
+    EverySec,
+    No,
+}
+
+impl FromStr for AppendFsync {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "always" => Ok(AppendFsync::Always),
+            "everysec" => Ok(AppendFsync::EverySec),
+            "no" => Ok(AppendFsync::No),
+            _ => anyhow::bail!("Invalid appendfsync policy: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for AppendFsync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppendFsync::Always => write!(f, "always"),
+            AppendFsync::EverySec => write!(f, "everysec"),
+            AppendFsync::No => write!(f, "no"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Aof {
+    file: Arc<File>,
+    fsync: AppendFsync,
+    // commands appended while a rewrite is running, added to the new file
+    // once the rewrite is done
+    rewrite_buffer: Option<Vec<u8>>,
+}
+
+impl Aof {
+    pub fn open(path: &Path, fsync: AppendFsync) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: Arc::new(file),
+            fsync,
+            rewrite_buffer: None,
+        })
+    }
+
+    pub fn append(&mut self, frame: &Frame) -> Result<()> {
+        let data = frame.encode();
+        (&*self.file).write_all(&data)?;
+
+        if let Some(buffer) = self.rewrite_buffer.as_mut() {
+            buffer.extend_from_slice(&data);
+        }
+
+        if self.fsync == AppendFsync::Always {
+            self.file.sync_data()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_fsync(&mut self, fsync: AppendFsync) {
+        self.fsync = fsync;
+    }
+
+    pub fn sync(&self) -> Result<()> {
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
+
+pub(crate) fn lock(backend: &Backend) -> MutexGuard<'_, Option<Aof>> {
+    backend.aof.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Logs the keys expired since the last entry as DEL, so replaying the AOF
+// deletes them at the same point. They are dropped while the AOF is disabled.
+pub(crate) fn append_expired(backend: &Backend, aof: Option<&mut Aof>) -> Result<()> {
+    let keys = backend.take_expired();
+
+    if let Some(aof) = aof {
+        for key in keys {
+            aof.append(&vec![b"DEL".into(), key.into()].into())?;
+        }
+    }
+
+    Ok(())
+}
+
+// Appends the writes of a transaction or script, wrapped in MULTI and EXEC
+// when there are several, so replaying the AOF applies all of them or none.
+pub(crate) fn append_transaction(backend: &Backend, entries: &[Frame]) -> Result<()> {
+    let mut guard = lock(backend);
+    append_expired(backend, guard.as_mut())?;
+
+    let Some(aof) = guard.as_mut() else {
+        return Ok(());
+    };
+
+    let wrap = entries.len() > 1;
+    if wrap {
+        aof.append(&vec![b"MULTI".into()].into())?;
+    }
+    for entry in entries {
+        aof.append(entry)?;
+    }
+    if wrap {
+        aof.append(&vec![b"EXEC".into()].into())?;
+    }
+
+    Ok(())
+}
+
+// Logs the keys expired by the active expiration cycle.
+pub fn propagate_expired(backend: &Backend) -> Result<()> {
+    append_expired(backend, lock(backend).as_mut())
+}
+
+// Starts logging writes to the AOF of the current configuration.
+pub fn enable(backend: &Backend) -> Result<()> {
+    let config = backend.config();
+    let aof = Aof::open(&config.aof_path(), config.appendfsync)?;
+    *lock(backend) = Some(aof);
+
+    Ok(())
+}
+
+// Stops logging writes, syncing what was already appended.
+pub fn disable(backend: &Backend) -> Result<()> {
+    if let Some(aof) = lock(backend).take() {
+        aof.sync()?;
+    }
+
+    Ok(())
+}
+
+// Replays the AOF through the command handlers, returning the number of
+// commands executed. A command cut short by a crash while it was being
+// appended is dropped and the file truncated to its last complete command.
+// Transactions are applied at their EXEC, one cut short is dropped whole.
+pub fn load(backend: &Backend, path: &Path) -> Result<usize> {
+    let data = Bytes::from(fs::read(path)?);
+    let mut cursor = Cursor::new(data.clone());
+    let mut loaded = 0;
+    // where the open transaction starts, with the commands it queued
+    let mut transaction: Option<(u64, Vec<Command>)> = None;
+
+    while (cursor.position() as usize) < data.len() {
+        let start = cursor.position();
+
+        match Frame::decode(&mut cursor) {
+            Ok(frame) => match Command::try_from(frame)? {
+                Command::Multi(_) => transaction = Some((start, Vec::new())),
+                Command::Exec(_) => {
+                    let (_, commands) = transaction
+                        .take()
+                        .ok_or_else(|| anyhow::anyhow!("EXEC without MULTI in AOF"))?;
+                    for command in commands {
+                        command.execute(backend.clone())?;
+                        loaded += 1;
+                    }
+                }
+                command => match transaction.as_mut() {
+                    Some((_, commands)) => commands.push(command),
+                    None => {
+                        command.execute(backend.clone())?;
+                        loaded += 1;
+                    }
+                },
+            },
+            Err(RespError::Incomplete) => {
+                let start = transaction.take().map_or(start, |(start, _)| start);
+                warn!(
+                    "AOF {} is truncated, discarding the last {} bytes",
+                    path.display(),
+                    data.len() as u64 - start
+                );
+                OpenOptions::new().write(true).open(path)?.set_len(start)?;
+                break;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if let Some((start, _)) = transaction {
+        warn!(
+            "AOF {} ends in a transaction without EXEC, discarding the last {} bytes",
+            path.display(),
+            data.len() as u64 - start
+        );
+        OpenOptions::new().write(true).open(path)?.set_len(start)?;
+    }
+
+    // replayed commands are already on disk
+    backend.dirty.store(0, Ordering::Relaxed);
+
+    Ok(loaded)
+}
+
+// Rewrites the AOF as the shortest sequence of commands that rebuilds the
+// current dataset. Writes keep being appended to the old file meanwhile, and
+// are copied over to the new one before it replaces the old one.
+pub fn rewrite(backend: &Backend) -> Result<()> {
+    let path = backend.config().aof_path();
+
+    // transactions and scripts apply under the keyspace lock and are logged
+    // after, the snapshot must not see one half applied
+    let entries = backend.with_keyspace_lock(false, || {
+        let mut aof = lock(backend);
+
+        if let Some(aof) = aof.as_mut() {
+            aof.rewrite_buffer = Some(Vec::new());
+        }
+
+        backend.snapshot()
+    });
+
+    let result = write_rewrite(backend, &path, &entries);
+
+    if result.is_err() {
+        if let Some(aof) = lock(backend).as_mut() {
+            aof.rewrite_buffer = None;
+        }
+    }
+
+    result
+}
+
+fn write_rewrite(backend: &Backend, path: &Path, entries: &[Entry]) -> Result<()> {
+    let tmp = path.with_file_name(format!("temp-rewriteaof-{}.aof", std::process::id()));
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+
+    for frame in rewrite_commands(entries) {
+        writer.write_all(&frame.encode())?;
+    }
+
+    let mut file = writer.into_inner()?;
+    let mut aof = lock(backend);
+
+    if let Some(buffer) = aof.as_mut().and_then(|aof| aof.rewrite_buffer.take()) {
+        file.write_all(&buffer)?;
+    }
+
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+
+    if let Some(aof) = aof.as_mut() {
+        aof.file = Arc::new(file);
+    }
+
+    info!("Background AOF rewrite finished successfully");
+
+    Ok(())
+}
+
+// Rewrites the AOF on a blocking thread. Returns false when a rewrite is
+// already running.
+pub fn bgrewrite(backend: &Backend) -> bool {
+    if backend.rewriting.swap(true, Ordering::AcqRel) {
+        return false;
+    }
+
+    let backend = backend.clone();
+    let task = move || {
+        if let Err(e) = rewrite(&backend) {
+            warn!("Background AOF rewrite error: {:?}", e);
+        }
+
+        backend.rewriting.store(false, Ordering::Release);
+    };
+
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn_blocking(task);
+        }
+        Err(_) => {
+            std::thread::spawn(task);
+        }
+    }
+
+    true
+}
+
+// Flushes the AOF to disk every second under the `everysec` policy. The sync
+// runs on a cloned handle, so writers are not blocked while it runs.
+pub async fn fsync_cron(backend: Backend) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        interval.tick().await;
+
+        let file = match lock(&backend).as_ref() {
+            Some(aof) if aof.fsync == AppendFsync::EverySec => aof.file.clone(),
+            _ => continue,
+        };
+
+        let result = tokio::task::spawn_blocking(move || file.sync_data()).await;
+
+        if let Ok(Err(e)) = result {
+            warn!("AOF fsync error: {:?}", e);
+        }
+    }
+}
+
+fn id_frame(id: StreamId) -> Frame {
+    id.to_string().as_bytes().into()
+}
+
+// Groups are created at the ID they read up to, and their pending entries
+// forced back into the list of their consumer with their delivery time and
+// counter. Consumers with nothing pending are created on their own.
+fn group_commands(key: &Frame, stream: &Stream) -> Vec<Frame> {
+    let mut commands = Vec::new();
+
+    for (name, group) in &stream.groups {
+        let entries_read = group.entries_read.map_or(-1, |read| read as i64);
+        commands.push(
+            vec![
+                b"XGROUP".into(),
+                b"CREATE".into(),
+                key.clone(),
+                name.clone().into(),
+                id_frame(group.last_id),
+                b"ENTRIESREAD".into(),
+                entries_read.to_string().as_bytes().into(),
+            ]
+            .into(),
+        );
+
+        for (id, entry) in &group.pending {
+            commands.push(
+                vec![
+                    b"XCLAIM".into(),
+                    key.clone(),
+                    name.clone().into(),
+                    entry.consumer.clone().into(),
+                    b"0".into(),
+                    id_frame(*id),
+                    b"TIME".into(),
+                    entry.delivery_time.to_string().as_bytes().into(),
+                    b"RETRYCOUNT".into(),
+                    entry.delivery_count.to_string().as_bytes().into(),
+                    b"JUSTID".into(),
+                    b"FORCE".into(),
+                ]
+                .into(),
+            );
+        }
+
+        for (consumer, _) in group.consumers.iter().filter(|(_, c)| c.pending.is_empty()) {
+            commands.push(
+                vec![
+                    b"XGROUP".into(),
+                    b"CREATECONSUMER".into(),
+                    key.clone(),
+                    name.clone().into(),
+                    consumer.clone().into(),
+                ]
+                .into(),
+            );
+        }
+    }
+
+    commands
+}
+
+fn rewrite_commands(entries: &[Entry]) -> Vec<Frame> {
+    let mut commands = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let key: Frame = entry.key.clone().into();
+
+        let command = match &entry.value {
+            Value::String(value) => vec![b"SET".into(), key.clone(), value.clone().into()],
+            Value::Hash(hash) => {
+                let mut command = vec![b"HSET".into(), key.clone()];
+                for (field, value) in hash {
+                    command.push(field.clone().into());
+                    command.push(value.clone().into());
+                }
+                command
+            }
+            Value::Set(set) => {
+                let mut command = vec![b"SADD".into(), key.clone()];
+                command.extend(set.iter().cloned().map(Frame::from));
+                command
+            }
+            Value::List(list) => {
+                let mut command = vec![b"RPUSH".into(), key.clone()];
+                command.extend(list.iter().cloned().map(Frame::from));
+                command
+            }
+            Value::ZSet(zset) => {
+                let mut command = vec![b"ZADD".into(), key.clone()];
+                for (member, score) in zset.iter() {
+                    command.push(score.to_string().as_bytes().into());
+                    command.push(member.clone().into());
+                }
+                command
+            }
+            Value::Stream(stream) => {
+                // an XADD per entry, an empty stream is created adding an
+                // entry it trims right away
+                if stream.is_empty() {
+                    commands.push(
+                        vec![
+                            b"XADD".into(),
+                            key.clone(),
+                            b"MAXLEN".into(),
+                            b"0".into(),
+                            b"0-1".into(),
+                            b"x".into(),
+                            b"y".into(),
+                        ]
+                        .into(),
+                    );
+                }
+
+                for (id, fields) in stream.iter() {
+                    let mut command = vec![b"XADD".into(), key.clone(), id_frame(*id)];
+                    for (field, value) in fields {
+                        command.push(field.clone().into());
+                        command.push(value.clone().into());
+                    }
+                    commands.push(command.into());
+                }
+
+                // XSETID restores the last ID and counters deleted entries
+                // leave behind
+                vec![
+                    b"XSETID".into(),
+                    key.clone(),
+                    id_frame(stream.last_id),
+                    b"ENTRIESADDED".into(),
+                    stream.entries_added.to_string().as_bytes().into(),
+                    b"MAXDELETEDID".into(),
+                    id_frame(stream.max_deleted_id),
+                ]
+            }
+        };
+        commands.push(command.into());
+
+        if let Value::Stream(stream) = &entry.value {
+            commands.extend(group_commands(&key, stream));
+        }
+
+        if let Some(at) = entry.expire_at {
+            commands.push(vec![b"PEXPIREAT".into(), key, at.to_string().as_bytes().into()].into());
+        }
+    }
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{now_millis, GroupReadId, XAddId};
+    use crate::config::ServerConfig;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("simple-redis-{}-{}.aof", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_aof_load_truncated() {
+        let path = temp_path("truncated");
+        let complete = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n";
+        let mut data = complete.to_vec();
+        data.extend_from_slice(b"*3\r\n$3\r\nSET\r\n$3\r\nfoo");
+        fs::write(&path, &data).unwrap();
+
+        let backend = Backend::new();
+        assert_eq!(load(&backend, &path).unwrap(), 1);
+        assert_eq!(backend.get(b"key").unwrap(), Some("value".into()));
+        assert_eq!(fs::read(&path).unwrap(), complete);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_aof_propagate_expired() {
+        let path = temp_path("expired");
+        let backend = Backend::new();
+        *lock(&backend) = Some(Aof::open(&path, AppendFsync::Always).unwrap());
+
+        backend.set(b"key", "value".into());
+        backend.expire_at(b"key", now_millis() + 1, &[]);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(backend.get(b"key").unwrap(), None);
+        propagate_expired(&backend).unwrap();
+
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            b"*2\r\n$3\r\nDEL\r\n$3\r\nkey\r\n"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_aof_load_transaction() {
+        let path = temp_path("transaction");
+        let backend = Backend::new();
+        *lock(&backend) = Some(Aof::open(&path, AppendFsync::Always).unwrap());
+
+        let set = |value: &str| -> Frame {
+            vec![b"SET".into(), b"key".into(), value.as_bytes().into()].into()
+        };
+        append_transaction(&backend, &[set("a")]).unwrap();
+        append_transaction(&backend, &[set("b"), set("c")]).unwrap();
+        let complete = fs::read(&path).unwrap();
+        assert!(complete.ends_with(b"*1\r\n$4\r\nEXEC\r\n"));
+
+        // a transaction cut short is dropped whole
+        let mut data = complete.clone();
+        data.extend_from_slice(&Frame::from(vec![b"MULTI".into()]).encode());
+        data.extend_from_slice(&set("d").encode());
+        fs::write(&path, &data).unwrap();
+
+        let loaded = Backend::new();
+        assert_eq!(load(&loaded, &path).unwrap(), 3);
+        assert_eq!(loaded.get(b"key").unwrap(), Some("c".into()));
+        assert_eq!(fs::read(&path).unwrap(), complete);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_aof_load_invalid() {
+        let path = temp_path("invalid");
+        fs::write(&path, b"*1\r\n$4\r\nNOPE\r\n").unwrap();
+
+        assert!(load(&Backend::new(), &path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_aof_rewrite() {
+        let path = temp_path("rewrite");
+        let backend = Backend::new();
+        backend.set_config(ServerConfig {
+            appendonly: true,
+            appendfilename: path.file_name().unwrap().to_str().unwrap().to_string(),
+            appendfsync: AppendFsync::Always,
+            dir: std::env::temp_dir(),
+            ..Default::default()
+        });
+
+        backend.set(b"key", "value".into());
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+        backend.sadd(b"set", vec!["member".into()]).unwrap();
+        for seq in 1..=2 {
+            let id = XAddId::Explicit(StreamId::new(1, seq));
+            let fields = vec![("field".into(), "value".into())];
+            backend.xadd(b"stream", id, fields, false, None).unwrap();
+        }
+        backend.xdel(b"stream", &[StreamId::new(1, 2)]).unwrap();
+        backend
+            .xgroup_create(b"stream", b"group", Some(StreamId::MIN), false, None)
+            .unwrap();
+        let streams = [("stream".into(), GroupReadId::New)];
+        backend
+            .xreadgroup(b"group", b"alice", &streams, None, false)
+            .unwrap();
+        backend
+            .xgroup_createconsumer(b"stream", b"group", b"bob")
+            .unwrap();
+        let at = now_millis() + 100_000;
+        backend.expire_at(b"key", at, &[]);
+
+        enable(&backend).unwrap();
+        rewrite(&backend).unwrap();
+        lock(&backend)
+            .as_mut()
+            .unwrap()
+            .append(&vec![b"DEL".into(), b"set".into()].into())
+            .unwrap();
+
+        let loaded = Backend::new();
+        assert_eq!(load(&loaded, &path).unwrap(), 10);
+        assert_eq!(loaded.get(b"key").unwrap(), Some("value".into()));
+        assert_eq!(loaded.pttl(b"key").unwrap().map(|_| ()), Some(()));
+        assert_eq!(
+            loaded.hget(b"hash", b"field").unwrap(),
+            Some("value".into())
+        );
+        assert!(!loaded.exists(b"set"));
+        assert_eq!(loaded.xlen(b"stream").unwrap(), 1);
+        assert_eq!(
+            loaded.stream_last_id(b"stream").unwrap(),
+            Some(StreamId::new(1, 2))
+        );
+        let summary = loaded.xpending_summary(b"stream", b"group").unwrap();
+        assert_eq!(summary.consumers, vec![("alice".into(), 1)]);
+        assert_eq!(
+            loaded
+                .xinfo_consumers(b"stream", b"group")
+                .unwrap()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+// CRC-64/Jones as used by Redis to checksum RDB files: reflected input and
+// output, polynomial 0xad93d23594c935a9, no final xor.
+const POLY: u64 = 0x95ac_9329_ac4b_c9b5;
+
+lazy_static::lazy_static! {
+    static ref TABLE: [u64; 256] = {
+        let mut table = [0u64; 256];
+
+        for (i, entry) in table.iter_mut().enumerate() {
+            let mut crc = i as u64;
+
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ POLY } else { crc >> 1 };
+            }
+
+            *entry = crc;
+        }
+
+        table
+    };
+}
+
+pub fn crc64(mut crc: u64, data: &[u8]) -> u64 {
+    for byte in data {
+        crc = TABLE[((crc ^ *byte as u64) & 0xff) as usize] ^ (crc >> 8);
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc64() {
+        assert_eq!(crc64(0, b"123456789"), 0xe9c6_d914_c4b8_d9ca);
+        assert_eq!(crc64(crc64(0, b"1234"), b"56789"), 0xe9c6_d914_c4b8_d9ca);
+    }
+}
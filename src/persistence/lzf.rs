@@ -0,0 +1,83 @@
+use anyhow::Result;
+
+// Decompresses LZF data, the compression Redis applies to long strings in
+// RDB files. Only decompression is needed since strings are saved raw.
+pub fn decompress(input: &[u8], expected_len: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(expected_len);
+    let mut i = 0;
+
+    while i < input.len() {
+        let ctrl = input[i] as usize;
+        i += 1;
+
+        if ctrl < 32 {
+            // literal run of ctrl + 1 bytes
+            let len = ctrl + 1;
+
+            if i + len > input.len() {
+                anyhow::bail!("Invalid LZF data: literal run out of bounds");
+            }
+
+            output.extend_from_slice(&input[i..i + len]);
+            i += len;
+        } else {
+            // back reference
+            let mut len = ctrl >> 5;
+
+            if len == 7 {
+                len += *input
+                    .get(i)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid LZF data: truncated length"))?
+                    as usize;
+                i += 1;
+            }
+
+            let low = *input
+                .get(i)
+                .ok_or_else(|| anyhow::anyhow!("Invalid LZF data: truncated offset"))?
+                as usize;
+            i += 1;
+
+            let offset = ((ctrl & 0x1f) << 8) + low + 1;
+
+            if offset > output.len() {
+                anyhow::bail!("Invalid LZF data: back reference out of bounds");
+            }
+
+            // the reference may overlap the bytes being produced
+            let start = output.len() - offset;
+            for j in 0..len + 2 {
+                output.push(output[start + j]);
+            }
+        }
+    }
+
+    if output.len() != expected_len {
+        anyhow::bail!(
+            "Invalid LZF data: expected {} bytes, got {}",
+            expected_len,
+            output.len()
+        );
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lzf_decompress() {
+        // "aaaaaaaaaaaaaaaaaaaa" as compressed by liblzf
+        let input = [0x00, b'a', 0xe0, 0x03, 0x00, 0x20, 0x00];
+        let result = decompress(&input, 1 + 12 + 3).unwrap();
+        assert_eq!(result, vec![b'a'; 16]);
+    }
+
+    #[test]
+    fn test_lzf_decompress_error() {
+        let input = [0x05, b'a'];
+        assert!(decompress(&input, 6).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+pub mod aof;
+mod crc64;
+mod lzf;
+pub mod rdb;
+
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+pub use aof::AppendFsync;
+
+use crate::backend::{now_millis, Backend};
+
+// seconds to wait after a failed background save before trying again
+const BGSAVE_RETRY_DELAY: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveRule {
+    pub seconds: u64,
+    pub changes: u64,
+}
+
+// Parses save rules in the `redis.conf` format: pairs of seconds and changes,
+// e.g. "3600 1 300 100". An empty string disables snapshotting.
+pub fn parse_save_rules(s: &str) -> Result<Vec<SaveRule>> {
+    let parts = s.split_whitespace().collect::<Vec<_>>();
+
+    if parts.len() % 2 != 0 {
+        anyhow::bail!("Invalid save parameters");
+    }
+
+    parts
+        .chunks(2)
+        .map(|pair| {
+            Ok(SaveRule {
+                seconds: u64::from_str(pair[0])?,
+                changes: u64::from_str(pair[1])?,
+            })
+        })
+        .collect()
+}
+
+pub fn format_save_rules(rules: &[SaveRule]) -> String {
+    rules
+        .iter()
+        .map(|rule| format!("{} {}", rule.seconds, rule.changes))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Loads the dataset on startup. When AOF is enabled it is preferred over the
+// RDB file, as it holds the most recent writes.
+pub fn load(backend: &Backend) -> Result<()> {
+    let config = backend.config();
+    let rdb_path = config.rdb_path();
+
+    if !config.appendonly {
+        if rdb_path.exists() {
+            let loaded = rdb::load(backend, &rdb_path)?;
+            info!("DB loaded from disk: {} keys", loaded);
+        }
+
+        return Ok(());
+    }
+
+    let aof_path = config.aof_path();
+
+    if aof_path.exists() {
+        let loaded = aof::load(backend, &aof_path)?;
+        info!("DB loaded from append only file: {} commands", loaded);
+        aof::enable(backend)?;
+    } else {
+        if rdb_path.exists() {
+            let loaded = rdb::load(backend, &rdb_path)?;
+            info!("DB loaded from disk: {} keys", loaded);
+        }
+
+        // start the AOF from what was loaded
+        aof::enable(backend)?;
+        aof::rewrite(backend)?;
+    }
+
+    Ok(())
+}
+
+// Persists everything before the server exits.
+pub fn shutdown(backend: &Backend) -> Result<()> {
+    if let Some(aof) = aof::lock(backend).as_mut() {
+        aof.sync()?;
+    }
+
+    if !backend.config().save.is_empty() {
+        backend.with_keyspace_lock(false, || save(backend))?;
+    }
+
+    Ok(())
+}
+
+// Saves the dataset in the foreground and resets the change counter. The
+// caller holds the keyspace lock, like commands do, so the snapshot does not
+// see a command touching several keys half applied.
+pub fn save(backend: &Backend) -> Result<()> {
+    let dirty = backend.dirty.load(Ordering::Relaxed);
+    write_snapshot(backend, &backend.snapshot(), dirty)
+}
+
+fn write_snapshot(backend: &Backend, entries: &[rdb::Entry], dirty: u64) -> Result<()> {
+    rdb::save(entries, &backend.config().rdb_path())?;
+
+    backend.dirty.fetch_sub(dirty, Ordering::Relaxed);
+    backend
+        .last_save
+        .store(now_millis() / 1000, Ordering::Relaxed);
+    info!("DB saved on disk");
+
+    Ok(())
+}
+
+// Saves the dataset on a blocking thread so clients keep being served while
+// the snapshot is written. Returns false when a save is already running.
+pub fn bgsave(backend: &Backend) -> bool {
+    if backend.saving.swap(true, Ordering::AcqRel) {
+        return false;
+    }
+
+    let backend = backend.clone();
+    let task = move || {
+        // the keyspace lock is only held while the values are copied
+        let (entries, dirty) = backend.with_keyspace_lock(false, || {
+            (backend.snapshot(), backend.dirty.load(Ordering::Relaxed))
+        });
+
+        match write_snapshot(&backend, &entries, dirty) {
+            Ok(()) => backend.last_bgsave_failure.store(0, Ordering::Relaxed),
+            Err(e) => {
+                warn!("Background saving error: {:?}", e);
+                backend
+                    .last_bgsave_failure
+                    .store(now_millis() / 1000, Ordering::Relaxed);
+            }
+        }
+
+        backend.saving.store(false, Ordering::Release);
+    };
+
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn_blocking(task);
+        }
+        Err(_) => {
+            std::thread::spawn(task);
+        }
+    }
+
+    true
+}
+
+// Triggers a background save whenever one of the save rules is met. After a
+// failed one, the next is only tried once `BGSAVE_RETRY_DELAY` seconds passed.
+pub async fn save_cron(backend: Backend) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        interval.tick().await;
+
+        let failed = backend.last_bgsave_failure.load(Ordering::Relaxed);
+        if failed > 0 && (now_millis() / 1000).saturating_sub(failed) < BGSAVE_RETRY_DELAY {
+            continue;
+        }
+
+        let dirty = backend.dirty.load(Ordering::Relaxed);
+        let elapsed =
+            (now_millis() / 1000).saturating_sub(backend.last_save.load(Ordering::Relaxed));
+
+        let rule = backend
+            .config()
+            .save
+            .into_iter()
+            .find(|rule| dirty >= rule.changes && elapsed >= rule.seconds);
+
+        if let Some(rule) = rule {
+            info!(
+                "{} changes in {} seconds. Saving...",
+                rule.changes, rule.seconds
+            );
+            bgsave(&backend);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_save_rules() {
+        let rules = parse_save_rules("3600 1 300 100").unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                SaveRule {
+                    seconds: 3600,
+                    changes: 1
+                },
+                SaveRule {
+                    seconds: 300,
+                    changes: 100
+                }
+            ]
+        );
+        assert_eq!(format_save_rules(&rules), "3600 1 300 100");
+
+        assert!(parse_save_rules("").unwrap().is_empty());
+        assert!(parse_save_rules("3600").is_err());
+        assert!(parse_save_rules("3600 one").is_err());
+    }
+}
@@ -0,0 +1,1174 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::crc64::crc64;
+use super::lzf;
+use crate::backend::{
+    parse_int, Backend, Consumer, ConsumerGroup, Hash, List, Set, Stream, StreamId, Value, ZSet,
+    STREAM_NODE_MAX_ENTRIES,
+};
+
+// streams are written with the consumer group fields of RDB 11
+const VERSION: u32 = 11;
+const MAX_VERSION: u32 = 12;
+
+const OPCODE_SLOT_INFO: u8 = 0xf4;
+const OPCODE_FUNCTION2: u8 = 0xf5;
+const OPCODE_IDLE: u8 = 0xf8;
+const OPCODE_FREQ: u8 = 0xf9;
+const OPCODE_AUX: u8 = 0xfa;
+const OPCODE_RESIZEDB: u8 = 0xfb;
+const OPCODE_EXPIRETIME_MS: u8 = 0xfc;
+const OPCODE_EXPIRETIME: u8 = 0xfd;
+const OPCODE_SELECTDB: u8 = 0xfe;
+const OPCODE_EOF: u8 = 0xff;
+
+const TYPE_STRING: u8 = 0;
+const TYPE_LIST: u8 = 1;
+const TYPE_SET: u8 = 2;
+const TYPE_ZSET: u8 = 3;
+const TYPE_HASH: u8 = 4;
+const TYPE_ZSET_2: u8 = 5;
+const TYPE_LIST_ZIPLIST: u8 = 10;
+const TYPE_SET_INTSET: u8 = 11;
+const TYPE_ZSET_ZIPLIST: u8 = 12;
+const TYPE_HASH_ZIPLIST: u8 = 13;
+const TYPE_LIST_QUICKLIST: u8 = 14;
+const TYPE_STREAM_LISTPACKS: u8 = 15;
+const TYPE_HASH_LISTPACK: u8 = 16;
+const TYPE_ZSET_LISTPACK: u8 = 17;
+const TYPE_LIST_QUICKLIST_2: u8 = 18;
+const TYPE_STREAM_LISTPACKS_2: u8 = 19;
+const TYPE_SET_LISTPACK: u8 = 20;
+const TYPE_STREAM_LISTPACKS_3: u8 = 21;
+
+const QUICKLIST_NODE_PLAIN: u64 = 1;
+const QUICKLIST_NODE_PACKED: u64 = 2;
+
+const ENC_INT8: u8 = 0;
+const ENC_INT16: u8 = 1;
+const ENC_INT32: u8 = 2;
+const ENC_LZF: u8 = 3;
+
+const STREAM_ITEM_FLAG_DELETED: i64 = 1;
+const STREAM_ITEM_FLAG_SAMEFIELDS: i64 = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: Bytes,
+    pub value: Value,
+    // absolute expiration time in unix milliseconds
+    pub expire_at: Option<u64>,
+}
+
+// Writes the snapshot to a temporary file first and renames it over the
+// target, so a crash while saving never leaves a truncated RDB behind.
+pub fn save(entries: &[Entry], path: &Path) -> Result<()> {
+    let data = encode(entries);
+
+    let tmp = path.with_file_name(format!("temp-{}.rdb", std::process::id()));
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+    writer.write_all(&data)?;
+    writer.into_inner()?.sync_all()?;
+    fs::rename(&tmp, path)?;
+
+    Ok(())
+}
+
+// Loads the RDB file into the backend, returning the number of keys loaded.
+// Keys that already expired are skipped.
+pub fn load(backend: &Backend, path: &Path) -> Result<usize> {
+    let data = fs::read(path)?;
+    let entries = decode(&data)?;
+    let now = crate::backend::now_millis();
+    let mut loaded = 0;
+
+    for entry in entries {
+        if entry.expire_at.is_some_and(|at| at <= now) {
+            continue;
+        }
+
+        backend.restore(entry.key, entry.value, entry.expire_at);
+        loaded += 1;
+    }
+
+    Ok(loaded)
+}
+
+pub fn encode(entries: &[Entry]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(format!("REDIS{:04}", VERSION).as_bytes());
+
+    write_aux(&mut buf, "redis-ver", "7.2.0");
+    write_aux(&mut buf, "redis-bits", "64");
+    write_aux(
+        &mut buf,
+        "ctime",
+        &(crate::backend::now_millis() / 1000).to_string(),
+    );
+
+    buf.push(OPCODE_SELECTDB);
+    write_length(&mut buf, 0);
+
+    let expires = entries.iter().filter(|e| e.expire_at.is_some()).count();
+    buf.push(OPCODE_RESIZEDB);
+    write_length(&mut buf, entries.len() as u64);
+    write_length(&mut buf, expires as u64);
+
+    for entry in entries {
+        if let Some(at) = entry.expire_at {
+            buf.push(OPCODE_EXPIRETIME_MS);
+            buf.extend_from_slice(&at.to_le_bytes());
+        }
+
+        match &entry.value {
+            Value::String(value) => {
+                buf.push(TYPE_STRING);
+                write_string(&mut buf, &entry.key);
+                write_string(&mut buf, value);
+            }
+            Value::Set(set) => {
+                buf.push(TYPE_SET);
+                write_string(&mut buf, &entry.key);
+                write_length(&mut buf, set.len() as u64);
+
+                for member in set {
+                    write_string(&mut buf, member);
+                }
+            }
+            Value::List(list) => {
+                buf.push(TYPE_LIST);
+                write_string(&mut buf, &entry.key);
+                write_length(&mut buf, list.len() as u64);
+
+                for element in list {
+                    write_string(&mut buf, element);
+                }
+            }
+            Value::ZSet(zset) => {
+                buf.push(TYPE_ZSET_2);
+                write_string(&mut buf, &entry.key);
+                write_length(&mut buf, zset.len() as u64);
+
+                for (member, score) in zset.iter() {
+                    write_string(&mut buf, member);
+                    buf.extend_from_slice(&score.to_le_bytes());
+                }
+            }
+            Value::Hash(hash) => {
+                buf.push(TYPE_HASH);
+                write_string(&mut buf, &entry.key);
+                write_length(&mut buf, hash.len() as u64);
+
+                for (field, value) in hash {
+                    write_string(&mut buf, field);
+                    write_string(&mut buf, value);
+                }
+            }
+            Value::Stream(stream) => {
+                // the only stream type keeping the counters XSETID restores
+                // and the active time of consumers, Redis 7.2 loads it
+                // whatever the file version
+                buf.push(TYPE_STREAM_LISTPACKS_3);
+                write_string(&mut buf, &entry.key);
+                write_stream(&mut buf, stream);
+            }
+        }
+    }
+
+    buf.push(OPCODE_EOF);
+    let checksum = crc64(0, &buf);
+    buf.extend_from_slice(&checksum.to_le_bytes());
+    buf
+}
+
+pub fn decode(data: &[u8]) -> Result<Vec<Entry>> {
+    let mut reader = Reader::new(data);
+
+    let magic = reader.take(9)?;
+    if &magic[..5] != b"REDIS" {
+        anyhow::bail!("Wrong signature trying to load DB from file");
+    }
+
+    let version: u32 = std::str::from_utf8(&magic[5..])?.parse()?;
+    if !(1..=MAX_VERSION).contains(&version) {
+        anyhow::bail!("Can't handle RDB format version {}", version);
+    }
+
+    let mut entries = Vec::new();
+    let mut expire_at = None;
+
+    loop {
+        let opcode = reader.u8()?;
+
+        match opcode {
+            OPCODE_EOF => break,
+            OPCODE_SELECTDB => {
+                reader.length()?;
+            }
+            OPCODE_RESIZEDB => {
+                reader.length()?;
+                reader.length()?;
+            }
+            OPCODE_AUX => {
+                reader.string()?;
+                reader.string()?;
+            }
+            OPCODE_EXPIRETIME_MS => {
+                expire_at = Some(u64::from_le_bytes(reader.take(8)?.try_into()?));
+            }
+            OPCODE_EXPIRETIME => {
+                let seconds = u32::from_le_bytes(reader.take(4)?.try_into()?);
+                expire_at = Some(seconds as u64 * 1000);
+            }
+            OPCODE_IDLE => {
+                reader.length()?;
+            }
+            OPCODE_FREQ => {
+                reader.u8()?;
+            }
+            OPCODE_SLOT_INFO => {
+                reader.length()?;
+                reader.length()?;
+                reader.length()?;
+            }
+            OPCODE_FUNCTION2 => {
+                reader.string()?;
+            }
+            value_type => {
+                let key = Bytes::from(reader.string()?);
+                let value = reader.value(value_type)?;
+
+                entries.push(Entry {
+                    key,
+                    value,
+                    expire_at: expire_at.take(),
+                });
+            }
+        }
+    }
+
+    // version 5 and later end with a checksum, zero when checksums are disabled
+    if version >= 5 {
+        let end = reader.pos;
+        let expected = u64::from_le_bytes(reader.take(8)?.try_into()?);
+
+        if expected != 0 && expected != crc64(0, &data[..end]) {
+            anyhow::bail!("Wrong RDB checksum");
+        }
+    }
+
+    Ok(entries)
+}
+
+fn write_aux(buf: &mut Vec<u8>, key: &str, value: &str) {
+    buf.push(OPCODE_AUX);
+    write_string(buf, key.as_bytes());
+    write_string(buf, value.as_bytes());
+}
+
+fn write_length(buf: &mut Vec<u8>, len: u64) {
+    if len < 1 << 6 {
+        buf.push(len as u8);
+    } else if len < 1 << 14 {
+        buf.push(0x40 | (len >> 8) as u8);
+        buf.push(len as u8);
+    } else if len <= u32::MAX as u64 {
+        buf.push(0x80);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        buf.push(0x81);
+        buf.extend_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn write_string(buf: &mut Vec<u8>, s: &[u8]) {
+    write_length(buf, s.len() as u64);
+    buf.extend_from_slice(s);
+}
+
+// Entries are stored in listpack nodes keyed by the ID of their first entry,
+// the master entry. Entries with the same fields as it only store values.
+fn write_stream(buf: &mut Vec<u8>, stream: &Stream) {
+    let entries = stream.iter().collect::<Vec<_>>();
+    let nodes = entries.chunks(STREAM_NODE_MAX_ENTRIES);
+    write_length(buf, nodes.len() as u64);
+
+    for node in nodes {
+        let (master_id, master_fields) = node[0];
+        write_string(buf, &stream_id(*master_id));
+
+        let mut lp = ListpackWriter::default();
+        lp.push_int(node.len() as i64);
+        // deleted entries
+        lp.push_int(0);
+        lp.push_int(master_fields.len() as i64);
+        for (field, _) in master_fields {
+            lp.push_string(field);
+        }
+        lp.push_int(0);
+
+        for (id, fields) in node {
+            let same_fields = fields.len() == master_fields.len()
+                && fields
+                    .iter()
+                    .zip(master_fields)
+                    .all(|((field, _), (master, _))| field == master);
+
+            lp.push_int(if same_fields {
+                STREAM_ITEM_FLAG_SAMEFIELDS
+            } else {
+                0
+            });
+            lp.push_int(id.ms.wrapping_sub(master_id.ms) as i64);
+            lp.push_int(id.seq.wrapping_sub(master_id.seq) as i64);
+            if !same_fields {
+                lp.push_int(fields.len() as i64);
+            }
+
+            for (field, value) in fields.iter() {
+                if !same_fields {
+                    lp.push_string(field);
+                }
+                lp.push_string(value);
+            }
+
+            // number of items of the entry, to iterate backwards
+            let items = match same_fields {
+                true => fields.len() + 3,
+                false => fields.len() * 2 + 4,
+            };
+            lp.push_int(items as i64);
+        }
+
+        write_string(buf, &lp.finish());
+    }
+
+    write_length(buf, stream.len() as u64);
+    write_length(buf, stream.last_id.ms);
+    write_length(buf, stream.last_id.seq);
+    let first_id = stream.first_id().unwrap_or_default();
+    write_length(buf, first_id.ms);
+    write_length(buf, first_id.seq);
+    write_length(buf, stream.max_deleted_id.ms);
+    write_length(buf, stream.max_deleted_id.seq);
+    write_length(buf, stream.entries_added);
+
+    write_length(buf, stream.groups.len() as u64);
+    for (name, group) in &stream.groups {
+        write_string(buf, name);
+        write_length(buf, group.last_id.ms);
+        write_length(buf, group.last_id.seq);
+        // -1 when unknown, as Redis stores it
+        write_length(buf, group.entries_read.unwrap_or(u64::MAX));
+
+        write_length(buf, group.pending.len() as u64);
+        for (id, entry) in &group.pending {
+            buf.extend_from_slice(&stream_id(*id));
+            buf.extend_from_slice(&entry.delivery_time.to_le_bytes());
+            write_length(buf, entry.delivery_count);
+        }
+
+        // consumers only list the IDs of their pending entries
+        write_length(buf, group.consumers.len() as u64);
+        for (name, consumer) in &group.consumers {
+            write_string(buf, name);
+            buf.extend_from_slice(&consumer.seen_time.to_le_bytes());
+            let active_time = consumer.active_time.map_or(-1, |time| time as i64);
+            buf.extend_from_slice(&active_time.to_le_bytes());
+
+            write_length(buf, consumer.pending.len() as u64);
+            for id in &consumer.pending {
+                buf.extend_from_slice(&stream_id(*id));
+            }
+        }
+    }
+}
+
+// Stream IDs are stored as 16 big endian bytes in node keys.
+fn stream_id(id: StreamId) -> Vec<u8> {
+    let mut bytes = id.ms.to_be_bytes().to_vec();
+    bytes.extend_from_slice(&id.seq.to_be_bytes());
+    bytes
+}
+
+fn parse_stream_id(bytes: &[u8]) -> Result<StreamId> {
+    if bytes.len() != 16 {
+        anyhow::bail!("Invalid stream ID in RDB file");
+    }
+
+    Ok(StreamId::new(
+        u64::from_be_bytes(bytes[..8].try_into()?),
+        u64::from_be_bytes(bytes[8..].try_into()?),
+    ))
+}
+
+// Builds a listpack the way Redis encodes one, strings holding integers are
+// stored as integers.
+#[derive(Default)]
+struct ListpackWriter {
+    entries: Vec<u8>,
+    len: usize,
+}
+
+impl ListpackWriter {
+    fn push_int(&mut self, value: i64) {
+        let mut entry = Vec::with_capacity(9);
+
+        match value {
+            0..=127 => entry.push(value as u8),
+            -4096..=4095 => {
+                let value = value as u16 & 0x1fff;
+                entry.extend_from_slice(&[0xc0 | (value >> 8) as u8, value as u8]);
+            }
+            -32768..=32767 => {
+                entry.push(0xf1);
+                entry.extend_from_slice(&(value as i16).to_le_bytes());
+            }
+            -8388608..=8388607 => {
+                entry.push(0xf2);
+                entry.extend_from_slice(&(value as i32).to_le_bytes()[..3]);
+            }
+            -2147483648..=2147483647 => {
+                entry.push(0xf3);
+                entry.extend_from_slice(&(value as i32).to_le_bytes());
+            }
+            _ => {
+                entry.push(0xf4);
+                entry.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        self.push_entry(&entry);
+    }
+
+    fn push_string(&mut self, s: &[u8]) {
+        if let Some(value) = parse_int(s) {
+            return self.push_int(value);
+        }
+
+        let mut entry = Vec::with_capacity(s.len() + 5);
+        match s.len() {
+            len @ 0..=63 => entry.push(0x80 | len as u8),
+            len @ 64..=4095 => entry.extend_from_slice(&[0xe0 | (len >> 8) as u8, len as u8]),
+            len => {
+                entry.push(0xf0);
+                entry.extend_from_slice(&(len as u32).to_le_bytes());
+            }
+        }
+        entry.extend_from_slice(s);
+
+        self.push_entry(&entry);
+    }
+
+    // Entries are followed by their length, 7 bits per byte with the most
+    // significant first, so the listpack can be read backwards.
+    fn push_entry(&mut self, entry: &[u8]) {
+        self.entries.extend_from_slice(entry);
+        self.len += 1;
+
+        let len = entry.len();
+        let size = match len {
+            0..=127 => 1,
+            128..=16382 => 2,
+            16383..=2097150 => 3,
+            2097151..=268435454 => 4,
+            _ => 5,
+        };
+
+        for i in (0..size).rev() {
+            let byte = ((len >> (7 * i)) & 127) as u8;
+            self.entries
+                .push(if i == size - 1 { byte } else { byte | 128 });
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        let total = 6 + self.entries.len() + 1;
+        let mut blob = Vec::with_capacity(total);
+
+        blob.extend_from_slice(&(total as u32).to_le_bytes());
+        blob.extend_from_slice(&(self.len.min(u16::MAX as usize) as u16).to_le_bytes());
+        blob.extend_from_slice(&self.entries);
+        blob.push(0xff);
+
+        blob
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+enum Length {
+    Len(u64),
+    Encoded(u8),
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.pos + n > self.data.len() {
+            anyhow::bail!("Unexpected EOF reading RDB file");
+        }
+
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn length_or_encoding(&mut self) -> Result<Length> {
+        let first = self.u8()?;
+
+        let len = match first >> 6 {
+            0 => (first & 0x3f) as u64,
+            1 => (((first & 0x3f) as u64) << 8) | self.u8()? as u64,
+            2 => match first {
+                0x80 => u32::from_be_bytes(self.take(4)?.try_into()?) as u64,
+                0x81 => u64::from_be_bytes(self.take(8)?.try_into()?),
+                _ => anyhow::bail!("Unknown length encoding {} in RDB file", first),
+            },
+            _ => return Ok(Length::Encoded(first & 0x3f)),
+        };
+
+        Ok(Length::Len(len))
+    }
+
+    fn length(&mut self) -> Result<u64> {
+        match self.length_or_encoding()? {
+            Length::Len(len) => Ok(len),
+            Length::Encoded(_) => anyhow::bail!("Unexpected string encoding in RDB file"),
+        }
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>> {
+        match self.length_or_encoding()? {
+            Length::Len(len) => Ok(self.take(len as usize)?.to_vec()),
+            Length::Encoded(ENC_INT8) => Ok((self.u8()? as i8).to_string().into_bytes()),
+            Length::Encoded(ENC_INT16) => {
+                let value = i16::from_le_bytes(self.take(2)?.try_into()?);
+                Ok(value.to_string().into_bytes())
+            }
+            Length::Encoded(ENC_INT32) => {
+                let value = i32::from_le_bytes(self.take(4)?.try_into()?);
+                Ok(value.to_string().into_bytes())
+            }
+            Length::Encoded(ENC_LZF) => {
+                let compressed_len = self.length()? as usize;
+                let len = self.length()? as usize;
+                lzf::decompress(self.take(compressed_len)?, len)
+            }
+            Length::Encoded(encoding) => {
+                anyhow::bail!("Unknown string encoding {} in RDB file", encoding)
+            }
+        }
+    }
+
+    // Length prefixed ASCII score, with special lengths for NaN and the
+    // infinities.
+    fn score(&mut self) -> Result<f64> {
+        let score = match self.u8()? {
+            253 => anyhow::bail!("Sorted set with NaN score in RDB file"),
+            254 => f64::INFINITY,
+            255 => f64::NEG_INFINITY,
+            len => std::str::from_utf8(self.take(len as usize)?)?.parse()?,
+        };
+
+        Ok(score)
+    }
+
+    fn value(&mut self, value_type: u8) -> Result<Value> {
+        let value = match value_type {
+            TYPE_STRING => Value::String(self.string()?.into()),
+            TYPE_SET => {
+                let len = self.length()?;
+                let mut set = Set::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    set.insert(self.string()?.into());
+                }
+
+                Value::Set(set)
+            }
+            TYPE_HASH => {
+                let len = self.length()?;
+                let mut hash = Hash::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    let field = self.string()?;
+                    let value = self.string()?;
+                    hash.insert(field.into(), value.into());
+                }
+
+                Value::Hash(hash)
+            }
+            TYPE_LIST => {
+                let len = self.length()?;
+                let mut list = List::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    list.push_back(self.string()?.into());
+                }
+
+                Value::List(list)
+            }
+            TYPE_LIST_ZIPLIST => {
+                let blob = self.string()?;
+                Value::List(ziplist(&blob)?.into_iter().map(Bytes::from).collect())
+            }
+            // lists of nodes, each a ziplist or, from version 2, either a
+            // listpack or a single plain element
+            TYPE_LIST_QUICKLIST | TYPE_LIST_QUICKLIST_2 => {
+                let len = self.length()?;
+                let mut list = List::new();
+
+                for _ in 0..len {
+                    let container = match value_type {
+                        TYPE_LIST_QUICKLIST_2 => self.length()?,
+                        _ => QUICKLIST_NODE_PACKED,
+                    };
+                    let blob = self.string()?;
+
+                    match (value_type, container) {
+                        (_, QUICKLIST_NODE_PLAIN) => list.push_back(blob.into()),
+                        (TYPE_LIST_QUICKLIST, _) => {
+                            list.extend(ziplist(&blob)?.into_iter().map(Bytes::from))
+                        }
+                        _ => list.extend(listpack(&blob)?.into_iter().map(Bytes::from)),
+                    }
+                }
+
+                Value::List(list)
+            }
+            TYPE_SET_INTSET => {
+                let blob = self.string()?;
+                Value::Set(intset(&blob)?.into_iter().map(Bytes::from).collect())
+            }
+            TYPE_SET_LISTPACK => {
+                let blob = self.string()?;
+                Value::Set(listpack(&blob)?.into_iter().map(Bytes::from).collect())
+            }
+            TYPE_HASH_ZIPLIST | TYPE_HASH_LISTPACK => {
+                let blob = self.string()?;
+                let items = match value_type {
+                    TYPE_HASH_ZIPLIST => ziplist(&blob)?,
+                    _ => listpack(&blob)?,
+                };
+
+                let mut hash = Hash::with_capacity(items.len() / 2);
+                let mut items = items.into_iter();
+
+                while let (Some(field), Some(value)) = (items.next(), items.next()) {
+                    hash.insert(field.into(), value.into());
+                }
+
+                Value::Hash(hash)
+            }
+            // scores are stored as strings in the first version, as binary
+            // doubles from the second one
+            TYPE_ZSET | TYPE_ZSET_2 => {
+                let len = self.length()?;
+                let mut zset = ZSet::default();
+
+                for _ in 0..len {
+                    let member = self.string()?;
+                    let score = match value_type {
+                        TYPE_ZSET_2 => f64::from_le_bytes(self.take(8)?.try_into()?),
+                        _ => self.score()?,
+                    };
+                    zset.insert(member.into(), score);
+                }
+
+                Value::ZSet(zset)
+            }
+            TYPE_ZSET_ZIPLIST | TYPE_ZSET_LISTPACK => {
+                let blob = self.string()?;
+                let items = match value_type {
+                    TYPE_ZSET_ZIPLIST => ziplist(&blob)?,
+                    _ => listpack(&blob)?,
+                };
+
+                let mut zset = ZSet::default();
+                let mut items = items.into_iter();
+
+                while let (Some(member), Some(score)) = (items.next(), items.next()) {
+                    zset.insert(member.into(), std::str::from_utf8(&score)?.parse()?);
+                }
+
+                Value::ZSet(zset)
+            }
+            TYPE_STREAM_LISTPACKS | TYPE_STREAM_LISTPACKS_2 | TYPE_STREAM_LISTPACKS_3 => {
+                let mut stream = Stream::default();
+
+                for _ in 0..self.length()? {
+                    let master_id = parse_stream_id(&self.string()?)?;
+                    let blob = self.string()?;
+                    stream_node(&mut stream, master_id, listpack(&blob)?)?;
+                }
+
+                let len = self.length()?;
+                let (ms, seq) = (self.length()?, self.length()?);
+                stream.last_id = StreamId::new(ms, seq);
+
+                if value_type == TYPE_STREAM_LISTPACKS {
+                    stream.entries_added = len;
+                } else {
+                    // first ID, which follows from the entries
+                    self.length()?;
+                    self.length()?;
+                    let (ms, seq) = (self.length()?, self.length()?);
+                    stream.max_deleted_id = StreamId::new(ms, seq);
+                    stream.entries_added = self.length()?;
+                }
+
+                for _ in 0..self.length()? {
+                    let (name, group) = self.consumer_group(&stream, value_type)?;
+                    stream.groups.insert(name.into(), group);
+                }
+
+                Value::Stream(stream)
+            }
+            value_type => anyhow::bail!("Unsupported value type {} in RDB file", value_type),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Reader<'_> {
+    // Reads a consumer group, see `write_stream` for its layout. The first
+    // stream type has no entries read counter, and consumers only have an
+    // active time from the third one.
+    fn consumer_group(
+        &mut self,
+        stream: &Stream,
+        value_type: u8,
+    ) -> Result<(Vec<u8>, ConsumerGroup)> {
+        let name = self.string()?;
+        let (ms, seq) = (self.length()?, self.length()?);
+        let last_id = StreamId::new(ms, seq);
+
+        let entries_read = match value_type {
+            TYPE_STREAM_LISTPACKS => stream.entries_read_at(last_id),
+            _ => match self.length()? {
+                u64::MAX => None,
+                read => Some(read),
+            },
+        };
+        let mut group = ConsumerGroup::new(last_id, entries_read);
+
+        let mut pending = BTreeMap::new();
+        for _ in 0..self.length()? {
+            let id = parse_stream_id(self.take(16)?)?;
+            let delivery_time = u64::from_le_bytes(self.take(8)?.try_into()?);
+            pending.insert(id, (delivery_time, self.length()?));
+        }
+
+        for _ in 0..self.length()? {
+            let consumer = Bytes::from(self.string()?);
+            let seen_time = u64::from_le_bytes(self.take(8)?.try_into()?);
+            let active_time = match value_type {
+                TYPE_STREAM_LISTPACKS_3 => match i64::from_le_bytes(self.take(8)?.try_into()?) {
+                    -1 => None,
+                    time => Some(time as u64),
+                },
+                _ => Some(seen_time),
+            };
+
+            group.consumers.insert(
+                consumer.clone(),
+                Consumer {
+                    seen_time,
+                    active_time,
+                    pending: Default::default(),
+                },
+            );
+
+            for _ in 0..self.length()? {
+                let id = parse_stream_id(self.take(16)?)?;
+                let Some((delivery_time, count)) = pending.remove(&id) else {
+                    anyhow::bail!("Consumer pending entry missing from its group in RDB file");
+                };
+                group.assign(id, &consumer, delivery_time, count);
+            }
+        }
+
+        if !pending.is_empty() {
+            anyhow::bail!("Group pending entry without a consumer in RDB file");
+        }
+
+        Ok((name, group))
+    }
+}
+
+// Adds the entries of a stream node, see `write_stream` for its layout.
+fn stream_node(stream: &mut Stream, master_id: StreamId, items: Vec<Vec<u8>>) -> Result<()> {
+    let mut items = items.into_iter().map(Bytes::from);
+    let mut next = || {
+        items
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Truncated stream node in RDB file"))
+    };
+    let int = |item: Bytes| -> Result<i64> { Ok(std::str::from_utf8(&item)?.parse()?) };
+
+    // live and deleted entries
+    let count = int(next()?)? + int(next()?)?;
+    let master_fields = (0..int(next()?)?)
+        .map(|_| next())
+        .collect::<Result<Vec<_>>>()?;
+    next()?;
+
+    for _ in 0..count {
+        let flags = int(next()?)?;
+        let ms = master_id.ms.wrapping_add(int(next()?)? as u64);
+        let seq = master_id.seq.wrapping_add(int(next()?)? as u64);
+
+        let fields = match flags & STREAM_ITEM_FLAG_SAMEFIELDS {
+            0 => (0..int(next()?)?)
+                .map(|_| Ok((next()?, next()?)))
+                .collect::<Result<Vec<_>>>()?,
+            _ => master_fields
+                .iter()
+                .map(|field| Ok((field.clone(), next()?)))
+                .collect::<Result<Vec<_>>>()?,
+        };
+        // number of items of the entry
+        next()?;
+
+        if flags & STREAM_ITEM_FLAG_DELETED == 0 {
+            stream.insert(StreamId::new(ms, seq), fields);
+        }
+    }
+
+    Ok(())
+}
+
+fn intset(blob: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let mut reader = Reader::new(blob);
+    let encoding = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+    let len = u32::from_le_bytes(reader.take(4)?.try_into()?);
+    let mut items = Vec::with_capacity(len as usize);
+
+    for _ in 0..len {
+        let bytes = reader.take(encoding)?;
+        let value = match encoding {
+            2 => i16::from_le_bytes(bytes.try_into()?) as i64,
+            4 => i32::from_le_bytes(bytes.try_into()?) as i64,
+            8 => i64::from_le_bytes(bytes.try_into()?),
+            _ => anyhow::bail!("Unknown intset encoding {}", encoding),
+        };
+
+        items.push(value.to_string().into_bytes());
+    }
+
+    Ok(items)
+}
+
+fn ziplist(blob: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let mut reader = Reader::new(blob);
+    // zlbytes, zltail and zllen
+    reader.take(10)?;
+    let mut items = Vec::new();
+
+    loop {
+        let prevlen = reader.u8()?;
+        if prevlen == 0xff {
+            break;
+        }
+
+        if prevlen == 0xfe {
+            reader.take(4)?;
+        }
+
+        let encoding = reader.u8()?;
+        let item = match encoding >> 6 {
+            0 => reader.take((encoding & 0x3f) as usize)?.to_vec(),
+            1 => {
+                let len = (((encoding & 0x3f) as usize) << 8) | reader.u8()? as usize;
+                reader.take(len)?.to_vec()
+            }
+            2 => {
+                let len = u32::from_be_bytes(reader.take(4)?.try_into()?) as usize;
+                reader.take(len)?.to_vec()
+            }
+            _ => {
+                let value = match encoding {
+                    0xc0 => i16::from_le_bytes(reader.take(2)?.try_into()?) as i64,
+                    0xd0 => i32::from_le_bytes(reader.take(4)?.try_into()?) as i64,
+                    0xe0 => i64::from_le_bytes(reader.take(8)?.try_into()?),
+                    0xf0 => {
+                        let bytes = reader.take(3)?;
+                        (i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8) as i64
+                    }
+                    0xfe => reader.u8()? as i8 as i64,
+                    0xf1..=0xfd => (encoding & 0x0f) as i64 - 1,
+                    _ => anyhow::bail!("Unknown ziplist encoding {}", encoding),
+                };
+
+                value.to_string().into_bytes()
+            }
+        };
+
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+fn listpack(blob: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let mut reader = Reader::new(blob);
+    // total bytes and number of elements
+    reader.take(6)?;
+    let mut items = Vec::new();
+
+    loop {
+        let start = reader.pos;
+        let encoding = reader.u8()?;
+
+        if encoding == 0xff {
+            break;
+        }
+
+        let item = if encoding & 0x80 == 0 {
+            (encoding as i64).to_string().into_bytes()
+        } else if encoding & 0xc0 == 0x80 {
+            reader.take((encoding & 0x3f) as usize)?.to_vec()
+        } else if encoding & 0xe0 == 0xc0 {
+            let value = (((encoding & 0x1f) as i64) << 8) | reader.u8()? as i64;
+            signed(value, 13).to_string().into_bytes()
+        } else if encoding & 0xf0 == 0xe0 {
+            let len = (((encoding & 0x0f) as usize) << 8) | reader.u8()? as usize;
+            reader.take(len)?.to_vec()
+        } else {
+            match encoding {
+                0xf0 => {
+                    let len = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+                    reader.take(len)?.to_vec()
+                }
+                0xf1 => (i16::from_le_bytes(reader.take(2)?.try_into()?) as i64)
+                    .to_string()
+                    .into_bytes(),
+                0xf2 => {
+                    let bytes = reader.take(3)?;
+                    let value = (i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8) as i64;
+                    value.to_string().into_bytes()
+                }
+                0xf3 => (i32::from_le_bytes(reader.take(4)?.try_into()?) as i64)
+                    .to_string()
+                    .into_bytes(),
+                0xf4 => i64::from_le_bytes(reader.take(8)?.try_into()?)
+                    .to_string()
+                    .into_bytes(),
+                _ => anyhow::bail!("Unknown listpack encoding {}", encoding),
+            }
+        };
+
+        // skip the backlen, which encodes the size of the entry read so far
+        let entry_len = reader.pos - start;
+        let backlen = match entry_len {
+            0..=127 => 1,
+            128..=16382 => 2,
+            16383..=2097150 => 3,
+            2097151..=268435454 => 4,
+            _ => 5,
+        };
+        reader.take(backlen)?;
+
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+fn signed(value: i64, bits: u32) -> i64 {
+    if value >= 1 << (bits - 1) {
+        value - (1 << bits)
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::XAddId;
+
+    fn entries() -> Vec<Entry> {
+        let mut hash = Hash::new();
+        hash.insert("field".into(), "value".into());
+
+        let mut set = Set::new();
+        set.insert("member".into());
+
+        let list = List::from(vec!["a".into(), "b".into(), "a".into()]);
+
+        let mut zset = ZSet::default();
+        zset.insert("low".into(), f64::NEG_INFINITY);
+        zset.insert("high".into(), 1.5);
+
+        // integers of every listpack encoding, long strings and entries with
+        // other fields than the first one
+        let mut stream = Stream::default();
+        for (i, value) in ["1", "-5000", "100000", "-9000000", "5000000000"]
+            .into_iter()
+            .enumerate()
+        {
+            let fields = vec![("n".into(), Bytes::from(value))];
+            stream
+                .add(XAddId::Explicit(StreamId::new(1, i as u64)), fields)
+                .unwrap();
+        }
+        let fields = vec![("long".into(), Bytes::from("x".repeat(5000)))];
+        stream.add(XAddId::AutoSeq(u64::MAX - 1), fields).unwrap();
+        stream.delete(&[StreamId::new(1, 0)]);
+
+        let mut group = ConsumerGroup::new(StreamId::new(1, 2), Some(3));
+        let alice = Bytes::from("alice");
+        group.consumer(&alice, 1_700_000_000_000).active_time = Some(1_700_000_000_500);
+        group.consumer(b"bob", 1_700_000_000_000);
+        group.assign(StreamId::new(1, 1), &alice, 1_700_000_000_500, 2);
+        group.assign(StreamId::new(1, 2), &alice, 1_700_000_000_500, 1);
+        stream.groups.insert("group".into(), group);
+        stream
+            .groups
+            .insert("empty".into(), ConsumerGroup::new(StreamId::MIN, None));
+
+        vec![
+            Entry {
+                key: "string".into(),
+                value: Value::String(Bytes::from_static(b"binary\r\n\x00")),
+                expire_at: Some(4_102_444_800_000),
+            },
+            Entry {
+                key: "hash".into(),
+                value: Value::Hash(hash),
+                expire_at: None,
+            },
+            Entry {
+                key: "set".into(),
+                value: Value::Set(set),
+                expire_at: None,
+            },
+            Entry {
+                key: "list".into(),
+                value: Value::List(list),
+                expire_at: None,
+            },
+            Entry {
+                key: "zset".into(),
+                value: Value::ZSet(zset),
+                expire_at: None,
+            },
+            Entry {
+                key: "stream".into(),
+                value: Value::Stream(stream),
+                expire_at: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rdb_round_trip() {
+        let entries = entries();
+        let data = encode(&entries);
+
+        assert_eq!(&data[..9], b"REDIS0011");
+        assert_eq!(decode(&data).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_rdb_decode_checksum_error() {
+        let mut data = encode(&entries());
+        let len = data.len();
+        data[len - 1] ^= 0xff;
+
+        assert!(decode(&data).is_err());
+    }
+
+    #[test]
+    fn test_rdb_decode_redis_dump() {
+        // the compact encodings Redis 7.2 uses for `SET str 12345`,
+        // `HSET h f v`, `SADD s 1 2`, `RPUSH l a 1` and `ZADD z 1 a`, with
+        // checksum disabled
+        let mut data = b"REDIS0011".to_vec();
+        data.extend_from_slice(&[OPCODE_SELECTDB, 0, OPCODE_RESIZEDB, 3, 0]);
+        // int encoded string
+        data.extend_from_slice(&[TYPE_STRING, 3, b's', b't', b'r', 0xc1, 0x39, 0x30]);
+        // listpack hash with two string entries
+        data.extend_from_slice(&[TYPE_HASH_LISTPACK, 1, b'h', 13]);
+        data.extend_from_slice(&[13, 0, 0, 0, 2, 0, 0x81, b'f', 2, 0x81, b'v', 2, 0xff]);
+        // intset with two 16 bit integers
+        data.extend_from_slice(&[TYPE_SET_INTSET, 1, b's', 12]);
+        data.extend_from_slice(&[2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 2, 0]);
+        // quicklist with a single listpack node
+        data.extend_from_slice(&[TYPE_LIST_QUICKLIST_2, 1, b'l', 1, 2, 12]);
+        data.extend_from_slice(&[12, 0, 0, 0, 2, 0, 0x81, b'a', 2, 0x01, 1, 0xff]);
+        // listpack sorted set with member `a` and score 1
+        data.extend_from_slice(&[TYPE_ZSET_LISTPACK, 1, b'z', 12]);
+        data.extend_from_slice(&[12, 0, 0, 0, 2, 0, 0x81, b'a', 2, 0x01, 1, 0xff]);
+        data.push(OPCODE_EOF);
+        data.extend_from_slice(&[0; 8]);
+
+        let entries = decode(&data).unwrap();
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].value, Value::String("12345".into()));
+
+        match &entries[1].value {
+            Value::Hash(hash) => assert_eq!(hash.get(&b"f"[..]), Some(&"v".into())),
+            _ => panic!("Expected Hash"),
+        }
+
+        match &entries[2].value {
+            Value::Set(set) => {
+                assert!(set.contains(&b"1"[..]));
+                assert!(set.contains(&b"2"[..]));
+            }
+            _ => panic!("Expected Set"),
+        }
+
+        assert_eq!(
+            entries[3].value,
+            Value::List(List::from(vec!["a".into(), "1".into()]))
+        );
+
+        match &entries[4].value {
+            Value::ZSet(zset) => assert_eq!(zset.score(b"a"), Some(1.0)),
+            _ => panic!("Expected ZSet"),
+        }
+    }
+
+    #[test]
+    fn test_rdb_save_and_load() {
+        let backend = Backend::new();
+        backend.set(b"key", "value".into());
+        backend
+            .hset(b"hash", vec![("field".into(), "value".into())])
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("simple-redis-{}.rdb", std::process::id()));
+        save(&backend.snapshot(), &path).unwrap();
+
+        let restored = Backend::new();
+        assert_eq!(load(&restored, &path).unwrap(), 2);
+        assert_eq!(restored.get(b"key").unwrap(), Some("value".into()));
+        assert_eq!(
+            restored.hget(b"hash", b"field").unwrap(),
+            Some("value".into())
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
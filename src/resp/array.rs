@@ -2,7 +2,7 @@ use std::io::Cursor;
 use std::ops::Deref;
 
 use anyhow::Result;
-use bytes::Buf;
+use bytes::Bytes;
 
 use super::Frame;
 use super::{get_int, get_u8, RespDecode, RespEncode, RespError};
@@ -21,7 +21,7 @@ impl Array {
 impl RespDecode for Array {
     const PREFIX: u8 = b'*';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Array: {:?}",
@@ -38,10 +38,6 @@ impl RespDecode for Array {
             let mut inner = Vec::with_capacity(len);
 
             for _ in 0..len {
-                if !buf.has_remaining() {
-                    break;
-                }
-
                 let frame = Frame::decode(buf)?;
                 inner.push(frame);
             }
@@ -57,14 +53,7 @@ impl RespEncode for Array {
     fn encode(&self) -> Vec<u8> {
         let mut buf = Vec::new();
         buf.push(Self::PREFIX);
-        let len = self.inner.len();
-
-        if len == 0 {
-            buf.extend_from_slice(b"-1");
-        } else {
-            buf.extend(len.to_string().as_bytes());
-        }
-
+        buf.extend(self.inner.len().to_string().as_bytes());
         buf.extend_from_slice(b"\r\n");
 
         for frame in &self.inner {
@@ -89,11 +78,18 @@ mod tests {
 
     #[test]
     fn test_array_decode() {
-        let mut buf = Cursor::new(&b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"));
         let frame = Array::decode(&mut buf).unwrap();
         assert_eq!(frame, Array::new(vec![b"foo".into(), b"bar".into(),]));
     }
 
+    #[test]
+    fn test_array_decode_incomplete() {
+        let mut buf = Cursor::new(Bytes::from_static(b"*2\r\n$3\r\nfoo\r\n"));
+        let result = Array::decode(&mut buf);
+        assert!(matches!(result, Err(RespError::Incomplete)));
+    }
+
     #[test]
     fn test_array_encode() {
         let frame = Array::new(vec![b"foo".into(), b"bar".into()]);
@@ -102,14 +98,14 @@ mod tests {
 
     #[test]
     fn test_null_array_decode() {
-        let mut buf = Cursor::new(&b"*-1\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"*-1\r\n"));
         let frame = Array::decode(&mut buf).unwrap();
         assert_eq!(frame, Array::new(vec![]));
     }
 
     #[test]
-    fn test_null_array_encode() {
+    fn test_empty_array_encode() {
         let frame = Array::new(vec![]);
-        assert_eq!(frame.encode(), b"*-1\r\n");
+        assert_eq!(frame.encode(), b"*0\r\n");
     }
 }
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::{get_decimal, get_u8, Frame, RespDecode, RespEncode, RespError};
+
+// Auxiliary data about a reply, sent as a map right before it. The reply is
+// kept along with it, RESP2 clients only get the reply.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Attribute {
+    pub(crate) attributes: BTreeMap<Frame, Frame>,
+    pub(crate) value: Box<Frame>,
+}
+
+impl Attribute {
+    pub fn new(attributes: BTreeMap<Frame, Frame>, value: Frame) -> Self {
+        Self {
+            attributes,
+            value: Box::new(value),
+        }
+    }
+}
+
+impl RespDecode for Attribute {
+    const PREFIX: u8 = b'|';
+
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
+        if get_u8(buf)? != Self::PREFIX {
+            return Err(RespError::InvalidType(format!(
+                "Invalid prefix for Attribute: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        let len = get_decimal(buf)? as usize;
+        let mut attributes = BTreeMap::new();
+
+        for _ in 0..len {
+            let key = Frame::decode(buf)?;
+            let value = Frame::decode(buf)?;
+            attributes.insert(key, value);
+        }
+
+        let value = Frame::decode(buf)?;
+
+        Ok(Self::new(attributes, value))
+    }
+}
+
+impl RespEncode for Attribute {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.push(Self::PREFIX);
+        buf.extend(self.attributes.len().to_string().as_bytes());
+        buf.extend_from_slice(b"\r\n");
+
+        for (key, value) in &self.attributes {
+            buf.extend(key.encode());
+            buf.extend(value.encode());
+        }
+
+        buf.extend(self.value.encode());
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(b"|1\r\n+ttl\r\n:100\r\n$5\r\nhello\r\n"));
+        let result = Attribute::decode(&mut buf).unwrap();
+        assert_eq!(result.attributes.get(&"ttl".into()), Some(&100.into()));
+        assert_eq!(*result.value, b"hello".into());
+    }
+
+    #[test]
+    fn test_attribute_decode_incomplete() {
+        let mut buf = Cursor::new(Bytes::from_static(b"|1\r\n+ttl\r\n:100\r\n"));
+        assert!(matches!(
+            Attribute::decode(&mut buf),
+            Err(RespError::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn test_attribute_encode() {
+        let attributes = [("ttl".into(), 100.into())].into_iter().collect();
+        let attribute = Attribute::new(attributes, b"hello".into());
+        assert_eq!(attribute.encode(), b"|1\r\n+ttl\r\n:100\r\n$5\r\nhello\r\n");
+    }
+}
@@ -1,6 +1,7 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
@@ -20,7 +21,7 @@ impl BigNumber {
 impl RespDecode for BigNumber {
     const PREFIX: u8 = b'(';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for BigNumber: {:?}",
@@ -52,22 +53,22 @@ mod tests {
 
     #[test]
     fn test_big_number_decode() {
-        let mut buf = Cursor::new(&b"(1234567890\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"(1234567890\r\n"));
         let result = BigNumber::decode(&mut buf).unwrap();
         assert_eq!(result.inner, "1234567890");
 
-        let mut buf = Cursor::new(&b"(+1234567890\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"(+1234567890\r\n"));
         let result = BigNumber::decode(&mut buf).unwrap();
         assert_eq!(result.inner, "+1234567890");
 
-        let mut buf = Cursor::new(&b"(-1234567890\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"(-1234567890\r\n"));
         let result = BigNumber::decode(&mut buf).unwrap();
         assert_eq!(result.inner, "-1234567890");
     }
 
     #[test]
     fn test_big_number_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = BigNumber::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -1,12 +1,13 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Boolean {
-    inner: bool,
+    pub(crate) inner: bool,
 }
 
 impl Boolean {
@@ -18,7 +19,7 @@ impl Boolean {
 impl RespDecode for Boolean {
     const PREFIX: u8 = b'#';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Boolean: {:?}",
@@ -27,7 +28,7 @@ impl RespDecode for Boolean {
         }
 
         let line = get_line(buf)?;
-        let inner = match std::str::from_utf8(line)? {
+        let inner = match std::str::from_utf8(&line)? {
             "t" => true,
             "f" => false,
             _ => {
@@ -58,18 +59,18 @@ mod tests {
 
     #[test]
     fn test_boolean_decode() {
-        let mut buf = Cursor::new(&b"#t\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"#t\r\n"));
         let result = Boolean::decode(&mut buf).unwrap();
         assert!(result.inner);
 
-        let mut buf = Cursor::new(&b"#f\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"#f\r\n"));
         let result = Boolean::decode(&mut buf).unwrap();
         assert!(!result.inner);
     }
 
     #[test]
     fn test_boolean_decode_error() {
-        let mut buf = Cursor::new(&b"#x\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"#x\r\n"));
         let result = Boolean::decode(&mut buf);
         assert!(result.is_err());
     }
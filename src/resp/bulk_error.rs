@@ -1,6 +1,7 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_decimal, get_line, get_u8, RespDecode, RespEncode, RespError};
 
@@ -19,7 +20,7 @@ impl BulkError {
 
 impl RespDecode for BulkError {
     const PREFIX: u8 = b'!';
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for BulkError: {:?}",
@@ -59,14 +60,14 @@ mod tests {
 
     #[test]
     fn test_bulk_error_decode() {
-        let mut buf = Cursor::new(&b"!21\r\nSYNTAX invalid syntax\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"!21\r\nSYNTAX invalid syntax\r\n"));
         let result = BulkError::decode(&mut buf).unwrap();
         assert_eq!(result.inner, b"SYNTAX invalid syntax");
     }
 
     #[test]
     fn test_bulk_error_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = BulkError::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -1,16 +1,17 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
-use super::{get_int, get_line, get_u8, RespDecode, RespEncode, RespError};
+use super::{get_bytes, get_int, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BulkString {
-    pub(crate) inner: Vec<u8>,
+    pub(crate) inner: Bytes,
 }
 
 impl BulkString {
-    pub fn new(inner: impl Into<Vec<u8>>) -> Self {
+    pub fn new(inner: impl Into<Bytes>) -> Self {
         Self {
             inner: inner.into(),
         }
@@ -20,7 +21,7 @@ impl BulkString {
 impl RespDecode for BulkString {
     const PREFIX: u8 = b'$';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for BulkString: {:?}",
@@ -30,22 +31,17 @@ impl RespDecode for BulkString {
 
         let len = get_int(buf)?;
 
-        let inner = if len <= 0 {
-            vec![]
-        } else {
-            let len = len as usize;
-
-            let inner = get_line(buf)?.to_vec();
-
-            if inner.len() != len {
-                return Err(RespError::InvalidType(format!(
-                    "Invalid length for BulkString: {:?}",
-                    buf.get_ref()
-                )));
-            }
+        // null bulk strings (`$-1`) are decoded by NullBulkString
+        if len < 0 {
+            return Err(RespError::InvalidType(format!(
+                "Invalid length for BulkString: {:?}",
+                buf.get_ref()
+            )));
+        }
 
-            inner
-        };
+        // the payload is binary safe, so it is read by the declared length
+        // instead of scanning for the next CRLF
+        let inner = get_bytes(buf, len as usize)?;
 
         Ok(Self::new(inner))
     }
@@ -56,16 +52,9 @@ impl RespEncode for BulkString {
         let mut buf = Vec::new();
         buf.push(Self::PREFIX);
 
-        let len = self.inner.len();
-
-        if len == 0 {
-            buf.extend_from_slice(b"-1");
-        } else {
-            buf.extend(len.to_string().as_bytes());
-            buf.extend_from_slice(b"\r\n");
-            buf.extend(&self.inner);
-        }
-
+        buf.extend(self.inner.len().to_string().as_bytes());
+        buf.extend_from_slice(b"\r\n");
+        buf.extend(&self.inner);
         buf.extend_from_slice(b"\r\n");
         buf
     }
@@ -77,14 +66,39 @@ mod tests {
 
     #[test]
     fn test_bulk_string_decode() {
-        let mut buf = Cursor::new(&b"$5\r\nhello\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"$5\r\nhello\r\n"));
         let result = BulkString::decode(&mut buf).unwrap();
-        assert_eq!(result.inner, b"hello");
+        assert_eq!(result.inner, &b"hello"[..]);
+    }
+
+    #[test]
+    fn test_bulk_string_decode_binary() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$7\r\nfoo\r\nba\r\n"));
+        let result = BulkString::decode(&mut buf).unwrap();
+        assert_eq!(result.inner, &b"foo\r\nba"[..]);
+    }
+
+    #[test]
+    fn test_bulk_string_decode_incomplete() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$10\r\nhello\r\n"));
+        let result = BulkString::decode(&mut buf);
+        assert!(matches!(result, Err(RespError::Incomplete)));
+
+        let mut buf = Cursor::new(Bytes::from_static(b"$5\r\nhello"));
+        let result = BulkString::decode(&mut buf);
+        assert!(matches!(result, Err(RespError::Incomplete)));
+    }
+
+    #[test]
+    fn test_bulk_string_decode_invalid_length() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$3\r\nhello\r\n"));
+        let result = BulkString::decode(&mut buf);
+        assert!(matches!(result, Err(RespError::InvalidType(_))));
     }
 
     #[test]
     fn test_bulk_string_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = BulkString::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -97,16 +111,24 @@ mod tests {
     }
 
     #[test]
-    fn test_null_bulk_string_decode() {
-        let mut buf = Cursor::new(&b"$-1\r\n"[..]);
+    fn test_empty_bulk_string_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$0\r\n\r\n"));
         let result = BulkString::decode(&mut buf).unwrap();
-        assert_eq!(result.inner, b"");
+        assert_eq!(result.inner, &b""[..]);
+        assert_eq!(buf.position(), 6);
     }
 
     #[test]
-    fn test_null_bulk_string_encode() {
+    fn test_empty_bulk_string_encode() {
         let bulk_string = BulkString::new("");
         let result = bulk_string.encode();
-        assert_eq!(result, b"$-1\r\n");
+        assert_eq!(result, b"$0\r\n\r\n");
+    }
+
+    #[test]
+    fn test_null_bulk_string_decode_error() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$-1\r\n"));
+        let result = BulkString::decode(&mut buf);
+        assert!(result.is_err());
     }
 }
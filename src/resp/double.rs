@@ -1,12 +1,13 @@
 use std::{hash::Hash, io::Cursor};
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Double {
-    inner: f64,
+    pub(crate) inner: f64,
 }
 
 impl Eq for Double {}
@@ -42,7 +43,7 @@ impl RespDecode for Double {
     const PREFIX: u8 = b',';
 
     // decode with format: ,[<+|->]<integral>[.<fractional>][<E|e>[sign]<exponent>]\r\n
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Double: {:?}",
@@ -51,7 +52,7 @@ impl RespDecode for Double {
         }
 
         let line = get_line(buf)?;
-        let inner = std::str::from_utf8(line)?.parse::<f64>()?;
+        let inner = std::str::from_utf8(&line)?.parse::<f64>()?;
 
         Ok(Self::new(inner))
     }
@@ -73,42 +74,42 @@ mod tests {
 
     #[test]
     fn test_double_decode() {
-        let mut buf = Cursor::new(&b",12.345\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345);
 
-        let mut buf = Cursor::new(&b",12.345e-2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345e-2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345e-2);
 
-        let mut buf = Cursor::new(&b",12.345E-2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345E-2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345e-2);
 
-        let mut buf = Cursor::new(&b",12.345e2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345e2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345e2);
 
-        let mut buf = Cursor::new(&b",12.345E2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345E2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345e2);
 
-        let mut buf = Cursor::new(&b",12.345e+2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",12.345e+2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 12.345e2);
 
-        let mut buf = Cursor::new(&b",-12.345E+2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",-12.345E+2\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, -12.345e2);
 
-        let mut buf = Cursor::new(&b",+1.23456e-9\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",+1.23456e-9\r\n"));
         let result = Double::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 1.23456e-9);
     }
 
     #[test]
     fn test_double_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = Double::decode(&mut buf);
         assert!(result.is_err());
     }
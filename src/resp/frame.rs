@@ -1,10 +1,14 @@
+use bytes::{Buf, Bytes};
 use enum_dispatch::enum_dispatch;
 use std::io::Cursor;
 
 use super::{
-    array::Array, bignumber::BigNumber, boolean::Boolean, bulk_error::BulkError,
-    bulk_string::BulkString, double::Double, integer::Integer, map::Map, null::Null, peek_u8,
-    set::Set, simple_error::SimpleError, simple_string::SimpleString, RespDecode, RespError,
+    array::Array, attribute::Attribute, bignumber::BigNumber, boolean::Boolean,
+    bulk_error::BulkError, bulk_string::BulkString, bytes_range, double::Double, get_int, get_u8,
+    integer::Integer, line_range, map::Map, null::Null, null_array::NullArray,
+    null_bulk_string::NullBulkString, peek_u8, push::Push, set::Set, simple_error::SimpleError,
+    simple_string::SimpleString, verbatim_string::VerbatimString, RespDecode, RespError,
+    MAX_BULK_LEN,
 };
 
 #[enum_dispatch(RespEncode)]
@@ -14,7 +18,9 @@ pub enum Frame {
     SimpleError(SimpleError),
     Integer(Integer),
     BulkString(BulkString),
+    NullBulkString(NullBulkString),
     Array(Array),
+    NullArray(NullArray),
     Null(Null),
     Boolean(Boolean),
     Double(Double),
@@ -22,19 +28,26 @@ pub enum Frame {
     BulkError(BulkError),
     Map(Map),
     Set(Set),
+    Push(Push),
+    VerbatimString(VerbatimString),
+    Attribute(Attribute),
 }
 
 impl RespDecode for Frame {
     const PREFIX: u8 = 0;
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         let prefix = peek_u8(buf)?;
 
         match prefix {
             b'+' => SimpleString::decode(buf).map(Into::into),
             b'-' => SimpleError::decode(buf).map(Into::into),
             b':' => Integer::decode(buf).map(Into::into),
+            b'$' if buf.chunk().starts_with(b"$-1\r\n") => {
+                NullBulkString::decode(buf).map(Into::into)
+            }
             b'$' => BulkString::decode(buf).map(Into::into),
+            b'*' if buf.chunk().starts_with(b"*-1\r\n") => NullArray::decode(buf).map(Into::into),
             b'*' => Array::decode(buf).map(Into::into),
             b'_' => Null::decode(buf).map(Into::into),
             b'#' => Boolean::decode(buf).map(Into::into),
@@ -43,6 +56,9 @@ impl RespDecode for Frame {
             b'!' => BulkError::decode(buf).map(Into::into),
             b'%' => Map::decode(buf).map(Into::into),
             b'~' => Set::decode(buf).map(Into::into),
+            b'>' => Push::decode(buf).map(Into::into),
+            b'=' => VerbatimString::decode(buf).map(Into::into),
+            b'|' => Attribute::decode(buf).map(Into::into),
             _ => Err(RespError::InvalidType(format!(
                 "Invalid prefix for Frame: {:?}",
                 buf.get_ref()
@@ -51,6 +67,149 @@ impl RespDecode for Frame {
     }
 }
 
+impl Frame {
+    // Checks that a whole frame is buffered without decoding it, so only
+    // complete frames are split off the read buffer.
+    pub fn check(buf: &mut Cursor<&[u8]>) -> Result<(), RespError> {
+        match get_u8(buf)? {
+            b'+' | b'-' | b':' | b'_' | b'#' | b',' | b'(' => {
+                line_range(buf)?;
+            }
+            b'$' | b'!' | b'=' => {
+                let len = get_int(buf)?;
+
+                if len > MAX_BULK_LEN {
+                    return Err(RespError::InvalidBulkLength);
+                }
+                if len >= 0 {
+                    bytes_range(buf, len as usize)?;
+                }
+            }
+            b'*' | b'~' | b'>' => {
+                for _ in 0..get_int(buf)?.max(0) {
+                    Frame::check(buf)?;
+                }
+            }
+            b'%' => {
+                for _ in 0..get_int(buf)?.max(0) * 2 {
+                    Frame::check(buf)?;
+                }
+            }
+            // the attributes, then the reply they are about
+            b'|' => {
+                for _ in 0..get_int(buf)?.max(0) * 2 + 1 {
+                    Frame::check(buf)?;
+                }
+            }
+            prefix => {
+                return Err(RespError::InvalidType(format!(
+                    "Invalid prefix for Frame: {:?}",
+                    prefix as char
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks like `check`, but a top-level array resumes from `progress`:
+    // the elements left to check and where the next one starts. Large arrays
+    // take many reads to arrive, their buffered elements are then not checked
+    // again on every read. `progress` is kept while the array is incomplete.
+    pub fn check_resume(
+        buf: &mut Cursor<&[u8]>,
+        progress: &mut Option<(i64, u64)>,
+    ) -> Result<(), RespError> {
+        let (left, position) = match progress.take() {
+            Some(progress) => progress,
+            None if peek_u8(buf)? == b'*' => {
+                buf.advance(1);
+                (get_int(buf)?.max(0), buf.position())
+            }
+            None => return Frame::check(buf),
+        };
+
+        buf.set_position(position);
+        for left in (1..=left).rev() {
+            let position = buf.position();
+            match Frame::check(buf) {
+                Ok(()) => {}
+                Err(RespError::Incomplete) => {
+                    *progress = Some((left, position));
+                    return Err(RespError::Incomplete);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Frame {
+    // Replies are built with RESP3 types, clients speaking RESP2 get the
+    // closest RESP2 type instead.
+    pub fn into_resp2(self) -> Frame {
+        match self {
+            Frame::Double(double) => double.inner.to_string().as_bytes().into(),
+            Frame::Null(_) => Frame::NullBulkString(NullBulkString),
+            Frame::Boolean(boolean) => Frame::Integer(Integer::new(boolean.inner as i64)),
+            Frame::Array(array) => array
+                .inner
+                .into_iter()
+                .map(Frame::into_resp2)
+                .collect::<Vec<_>>()
+                .into(),
+            Frame::Set(set) => set
+                .inner
+                .into_iter()
+                .map(Frame::into_resp2)
+                .collect::<Vec<_>>()
+                .into(),
+            Frame::Push(push) => push
+                .inner
+                .into_iter()
+                .map(Frame::into_resp2)
+                .collect::<Vec<_>>()
+                .into(),
+            Frame::VerbatimString(verbatim) => verbatim.inner.into(),
+            Frame::Attribute(attribute) => attribute.value.into_resp2(),
+            Frame::Map(map) => map
+                .inner
+                .into_iter()
+                .flat_map(|(key, value)| [key.into_resp2(), value.into_resp2()])
+                .collect::<Vec<_>>()
+                .into(),
+            frame => frame,
+        }
+    }
+
+    // RESP3 has a single null, sent in place of the RESP2 null bulk string
+    // and null array replies are built with.
+    pub fn into_resp3(self) -> Frame {
+        match self {
+            Frame::NullBulkString(_) | Frame::NullArray(_) => Frame::Null(Null),
+            Frame::Array(array) => array
+                .inner
+                .into_iter()
+                .map(Frame::into_resp3)
+                .collect::<Vec<_>>()
+                .into(),
+            Frame::Push(push) => {
+                Push::new(push.inner.into_iter().map(Frame::into_resp3).collect()).into()
+            }
+            Frame::Map(map) => Map::new(
+                map.inner
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_resp3()))
+                    .collect(),
+            )
+            .into(),
+            frame => frame,
+        }
+    }
+}
+
 impl From<String> for Frame {
     fn from(s: String) -> Self {
         Frame::SimpleString(SimpleString::new(s))
@@ -81,6 +240,12 @@ impl From<&[u8]> for Frame {
     }
 }
 
+impl From<Bytes> for Frame {
+    fn from(s: Bytes) -> Self {
+        Frame::BulkString(BulkString::new(s))
+    }
+}
+
 impl From<bool> for Frame {
     fn from(b: bool) -> Self {
         Frame::Boolean(Boolean::new(b))
@@ -102,50 +267,180 @@ impl From<f64> for Frame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resp::RespEncode;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_frame_bulk_string_round_trip(data in prop::collection::vec(any::<u8>(), 0..1024)) {
+            let frame: Frame = data.as_slice().into();
+            let encoded = frame.encode();
+
+            let mut buf = Cursor::new(Bytes::from(encoded.clone()));
+            let decoded = Frame::decode(&mut buf).unwrap();
+
+            prop_assert_eq!(decoded, frame);
+            prop_assert_eq!(buf.position() as usize, encoded.len());
+        }
+    }
 
     #[test]
     fn test_frame_decode() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, "OK".into());
 
-        let mut buf = Cursor::new(&b"-ERR\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"-ERR\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, Frame::SimpleError(SimpleError::new("ERR")));
 
-        let mut buf = Cursor::new(&b":1000\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b":1000\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, 1000.into());
 
-        let mut buf = Cursor::new(&b"$6\r\nfoobar\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"$6\r\nfoobar\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, b"foobar".into());
 
-        let mut buf = Cursor::new(&b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"$0\r\n\r\n"));
+        let result = Frame::decode(&mut buf).unwrap();
+        assert_eq!(result, b"".into());
+
+        let mut buf = Cursor::new(Bytes::from_static(b"$-1\r\n"));
+        let result = Frame::decode(&mut buf).unwrap();
+        assert_eq!(result, Frame::NullBulkString(NullBulkString));
+
+        let mut buf = Cursor::new(Bytes::from_static(b"*-1\r\n"));
+        let result = Frame::decode(&mut buf).unwrap();
+        assert_eq!(result, Frame::NullArray(NullArray));
+
+        let mut buf = Cursor::new(Bytes::from_static(b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, vec![b"foo".into(), b"bar".into()].into());
 
-        let mut buf = Cursor::new(&b"_\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"_\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, Frame::Null(Null));
 
-        let mut buf = Cursor::new(&b"#t\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"#t\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, true.into());
 
-        let mut buf = Cursor::new(&b",1.234\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b",1.234\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, 1.234.into());
 
-        let mut buf = Cursor::new(&b"(1234567890\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"(1234567890\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(result, Frame::BigNumber(BigNumber::new("1234567890")));
 
-        let mut buf = Cursor::new(&b"!21\r\nSYNTAX invalid syntax\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"!21\r\nSYNTAX invalid syntax\r\n"));
         let result = Frame::decode(&mut buf).unwrap();
         assert_eq!(
             result,
             Frame::BulkError(BulkError::new("SYNTAX invalid syntax"))
         );
     }
+
+    #[test]
+    fn test_frame_into_resp2() {
+        let frame: Frame =
+            vec![b"member".into(), 1.5.into(), Frame::Null(Null), true.into()].into();
+
+        assert_eq!(
+            frame.into_resp2(),
+            vec![
+                b"member".into(),
+                b"1.5".into(),
+                Frame::NullBulkString(NullBulkString),
+                1.into()
+            ]
+            .into()
+        );
+        assert_eq!(Frame::from(f64::NEG_INFINITY).into_resp2(), b"-inf".into());
+
+        let frame = Frame::VerbatimString(VerbatimString::new(*b"txt", "hello"));
+        assert_eq!(frame.into_resp2(), b"hello".into());
+
+        let attributes = [("ttl".into(), 100.into())].into_iter().collect();
+        let frame = Frame::Attribute(Attribute::new(attributes, Frame::Null(Null)));
+        assert_eq!(frame.into_resp2(), Frame::NullBulkString(NullBulkString));
+    }
+
+    #[test]
+    fn test_frame_into_resp3() {
+        let frame: Frame = vec![
+            b"member".into(),
+            Frame::NullBulkString(NullBulkString),
+            vec![Frame::NullArray(NullArray)].into(),
+        ]
+        .into();
+
+        assert_eq!(
+            frame.into_resp3(),
+            vec![
+                b"member".into(),
+                Frame::Null(Null),
+                vec![Frame::Null(Null)].into()
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_frame_check() {
+        let data = b"*3\r\n$3\r\nfoo\r\n:1\r\n%1\r\n+a\r\n$-1\r\n+next\r\n";
+        let mut buf = Cursor::new(&data[..]);
+        Frame::check(&mut buf).unwrap();
+        assert_eq!(buf.position() as usize, data.len() - 7);
+
+        let mut buf = Cursor::new(&b"*2\r\n$3\r\nfoo\r\n$3\r\nba"[..]);
+        assert!(matches!(Frame::check(&mut buf), Err(RespError::Incomplete)));
+
+        // an attribute only ends with the reply following it
+        let mut buf = Cursor::new(&b"|1\r\n+ttl\r\n:1\r\n"[..]);
+        assert!(matches!(Frame::check(&mut buf), Err(RespError::Incomplete)));
+
+        let data = b"|1\r\n+ttl\r\n:1\r\n=7\r\ntxt:abc\r\n";
+        let mut buf = Cursor::new(&data[..]);
+        Frame::check(&mut buf).unwrap();
+        assert_eq!(buf.position() as usize, data.len());
+
+        let mut buf = Cursor::new(&b"@foo\r\n"[..]);
+        assert!(matches!(
+            Frame::check(&mut buf),
+            Err(RespError::InvalidType(_))
+        ));
+
+        let mut buf = Cursor::new(&b"*1\r\n$536870913\r\n"[..]);
+        assert!(matches!(
+            Frame::check(&mut buf),
+            Err(RespError::InvalidBulkLength)
+        ));
+    }
+
+    #[test]
+    fn test_frame_check_resume() {
+        let data = b"*3\r\n$3\r\nfoo\r\n$3\r\nbar\r\n$3\r\nba";
+        let mut progress = None;
+
+        let mut buf = Cursor::new(&data[..]);
+        assert!(matches!(
+            Frame::check_resume(&mut buf, &mut progress),
+            Err(RespError::Incomplete)
+        ));
+        assert_eq!(progress, Some((1, 22)));
+
+        // the checked elements are skipped, even if they were garbage now
+        let data = b"*3\r\n@@@@@@@@@@@@@@@@@@$3\r\nbaz\r\n+next\r\n";
+        let mut buf = Cursor::new(&data[..]);
+        Frame::check_resume(&mut buf, &mut progress).unwrap();
+        assert_eq!(buf.position() as usize, data.len() - 7);
+        assert_eq!(progress, None);
+
+        let data = b"+OK\r\n";
+        let mut buf = Cursor::new(&data[..]);
+        Frame::check_resume(&mut buf, &mut progress).unwrap();
+        assert_eq!(buf.position() as usize, data.len());
+    }
 }
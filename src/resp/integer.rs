@@ -1,6 +1,7 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
@@ -18,7 +19,7 @@ impl Integer {
 impl RespDecode for Integer {
     const PREFIX: u8 = b':';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Integer: {:?}",
@@ -27,7 +28,7 @@ impl RespDecode for Integer {
         }
 
         let line = get_line(buf)?;
-        let inner = std::str::from_utf8(line)?.parse()?;
+        let inner = std::str::from_utf8(&line)?.parse()?;
         Ok(Self::new(inner))
     }
 }
@@ -48,14 +49,14 @@ mod tests {
 
     #[test]
     fn test_integer_decode() {
-        let mut buf = Cursor::new(&b":1000\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b":1000\r\n"));
         let result = Integer::decode(&mut buf).unwrap();
         assert_eq!(result.inner, 1000);
     }
 
     #[test]
     fn test_integer_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = Integer::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -1,13 +1,13 @@
 use std::{collections::BTreeMap, io::Cursor};
 
 use anyhow::Result;
-use bytes::Buf;
+use bytes::Bytes;
 
 use super::{get_decimal, get_u8, Frame, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Map {
-    inner: BTreeMap<Frame, Frame>,
+    pub(crate) inner: BTreeMap<Frame, Frame>,
 }
 
 impl Map {
@@ -19,7 +19,7 @@ impl Map {
 impl RespDecode for Map {
     const PREFIX: u8 = b'%';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Map: {:?}",
@@ -31,10 +31,6 @@ impl RespDecode for Map {
         let mut inner = BTreeMap::new();
 
         for _ in 0..len {
-            if !buf.has_remaining() {
-                break;
-            }
-
             let key = Frame::decode(buf)?;
             let value = Frame::decode(buf)?;
             inner.insert(key, value);
@@ -66,7 +62,9 @@ mod tests {
 
     #[test]
     fn test_map_decode() {
-        let mut buf = Cursor::new(&b"%2\r\n+first\r\n:1\r\n+second\r\n:2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(
+            b"%2\r\n+first\r\n:1\r\n+second\r\n:2\r\n",
+        ));
         let result = Map::decode(&mut buf).unwrap();
         assert_eq!(result.inner.len(), 2);
 
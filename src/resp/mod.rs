@@ -1,4 +1,5 @@
 pub mod array;
+pub mod attribute;
 mod bignumber;
 mod boolean;
 mod bulk_error;
@@ -6,20 +7,26 @@ mod bulk_string;
 mod double;
 pub mod frame;
 mod integer;
-mod map;
+pub mod map;
 pub mod null;
+pub mod null_array;
+pub mod null_bulk_string;
+pub mod push;
 mod set;
-mod simple_error;
+pub mod simple_error;
 mod simple_string;
+pub mod verbatim_string;
 
 use std::io::Cursor;
+use std::ops::Range;
 
 use anyhow::Result;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use enum_dispatch::enum_dispatch;
 use thiserror::Error;
 
 use array::Array;
+use attribute::Attribute;
 use bignumber::BigNumber;
 use boolean::Boolean;
 use bulk_error::BulkError;
@@ -29,9 +36,13 @@ use frame::Frame;
 use integer::Integer;
 use map::Map;
 use null::Null;
+use null_array::NullArray;
+use null_bulk_string::NullBulkString;
+use push::Push;
 use set::Set;
 use simple_error::SimpleError;
 use simple_string::SimpleString;
+use verbatim_string::VerbatimString;
 
 #[derive(Debug, Error)]
 pub enum RespError {
@@ -41,6 +52,9 @@ pub enum RespError {
     #[error("Invalid type: {0}")]
     InvalidType(String),
 
+    #[error("Protocol error: invalid bulk length")]
+    InvalidBulkLength,
+
     #[error("Utf8 error: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
 
@@ -54,10 +68,21 @@ pub enum RespError {
     ParseFloatError(#[from] std::num::ParseFloatError),
 }
 
+// Bulk strings longer than this are refused before they are buffered, like
+// Redis' default proto-max-bulk-len.
+pub const MAX_BULK_LEN: i64 = 512 * 1024 * 1024;
+
+// Payloads from this size on are sliced from the read buffer, like Redis
+// reuses the query buffer for big arguments. Smaller ones are copied, a slice
+// kept in the keyspace would keep the whole buffer it came from alive.
+const BIG_BULK_LEN: usize = 32 * 1024;
+
 pub trait RespDecode: Sized {
     const PREFIX: u8;
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError>;
+    // Frames are decoded out of a shared `Bytes` buffer, large string
+    // payloads are sliced from it instead of being copied.
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError>;
 }
 
 #[enum_dispatch]
@@ -65,7 +90,7 @@ pub trait RespEncode {
     fn encode(&self) -> Vec<u8>;
 }
 
-fn get_u8(buf: &mut Cursor<&[u8]>) -> Result<u8, RespError> {
+fn get_u8(buf: &mut impl Buf) -> Result<u8, RespError> {
     if !buf.has_remaining() {
         return Err(RespError::Incomplete);
     }
@@ -73,7 +98,7 @@ fn get_u8(buf: &mut Cursor<&[u8]>) -> Result<u8, RespError> {
     Ok(buf.get_u8())
 }
 
-fn peek_u8(buf: &mut Cursor<&[u8]>) -> Result<u8, RespError> {
+fn peek_u8(buf: &mut impl Buf) -> Result<u8, RespError> {
     if !buf.has_remaining() {
         return Err(RespError::Incomplete);
     }
@@ -81,26 +106,65 @@ fn peek_u8(buf: &mut Cursor<&[u8]>) -> Result<u8, RespError> {
     Ok(buf.chunk()[0])
 }
 
-fn get_decimal(buf: &mut Cursor<&[u8]>) -> Result<u64, RespError> {
-    let line = get_line(buf)?;
-    let inner = std::str::from_utf8(line)?.parse()?;
+fn get_decimal<T: AsRef<[u8]>>(buf: &mut Cursor<T>) -> Result<u64, RespError> {
+    let range = line_range(buf)?;
+    let inner = std::str::from_utf8(&buf.get_ref().as_ref()[range])?.parse()?;
     Ok(inner)
 }
 
-fn get_int(buf: &mut Cursor<&[u8]>) -> Result<i64, RespError> {
-    let line = get_line(buf)?;
-    let inner = std::str::from_utf8(line)?.parse()?;
+fn get_int<T: AsRef<[u8]>>(buf: &mut Cursor<T>) -> Result<i64, RespError> {
+    let range = line_range(buf)?;
+    let inner = std::str::from_utf8(&buf.get_ref().as_ref()[range])?.parse()?;
     Ok(inner)
 }
 
-fn get_line<'a>(buf: &mut Cursor<&'a [u8]>) -> Result<&'a [u8], RespError> {
+fn get_bytes(buf: &mut Cursor<Bytes>, len: usize) -> Result<Bytes, RespError> {
+    let range = bytes_range(buf, len)?;
+    Ok(slice_or_copy(buf.get_ref(), range))
+}
+
+fn get_line(buf: &mut Cursor<Bytes>) -> Result<Bytes, RespError> {
+    let range = line_range(buf)?;
+    Ok(slice_or_copy(buf.get_ref(), range))
+}
+
+fn slice_or_copy(data: &Bytes, range: Range<usize>) -> Bytes {
+    match range.len() >= BIG_BULK_LEN {
+        true => data.slice(range),
+        false => Bytes::copy_from_slice(&data[range]),
+    }
+}
+
+// Position of the next `len` bytes, which must be followed by a CRLF.
+fn bytes_range<T: AsRef<[u8]>>(buf: &mut Cursor<T>, len: usize) -> Result<Range<usize>, RespError> {
+    let data = buf.get_ref().as_ref();
+    let start = buf.position() as usize;
+    let end = start + len;
+
+    if data.len() < end + 2 {
+        return Err(RespError::Incomplete);
+    }
+
+    if &data[end..end + 2] != b"\r\n" {
+        return Err(RespError::InvalidType(format!(
+            "Missing CRLF after {} bytes: {:?}",
+            len, data
+        )));
+    }
+
+    buf.set_position((end + 2) as u64);
+    Ok(start..end)
+}
+
+// Position of the next line, without its CRLF.
+fn line_range<T: AsRef<[u8]>>(buf: &mut Cursor<T>) -> Result<Range<usize>, RespError> {
+    let data = buf.get_ref().as_ref();
     let start = buf.position() as usize;
-    let end = buf.get_ref().len() - 1;
 
-    for i in start..end {
-        if buf.get_ref()[i] == b'\r' && buf.get_ref()[i + 1] == b'\n' {
+    for i in start..data.len().saturating_sub(1) {
+        if data[i] == b'\r' && data[i + 1] == b'\n' {
             buf.set_position((i + 2) as u64);
-            return Ok(&buf.get_ref()[start..i]);
+            return Ok(start..i);
         }
     }
 
@@ -1,6 +1,7 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
@@ -10,7 +11,7 @@ pub struct Null;
 impl RespDecode for Null {
     const PREFIX: u8 = b'_';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Null: {:?}",
@@ -39,14 +40,14 @@ mod tests {
 
     #[test]
     fn test_null_decode() {
-        let mut buf = Cursor::new(&b"_\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"_\r\n"));
         let result = Null::decode(&mut buf).unwrap();
         assert_eq!(result, Null);
     }
 
     #[test]
     fn test_null_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = Null::decode(&mut buf);
         assert!(result.is_err());
     }
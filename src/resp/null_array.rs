@@ -0,0 +1,63 @@
+use std::io::Cursor;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::{get_int, get_u8, RespDecode, RespEncode, RespError};
+
+// RESP2 null array reply (`*-1`), distinct from an empty array (`*0`).
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NullArray;
+
+impl RespDecode for NullArray {
+    const PREFIX: u8 = b'*';
+
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
+        if get_u8(buf)? != Self::PREFIX {
+            return Err(RespError::InvalidType(format!(
+                "Invalid prefix for NullArray: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        if get_int(buf)? != -1 {
+            return Err(RespError::InvalidType(format!(
+                "Invalid length for NullArray: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        Ok(NullArray)
+    }
+}
+
+impl RespEncode for NullArray {
+    fn encode(&self) -> Vec<u8> {
+        b"*-1\r\n".to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_array_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(b"*-1\r\n"));
+        let result = NullArray::decode(&mut buf).unwrap();
+        assert_eq!(result, NullArray);
+    }
+
+    #[test]
+    fn test_null_array_decode_error() {
+        let mut buf = Cursor::new(Bytes::from_static(b"*0\r\n"));
+        let result = NullArray::decode(&mut buf);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_null_array_encode() {
+        let result = NullArray.encode();
+        assert_eq!(result, b"*-1\r\n");
+    }
+}
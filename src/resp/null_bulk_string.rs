@@ -0,0 +1,63 @@
+use std::io::Cursor;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::{get_int, get_u8, RespDecode, RespEncode, RespError};
+
+// RESP2 null reply (`$-1`), distinct from an empty bulk string (`$0`).
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NullBulkString;
+
+impl RespDecode for NullBulkString {
+    const PREFIX: u8 = b'$';
+
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
+        if get_u8(buf)? != Self::PREFIX {
+            return Err(RespError::InvalidType(format!(
+                "Invalid prefix for NullBulkString: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        if get_int(buf)? != -1 {
+            return Err(RespError::InvalidType(format!(
+                "Invalid length for NullBulkString: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        Ok(NullBulkString)
+    }
+}
+
+impl RespEncode for NullBulkString {
+    fn encode(&self) -> Vec<u8> {
+        b"$-1\r\n".to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_bulk_string_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$-1\r\n"));
+        let result = NullBulkString::decode(&mut buf).unwrap();
+        assert_eq!(result, NullBulkString);
+    }
+
+    #[test]
+    fn test_null_bulk_string_decode_error() {
+        let mut buf = Cursor::new(Bytes::from_static(b"$0\r\n\r\n"));
+        let result = NullBulkString::decode(&mut buf);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_null_bulk_string_encode() {
+        let result = NullBulkString.encode();
+        assert_eq!(result, b"$-1\r\n");
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::Cursor;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::Frame;
+use super::{get_decimal, get_u8, RespDecode, RespEncode, RespError};
+
+// Out of band data, like Pub/Sub messages, sent to RESP3 clients. RESP2
+// clients get them as plain arrays.
+#[derive(Debug, Hash, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Push {
+    pub(crate) inner: Vec<Frame>,
+}
+
+impl Push {
+    pub fn new(inner: Vec<Frame>) -> Self {
+        Self { inner }
+    }
+}
+
+impl RespDecode for Push {
+    const PREFIX: u8 = b'>';
+
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
+        if get_u8(buf)? != Self::PREFIX {
+            return Err(RespError::InvalidType(format!(
+                "Invalid prefix for Push: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        let len = get_decimal(buf)? as usize;
+        let mut inner = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            inner.push(Frame::decode(buf)?);
+        }
+
+        Ok(Self::new(inner))
+    }
+}
+
+impl RespEncode for Push {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.push(Self::PREFIX);
+        buf.extend(self.inner.len().to_string().as_bytes());
+        buf.extend_from_slice(b"\r\n");
+
+        for frame in &self.inner {
+            buf.extend(frame.encode());
+        }
+
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(
+            b">3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$2\r\nhi\r\n",
+        ));
+        let result = Push::decode(&mut buf).unwrap();
+        assert_eq!(
+            result.inner,
+            vec![b"message".into(), b"ch".into(), b"hi".into()]
+        );
+    }
+
+    #[test]
+    fn test_push_decode_error() {
+        let mut buf = Cursor::new(Bytes::from_static(b"*1\r\n:1\r\n"));
+        assert!(Push::decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_push_encode() {
+        let push = Push::new(vec![b"subscribe".into(), b"ch".into(), 1.into()]);
+        assert_eq!(
+            push.encode(),
+            b">3\r\n$9\r\nsubscribe\r\n$2\r\nch\r\n:1\r\n"
+        );
+    }
+}
@@ -2,14 +2,14 @@ use std::collections::BTreeSet;
 use std::io::Cursor;
 
 use anyhow::Result;
-use bytes::Buf;
+use bytes::Bytes;
 
 use super::Frame;
 use super::{get_decimal, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Set {
-    inner: BTreeSet<Frame>,
+    pub(crate) inner: BTreeSet<Frame>,
 }
 
 impl Set {
@@ -21,7 +21,7 @@ impl Set {
 impl RespDecode for Set {
     const PREFIX: u8 = b'*';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for Set: {:?}",
@@ -33,10 +33,6 @@ impl RespDecode for Set {
         let mut inner = BTreeSet::new();
 
         for _ in 0..len {
-            if !buf.has_remaining() {
-                break;
-            }
-
             let frame = Frame::decode(buf)?;
             inner.insert(frame);
         }
@@ -66,7 +62,7 @@ mod tests {
 
     #[test]
     fn test_set_decode() {
-        let mut buf = Cursor::new(&b"*2\r\n:1\r\n:2\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"*2\r\n:1\r\n:2\r\n"));
         let result = Set::decode(&mut buf).unwrap();
         assert_eq!(result.inner.len(), 2);
         assert!(result.inner.contains(&1.into()));
@@ -75,7 +71,7 @@ mod tests {
 
     #[test]
     fn test_set_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = Set::decode(&mut buf);
         assert!(result.is_err());
     }
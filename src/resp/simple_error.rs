@@ -1,12 +1,13 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SimpleError {
-    inner: String,
+    pub(crate) inner: String,
 }
 
 impl SimpleError {
@@ -20,7 +21,7 @@ impl SimpleError {
 impl RespDecode for SimpleError {
     const PREFIX: u8 = b'-';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for SimpleError: {:?}",
@@ -50,14 +51,14 @@ mod tests {
 
     #[test]
     fn test_simple_error_decode() {
-        let mut buf = Cursor::new(&b"-ERR\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"-ERR\r\n"));
         let result = SimpleError::decode(&mut buf).unwrap();
         assert_eq!(result.inner, "ERR");
     }
 
     #[test]
     fn test_simple_error_decode_error() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = SimpleError::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -1,18 +1,19 @@
 use std::io::Cursor;
 
 use anyhow::Result;
+use bytes::Bytes;
 
 use super::{get_line, get_u8, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SimpleString {
-    pub(crate) inner: String,
+    pub(crate) inner: Bytes,
 }
 
 impl SimpleString {
     pub fn new(inner: impl ToString) -> Self {
         Self {
-            inner: inner.to_string(),
+            inner: inner.to_string().into(),
         }
     }
 }
@@ -20,7 +21,7 @@ impl SimpleString {
 impl RespDecode for SimpleString {
     const PREFIX: u8 = b'+';
 
-    fn decode(buf: &mut Cursor<&[u8]>) -> Result<Self, RespError> {
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
         if get_u8(buf)? != Self::PREFIX {
             return Err(RespError::InvalidType(format!(
                 "Invalid prefix for SimpleString: {:?}",
@@ -28,9 +29,10 @@ impl RespDecode for SimpleString {
             )));
         }
 
-        let line = get_line(buf)?.to_vec();
-        let inner = String::from_utf8(line)?;
-        Ok(Self::new(inner))
+        let inner = get_line(buf)?;
+        std::str::from_utf8(&inner)?;
+
+        Ok(Self { inner })
     }
 }
 
@@ -38,7 +40,7 @@ impl RespEncode for SimpleString {
     fn encode(&self) -> Vec<u8> {
         let mut buf = Vec::new();
         buf.push(Self::PREFIX);
-        buf.extend(self.inner.as_bytes());
+        buf.extend(&self.inner);
         buf.extend_from_slice(b"\r\n");
         buf
     }
@@ -56,14 +58,14 @@ mod tests {
 
     #[test]
     fn test_simple_string_decode() {
-        let mut buf = Cursor::new(&b"+OK\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"+OK\r\n"));
         let result = SimpleString::decode(&mut buf).unwrap();
         assert_eq!(result.inner, "OK");
     }
 
     #[test]
     fn test_simple_string_decode_error() {
-        let mut buf = Cursor::new(&b"-ERR\r\n"[..]);
+        let mut buf = Cursor::new(Bytes::from_static(b"-ERR\r\n"));
         let result = SimpleString::decode(&mut buf);
         assert!(result.is_err());
     }
@@ -0,0 +1,92 @@
+use std::io::Cursor;
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use super::{get_bytes, get_decimal, get_u8, RespDecode, RespEncode, RespError};
+
+// A bulk string with a three bytes format, like `txt` or `mkd`, telling
+// clients how to display it. RESP2 clients get the bare string.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VerbatimString {
+    pub(crate) format: [u8; 3],
+    pub(crate) inner: Bytes,
+}
+
+impl VerbatimString {
+    pub fn new(format: [u8; 3], inner: impl Into<Bytes>) -> Self {
+        Self {
+            format,
+            inner: inner.into(),
+        }
+    }
+}
+
+impl RespDecode for VerbatimString {
+    const PREFIX: u8 = b'=';
+
+    fn decode(buf: &mut Cursor<Bytes>) -> Result<Self, RespError> {
+        if get_u8(buf)? != Self::PREFIX {
+            return Err(RespError::InvalidType(format!(
+                "Invalid prefix for VerbatimString: {:?}",
+                buf.get_ref()
+            )));
+        }
+
+        let len = get_decimal(buf)? as usize;
+        let data = get_bytes(buf, len)?;
+
+        // the format and its `:` separator are counted in the length
+        if data.len() < 4 || data[3] != b':' {
+            return Err(RespError::InvalidType(format!(
+                "Invalid format for VerbatimString: {:?}",
+                data
+            )));
+        }
+
+        let format = [data[0], data[1], data[2]];
+        Ok(Self::new(format, data.slice(4..)))
+    }
+}
+
+impl RespEncode for VerbatimString {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.push(Self::PREFIX);
+        buf.extend((self.inner.len() + 4).to_string().as_bytes());
+        buf.extend_from_slice(b"\r\n");
+        buf.extend(self.format);
+        buf.push(b':');
+        buf.extend(&self.inner);
+        buf.extend_from_slice(b"\r\n");
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbatim_string_decode() {
+        let mut buf = Cursor::new(Bytes::from_static(b"=15\r\ntxt:Some string\r\n"));
+        let result = VerbatimString::decode(&mut buf).unwrap();
+        assert_eq!(&result.format, b"txt");
+        assert_eq!(result.inner, &b"Some string"[..]);
+    }
+
+    #[test]
+    fn test_verbatim_string_decode_error() {
+        let mut buf = Cursor::new(Bytes::from_static(b"=3\r\ntxt\r\n"));
+        assert!(VerbatimString::decode(&mut buf).is_err());
+
+        let mut buf = Cursor::new(Bytes::from_static(b"=5\r\ntxt-a\r\n"));
+        assert!(VerbatimString::decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_verbatim_string_encode() {
+        let verbatim = VerbatimString::new(*b"mkd", "# hi");
+        assert_eq!(verbatim.encode(), b"=8\r\nmkd:# hi\r\n");
+    }
+}
@@ -0,0 +1,708 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use mlua::{Function, HookTriggers, Lua, LuaOptions, RegistryKey, StdLib, Table, Value, Variadic};
+use sha1::{Digest, Sha1};
+use tracing::{debug, info, warn};
+
+use crate::backend::now_millis;
+use crate::command::CommandError;
+use crate::resp::frame::Frame;
+use crate::resp::null_bulk_string::NullBulkString;
+use crate::resp::simple_error::SimpleError;
+
+// `redis.call` is `redis.pcall` raising the error tables it returns, the
+// script then fails with the error of the command.
+const REDIS_CALL: &str = r#"
+redis.call = function(...)
+    local reply = redis.pcall(...)
+    if type(reply) == "table" and reply.err then
+        error(reply, 0)
+    end
+    return reply
+end
+"#;
+
+// Scripts can't change the globals for the next ones, like in Redis, nor read
+// globals that don't exist. The globals move to a table scripts only read
+// through `_G`, and the libraries, `redis` included, are read only. The
+// chunk returns the table the globals moved to.
+const PROTECT_GLOBALS: &str = r#"
+local G, error, tostring = _G, error, tostring
+local getmetatable, setmetatable, rawset = getmetatable, setmetatable, rawset
+
+local function readonly_error()
+    error("Attempt to modify a readonly table", 3)
+end
+
+local function readonly(t)
+    return setmetatable({}, {
+        __index = t,
+        __newindex = function()
+            readonly_error()
+        end,
+        __metatable = false,
+    })
+end
+
+local globals = {}
+for name, value in pairs(G) do
+    globals[name] = value
+end
+for _, name in ipairs({"redis", "string", "table", "math"}) do
+    globals[name] = readonly(G[name])
+end
+getmetatable("").__index = globals.string
+getmetatable("").__metatable = false
+
+-- rawset would write past the checks of read only tables
+globals.rawset = function(t, key, value)
+    if getmetatable(t) == false then
+        readonly_error()
+    end
+    return rawset(t, key, value)
+end
+
+for name in pairs(globals) do
+    rawset(G, name, nil)
+end
+globals._G = G
+
+setmetatable(G, {
+    __index = function(_, name)
+        local value = globals[name]
+        if value == nil then
+            error("Script attempted to access nonexistent global variable '" .. tostring(name) .. "'", 2)
+        end
+        return value
+    end,
+    __newindex = function(_, name)
+        if globals[name] ~= nil then
+            readonly_error()
+        end
+        error("Script attempted to create global variable '" .. tostring(name) .. "'", 2)
+    end,
+    __metatable = false,
+})
+
+return globals
+"#;
+
+// The levels of `redis.log`, by their name in the `redis` table.
+const LOG_LEVELS: [(&str, i64); 4] = [
+    ("LOG_DEBUG", 0),
+    ("LOG_VERBOSE", 1),
+    ("LOG_NOTICE", 2),
+    ("LOG_WARNING", 3),
+];
+
+// How long, in milliseconds, a script runs before other clients are replied
+// BUSY, like Redis' busy-reply-threshold.
+const BUSY_THRESHOLD: u64 = 5000;
+
+// Instructions a script runs between checks of SCRIPT KILL.
+const KILL_CHECK_INSTRUCTIONS: u32 = 100_000;
+
+const KILLED: &str = "Script killed by user with SCRIPT KILL...";
+
+// The script running, if any. Clients check it while the script holds the
+// interpreter and the keyspace lock.
+#[derive(Debug, Default)]
+pub struct RunningScript {
+    // milliseconds since the epoch the script started at, 0 when none runs
+    started: AtomicU64,
+    wrote: AtomicBool,
+    killed: AtomicBool,
+}
+
+impl RunningScript {
+    pub fn busy(&self) -> bool {
+        let started = self.started.load(Ordering::SeqCst);
+        started != 0 && now_millis().saturating_sub(started) >= BUSY_THRESHOLD
+    }
+
+    // Once a script wrote, killing it would leave its writes half made.
+    pub fn wrote(&self) {
+        self.wrote.store(true, Ordering::SeqCst);
+    }
+
+    // SCRIPT KILL, the script fails the next time it checks.
+    pub fn kill(&self) -> Result<(), CommandError> {
+        if self.started.load(Ordering::SeqCst) == 0 {
+            return Err(CommandError::NotBusy);
+        }
+        if self.wrote.load(Ordering::SeqCst) {
+            return Err(CommandError::Unkillable);
+        }
+
+        self.killed.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn start(&self) {
+        self.wrote.store(false, Ordering::SeqCst);
+        self.killed.store(false, Ordering::SeqCst);
+        self.started.store(now_millis().max(1), Ordering::SeqCst);
+    }
+
+    fn stop(&self) {
+        self.started.store(0, Ordering::SeqCst);
+    }
+}
+
+// Scripts are cached and called by the SHA1 digest of their body, in hex.
+pub fn sha1_hex(body: &[u8]) -> String {
+    Sha1::digest(body)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+// The interpreter scripts run in, kept for as long as the server runs, like
+// Redis does. Scripts are compiled once, their functions cached by the SHA1
+// digest of their body.
+#[derive(Debug)]
+pub struct Scripting {
+    lua: Lua,
+    functions: HashMap<String, RegistryKey>,
+    // the globals scripts read, and the `redis` table they read through
+    // `redis`, see `PROTECT_GLOBALS`
+    globals: RegistryKey,
+    redis: RegistryKey,
+    running: Arc<RunningScript>,
+}
+
+impl Scripting {
+    // The interpreter with the libraries Redis gives scripts, the scripts it
+    // runs killed through `running`.
+    pub fn new(running: Arc<RunningScript>) -> mlua::Result<Self> {
+        let lua = Lua::new_with(
+            StdLib::TABLE | StdLib::STRING | StdLib::MATH,
+            LuaOptions::new(),
+        )?;
+
+        // the base library can read files, scripts can't
+        let globals = lua.globals();
+        for name in ["dofile", "loadfile"] {
+            globals.raw_set(name, Value::Nil)?;
+        }
+
+        let redis = lua.create_table()?;
+        redis.raw_set(
+            "error_reply",
+            lua.create_function(|lua, message: mlua::String| reply_table(lua, "err", message))?,
+        )?;
+        redis.raw_set(
+            "status_reply",
+            lua.create_function(|lua, message: mlua::String| reply_table(lua, "ok", message))?,
+        )?;
+        redis.raw_set(
+            "sha1hex",
+            lua.create_function(|_, body: mlua::String| Ok(sha1_hex(body.as_bytes())))?,
+        )?;
+        redis.raw_set("log", lua.create_function(log)?)?;
+        for (name, level) in LOG_LEVELS {
+            redis.raw_set(name, level)?;
+        }
+        globals.raw_set("redis", redis.clone())?;
+        lua.load(REDIS_CALL).exec()?;
+        let protected: Table = lua.load(PROTECT_GLOBALS).call(())?;
+        let globals_key = lua.create_registry_value(protected)?;
+        let redis_key = lua.create_registry_value(redis)?;
+        drop(globals);
+
+        Ok(Self {
+            lua,
+            functions: HashMap::new(),
+            globals: globals_key,
+            redis: redis_key,
+            running,
+        })
+    }
+
+    // Compiles the script and caches it, returning its digest. Scripts
+    // already cached are not compiled again.
+    pub fn load(&mut self, body: &[u8]) -> Result<String, CommandError> {
+        let sha = sha1_hex(body);
+
+        if !self.functions.contains_key(&sha) {
+            let function = compile(&self.lua, body)?;
+            let key = self
+                .lua
+                .create_registry_value(function)
+                .map_err(lua_error)?;
+            self.functions.insert(sha.clone(), key);
+        }
+
+        Ok(sha)
+    }
+
+    // Digests are matched regardless of case, like Redis does.
+    pub fn exists(&self, sha: &str) -> bool {
+        self.functions.contains_key(&sha.to_lowercase())
+    }
+
+    pub fn flush(&mut self) {
+        self.functions.clear();
+        self.lua.expire_registry_values();
+    }
+
+    // Runs a cached script with its KEYS and ARGV. `call` runs the commands
+    // the script calls, replying errors as error frames. A script failing
+    // with an error table, like the one a failed `redis.call` raises, replies
+    // that error.
+    pub fn run(
+        &self,
+        sha: &str,
+        keys: &[Bytes],
+        args: &[Bytes],
+        mut call: impl FnMut(Frame) -> Frame,
+    ) -> Result<Frame, CommandError> {
+        let sha = sha.to_lowercase();
+        let key = self.functions.get(&sha).ok_or(CommandError::NoScript)?;
+        let lua = &self.lua;
+
+        // once killed, the script fails at every instruction, catching the
+        // error doesn't keep it running
+        let running = self.running.clone();
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(KILL_CHECK_INSTRUCTIONS),
+            move |lua, _| match running.killed.load(Ordering::SeqCst) {
+                true => {
+                    lua.set_hook(HookTriggers::new().every_nth_instruction(1), |_, _| {
+                        Err(mlua::Error::runtime(KILLED))
+                    });
+                    Err(mlua::Error::runtime(KILLED))
+                }
+                false => Ok(()),
+            },
+        );
+
+        self.running.start();
+        let result = lua.scope(|scope| {
+            let function: Function = lua.registry_value(key)?;
+            let globals: Table = lua.registry_value(&self.globals)?;
+            globals.raw_set("KEYS", strings(lua, keys)?)?;
+            globals.raw_set("ARGV", strings(lua, args)?)?;
+
+            // only callable while this script runs
+            let pcall = scope.create_function_mut(|lua, args: Variadic<Value>| {
+                let reply = match command(lua, args)? {
+                    Ok(frame) => call(frame),
+                    Err(message) => SimpleError::new(message).into(),
+                };
+                to_lua(lua, reply)
+            })?;
+            let redis: Table = lua.registry_value(&self.redis)?;
+            redis.raw_set("pcall", pcall)?;
+
+            // errors are caught in Lua, where error tables are still tables
+            let pcall: Function = globals.raw_get("pcall")?;
+            let (ok, value): (bool, Value) = pcall.call(function)?;
+
+            Ok(match (ok, value) {
+                (true, value) => Ok(to_frame(value)),
+                (false, Value::Table(table)) if is_error_table(&table) => {
+                    Ok(to_frame(Value::Table(table)))
+                }
+                (false, Value::String(message)) => Err(message.to_string_lossy().into_owned()),
+                (false, value) => Err(value.to_string().unwrap_or_default()),
+            })
+        });
+        self.running.stop();
+
+        if self.running.killed.load(Ordering::SeqCst) {
+            return Err(CommandError::ScriptKilled);
+        }
+
+        match result.map_err(lua_error)? {
+            Ok(frame) => Ok(frame),
+            Err(message) => Err(CommandError::InvalidArgument(format!(
+                "Error running script (call to f_{}): {}",
+                sha, message
+            ))),
+        }
+    }
+}
+
+fn compile<'lua>(lua: &'lua Lua, body: &[u8]) -> Result<Function<'lua>, CommandError> {
+    lua.load(body)
+        .set_name("@user_script")
+        .into_function()
+        .map_err(|error| match error {
+            mlua::Error::SyntaxError { message, .. } => CommandError::InvalidArgument(format!(
+                "Error compiling script (new function): {}",
+                message
+            )),
+            error => lua_error(error),
+        })
+}
+
+// `redis.log(level, message, ...)`, the message parts joined by spaces.
+fn log(_: &Lua, (level, parts): (i64, Variadic<mlua::String>)) -> mlua::Result<()> {
+    if parts.is_empty() {
+        return Err(mlua::Error::runtime(
+            "redis.log() requires two arguments or more.",
+        ));
+    }
+
+    let message = parts
+        .iter()
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    match level {
+        0 | 1 => debug!("{}", message),
+        2 => info!("{}", message),
+        3 => warn!("{}", message),
+        _ => return Err(mlua::Error::runtime("Invalid debug level.")),
+    }
+    Ok(())
+}
+
+fn lua_error(error: mlua::Error) -> CommandError {
+    CommandError::InvalidArgument(error.to_string())
+}
+
+fn strings<'lua>(lua: &'lua Lua, values: &[Bytes]) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    for (i, value) in values.iter().enumerate() {
+        table.raw_set(i + 1, lua.create_string(value)?)?;
+    }
+    Ok(table)
+}
+
+fn reply_table<'lua>(
+    lua: &'lua Lua,
+    field: &str,
+    message: mlua::String<'lua>,
+) -> mlua::Result<Value<'lua>> {
+    let table = lua.create_table()?;
+    table.raw_set(field, message)?;
+    Ok(Value::Table(table))
+}
+
+fn is_error_table(table: &Table) -> bool {
+    matches!(table.raw_get("err"), Ok(Value::String(_)))
+}
+
+// The command called by a script, from strings and numbers only. The inner
+// error is replied to the script rather than raised.
+fn command(lua: &Lua, args: Variadic<Value>) -> mlua::Result<Result<Frame, &'static str>> {
+    if args.is_empty() {
+        return Ok(Err(
+            "ERR Please specify at least one argument for this redis lib call",
+        ));
+    }
+
+    let mut frames = Vec::with_capacity(args.len());
+    for arg in args {
+        // numbers are formatted the way Lua prints them
+        let arg = match arg {
+            Value::String(_) | Value::Integer(_) | Value::Number(_) => lua.coerce_string(arg)?,
+            _ => None,
+        };
+
+        match arg {
+            Some(arg) => frames.push(Frame::from(arg.as_bytes())),
+            None => {
+                return Ok(Err(
+                    "ERR Lua redis lib command arguments must be strings or integers",
+                ))
+            }
+        }
+    }
+
+    Ok(Ok(frames.into()))
+}
+
+// Replies converted the way Redis hands them to scripts: status and error
+// replies become tables with an `ok` or `err` field, nulls become false.
+fn to_lua<'lua>(lua: &'lua Lua, frame: Frame) -> mlua::Result<Value<'lua>> {
+    let value = match frame.into_resp2() {
+        Frame::Integer(integer) => Value::Integer(integer.inner as mlua::Integer),
+        Frame::BulkString(bulk) => Value::String(lua.create_string(&bulk.inner)?),
+        Frame::SimpleString(status) => reply_table(lua, "ok", lua.create_string(&status.inner)?)?,
+        Frame::SimpleError(error) => reply_table(lua, "err", lua.create_string(&error.inner)?)?,
+        Frame::Array(array) => {
+            let table = lua.create_table()?;
+            for (i, frame) in array.inner.into_iter().enumerate() {
+                table.raw_set(i + 1, to_lua(lua, frame)?)?;
+            }
+            Value::Table(table)
+        }
+        _ => Value::Boolean(false),
+    };
+
+    Ok(value)
+}
+
+// Script results converted back the way Redis does: numbers are truncated to
+// integers, true is 1 and false a null, and arrays stop at the first nil.
+fn to_frame(value: Value) -> Frame {
+    match value {
+        Value::Boolean(true) => 1.into(),
+        Value::Integer(integer) => integer.into(),
+        Value::Number(number) => (number as i64).into(),
+        Value::String(string) => string.as_bytes().into(),
+        Value::Table(table) => {
+            if let Ok(Value::String(error)) = table.raw_get("err") {
+                let error = error.to_string_lossy().replace(['\r', '\n'], " ");
+                return SimpleError::new(error).into();
+            }
+            if let Ok(Value::String(status)) = table.raw_get("ok") {
+                let status = status.to_string_lossy().replace(['\r', '\n'], " ");
+                return status.into();
+            }
+
+            table
+                .sequence_values::<Value>()
+                .map_while(Result::ok)
+                .map(to_frame)
+                .collect::<Vec<_>>()
+                .into()
+        }
+        _ => NullBulkString.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(
+        body: &[u8],
+        keys: &[Bytes],
+        args: &[Bytes],
+        call: impl FnMut(Frame) -> Frame,
+    ) -> Result<Frame, CommandError> {
+        let mut scripting = Scripting::new(Arc::default()).unwrap();
+        let sha = scripting.load(body)?;
+        scripting.run(&sha, keys, args, call)
+    }
+
+    fn eval(body: &str, keys: &[&str], args: &[&str]) -> Result<Frame, CommandError> {
+        let keys = keys
+            .iter()
+            .map(|key| Bytes::from(key.to_string()))
+            .collect::<Vec<_>>();
+        let args = args
+            .iter()
+            .map(|arg| Bytes::from(arg.to_string()))
+            .collect::<Vec<_>>();
+        run(body.as_bytes(), &keys, &args, |frame| frame)
+    }
+
+    #[test]
+    fn test_sha1_hex() {
+        assert_eq!(
+            sha1_hex(b"return 1"),
+            "e0e1f9fabfc9d4800c877a703b823ac0578ff8db"
+        );
+    }
+
+    #[test]
+    fn test_run_conversions() {
+        assert_eq!(eval("return 3.99", &[], &[]).unwrap(), 3.into());
+        assert_eq!(eval("return true", &[], &[]).unwrap(), 1.into());
+        assert_eq!(
+            eval("return false", &[], &[]).unwrap(),
+            Frame::NullBulkString(NullBulkString)
+        );
+        assert_eq!(
+            eval("return {KEYS[1], ARGV[1], nil, 'x'}", &["k"], &["a"]).unwrap(),
+            vec![b"k".into(), b"a".into()].into()
+        );
+        assert_eq!(
+            eval("return redis.status_reply('FINE')", &[], &[]).unwrap(),
+            "FINE".into()
+        );
+        assert_eq!(
+            eval("return {err = 'BAD thing'}", &[], &[]).unwrap(),
+            Frame::SimpleError(SimpleError::new("BAD thing"))
+        );
+    }
+
+    #[test]
+    fn test_run_call() {
+        // the echoed command comes back as a table of strings
+        let reply = eval("return redis.call('get', KEYS[1], 1.5)", &["k"], &[]);
+        assert_eq!(
+            reply.unwrap(),
+            vec![b"get".into(), b"k".into(), b"1.5".into()].into()
+        );
+
+        let reply = run(b"local r = redis.pcall('x') return r.err", &[], &[], |_| {
+            SimpleError::new("ERR failed").into()
+        });
+        assert_eq!(reply.unwrap(), b"ERR failed".into());
+
+        let reply = run(b"redis.call('x') return 1", &[], &[], |_| {
+            SimpleError::new("ERR failed").into()
+        });
+        assert_eq!(
+            reply.unwrap(),
+            Frame::SimpleError(SimpleError::new("ERR failed"))
+        );
+
+        let reply = eval("return redis.call({})", &[], &[]);
+        assert_eq!(
+            reply.unwrap(),
+            Frame::SimpleError(SimpleError::new(
+                "ERR Lua redis lib command arguments must be strings or integers"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_running_script_kill() {
+        let running = RunningScript::default();
+        assert!(matches!(running.kill(), Err(CommandError::NotBusy)));
+
+        running.start();
+        assert!(!running.busy());
+        running.wrote();
+        assert!(matches!(running.kill(), Err(CommandError::Unkillable)));
+        running.stop();
+    }
+
+    #[test]
+    fn test_run_killed() {
+        let running = Arc::new(RunningScript::default());
+        let mut scripting = Scripting::new(running.clone()).unwrap();
+        // the script catching the error doesn't save it
+        let sha = scripting
+            .load(b"while true do pcall(function() while true do end end) end")
+            .unwrap();
+
+        let killer = std::thread::spawn(move || {
+            while running.kill().is_err() {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        });
+        let error = scripting.run(&sha, &[], &[], |frame| frame).unwrap_err();
+        killer.join().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "ERR Script killed by user with SCRIPT KILL..."
+        );
+    }
+
+    #[test]
+    fn test_scripting_cache() {
+        let mut scripting = Scripting::new(Arc::default()).unwrap();
+        let sha = scripting.load(b"return ARGV[1]").unwrap();
+        assert!(scripting.exists(&sha.to_uppercase()));
+
+        // the compiled function is run again with other arguments
+        for arg in ["a", "b"] {
+            let reply = scripting.run(&sha, &[], &[Bytes::from(arg)], |frame| frame);
+            assert_eq!(reply.unwrap(), arg.as_bytes().into());
+        }
+
+        scripting.flush();
+        assert!(!scripting.exists(&sha));
+        let error = scripting.run(&sha, &[], &[], |frame| frame).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "NOSCRIPT No matching script. Please use EVAL."
+        );
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let error = eval("return +", &[], &[]).unwrap_err().to_string();
+        assert!(error.starts_with("ERR Error compiling script (new function): user_script:1:"));
+
+        let error = eval("return nope()", &[], &[]).unwrap_err().to_string();
+        let sha = sha1_hex(b"return nope()");
+        assert!(error.starts_with(&format!(
+            "ERR Error running script (call to f_{}): user_script:1:",
+            sha
+        )));
+
+        let error = eval("return dofile('/etc/passwd')", &[], &[]).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("Script attempted to access nonexistent global variable 'dofile'"));
+    }
+
+    #[test]
+    fn test_run_globals() {
+        let mut scripting = Scripting::new(Arc::default()).unwrap();
+        let set = scripting.load(b"x = 1 return 1").unwrap();
+        let error = scripting.run(&set, &[], &[], |frame| frame).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("user_script:1: Script attempted to create global variable 'x'"));
+
+        // globals of a script are not seen by the next
+        let get = scripting.load(b"return rawget(_G, 'x')").unwrap();
+        let reply = scripting.run(&get, &[], &[], |frame| frame);
+        assert_eq!(reply.unwrap(), NullBulkString.into());
+
+        let reply = scripting.run(&set, &[], &[], |frame| frame);
+        assert!(reply.is_err());
+
+        // the globals and libraries can't be changed, even bypassing `_G`
+        for (body, message) in [
+            (
+                "redis.call = nil",
+                "user_script:1: Attempt to modify a readonly table",
+            ),
+            ("redis = nil", "Attempt to modify a readonly table"),
+            ("string.rep = nil", "Attempt to modify a readonly table"),
+            (
+                "getmetatable('').__index.rep = nil",
+                "attempt to index a boolean value",
+            ),
+            ("rawset(_G, 'x', 1)", "Attempt to modify a readonly table"),
+            (
+                "rawset(redis, 'call', 1)",
+                "Attempt to modify a readonly table",
+            ),
+            (
+                "setmetatable(_G, nil)",
+                "cannot change a protected metatable",
+            ),
+        ] {
+            let sha = scripting.load(body.as_bytes()).unwrap();
+            let error = scripting.run(&sha, &[], &[], |frame| frame).unwrap_err();
+            assert!(error.to_string().contains(message), "{}: {}", body, error);
+        }
+
+        let sha = scripting
+            .load(b"return {redis.call('echo', 'a'), string.rep('b', 2), ('c'):upper()}")
+            .unwrap();
+        let reply = scripting.run(&sha, &[], &[], |frame| match frame {
+            Frame::Array(args) => args.last().unwrap().clone(),
+            frame => frame,
+        });
+        assert_eq!(
+            reply.unwrap(),
+            vec![b"a".into(), b"bb".into(), b"C".into()].into()
+        );
+    }
+
+    #[test]
+    fn test_run_helpers() {
+        assert_eq!(
+            eval("return redis.sha1hex(ARGV[1])", &[], &["return 1"]).unwrap(),
+            b"e0e1f9fabfc9d4800c877a703b823ac0578ff8db".into()
+        );
+        assert_eq!(
+            eval("redis.log(redis.LOG_WARNING, 'a', 'b') return 1", &[], &[]).unwrap(),
+            1.into()
+        );
+
+        let error = eval("redis.log(redis.LOG_NOTICE)", &[], &[]).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("redis.log() requires two arguments or more."));
+        let error = eval("redis.log(9, 'a')", &[], &[]).unwrap_err();
+        assert!(error.to_string().contains("Invalid debug level."));
+    }
+}